@@ -3,6 +3,7 @@ import MainLayout from './src/components/layout/MainLayout';
 import { useProgressiveOnboarding } from './src/hooks/useProgressiveOnboarding';
 import AppRoutes from './src/routes';
 import ErrorBoundary from './src/components/common/ErrorBoundary';
+import AppNotifications from './src/components/ui/AppNotifications';
 
 const ProgressiveOnboarding = React.lazy(() => import('./src/components/ui/ProgressiveOnboarding.tsx'));
 const ContextualHints = React.lazy(() => import('./src/components/ui/ContextualHints.tsx'));
@@ -20,6 +21,7 @@ const App: React.FC = () => {
     <ErrorBoundary>
       <MainLayout>
         <AppRoutes />
+        <AppNotifications />
         <Suspense fallback={<div />}>
           <ProgressiveOnboarding
             isActive={showProgressiveOnboarding}
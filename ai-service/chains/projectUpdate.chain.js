@@ -51,7 +51,7 @@ Focus on identifying:
 {format_instructions}
 `);
 
-export async function createProjectUpdateChain(modelType = 'gemini') {
+export async function createProjectUpdateChain(modelType = 'gemini', apiKey = null) {
   logger.info('Creating project update analysis chain', { modelType });
   
   let model;
@@ -67,7 +67,8 @@ export async function createProjectUpdateChain(modelType = 'gemini') {
       model = new ChatGoogleGenerativeAI({
         modelName: 'gemini-1.5-pro-latest',
         temperature: 0.2,
-        maxTokens: 4096
+        maxTokens: 4096,
+        apiKey: apiKey || process.env.GOOGLE_API_KEY
       });
       break;
     case 'openai':
@@ -93,13 +94,13 @@ export async function createProjectUpdateChain(modelType = 'gemini') {
   return chain;
 }
 
-export async function analyzeProjectUpdates(messages, projectContext = {}, modelType = 'gemini') {
+export async function analyzeProjectUpdates(messages, projectContext = {}, modelType = 'gemini', apiKey = null) {
   try {
     logger.info('Analyzing messages for project updates', { 
       messageCount: Array.isArray(messages) ? messages.length : 1 
     });
 
-    const chain = await createProjectUpdateChain(modelType);
+    const chain = await createProjectUpdateChain(modelType, apiKey);
     
     const formattedMessages = Array.isArray(messages)
       ? messages.map(m => `[${m.timestamp}] ${m.user}: ${m.text}`).join('\n')
@@ -36,7 +36,7 @@ Create a comprehensive summary that:
 Final Summary:
 `);
 
-export async function createSmartSummaryChain(modelType = 'gemini') {
+export async function createSmartSummaryChain(modelType = 'gemini', apiKey = null) {
   logger.info('Creating smart summary chain', { modelType });
   
   let model;
@@ -52,7 +52,8 @@ export async function createSmartSummaryChain(modelType = 'gemini') {
       model = new ChatGoogleGenerativeAI({
         modelName: 'gemini-1.5-pro-latest',
         temperature: 0.3,
-        maxTokens: 1024
+        maxTokens: 1024,
+        apiKey: apiKey || process.env.GOOGLE_API_KEY
       });
       break;
     case 'openai':
@@ -84,6 +85,7 @@ export async function createSmartSummaryChain(modelType = 'gemini') {
 export async function summarizeText(text, options = {}) {
   const {
     modelType = 'gemini',
+    apiKey = null,
     chunkSize = 4000,
     chunkOverlap = 200,
     maxSummaryLength = 1000
@@ -97,7 +99,7 @@ export async function summarizeText(text, options = {}) {
 
     // If text is short enough, summarize directly
     if (text.length < chunkSize) {
-      const { summaryChain } = await createSmartSummaryChain(modelType);
+      const { summaryChain } = await createSmartSummaryChain(modelType, apiKey);
       const summary = await summaryChain.invoke({ text });
       return summary;
     }
@@ -111,7 +113,7 @@ export async function summarizeText(text, options = {}) {
     const chunks = await splitter.splitText(text);
     logger.info(`Split text into ${chunks.length} chunks`);
 
-    const { summaryChain, combineChain } = await createSmartSummaryChain(modelType);
+    const { summaryChain, combineChain } = await createSmartSummaryChain(modelType, apiKey);
 
     // Summarize each chunk
     const chunkSummaries = await Promise.all(
@@ -63,7 +63,7 @@ Context about the project:
 ]);
 
 // Create the chain
-export async function createTaskAnalysisChain(modelType = 'gemini') {
+export async function createTaskAnalysisChain(modelType = 'gemini', apiKeyOverride = null) {
   logger.info('Creating task analysis chain', { modelType });
   
   // Select model based on type
@@ -85,7 +85,7 @@ export async function createTaskAnalysisChain(modelType = 'gemini') {
       break;
     case 'gemini':
     default:
-      const apiKey = process.env.GOOGLE_API_KEY;
+      const apiKey = apiKeyOverride || process.env.GOOGLE_API_KEY;
       
       if (!apiKey || apiKey.trim() === '') {
         throw new Error('Google API key is missing or empty. Please set GOOGLE_API_KEY in your environment file.');
@@ -115,14 +115,14 @@ export async function createTaskAnalysisChain(modelType = 'gemini') {
 }
 
 // Analyze messages for tasks
-export async function analyzeTasksFromMessages(messages, context = {}, modelType = 'gemini') {
+export async function analyzeTasksFromMessages(messages, context = {}, modelType = 'gemini', apiKey = null) {
   try {
     logger.info('Analyzing messages for tasks', { 
       messageCount: Array.isArray(messages) ? messages.length : 1,
       modelType 
     });
 
-    const chain = await createTaskAnalysisChain(modelType);
+    const chain = await createTaskAnalysisChain(modelType, apiKey);
     
     // Format messages for analysis
     const formattedMessages = Array.isArray(messages) 
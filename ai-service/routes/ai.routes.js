@@ -66,7 +66,7 @@ router.post('/analyze-tasks',
       
       logger.info('Task analysis request received', { model });
       
-      const result = await analyzeTasksFromMessages(messages, context, model);
+      const result = await analyzeTasksFromMessages(messages, context, model, req.get('x-ai-api-key'));
       
       res.json({
         success: true,
@@ -92,7 +92,7 @@ router.post('/analyze-project-updates',
         model 
       });
       
-      const result = await analyzeProjectUpdates(messages, project_context, model);
+      const result = await analyzeProjectUpdates(messages, project_context, model, req.get('x-ai-api-key'));
       
       res.json({
         success: true,
@@ -111,7 +111,9 @@ router.post('/summarize',
   validateRequest(SummaryRequest),
   async (req, res, next) => {
     try {
-      const { text, type, options = {} } = req.body;
+      const { text, type } = req.body;
+      // The desktop app sends the key the user saved; without it the service's own key is used
+      const options = { ...(req.body.options || {}), apiKey: req.get('x-ai-api-key') };
       
       logger.info('Summary request received', { type, model: options.model });
       
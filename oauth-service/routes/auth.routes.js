@@ -64,8 +64,10 @@ router.post('/refresh', async (req, res) => {
       expiresIn: process.env.JWT_EXPIRY || '24h'
     });
     
+    const { exp } = jwt.decode(newToken);
     res.json({
-      token: newToken
+      token: newToken,
+      expires_at: new Date(exp * 1000).toISOString()
     });
   } catch (error) {
     logger.error('Token refresh failed', error);
@@ -147,6 +147,29 @@ class QueueManager {
   async addJob(queueName, jobType, data, options = {}) {
     try {
       const queue = this.getQueue(queueName);
+
+      // A jobId is a dedup key: hand back the existing job instead of queueing another.
+      // Only a completed job is replaced; a failed one is kept so it can be inspected and retried.
+      // Bull would also refuse to re-add a completed job with the same id, so clear it first.
+      if (options && options.jobId) {
+        const existing = await queue.getJob(options.jobId);
+        if (existing) {
+          const state = await existing.getState();
+          if (state !== 'completed') {
+            logger.info(`Job ${existing.id} already ${state} in queue ${queueName}, not adding a duplicate`);
+            return {
+              id: existing.id,
+              queue: queueName,
+              type: existing.name,
+              status: state,
+              createdAt: new Date(existing.timestamp).toISOString(),
+              deduplicated: true
+            };
+          }
+          await existing.remove();
+        }
+      }
+
       const job = await queue.add(jobType, data, options);
       
       logger.info(`Job ${job.id} added to queue ${queueName} with type ${jobType}`);
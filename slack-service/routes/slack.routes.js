@@ -58,6 +58,32 @@ router.get('/channels/:channelId/history', async (req, res) => {
   }
 });
 
+// Get the replies of a thread, parent message first
+router.get('/channels/:channelId/threads/:threadTs/replies', async (req, res) => {
+  try {
+    const { channelId, threadTs } = req.params;
+    const { limit, cursor } = req.query;
+    
+    const options = {};
+    if (limit) options.limit = parseInt(limit);
+    if (cursor) options.cursor = cursor;
+    
+    const replies = await slackApp.getThreadReplies(channelId, threadTs, options);
+    
+    res.json({
+      success: true,
+      ...replies
+    });
+  } catch (error) {
+    logger.error('Failed to get thread replies:', error);
+    res.status(500).json({
+      success: false,
+      error: 'Failed to fetch thread replies',
+      message: error.message
+    });
+  }
+});
+
 // Join a channel
 router.post('/channels/:channelId/join', async (req, res) => {
   try {
@@ -265,6 +265,30 @@ class SlackService {
     }
   }
 
+  async getThreadReplies(channelId, threadTs, options = {}) {
+    try {
+      const result = await this.webClient.conversations.replies({
+        channel: channelId,
+        ts: threadTs,
+        limit: options.limit || 100,
+        cursor: options.cursor
+      });
+      
+      if (!result.ok) {
+        throw new Error(`Slack API error: ${result.error}`);
+      }
+      
+      return {
+        messages: result.messages,
+        has_more: result.has_more,
+        response_metadata: result.response_metadata
+      };
+    } catch (error) {
+      logger.error('Error fetching thread replies:', error);
+      throw error;
+    }
+  }
+
   async joinChannel(channelId) {
     try {
       const result = await this.webClient.conversations.join({
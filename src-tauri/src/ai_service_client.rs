@@ -3,6 +3,11 @@ use reqwest;
 use log::{info, warn, error, debug};
 use thiserror::Error;
 use std::time::Duration;
+use std::collections::HashMap;
+use std::future::Future;
+use once_cell::sync::Lazy;
+use crate::service_timeouts::TimeoutPolicy;
+use crate::http_client::{send_traced, RequestTraced, Traced};
 
 #[derive(Error, Debug)]
 pub enum AIServiceError {
@@ -16,6 +21,41 @@ pub enum AIServiceError {
     RateLimitExceeded(u64),
     #[error("Service error: {0}")]
     ServiceError(String),
+    #[error(transparent)]
+    Traced(#[from] Traced<AIServiceError>),
+}
+
+impl RequestTraced for AIServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            AIServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
+}
+
+impl AIServiceError {
+    pub fn kind(&self) -> AIErrorKind {
+        match self {
+            AIServiceError::Http(e) if e.is_timeout() => AIErrorKind::Timeout,
+            AIServiceError::Http(e) if e.is_connect() => AIErrorKind::ServiceDown,
+            AIServiceError::Http(e) if e.is_decode() || e.is_body() => AIErrorKind::BadResponse,
+            AIServiceError::Http(_) => AIErrorKind::ServiceDown,
+            AIServiceError::ServiceUnavailable(_) => AIErrorKind::ServiceDown,
+            AIServiceError::InvalidResponse(_) => AIErrorKind::BadResponse,
+            AIServiceError::RateLimitExceeded(_) => AIErrorKind::RateLimited,
+            AIServiceError::ServiceError(_) => AIErrorKind::ServiceError,
+            AIServiceError::Traced(traced) => traced.source.kind(),
+        }
+    }
+    
+    pub fn info(&self) -> AIErrorInfo {
+        AIErrorInfo {
+            kind: self.kind(),
+            message: self.to_string(),
+            request_id: self.request_id().map(str::to_string),
+        }
+    }
 }
 
 // Request/Response types
@@ -26,6 +66,9 @@ pub struct TaskAnalysisRequest {
     pub context: Option<ProjectContext>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+    /// Replaces the service's default task-extraction prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +115,41 @@ pub struct TaskAnalysisResult {
     pub tasks: Vec<DetectedTask>,
     pub summary: String,
     pub confidence_score: f64,
+    /// Model that produced the analysis, when the service reports it
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    #[serde(default)]
+    pub total_tokens: u64,
+}
+
+/// Coarse classification of an AI service failure, for surfacing to the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AIErrorKind {
+    Timeout,
+    ServiceDown,
+    BadResponse,
+    RateLimited,
+    ServiceError,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIErrorInfo {
+    pub kind: AIErrorKind,
+    pub message: String,
+    /// `X-Request-Id` of the failed request, for matching the error against service logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,10 +194,196 @@ pub struct QueuedJobResponse {
     pub status: String,
 }
 
+/// Result of checking an API key against the AI provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyValidation {
+    pub is_valid: bool,
+    pub provider: String,
+    pub error_message: Option<String>,
+    pub timestamp: String,
+}
+
+/// Messages sent to the AI service per chunk when analyzing large batches
+pub const DEFAULT_ANALYSIS_CHUNK_SIZE: usize = 50;
+
+/// One slice of a chunked analysis and its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisChunk {
+    pub index: usize,
+    pub messages: Vec<Message>,
+    pub succeeded: bool,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub error_kind: Option<AIErrorKind>,
+    pub tasks: Vec<DetectedTask>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+/// A task analysis split into chunks, tracking which chunks succeeded so failed ones
+/// can be retried without re-sending the rest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedAnalysis {
+    pub analysis_id: String,
+    pub context: Option<ProjectContext>,
+    /// Custom prompt sent with every chunk, kept so retries use the same one
+    #[serde(default)]
+    pub prompt: Option<String>,
+    pub chunks: Vec<AnalysisChunk>,
+}
+
+impl ChunkedAnalysis {
+    pub fn new(analysis_id: String, messages: Vec<Message>, context: Option<ProjectContext>, chunk_size: usize) -> Self {
+        let chunks = messages
+            .chunks(chunk_size.max(1))
+            .enumerate()
+            .map(|(index, chunk)| AnalysisChunk {
+                index,
+                messages: chunk.to_vec(),
+                succeeded: false,
+                error: None,
+                error_kind: None,
+                tasks: Vec::new(),
+                model: None,
+                usage: None,
+            })
+            .collect();
+        
+        Self { analysis_id, context, prompt: None, chunks }
+    }
+    
+    pub fn failed_chunk_indices(&self) -> Vec<usize> {
+        self.chunks.iter().filter(|c| !c.succeeded).map(|c| c.index).collect()
+    }
+    
+    /// Tasks from all successful chunks, in chunk order
+    pub fn tasks(&self) -> Vec<DetectedTask> {
+        self.chunks.iter().flat_map(|c| c.tasks.iter().cloned()).collect()
+    }
+    
+    /// Tasks from all chunks with duplicates (same source message) removed
+    pub fn deduplicated_tasks(&self) -> Vec<DetectedTask> {
+        dedup_tasks_by_source_timestamp(self.tasks())
+    }
+    
+    /// Token usage summed over successful chunks, if any chunk reported it
+    pub fn total_usage(&self) -> Option<TokenUsage> {
+        self.chunks.iter().filter_map(|c| c.usage.as_ref()).fold(None, |total, usage| {
+            let mut total: TokenUsage = total.unwrap_or_default();
+            total.prompt_tokens += usage.prompt_tokens;
+            total.completion_tokens += usage.completion_tokens;
+            total.total_tokens += usage.total_tokens;
+            Some(total)
+        })
+    }
+    
+    /// Run `analyze` on the given chunks, recording success or failure for each
+    pub async fn process_chunks<F, Fut>(&mut self, indices: &[usize], mut analyze: F)
+    where
+        F: FnMut(TaskAnalysisRequest) -> Fut,
+        Fut: Future<Output = Result<TaskAnalysisResult, AIServiceError>>,
+    {
+        for &index in indices {
+            let request = match self.chunks.get(index) {
+                Some(chunk) => TaskAnalysisRequest {
+                    messages: MessageInput::Messages(chunk.messages.clone()),
+                    context: self.context.clone(),
+                    model: None,
+                    prompt: self.prompt.clone(),
+                },
+                None => continue,
+            };
+            
+            let result = analyze(request).await;
+            let chunk = &mut self.chunks[index];
+            match result {
+                Ok(result) => {
+                    chunk.succeeded = true;
+                    chunk.error = None;
+                    chunk.error_kind = None;
+                    chunk.tasks = result.tasks;
+                    chunk.model = result.model;
+                    chunk.usage = result.usage;
+                }
+                Err(e) => {
+                    warn!("⚠️ Analysis chunk {} of {} failed: {}", index, self.analysis_id, e);
+                    chunk.succeeded = false;
+                    chunk.error = Some(e.to_string());
+                    chunk.error_kind = Some(e.kind());
+                }
+            }
+        }
+    }
+    
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "analysis_id": self.analysis_id,
+            "tasks": self.tasks(),
+            "total_chunks": self.chunks.len(),
+            "failed_chunks": self.failed_chunk_indices(),
+        })
+    }
+}
+
+/// Drop tasks detected from the same source message more than once (e.g. when overlapping
+/// chunks or retries report it again). Tasks without a source timestamp are all kept.
+pub fn dedup_tasks_by_source_timestamp(tasks: Vec<DetectedTask>) -> Vec<DetectedTask> {
+    let mut seen = std::collections::HashSet::new();
+    tasks
+        .into_iter()
+        .filter(|task| match &task.source_timestamp {
+            Some(ts) => seen.insert(ts.clone()),
+            None => true,
+        })
+        .collect()
+}
+
+// Chunked analyses with failed chunks, kept in memory (with when they were stored) so
+// those chunks can be re-run
+static CHUNKED_ANALYSES: Lazy<std::sync::Mutex<HashMap<String, (std::time::Instant, ChunkedAnalysis)>>> = Lazy::new(|| {
+    std::sync::Mutex::new(HashMap::new())
+});
+
+/// How long an analysis with failed chunks can still be passed to `reanalyze_failed_chunks`
+const CHUNKED_ANALYSIS_TTL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Most analyses kept at once; Slack backfills hold whole channel histories, so the oldest
+/// are dropped first
+const MAX_CHUNKED_ANALYSES: usize = 20;
+
+/// Keep `analysis` for retries while it has failed chunks, forget it once it's complete,
+/// and drop any analysis older than `CHUNKED_ANALYSIS_TTL` or beyond `MAX_CHUNKED_ANALYSES`
+fn remember_chunked_analysis(
+    analyses: &mut HashMap<String, (std::time::Instant, ChunkedAnalysis)>,
+    analysis: &ChunkedAnalysis,
+    now: std::time::Instant,
+) {
+    analyses.retain(|_, (stored_at, _)| now.duration_since(*stored_at) < CHUNKED_ANALYSIS_TTL);
+    if analysis.failed_chunk_indices().is_empty() {
+        analyses.remove(&analysis.analysis_id);
+    } else {
+        analyses.insert(analysis.analysis_id.clone(), (now, analysis.clone()));
+    }
+    while analyses.len() > MAX_CHUNKED_ANALYSES {
+        let oldest = analyses.iter()
+            .min_by_key(|(_, (stored_at, _))| *stored_at)
+            .map(|(id, _)| id.clone());
+        match oldest {
+            Some(id) => analyses.remove(&id),
+            None => break,
+        };
+    }
+}
+
 #[derive(Clone)]
 pub struct AIServiceClient {
     base_url: String,
     client: reqwest::Client,
+    timeouts: TimeoutPolicy,
+    api_key: Option<String>,
 }
 
 impl AIServiceClient {
@@ -128,12 +392,32 @@ impl AIServiceClient {
         
         info!("🚀 Initializing AI Service Client at {}", base_url);
         
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(120)) // 2 minute timeout for AI operations
-            .build()
-            .expect("Failed to create HTTP client");
-        
-        Self { base_url, client }
+        Self { base_url, client: crate::http_client::shared_client(), timeouts: TimeoutPolicy::configured(), api_key: None }
+    }
+    
+    /// Send `api_key` with model calls (as `X-AI-Api-Key`), in place of the key the service
+    /// was started with
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+    
+    /// POST to an endpoint that calls a model, with the user's API key when one is set
+    fn post_model_call(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.post(url);
+        match &self.api_key {
+            Some(api_key) => request.header("X-AI-Api-Key", api_key),
+            None => request,
+        }
+    }
+    
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+    
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
     }
     
     pub async fn health_check(&self) -> Result<bool, AIServiceError> {
@@ -141,7 +425,8 @@ impl AIServiceClient {
         
         let url = format!("{}/health", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("ai", self.client.get(&url).timeout(self.timeouts.health)).await;
+        match response {
             Ok(response) => {
                 if response.status().is_success() {
                     info!("✅ AI service health check passed");
@@ -153,7 +438,7 @@ impl AIServiceClient {
             }
             Err(e) => {
                 error!("❌ AI service health check request failed: {}", e);
-                Err(AIServiceError::Http(e))
+                Err(AIServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -163,13 +448,58 @@ impl AIServiceClient {
         
         let url = format!("{}/api/ai/analyze-tasks", self.base_url);
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "ai",
+            self.post_model_call(&url).json(&request).timeout(self.timeouts.analysis),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
+        
+        self.handle_response::<TaskAnalysisResult>(response).await.map_err(|e| e.with_request_id(&request_id))
+    }
+    
+    /// Analyze messages in chunks, remembering per-chunk results under a new analysis id.
+    /// `prompt` overrides the service's default extraction prompt.
+    pub async fn analyze_tasks_chunked(
+        &self,
+        messages: Vec<Message>,
+        context: Option<ProjectContext>,
+        prompt: Option<String>,
+        chunk_size: usize,
+    ) -> ChunkedAnalysis {
+        let analysis_id = format!("analysis_{}", uuid::Uuid::new_v4().simple());
+        let mut analysis = ChunkedAnalysis::new(analysis_id, messages, context, chunk_size);
+        analysis.prompt = prompt;
+        info!("🧩 Analyzing {} chunks for {}", analysis.chunks.len(), analysis.analysis_id);
         
-        self.handle_response::<TaskAnalysisResult>(response).await
+        let indices: Vec<usize> = (0..analysis.chunks.len()).collect();
+        analysis.process_chunks(&indices, |request| self.analyze_tasks(request)).await;
+        
+        remember_chunked_analysis(
+            &mut CHUNKED_ANALYSES.lock().unwrap_or_else(|e| e.into_inner()),
+            &analysis,
+            std::time::Instant::now(),
+        );
+        analysis
+    }
+    
+    /// Re-run only the chunks of a previous analysis that errored, merging into its result
+    pub async fn reanalyze_failed_chunks(&self, analysis_id: &str) -> Result<ChunkedAnalysis, AIServiceError> {
+        let mut analysis = CHUNKED_ANALYSES.lock().unwrap_or_else(|e| e.into_inner())
+            .get(analysis_id)
+            .filter(|(stored_at, _)| stored_at.elapsed() < CHUNKED_ANALYSIS_TTL)
+            .map(|(_, analysis)| analysis.clone())
+            .ok_or_else(|| AIServiceError::InvalidResponse(format!("Unknown or expired analysis id: {}", analysis_id)))?;
+        
+        let failed = analysis.failed_chunk_indices();
+        info!("🔁 Re-analyzing {} failed chunks for {}", failed.len(), analysis_id);
+        analysis.process_chunks(&failed, |request| self.analyze_tasks(request)).await;
+        
+        remember_chunked_analysis(
+            &mut CHUNKED_ANALYSES.lock().unwrap_or_else(|e| e.into_inner()),
+            &analysis,
+            std::time::Instant::now(),
+        );
+        Ok(analysis)
     }
     
     pub async fn analyze_project_updates(
@@ -188,13 +518,13 @@ impl AIServiceClient {
             "model": model
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "ai",
+            self.post_model_call(&url).json(&request).timeout(self.timeouts.analysis),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<ProjectUpdateResult>(response).await
+        self.handle_response::<ProjectUpdateResult>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn summarize(&self, text: String, options: Option<serde_json::Value>) -> Result<SummaryResult, AIServiceError> {
@@ -208,13 +538,33 @@ impl AIServiceClient {
             "options": options
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "ai",
+            self.post_model_call(&url).json(&request).timeout(self.timeouts.analysis),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<SummaryResult>(response).await
+        self.handle_response::<SummaryResult>(response).await.map_err(|e| e.with_request_id(&request_id))
+    }
+    
+    /// Check an API key with a minimal model call on the AI service
+    pub async fn validate_api_key(&self, api_key: &str, provider: &str) -> Result<ApiKeyValidation, AIServiceError> {
+        info!("🔑 Validating {} API key", provider);
+        
+        let url = format!("{}/api/ai/validate-api-key", self.base_url);
+        
+        let request = serde_json::json!({
+            "apiKey": api_key,
+            "provider": provider
+        });
+        
+        let (request_id, response) = send_traced(
+            "ai",
+            self.client.post(&url).json(&request).timeout(self.timeouts.analysis),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
+        
+        self.handle_response::<ApiKeyValidation>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn queue_analysis(
@@ -233,13 +583,13 @@ impl AIServiceClient {
             "options": options
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "ai",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<QueuedJobResponse>(response).await
+        self.handle_response::<QueuedJobResponse>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn get_job_status(&self, job_id: &str) -> Result<serde_json::Value, AIServiceError> {
@@ -247,12 +597,13 @@ impl AIServiceClient {
         
         let url = format!("{}/api/ai/job/{}", self.base_url, job_id);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "ai",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| AIServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<serde_json::Value>(response).await
+        self.handle_response::<serde_json::Value>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     async fn handle_response<T>(&self, response: reqwest::Response) -> Result<T, AIServiceError> 
@@ -301,7 +652,181 @@ impl AIServiceClient {
         } else {
             error!("❌ AI service request failed with status: {}", status);
             error!("Response: {}", response_text);
-            Err(AIServiceError::ServiceError(format!("HTTP {}: {}", status, response_text)))
+            match status {
+                reqwest::StatusCode::BAD_GATEWAY
+                | reqwest::StatusCode::SERVICE_UNAVAILABLE
+                | reqwest::StatusCode::GATEWAY_TIMEOUT => {
+                    Err(AIServiceError::ServiceUnavailable(format!("HTTP {}: {}", status, response_text)))
+                }
+                _ => Err(AIServiceError::ServiceError(format!("HTTP {}: {}", status, response_text))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_classified_for_the_ui() {
+        assert_eq!(AIServiceError::ServiceUnavailable("down".to_string()).kind(), AIErrorKind::ServiceDown);
+        assert_eq!(AIServiceError::InvalidResponse("bad json".to_string()).kind(), AIErrorKind::BadResponse);
+        assert_eq!(AIServiceError::RateLimitExceeded(30).kind(), AIErrorKind::RateLimited);
+        
+        let info = AIServiceError::ServiceError("invalid api key".to_string()).info();
+        assert_eq!(info.kind, AIErrorKind::ServiceError);
+        assert!(info.message.contains("invalid api key"));
+    }
+
+    #[test]
+    fn request_ids_survive_wrapping() {
+        let error = AIServiceError::RateLimitExceeded(30)
+            .with_request_id("req-1")
+            .with_request_id("req-2");
+        assert_eq!(error.request_id(), Some("req-1"));
+        assert_eq!(error.kind(), AIErrorKind::RateLimited);
+        assert!(matches!(error.untraced(), AIServiceError::RateLimitExceeded(30)));
+
+        let info = error.info();
+        assert_eq!(info.request_id.as_deref(), Some("req-1"));
+        assert!(info.message.contains("req-1"));
+    }
+
+    #[test]
+    fn analysis_result_reads_model_and_usage_when_present() {
+        let result: TaskAnalysisResult = serde_json::from_value(serde_json::json!({
+            "tasks": [], "summary": "", "confidence_score": 0.9,
+            "model": "gpt-4o-mini", "usage": {"prompt_tokens": 120, "completion_tokens": 30, "total_tokens": 150}
+        })).unwrap();
+        assert_eq!(result.model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(result.usage.unwrap().total_tokens, 150);
+        
+        let legacy: TaskAnalysisResult = serde_json::from_value(serde_json::json!({
+            "tasks": [], "summary": "", "confidence_score": 0.9
+        })).unwrap();
+        assert!(legacy.model.is_none());
+    }
+
+    fn message(text: &str) -> Message {
+        Message {
+            text: text.to_string(),
+            user: "U1".to_string(),
+            timestamp: "1700000000.000100".to_string(),
+        }
+    }
+
+    fn task(title: &str) -> DetectedTask {
+        DetectedTask {
+            title: title.to_string(),
+            description: String::new(),
+            assignee: None,
+            priority: "medium".to_string(),
+            status: "todo".to_string(),
+            source_message: title.to_string(),
+            source_user: "U1".to_string(),
+            source_timestamp: None,
+            estimated_hours: None,
+            due_date: None,
+            tags: Vec::new(),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn duplicate_source_messages_are_dropped() {
+        let with_ts = |title: &str, ts: Option<&str>| DetectedTask {
+            source_timestamp: ts.map(|t| t.to_string()),
+            ..task(title)
+        };
+        let tasks = vec![
+            with_ts("a", Some("1.1")),
+            with_ts("a again", Some("1.1")),
+            with_ts("b", Some("2.2")),
+            with_ts("c", None),
+            with_ts("d", None),
+        ];
+
+        let titles: Vec<String> = dedup_tasks_by_source_timestamp(tasks).into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, vec!["a", "b", "c", "d"]);
+    }
+
+    fn first_text(request: &TaskAnalysisRequest) -> String {
+        match &request.messages {
+            MessageInput::Messages(messages) => messages[0].text.clone(),
+            MessageInput::Text(text) => text.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reanalysis_only_resends_failed_chunks() {
+        let messages = vec![message("a"), message("b"), message("c")];
+        let mut analysis = ChunkedAnalysis::new("analysis_test".to_string(), messages, None, 1);
+
+        analysis.process_chunks(&[0, 1, 2], |request| {
+            let text = first_text(&request);
+            async move {
+                if text == "b" {
+                    Err(AIServiceError::ServiceUnavailable("down".to_string()))
+                } else {
+                    Ok(TaskAnalysisResult { tasks: vec![task(&text)], summary: String::new(), confidence_score: 1.0, model: None, usage: None })
+                }
+            }
+        }).await;
+        assert_eq!(analysis.failed_chunk_indices(), vec![1]);
+
+        let mut sent = Vec::new();
+        let failed = analysis.failed_chunk_indices();
+        analysis.process_chunks(&failed, |request| {
+            let text = first_text(&request);
+            sent.push(text.clone());
+            async move {
+                Ok(TaskAnalysisResult { tasks: vec![task(&text)], summary: String::new(), confidence_score: 1.0, model: None, usage: None })
+            }
+        }).await;
+
+        assert_eq!(sent, vec!["b".to_string()]);
+        assert!(analysis.failed_chunk_indices().is_empty());
+        let titles: Vec<String> = analysis.tasks().into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn only_recent_analyses_with_failed_chunks_are_kept() {
+        let start = std::time::Instant::now();
+        let mut analyses = HashMap::new();
+        let mut failed = ChunkedAnalysis::new("analysis_failed".to_string(), vec![message("a")], None, 1);
+        remember_chunked_analysis(&mut analyses, &failed, start);
+        assert!(analyses.contains_key("analysis_failed"));
+
+        // Completing the retry forgets it
+        failed.chunks[0].succeeded = true;
+        remember_chunked_analysis(&mut analyses, &failed, start);
+        assert!(analyses.is_empty());
+
+        let stale = ChunkedAnalysis::new("analysis_stale".to_string(), vec![message("b")], None, 1);
+        remember_chunked_analysis(&mut analyses, &stale, start);
+        let fresh = ChunkedAnalysis::new("analysis_fresh".to_string(), vec![message("c")], None, 1);
+        remember_chunked_analysis(&mut analyses, &fresh, start + CHUNKED_ANALYSIS_TTL);
+        assert_eq!(analyses.keys().collect::<Vec<_>>(), vec!["analysis_fresh"]);
+
+        for i in 0..MAX_CHUNKED_ANALYSES {
+            let newer = ChunkedAnalysis::new(format!("analysis_{}", i), vec![message("d")], None, 1);
+            remember_chunked_analysis(&mut analyses, &newer, start + CHUNKED_ANALYSIS_TTL + std::time::Duration::from_secs(i as u64 + 1));
+        }
+        assert_eq!(analyses.len(), MAX_CHUNKED_ANALYSES);
+        assert!(!analyses.contains_key("analysis_fresh"));
+    }
+
+    #[test]
+    fn health_check_timeout_is_shorter_than_analysis_timeout() {
+        let client = AIServiceClient::new(None);
+        let policy = client.timeout_policy();
+
+        assert!(policy.health < policy.analysis);
+        assert!(policy.status <= policy.standard);
+
+        let custom = TimeoutPolicy { health: Duration::from_secs(1), ..TimeoutPolicy::default() };
+        let client = client.with_timeout_policy(custom);
+        assert_eq!(client.timeout_policy().health, Duration::from_secs(1));
+    }
+}
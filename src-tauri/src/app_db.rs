@@ -0,0 +1,52 @@
+// Access to the frontend's SQLite database (`project_boxes.db` in the app data directory).
+// Connections are opened and used on a blocking thread, never on the async runtime.
+
+use std::path::PathBuf;
+
+use tauri::Manager;
+
+/// Path of the app database, creating the app data directory if needed
+pub fn app_db_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_data_dir.join("project_boxes.db"))
+}
+
+/// Open the database at `db_path` on a blocking thread and run `f` on it
+pub async fn with_db_at<T, F>(db_path: PathBuf, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut rusqlite::Connection) -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let mut conn = rusqlite::Connection::open(&db_path)
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        f(&mut conn)
+    })
+    .await
+    .map_err(|e| format!("Database task failed: {}", e))?
+}
+
+/// Run `f` against the app database, creating the file if the frontend hasn't yet
+pub async fn with_app_db<T, F>(app: &tauri::AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut rusqlite::Connection) -> Result<T, String> + Send + 'static,
+{
+    with_db_at(app_db_path(app)?, f).await
+}
+
+/// Run `f` against the app database; `None` before the database file has been created
+pub async fn with_existing_app_db<T, F>(app: &tauri::AppHandle, f: F) -> Result<Option<T>, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut rusqlite::Connection) -> Result<T, String> + Send + 'static,
+{
+    let db_path = app_db_path(app)?;
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    with_db_at(db_path, f).await.map(Some)
+}
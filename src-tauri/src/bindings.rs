@@ -0,0 +1,89 @@
+// TypeScript bindings for the types commands exchange with the frontend, generated with
+// Specta so the frontend types can't drift from the Rust structs. The file is committed;
+// debug builds rewrite it on startup and the tests fail while it is stale.
+
+use std::path::Path;
+
+use specta::ts::{self, BigIntExportBehavior, ExportConfiguration};
+
+/// The committed bindings file, rewritten by debug builds
+pub const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/types/bindings.ts");
+
+/// Every exported type, in the order they appear in the bindings file
+macro_rules! export_all {
+    ($conf:expr; $($ty:ty),* $(,)?) => {
+        vec![$(ts::export::<$ty>($conf)),*]
+    };
+}
+
+/// The bindings file contents
+pub fn typescript_bindings() -> Result<String, String> {
+    // Timestamps and counts are i64 but stay well within a JS number
+    let conf = ExportConfiguration::default().bigint(BigIntExportBehavior::Number);
+    let exports = export_all![&conf;
+        crate::slack_sync::SlackSyncMetadata,
+        crate::slack_sync::ConnectedChannel,
+        crate::credentials::SlackCredentialsStatus,
+        crate::commands::slack_integration::SyncRevalidation,
+        crate::commands::slack_integration::SyncNowResult,
+        crate::commands::slack_integration::ChannelConnection,
+        crate::slack_message_store::StoredSlackMessage,
+        crate::whatsapp_service_client::HealthStatus,
+        crate::whatsapp_service_client::WhatsAppConnectionState,
+        crate::whatsapp_service_client::WhatsAppMessage,
+        crate::whatsapp_service_client::WhatsAppChat,
+        crate::whatsapp_service_client::MarkProcessedResult,
+        crate::queue_service_client::JobStatus,
+        crate::queue_service_client::JobInfo,
+        crate::project_commands::ProjectPage,
+        crate::log_sink::LogLine,
+        crate::commands::command_error::CommandError,
+    ];
+
+    let mut bindings = String::from("// Generated from the Rust command types by src-tauri/src/bindings.rs; do not edit.\n");
+    for export in exports {
+        bindings.push('\n');
+        bindings.push_str(&export.map_err(|e| format!("Failed to export type: {:?}", e))?);
+        bindings.push('\n');
+    }
+    Ok(bindings)
+}
+
+/// Write the bindings to `path`, leaving the file alone when nothing changed
+pub fn export_bindings(path: &Path) -> Result<(), String> {
+    let bindings = typescript_bindings()?;
+    if std::fs::read_to_string(path).ok().as_deref() == Some(bindings.as_str()) {
+        return Ok(());
+    }
+    std::fs::write(path, bindings).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_command_type_exports() {
+        let bindings = typescript_bindings().unwrap();
+
+        assert!(bindings.contains("SlackSyncMetadata"));
+        assert!(bindings.contains("WhatsAppConnectionState"));
+        assert!(bindings.contains("CommandError"));
+        // serde renames carry over to the TypeScript field names
+        assert!(bindings.contains("projectId"));
+    }
+
+    #[test]
+    fn committed_bindings_are_current() {
+        let path = Path::new(BINDINGS_PATH);
+        if std::env::var_os("UPDATE_BINDINGS").is_some() {
+            export_bindings(path).unwrap();
+        }
+
+        let committed = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(
+            committed == typescript_bindings().unwrap(),
+            "src/types/bindings.ts is stale; regenerate it with `UPDATE_BINDINGS=1 cargo test committed_bindings_are_current`"
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use tauri::AppHandle;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde_json;
 use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use crate::app_db::{with_app_db, with_existing_app_db};
 
 // Validation helper functions
 pub fn validate_event_id(event_id: &str) -> Result<(), String> {
@@ -31,12 +34,420 @@ pub fn validate_date_range(start_date: &str, end_date: &str) -> Result<(), Strin
     Ok(())
 }
 
-pub async fn create_calendar_event(
-    _app: AppHandle,
-    event: serde_json::Value,
-) -> Result<serde_json::Value, String> {
-    println!("📅 [create_calendar_event] Creating calendar event: {:?}", event);
+/// Timezone assumed for events stored without one
+const DEFAULT_TIMEZONE: &str = "UTC";
+
+pub fn parse_timezone(timezone: &str) -> Result<Tz, String> {
+    timezone.trim().parse::<Tz>()
+        .map_err(|_| format!("Invalid timezone '{}': expected an IANA name like Europe/Lisbon", timezone))
+}
+
+/// Resolve a wall-clock time in `tz` to a UTC instant. Ambiguous times (DST fall-back) use the
+/// earlier instant; times skipped by a DST jump are moved forward by an hour.
+fn local_to_utc(naive: NaiveDateTime, tz: Tz) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(naive + chrono::Duration::hours(1))).earliest())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Parse a range boundary. Values with an explicit offset are used as-is; local values
+/// (`YYYY-MM-DDTHH:MM:SS` or `YYYY-MM-DD`) are interpreted in the caller's timezone.
+pub fn resolve_boundary(value: &str, tz: Tz) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|d| d.and_hms_opt(0, 0, 0).unwrap()))
+        .map_err(|_| format!("Invalid date '{}'", value))?;
+    
+    local_to_utc(naive, tz).ok_or_else(|| format!("Date '{}' does not exist in {}", value, tz))
+}
+
+/// Add `*_utc` and `*_local` instants for the event's start/end in its stored timezone
+pub fn with_local_instants(event: &mut serde_json::Value) -> Result<(), String> {
+    let tz = parse_timezone(event.get("timezone").and_then(|v| v.as_str()).unwrap_or(DEFAULT_TIMEZONE))?;
+    
+    for key in ["start_date", "end_date"] {
+        if let Some(instant) = parse_event_instant(event, key) {
+            event[format!("{}_utc", key)] = serde_json::json!(instant.to_rfc3339());
+            event[format!("{}_local", key)] = serde_json::json!(instant.with_timezone(&tz).to_rfc3339());
+        }
+    }
+    
+    Ok(())
+}
+
+/// Recurrence rule restricted to the subset we can expand (FREQ, INTERVAL, COUNT, UNTIL, BYDAY)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedRrule {
+    pub freq: String,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<String>,
+    pub by_day: Vec<String>,
+    pub rrule: String,
+}
+
+const SUPPORTED_FREQUENCIES: [&str; 3] = ["DAILY", "WEEKLY", "MONTHLY"];
+const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+fn parse_rrule_until(value: &str) -> Result<String, String> {
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(dt.and_utc().to_rfc3339());
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Ok(date.and_hms_opt(23, 59, 59).unwrap().and_utc().to_rfc3339());
+    }
+    Err(format!("Invalid UNTIL value '{}': expected YYYYMMDD or YYYYMMDDTHHMMSSZ", value))
+}
+
+pub fn validate_rrule(rrule: &str) -> Result<NormalizedRrule, String> {
+    let body = rrule.trim();
+    let body = body.strip_prefix("RRULE:").unwrap_or(body);
+    if body.is_empty() {
+        return Err("RRULE cannot be empty".to_string());
+    }
+
+    let mut freq = None;
+    let mut interval = 1;
+    let mut count = None;
+    let mut until = None;
+    let mut by_day = Vec::new();
+
+    for part in body.split(';').filter(|p| !p.trim().is_empty()) {
+        let (key, value) = part.split_once('=')
+            .ok_or_else(|| format!("Malformed RRULE part '{}'", part))?;
+        let key = key.trim().to_uppercase();
+        let value = value.trim().to_uppercase();
+
+        match key.as_str() {
+            "FREQ" => {
+                if !SUPPORTED_FREQUENCIES.contains(&value.as_str()) {
+                    return Err(format!("Unsupported FREQ '{}': only DAILY, WEEKLY and MONTHLY are supported", value));
+                }
+                freq = Some(value);
+            }
+            "INTERVAL" => {
+                interval = value.parse::<u32>()
+                    .ok()
+                    .filter(|i| *i > 0)
+                    .ok_or_else(|| format!("Invalid INTERVAL '{}': must be a positive integer", value))?;
+            }
+            "COUNT" => {
+                count = Some(value.parse::<u32>()
+                    .ok()
+                    .filter(|c| *c > 0)
+                    .ok_or_else(|| format!("Invalid COUNT '{}': must be a positive integer", value))?);
+            }
+            "UNTIL" => until = Some(parse_rrule_until(&value)?),
+            "BYDAY" => {
+                for day in value.split(',') {
+                    if !WEEKDAYS.contains(&day) {
+                        return Err(format!("Unsupported BYDAY value '{}': only plain weekdays (MO..SU) are supported", day));
+                    }
+                    if !by_day.iter().any(|d| d == day) {
+                        by_day.push(day.to_string());
+                    }
+                }
+            }
+            other => return Err(format!("Unsupported RRULE part '{}'", other)),
+        }
+    }
+
+    let freq = freq.ok_or("RRULE is missing FREQ".to_string())?;
+    if count.is_some() && until.is_some() {
+        return Err("RRULE cannot contain both COUNT and UNTIL".to_string());
+    }
+    if !by_day.is_empty() && freq != "WEEKLY" {
+        return Err("BYDAY is only supported with FREQ=WEEKLY".to_string());
+    }
+    by_day.sort_by_key(|d| WEEKDAYS.iter().position(|w| w == d));
+
+    // Canonical form so equivalent rules compare equal
+    let mut parts = vec![format!("FREQ={}", freq)];
+    if interval != 1 {
+        parts.push(format!("INTERVAL={}", interval));
+    }
+    if let Some(c) = count {
+        parts.push(format!("COUNT={}", c));
+    }
+    if let Some(u) = &until {
+        let until_dt = DateTime::parse_from_rfc3339(u).map_err(|e| e.to_string())?;
+        parts.push(format!("UNTIL={}", until_dt.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ")));
+    }
+    if !by_day.is_empty() {
+        parts.push(format!("BYDAY={}", by_day.join(",")));
+    }
+
+    Ok(NormalizedRrule {
+        freq,
+        interval,
+        count,
+        until,
+        by_day,
+        rrule: parts.join(";"),
+    })
+}
+
+/// Upper bound on instances produced for one recurring event in a single range query
+const MAX_EXPANDED_INSTANCES: usize = 500;
+
+/// Candidate occurrence starts for the period `offset` frequency units after `start`
+fn period_candidates(start: DateTime<Utc>, rule: &NormalizedRrule, offset: i64) -> Vec<DateTime<Utc>> {
+    match rule.freq.as_str() {
+        "DAILY" => vec![start + chrono::Duration::days(offset)],
+        "WEEKLY" if rule.by_day.is_empty() => vec![start + chrono::Duration::weeks(offset)],
+        "WEEKLY" => {
+            let week_start = start
+                - chrono::Duration::days(start.weekday().num_days_from_monday() as i64)
+                + chrono::Duration::weeks(offset);
+            rule.by_day.iter()
+                .filter_map(|day| WEEKDAYS.iter().position(|w| w == day))
+                .map(|days| week_start + chrono::Duration::days(days as i64))
+                .collect()
+        }
+        "MONTHLY" => {
+            // Months without the start's day of month (e.g. the 31st) are skipped, as in RFC 5545
+            let months = start.month0() as i64 + offset;
+            let year = start.year() + months.div_euclid(12) as i32;
+            let month = months.rem_euclid(12) as u32 + 1;
+            chrono::NaiveDate::from_ymd_opt(year, month, start.day())
+                .map(|date| date.and_time(start.time()).and_utc())
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Start times of the occurrences of `rule` (first occurrence at `start`) that overlap the window
+pub fn expand_occurrences(
+    start: DateTime<Utc>,
+    duration: chrono::Duration,
+    rule: &NormalizedRrule,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let until = rule.until.as_deref()
+        .and_then(|u| DateTime::parse_from_rfc3339(u).ok())
+        .map(|u| u.with_timezone(&Utc));
+    let interval = rule.interval.max(1) as i64;
+
+    let mut occurrences = Vec::new();
+    let mut seen = 0u32;
+    let mut period = 0i64;
+
+    loop {
+        for occurrence in period_candidates(start, rule, interval * period) {
+            if occurrence < start {
+                continue;
+            }
+            if occurrence > window_end || until.map_or(false, |u| occurrence > u) {
+                return occurrences;
+            }
+            // COUNT includes occurrences before the window
+            if rule.count.map_or(false, |c| seen >= c) {
+                return occurrences;
+            }
+            seen += 1;
+
+            if occurrence + duration >= window_start {
+                occurrences.push(occurrence);
+                if occurrences.len() >= MAX_EXPANDED_INSTANCES {
+                    return occurrences;
+                }
+            }
+        }
+        period += 1;
+    }
+}
+
+/// Expand a stored event into the concrete instances that fall in the window. Events without
+/// a `recurrence` rule are returned unchanged.
+pub fn expand_event_instances(
+    event: &serde_json::Value,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let rule = match event.get("recurrence").and_then(|v| v.as_str()) {
+        Some(recurrence) => validate_rrule(recurrence)?,
+        None => return Ok(vec![event.clone()]),
+    };
+
+    let start = event.get("start_date")
+        .and_then(|v| v.as_str())
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|v| v.with_timezone(&Utc))
+        .ok_or("Recurring event has an invalid start_date")?;
+    let end = event.get("end_date")
+        .and_then(|v| v.as_str())
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|v| v.with_timezone(&Utc));
+    let duration = end.map(|e| e - start).unwrap_or_else(chrono::Duration::zero);
+    let event_id = event.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+    let tz = parse_timezone(event.get("timezone").and_then(|v| v.as_str()).unwrap_or(DEFAULT_TIMEZONE))?;
+
+    // Expand on the event's wall clock so instances keep their local time across DST changes
+    let as_local = |instant: DateTime<Utc>| instant.with_timezone(&tz).naive_local().and_utc();
+    let occurrences = expand_occurrences(as_local(start), duration, &rule, as_local(window_start), as_local(window_end));
+
+    let instances = occurrences
+        .into_iter()
+        .filter_map(|local| local_to_utc(local.naive_utc(), tz))
+        .map(|occurrence| {
+            let mut instance = event.clone();
+            instance["id"] = serde_json::json!(format!("{}_{}", event_id, occurrence.format("%Y%m%dT%H%M%SZ")));
+            instance["recurring_event_id"] = serde_json::json!(event_id);
+            instance["start_date"] = serde_json::json!(occurrence.to_rfc3339());
+            if end.is_some() {
+                instance["end_date"] = serde_json::json!((occurrence + duration).to_rfc3339());
+            }
+            instance
+        })
+        .collect();
+
+    Ok(instances)
+}
+
+/// Length assumed for events without an end date when checking for conflicts
+const DEFAULT_EVENT_DURATION_MINUTES: i64 = 30;
+
+fn parse_event_instant(event: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    event.get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|v| v.with_timezone(&Utc))
+}
+
+/// Whether an event overlaps `[range_start, range_end)`. Both ranges are inclusive at the start
+/// and exclusive at the end, so back-to-back events don't conflict.
+pub fn event_overlaps(event: &serde_json::Value, range_start: DateTime<Utc>, range_end: DateTime<Utc>) -> bool {
+    let start = match parse_event_instant(event, "start_date") {
+        Some(start) => start,
+        None => return false,
+    };
+    let end = parse_event_instant(event, "end_date")
+        .unwrap_or_else(|| start + chrono::Duration::minutes(DEFAULT_EVENT_DURATION_MINUTES));
+    
+    start < range_end && range_start < end
+}
+
+/// Start of a stored event as RFC 3339, reading dates saved without an offset as UTC
+fn stored_event_start(date: &str) -> String {
+    resolve_boundary(date, Tz::UTC)
+        .map(|start| start.to_rfc3339())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Add the `timezone` column to `events` if the frontend hasn't migrated the table yet
+fn ensure_event_timezone_column(conn: &rusqlite::Connection) -> Result<(), String> {
+    if !crate::slack_sync::has_column(conn, "events", "timezone")? {
+        conn.execute("ALTER TABLE events ADD COLUMN timezone TEXT", [])
+            .map_err(|e| format!("Failed to add column timezone: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Events saved in the frontend's `events` table, optionally only one project's, in the
+/// shape the calendar commands return. Returns nothing before the table exists.
+pub fn load_stored_events(conn: &rusqlite::Connection, project_id: Option<&str>) -> Result<Vec<serde_json::Value>, String> {
+    query_stored_events(conn, project_id, None)
+}
+
+/// One stored event, if it exists
+pub fn load_stored_event(conn: &rusqlite::Connection, event_id: &str) -> Result<Option<serde_json::Value>, String> {
+    Ok(query_stored_events(conn, None, Some(event_id))?.into_iter().next())
+}
+
+fn query_stored_events(
+    conn: &rusqlite::Connection,
+    project_id: Option<&str>,
+    event_id: Option<&str>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let table_exists: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'events'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .map_err(|e| format!("Failed to inspect database: {}", e))?;
+    if !table_exists {
+        return Ok(vec![]);
+    }
+    
+    // Events saved before timezones were stored read back as UTC
+    let timezone_column = if crate::slack_sync::has_column(conn, "events", "timezone")? { "timezone" } else { "NULL" };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, projectId, title, description, date, type, duration, recurring, createdAt, updatedAt, {}
+             FROM events
+             WHERE (?1 IS NULL OR projectId = ?1) AND (?2 IS NULL OR id = ?2)
+             ORDER BY date",
+            timezone_column
+        ))
+        .map_err(|e| format!("Failed to prepare event query: {}", e))?;
+    let rows = stmt
+        .query_map([project_id, event_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<i64>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+                row.get::<_, Option<String>>(9)?,
+                row.get::<_, Option<String>>(10)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to load events: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to load events: {}", e))?;
+    
+    Ok(rows.into_iter().map(|(id, project_id, title, description, date, event_type, duration, recurring, created_at, updated_at, timezone)| {
+        let start_date = stored_event_start(&date);
+        let end_date = duration
+            .zip(DateTime::parse_from_rfc3339(&start_date).ok())
+            .map(|(minutes, start)| (start + chrono::Duration::minutes(minutes)).to_rfc3339());
+        // The frontend keeps labels like "none" here; only real rules are expanded
+        let recurrence = recurring.and_then(|rule| validate_rrule(&rule).ok()).map(|rule| rule.rrule);
+        serde_json::json!({
+            "id": id,
+            "project_id": project_id,
+            "title": title,
+            "description": description,
+            "start_date": start_date,
+            "end_date": end_date,
+            "event_type": event_type,
+            "recurrence": recurrence,
+            "timezone": timezone.unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()),
+            "created_at": created_at,
+            "updated_at": updated_at
+        })
+    }).collect())
+}
+
+/// Events (including recurring instances) that overlap `[range_start, range_end)`
+pub async fn find_conflicts(
+    app: AppHandle,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let events = get_events_in_range(app, range_start.to_rfc3339(), range_end.to_rfc3339(), None, None).await?;
     
+    Ok(events.into_iter()
+        .filter(|event| event_overlaps(event, range_start, range_end))
+        .collect())
+}
+
+/// Validate `create_calendar_event` input and build the event record to store
+pub fn build_event_record(event: &serde_json::Value, event_id: &str, now: &str) -> Result<serde_json::Value, String> {
     let title = event.get("title")
         .and_then(|v| v.as_str())
         .ok_or("Missing or invalid title")?;
@@ -66,114 +477,268 @@ pub async fn create_calendar_event(
     let created_by = event.get("createdBy")
         .and_then(|v| v.as_str())
         .unwrap_or("system");
+    
+    let project_id = event.get("projectId")
+        .and_then(|v| v.as_str());
+    if let Some(project_id) = project_id {
+        validate_project_id(project_id)?;
+    }
+    
+    let event_type = event.get("eventType")
+        .and_then(|v| v.as_str())
+        .unwrap_or("other");
+    
+    let recurrence = match event.get("recurrence").and_then(|v| v.as_str()) {
+        Some(rule) => Some(validate_rrule(rule)?.rrule),
+        None => None,
+    };
+    
+    let timezone = event.get("timezone")
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_TIMEZONE);
+    let timezone = parse_timezone(timezone)?.name().to_string();
 
     // Validate date if end_date is provided
     if let Some(end) = end_date {
         validate_date_range(start_date, end)?;
     }
-
-    let event_id = Uuid::new_v4().to_string();
-    let now = Utc::now().to_rfc3339();
+    DateTime::parse_from_rfc3339(start_date)
+        .map_err(|_| "Invalid start date format".to_string())?;
     
-    // This would normally insert into the events table
-    let created_event = serde_json::json!({
+    Ok(serde_json::json!({
         "id": event_id,
+        "project_id": project_id,
         "title": title,
         "description": description,
         "start_date": start_date,
         "end_date": end_date,
         "is_all_day": is_all_day,
+        "event_type": event_type,
         "source": source,
         "source_message_id": source_message_id,
         "created_by": created_by,
+        "recurrence": recurrence,
+        "timezone": timezone,
         "created_at": now,
         "updated_at": now
-    });
+    }))
+}
+
+/// The span an event record blocks, `[start, end)`
+fn event_record_range(record: &serde_json::Value) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let start = parse_event_instant(record, "start_date").ok_or("Invalid start date format")?;
+    let end = parse_event_instant(record, "end_date")
+        .unwrap_or_else(|| start + chrono::Duration::minutes(DEFAULT_EVENT_DURATION_MINUTES));
+    Ok((start, end))
+}
+
+/// Stored events (including recurring instances) that overlap `[range_start, range_end)`
+pub fn stored_conflicts(
+    conn: &rusqlite::Connection,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut conflicts = Vec::new();
+    for event in load_stored_events(conn, None)? {
+        conflicts.extend(expand_event_instances(&event, range_start, range_end)?
+            .into_iter()
+            .filter(|instance| event_overlaps(instance, range_start, range_end)));
+    }
+    Ok(conflicts)
+}
+
+/// Save an event record built by `build_event_record` to the `events` table. The record
+/// needs a project, since every stored event belongs to one.
+pub fn insert_event(conn: &rusqlite::Connection, record: &serde_json::Value) -> Result<(), String> {
+    let project_id = record["project_id"].as_str().ok_or("Events can only be saved for a project")?;
+    let (start, end) = event_record_range(record)?;
+    let duration = record["end_date"].as_str().map(|_| (end - start).num_minutes());
+    ensure_event_timezone_column(conn)?;
+    
+    conn.execute(
+        "INSERT INTO events (id, projectId, title, description, date, type, duration, recurring, timezone, createdAt, updatedAt, syncStatus)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10, 'local')",
+        rusqlite::params![
+            record["id"].as_str(),
+            project_id,
+            record["title"].as_str(),
+            record["description"].as_str(),
+            start.to_rfc3339(),
+            record["event_type"].as_str().unwrap_or("other"),
+            duration,
+            record["recurrence"].as_str(),
+            record["timezone"].as_str().unwrap_or(DEFAULT_TIMEZONE),
+            record["created_at"].as_str(),
+        ],
+    )
+    .map_err(|e| format!("Failed to save event: {}", e))?;
+    Ok(())
+}
+
+/// Apply `update_event` input to a stored event. Fields left out keep their stored values;
+/// `timezone` and `recurrence` are validated like on create. Returns the updated event.
+pub fn update_stored_event(
+    conn: &rusqlite::Connection,
+    event_id: &str,
+    event_data: &serde_json::Value,
+    now: &str,
+) -> Result<serde_json::Value, String> {
+    let stored = load_stored_event(conn, event_id)?
+        .ok_or_else(|| format!("Event {} not found", event_id))?;
+    let field = |key: &str| event_data.get(key).filter(|v| !v.is_null()).or_else(|| stored.get(key));
+    
+    let recurrence = match event_data.get("recurrence").and_then(|v| v.as_str()) {
+        Some(rule) => Some(validate_rrule(rule)?.rrule),
+        None => stored["recurrence"].as_str().map(String::from),
+    };
+    let timezone = parse_timezone(field("timezone").and_then(|v| v.as_str()).unwrap_or(DEFAULT_TIMEZONE))?
+        .name()
+        .to_string();
+    
+    let start_date = field("start_date").and_then(|v| v.as_str()).ok_or("Invalid start date format")?;
+    let start = DateTime::parse_from_rfc3339(start_date)
+        .map_err(|_| "Invalid start date format".to_string())?
+        .with_timezone(&Utc);
+    let end_date = field("end_date").and_then(|v| v.as_str());
+    if let Some(end) = end_date {
+        validate_date_range(start_date, end)?;
+    }
+    let duration = end_date
+        .and_then(|end| DateTime::parse_from_rfc3339(end).ok())
+        .map(|end| (end.with_timezone(&Utc) - start).num_minutes());
+    
+    ensure_event_timezone_column(conn)?;
+    conn.execute(
+        "UPDATE events SET title = ?1, description = ?2, date = ?3, duration = ?4, recurring = ?5, timezone = ?6, updatedAt = ?7
+         WHERE id = ?8",
+        rusqlite::params![
+            field("title").and_then(|v| v.as_str()),
+            field("description").and_then(|v| v.as_str()),
+            start.to_rfc3339(),
+            duration,
+            recurrence,
+            timezone,
+            now,
+            event_id,
+        ],
+    )
+    .map_err(|e| format!("Failed to update event: {}", e))?;
+    
+    load_stored_event(conn, event_id)?.ok_or_else(|| format!("Event {} not found", event_id))
+}
+
+pub async fn create_calendar_event(
+    app: AppHandle,
+    event: serde_json::Value,
+    force: bool,
+) -> Result<serde_json::Value, String> {
+    println!("📅 [create_calendar_event] Creating calendar event: {:?}", event);
+    
+    let event_id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let mut record = build_event_record(&event, &event_id, &now)?;
+    
+    // Refuse to double-book unless the caller explicitly forces it
+    let (range_start, range_end) = event_record_range(&record)?;
+    let conflicts = find_conflicts(app.clone(), range_start, range_end).await?;
+    if !conflicts.is_empty() {
+        if !force {
+            println!("⚠️ [create_calendar_event] {} conflicting events, not creating", conflicts.len());
+            return Ok(serde_json::json!({
+                "created": false,
+                "conflicts": conflicts
+            }));
+        }
+        println!("⚠️ [create_calendar_event] Creating despite {} conflicting events", conflicts.len());
+    }
+    
+    // Project events are saved; others are only returned for the caller to keep
+    if record["project_id"].is_string() {
+        let to_save = record.clone();
+        with_app_db(&app, move |conn| insert_event(conn, &to_save)).await?;
+    }
+    
+    record["created"] = serde_json::json!(true);
+    record["conflicts"] = serde_json::json!(conflicts);
     
     println!("✅ [create_calendar_event] Event created with ID: {}", event_id);
-    Ok(created_event)
+    Ok(record)
 }
 
 pub async fn get_event_by_id(
-    _app: AppHandle,
+    app: AppHandle,
     event_id: String,
 ) -> Result<serde_json::Value, String> {
     println!("🔍 [get_event_by_id] Fetching event: {}", event_id);
     
     validate_event_id(&event_id)?;
 
-    // This would normally query the events table
-    let mock_event = serde_json::json!({
-        "id": event_id,
-        "title": "Sample Event",
-        "description": "Event description",
-        "start_date": Utc::now().to_rfc3339(),
-        "end_date": null,
-        "is_all_day": false,
-        "source": "manual",
-        "created_by": "user@example.com",
-        "created_at": Utc::now().to_rfc3339(),
-        "updated_at": Utc::now().to_rfc3339()
-    });
+    let id = event_id.clone();
+    let mut event = with_app_db(&app, move |conn| load_stored_event(conn, &id)).await?
+        .ok_or_else(|| format!("Event {} not found", event_id))?;
+    with_local_instants(&mut event)?;
     
     println!("✅ [get_event_by_id] Event fetched successfully");
-    Ok(mock_event)
+    Ok(event)
 }
 
 pub async fn get_events_in_range(
-    _app: AppHandle,
+    app: AppHandle,
     start_date: String,
     end_date: String,
     project_id: Option<String>,
+    timezone: Option<String>,
 ) -> Result<Vec<serde_json::Value>, String> {
     println!("🔍 [get_events_in_range] Fetching events from {} to {}", start_date, end_date);
     
-    validate_date_range(&start_date, &end_date)?;
-    
     if let Some(pid) = &project_id {
         validate_project_id(pid)?;
     }
 
-    // This would normally query the events table with date range filtering
-    let mock_events = vec![
-        serde_json::json!({
-            "id": Uuid::new_v4().to_string(),
-            "title": "Event in Range",
-            "description": "Event within the specified date range",
-            "start_date": start_date,
-            "end_date": end_date,
-            "is_all_day": false,
-            "project_id": project_id,
-            "source": "manual",
-            "created_by": "user@example.com",
-            "created_at": Utc::now().to_rfc3339(),
-            "updated_at": Utc::now().to_rfc3339()
-        })
-    ];
+    // Boundaries without an offset are local times in the caller's timezone
+    let tz = parse_timezone(timezone.as_deref().unwrap_or(DEFAULT_TIMEZONE))?;
+    let window_start = resolve_boundary(&start_date, tz)
+        .map_err(|_| "Invalid start date format".to_string())?;
+    let window_end = resolve_boundary(&end_date, tz)
+        .map_err(|_| "Invalid end date format".to_string())?;
+    if window_start >= window_end {
+        return Err("Start date must be before end date".to_string());
+    }
+
+    let stored_events = with_existing_app_db(&app, move |conn| load_stored_events(conn, project_id.as_deref()))
+        .await?
+        .unwrap_or_default();
+    
+    // Recurring events are expanded into concrete instances within the window
+    let mut events = Vec::new();
+    for event in &stored_events {
+        for mut instance in expand_event_instances(event, window_start, window_end)? {
+            if !event_overlaps(&instance, window_start, window_end) {
+                continue;
+            }
+            with_local_instants(&mut instance)?;
+            events.push(instance);
+        }
+    }
     
-    println!("✅ [get_events_in_range] Found {} events in range", mock_events.len());
-    Ok(mock_events)
+    println!("✅ [get_events_in_range] Found {} events in range", events.len());
+    Ok(events)
 }
 
 pub async fn update_event(
-    _app: AppHandle,
+    app: AppHandle,
     event_id: String,
     event_data: serde_json::Value,
 ) -> Result<serde_json::Value, String> {
     println!("📝 [update_event] Updating event {}: {:?}", event_id, event_data);
     
     validate_event_id(&event_id)?;
-
-    // This would normally update the event in the database
-    let updated_event = serde_json::json!({
-        "id": event_id,
-        "title": event_data.get("title").unwrap_or(&serde_json::Value::String("Updated Event".to_string())),
-        "description": event_data.get("description").unwrap_or(&serde_json::Value::String("".to_string())),
-        "start_date": event_data.get("start_date").unwrap_or(&serde_json::Value::String(Utc::now().to_rfc3339())),
-        "end_date": event_data.get("end_date"),
-        "is_all_day": event_data.get("is_all_day").unwrap_or(&serde_json::Value::Bool(false)),
-        "updated_at": Utc::now().to_rfc3339()
-    });
+    
+    let id = event_id.clone();
+    let mut updated_event = with_app_db(&app, move |conn| {
+        update_stored_event(conn, &id, &event_data, &Utc::now().to_rfc3339())
+    }).await?;
+    with_local_instants(&mut updated_event)?;
     
     println!("✅ [update_event] Event updated successfully");
     Ok(updated_event)
@@ -199,7 +764,7 @@ pub async fn delete_event(
 }
 
 pub async fn store_event_detection(
-    _app: AppHandle,
+    app: AppHandle,
     event: serde_json::Value,
 ) -> Result<String, String> {
     println!("🤖 [store_event_detection] Storing AI-detected event: {:?}", event);
@@ -248,7 +813,7 @@ pub async fn store_event_detection(
     let now = Utc::now().to_rfc3339();
     
     // This would normally insert into the events table
-    let _event_data = serde_json::json!({
+    let event_data = serde_json::json!({
         "id": event_id,
         "project_id": project_id,
         "name": name,
@@ -268,6 +833,400 @@ pub async fn store_event_detection(
         "updated_at": now
     });
     
+    let detection_id = event_id.clone();
+    with_app_db(&app, move |conn| save_detection(conn, &detection_id, &event_data)).await?;
+    
     println!("✅ [store_event_detection] AI event stored with ID: {}", event_id);
     Ok(event_id)
-}
\ No newline at end of file
+}
+
+/// Outcome of turning one stored detection into a calendar event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionConversion {
+    pub detection_id: String,
+    pub success: bool,
+    pub event: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub conflicts: Vec<serde_json::Value>,
+}
+
+fn ensure_detections_table(conn: &rusqlite::Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS event_detections (
+            id TEXT PRIMARY KEY,
+            projectId TEXT NOT NULL,
+            detection TEXT NOT NULL,
+            createdAt TEXT NOT NULL
+        )",
+    )
+    .map_err(|e| format!("Failed to create detections table: {}", e))
+}
+
+/// Keep an AI-detected event until it is turned into a calendar event
+pub fn save_detection(conn: &rusqlite::Connection, detection_id: &str, detection: &serde_json::Value) -> Result<(), String> {
+    ensure_detections_table(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO event_detections (id, projectId, detection, createdAt) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            detection_id,
+            detection["project_id"].as_str(),
+            detection.to_string(),
+            detection["created_at"].as_str().unwrap_or_default(),
+        ],
+    )
+    .map_err(|e| format!("Failed to store detection: {}", e))?;
+    Ok(())
+}
+
+fn load_detection(conn: &rusqlite::Connection, detection_id: &str) -> Result<Option<serde_json::Value>, String> {
+    ensure_detections_table(conn)?;
+    let stored: Option<String> = conn
+        .query_row("SELECT detection FROM event_detections WHERE id = ?1", [detection_id], |row| row.get(0))
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(format!("Failed to load detection: {}", e)),
+        })?;
+    stored
+        .map(|json| serde_json::from_str(&json).map_err(|e| format!("Stored detection is not valid JSON: {}", e)))
+        .transpose()
+}
+
+/// When a detected event starts: its `date`, with the detected `time` of day (`HH:MM`) applied
+pub fn detection_start(detection: &serde_json::Value) -> Option<String> {
+    let date = resolve_boundary(detection.get("date")?.as_str()?, Tz::UTC).ok()?;
+    let time = detection.get("time")
+        .and_then(|v| v.as_str())
+        .and_then(|time| {
+            chrono::NaiveTime::parse_from_str(time, "%H:%M")
+                .or_else(|_| chrono::NaiveTime::parse_from_str(time, "%H:%M:%S"))
+                .ok()
+        });
+    let start = match time {
+        Some(time) => date.date_naive().and_time(time).and_utc(),
+        None => date,
+    };
+    Some(start.to_rfc3339())
+}
+
+/// Map a stored detection onto the input accepted by `create_calendar_event`
+pub fn detection_to_event_input(detection: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "title": detection.get("name"),
+        "description": detection.get("description").and_then(|v| v.as_str()).unwrap_or(""),
+        "startDate": detection_start(detection),
+        "projectId": detection.get("project_id"),
+        "eventType": detection.get("event_type"),
+        "source": "ai_detection",
+        "sourceMessageId": detection.get("source_slack_message"),
+        "createdBy": "ai"
+    })
+}
+
+fn failed_conversion(detection_id: String, error: String, conflicts: Vec<serde_json::Value>) -> DetectionConversion {
+    DetectionConversion { detection_id, success: false, event: None, error: Some(error), conflicts }
+}
+
+/// Turn stored detections into saved events, one at a time so a detection that overlaps an
+/// event created earlier in the same batch is caught too. Converted detections are removed.
+pub fn convert_detections(
+    conn: &rusqlite::Connection,
+    detection_ids: Vec<String>,
+    now: &str,
+) -> Result<Vec<DetectionConversion>, String> {
+    let mut results = Vec::new();
+    
+    for detection_id in detection_ids {
+        let detection = match load_detection(conn, &detection_id)? {
+            Some(detection) => detection,
+            None => {
+                results.push(failed_conversion(detection_id, "Detection not found".to_string(), Vec::new()));
+                continue;
+            }
+        };
+        
+        let record = match build_event_record(&detection_to_event_input(&detection), &Uuid::new_v4().to_string(), now) {
+            Ok(record) => record,
+            Err(e) => {
+                results.push(failed_conversion(detection_id, e, Vec::new()));
+                continue;
+            }
+        };
+        let (range_start, range_end) = event_record_range(&record)?;
+        let conflicts = stored_conflicts(conn, range_start, range_end)?;
+        if !conflicts.is_empty() {
+            results.push(failed_conversion(detection_id, "Conflicts with existing events".to_string(), conflicts));
+            continue;
+        }
+        
+        if let Err(e) = insert_event(conn, &record) {
+            results.push(failed_conversion(detection_id, e, Vec::new()));
+            continue;
+        }
+        conn.execute("DELETE FROM event_detections WHERE id = ?1", [&detection_id])
+            .map_err(|e| format!("Failed to remove converted detection: {}", e))?;
+        results.push(DetectionConversion {
+            detection_id,
+            success: true,
+            event: Some(record),
+            error: None,
+            conflicts: Vec::new(),
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Create calendar events from several stored detections, skipping those that conflict
+pub async fn create_events_from_detections(
+    app: AppHandle,
+    detection_ids: Vec<String>,
+) -> Result<Vec<DetectionConversion>, String> {
+    println!("📅 [create_events_from_detections] Converting {} detections", detection_ids.len());
+    
+    let now = Utc::now().to_rfc3339();
+    let results = with_app_db(&app, move |conn| convert_detections(conn, detection_ids, &now)).await?;
+    
+    let created = results.iter().filter(|r| r.success).count();
+    println!("✅ [create_events_from_detections] Created {} of {} events", created, results.len());
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekly_rrule_is_normalized() {
+        let rule = validate_rrule("RRULE:freq=weekly;byday=FR,MO;interval=2;count=10").unwrap();
+
+        assert_eq!(rule.freq, "WEEKLY");
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.count, Some(10));
+        assert_eq!(rule.by_day, vec!["MO", "FR"]);
+        assert_eq!(rule.rrule, "FREQ=WEEKLY;INTERVAL=2;COUNT=10;BYDAY=MO,FR");
+    }
+
+    #[test]
+    fn unsupported_rrule_parts_are_rejected() {
+        let err = validate_rrule("FREQ=MONTHLY;BYSETPOS=-1;BYDAY=MO").unwrap_err();
+        assert!(err.contains("BYSETPOS"));
+
+        assert!(validate_rrule("FREQ=YEARLY").is_err());
+        assert!(validate_rrule("FREQ=DAILY;COUNT=3;UNTIL=20240101").is_err());
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn days(occurrences: &[DateTime<Utc>]) -> Vec<u32> {
+        occurrences.iter().map(|o| o.day()).collect()
+    }
+
+    #[test]
+    fn weekly_recurrence_honours_byday_and_count() {
+        let rule = validate_rrule("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4").unwrap();
+        let occurrences = expand_occurrences(
+            utc("2024-01-01T09:00:00Z"),
+            chrono::Duration::minutes(15),
+            &rule,
+            utc("2024-01-01T00:00:00Z"),
+            utc("2024-01-31T00:00:00Z"),
+        );
+
+        assert_eq!(days(&occurrences), vec![1, 3, 8, 10]);
+        assert!(occurrences.iter().all(|o| o.format("%H:%M").to_string() == "09:00"));
+    }
+
+    #[test]
+    fn daily_recurrence_stops_at_until_and_skips_before_window() {
+        let rule = validate_rrule("FREQ=DAILY;UNTIL=20240105").unwrap();
+        let occurrences = expand_occurrences(
+            utc("2024-01-01T09:00:00Z"),
+            chrono::Duration::zero(),
+            &rule,
+            utc("2024-01-03T00:00:00Z"),
+            utc("2024-01-10T00:00:00Z"),
+        );
+
+        assert_eq!(days(&occurrences), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn monthly_recurrence_skips_months_without_the_day() {
+        let rule = validate_rrule("FREQ=MONTHLY").unwrap();
+        let occurrences = expand_occurrences(
+            utc("2024-01-31T10:00:00Z"),
+            chrono::Duration::hours(1),
+            &rule,
+            utc("2024-01-01T00:00:00Z"),
+            utc("2024-06-01T00:00:00Z"),
+        );
+
+        let months: Vec<u32> = occurrences.iter().map(|o| o.month()).collect();
+        assert_eq!(months, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn recurring_event_expands_into_instances() {
+        let event = serde_json::json!({
+            "id": "evt1",
+            "title": "Standup",
+            "start_date": "2024-01-01T09:00:00Z",
+            "end_date": "2024-01-01T09:15:00Z",
+            "recurrence": "FREQ=DAILY;COUNT=3"
+        });
+
+        let instances = expand_event_instances(&event, utc("2024-01-01T00:00:00Z"), utc("2024-02-01T00:00:00Z")).unwrap();
+
+        assert_eq!(instances.len(), 3);
+        assert_eq!(instances[1]["id"], "evt1_20240102T090000Z");
+        assert_eq!(instances[1]["recurring_event_id"], "evt1");
+        assert_eq!(instances[1]["end_date"], "2024-01-02T09:15:00+00:00");
+    }
+
+    #[test]
+    fn overlap_is_inclusive_start_exclusive_end() {
+        let event = serde_json::json!({
+            "start_date": "2024-01-01T09:00:00Z",
+            "end_date": "2024-01-01T10:00:00Z"
+        });
+
+        assert!(event_overlaps(&event, utc("2024-01-01T09:30:00Z"), utc("2024-01-01T11:00:00Z")));
+        assert!(event_overlaps(&event, utc("2024-01-01T09:00:00Z"), utc("2024-01-01T09:01:00Z")));
+        assert!(!event_overlaps(&event, utc("2024-01-01T10:00:00Z"), utc("2024-01-01T11:00:00Z")));
+        assert!(!event_overlaps(&event, utc("2024-01-01T08:00:00Z"), utc("2024-01-01T09:00:00Z")));
+    }
+
+    fn events_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE events (id TEXT PRIMARY KEY, projectId TEXT NOT NULL, title TEXT NOT NULL, description TEXT,
+             date TEXT NOT NULL, type TEXT NOT NULL, duration INTEGER, recurring TEXT,
+             createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL, syncStatus TEXT DEFAULT 'local');
+             INSERT INTO events VALUES ('e1', 'p1', 'Planning', NULL, '2024-01-01T09:00:00Z', 'meeting', 60, 'none', '', '', 'local');
+             INSERT INTO events VALUES ('e2', 'p2', 'Review', NULL, '2024-01-01T14:00:00', 'meeting', NULL, NULL, '', '', 'local');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn conflicts_are_found_among_stored_events() {
+        assert!(load_stored_events(&rusqlite::Connection::open_in_memory().unwrap(), None).unwrap().is_empty());
+
+        let conn = events_db();
+        let events = load_stored_events(&conn, None).unwrap();
+        assert_eq!(events[0]["end_date"], "2024-01-01T10:00:00+00:00");
+        assert!(events[0]["recurrence"].is_null());
+        assert_eq!(events[1]["start_date"], "2024-01-01T14:00:00+00:00");
+
+        let conflicts: Vec<&str> = events.iter()
+            .filter(|event| event_overlaps(event, utc("2024-01-01T09:30:00Z"), utc("2024-01-01T14:10:00Z")))
+            .map(|event| event["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(conflicts, vec!["e1", "e2"]);
+        assert_eq!(load_stored_events(&conn, Some("p2")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn detections_become_events_unless_they_conflict() {
+        let conn = events_db();
+        let detection = |time: &str| serde_json::json!({
+            "project_id": "p1",
+            "name": "Kickoff",
+            "date": "2024-01-01T00:00:00Z",
+            "time": time,
+            "event_type": "meeting",
+            "created_at": "2024-01-01T00:00:00Z"
+        });
+        save_detection(&conn, "d_busy", &detection("09:30")).unwrap();
+        save_detection(&conn, "d_free", &detection("11:00")).unwrap();
+
+        let results = convert_detections(
+            &conn,
+            vec!["d_busy".to_string(), "d_free".to_string(), "d_missing".to_string()],
+            "2024-01-01T00:00:00Z",
+        ).unwrap();
+
+        assert!(!results[0].success);
+        assert_eq!(results[0].conflicts[0]["id"], "e1");
+        assert!(results[1].success);
+        assert_eq!(results[2].error.as_deref(), Some("Detection not found"));
+
+        let (project_id, date): (String, String) = conn
+            .query_row("SELECT projectId, date FROM events WHERE title = 'Kickoff'", [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(project_id, "p1");
+        assert_eq!(date, "2024-01-01T11:00:00+00:00");
+        assert!(load_detection(&conn, "d_free").unwrap().is_none());
+        assert!(load_detection(&conn, "d_busy").unwrap().is_some());
+    }
+
+    #[test]
+    fn local_boundaries_follow_the_callers_timezone_across_dst() {
+        let tz = parse_timezone("America/New_York").unwrap();
+
+        // EST (UTC-5) before the March change, EDT (UTC-4) after it
+        assert_eq!(resolve_boundary("2024-03-09T09:00:00", tz).unwrap(), utc("2024-03-09T14:00:00Z"));
+        assert_eq!(resolve_boundary("2024-03-11T09:00:00", tz).unwrap(), utc("2024-03-11T13:00:00Z"));
+        // Explicit offsets are kept as given
+        assert_eq!(resolve_boundary("2024-03-11T09:00:00Z", tz).unwrap(), utc("2024-03-11T09:00:00Z"));
+        assert!(parse_timezone("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn stored_timezone_round_trips_across_dst() {
+        let conn = events_db();
+        let input = serde_json::json!({
+            "title": "Standup",
+            "projectId": "p1",
+            "startDate": "2024-03-08T14:00:00Z",
+            "endDate": "2024-03-08T14:15:00Z",
+            "recurrence": "FREQ=DAILY;COUNT=4",
+            "timezone": "America/New_York"
+        });
+        insert_event(&conn, &build_event_record(&input, "standup", "now").unwrap()).unwrap();
+
+        let mut event = load_stored_event(&conn, "standup").unwrap().unwrap();
+        assert_eq!(event["timezone"], "America/New_York");
+        with_local_instants(&mut event).unwrap();
+        assert_eq!(event["start_date_utc"], "2024-03-08T14:00:00+00:00");
+        assert_eq!(event["start_date_local"], "2024-03-08T09:00:00-05:00");
+
+        // After the March change the same local time is an hour earlier in UTC
+        let mut after_dst = expand_event_instances(&event, utc("2024-03-11T00:00:00Z"), utc("2024-03-12T00:00:00Z")).unwrap().remove(0);
+        with_local_instants(&mut after_dst).unwrap();
+        assert_eq!(after_dst["start_date_utc"], "2024-03-11T13:00:00+00:00");
+        assert_eq!(after_dst["start_date_local"], "2024-03-11T09:00:00-04:00");
+
+        let moved = update_stored_event(&conn, "standup", &serde_json::json!({ "timezone": "Europe/Lisbon" }), "later").unwrap();
+        assert_eq!(moved["timezone"], "Europe/Lisbon");
+        assert_eq!(moved["title"], "Standup");
+        assert_eq!(moved["end_date"], "2024-03-08T14:15:00+00:00");
+        assert!(update_stored_event(&conn, "missing", &serde_json::json!({}), "later").is_err());
+        // Rows saved before the column existed read back as UTC
+        assert_eq!(load_stored_event(&conn, "e1").unwrap().unwrap()["timezone"], "UTC");
+    }
+
+    #[test]
+    fn recurring_instances_keep_local_time_across_dst() {
+        let event = serde_json::json!({
+            "id": "standup",
+            "start_date": "2024-03-08T14:00:00Z",
+            "recurrence": "FREQ=DAILY;COUNT=4",
+            "timezone": "America/New_York"
+        });
+
+        let instances = expand_event_instances(&event, utc("2024-03-01T00:00:00Z"), utc("2024-04-01T00:00:00Z")).unwrap();
+        let starts: Vec<&str> = instances.iter().map(|i| i["start_date"].as_str().unwrap()).collect();
+
+        assert_eq!(starts, vec![
+            "2024-03-08T14:00:00+00:00",
+            "2024-03-09T14:00:00+00:00",
+            "2024-03-10T13:00:00+00:00",
+            "2024-03-11T13:00:00+00:00",
+        ]);
+    }
+}
@@ -1,17 +1,31 @@
 use tauri::AppHandle;
 use serde_json::Value;
 
+/// Newest feedback entries kept for prompt improvement; older ones are dropped
+const MAX_FEEDBACK_ENTRIES: usize = 1000;
+/// Newest rejections kept; items rejected longer ago than that are no longer pending anyway
+const MAX_REJECTED_ITEMS: usize = 5000;
+
+/// AI service client that sends the API key saved with `set_ai_credentials`, if any
+pub async fn ai_client_for(app: &AppHandle) -> crate::ai_service_client::AIServiceClient {
+    let api_key = crate::credentials::get_ai_api_key(app.clone()).await.unwrap_or_else(|e| {
+        println!("⚠️ Could not read the stored AI API key, using the service's own: {}", e);
+        None
+    });
+    crate::ai_service_client::AIServiceClient::new(None).with_api_key(api_key)
+}
+
 /// Analyze text content with AI using the new AI service
 #[tauri::command]
 pub async fn analyze_with_ai(
-    _app: AppHandle,
+    app: AppHandle,
     content: String,
     analysis_type: String,
 ) -> Result<String, String> {
     println!("🤖 Analyzing content with AI: {}", analysis_type);
     
     // Use the new AI service client
-    let ai_client = crate::ai_service_client::AIServiceClient::new(None);
+    let ai_client = ai_client_for(&app).await;
     match ai_client.summarize(content, None).await {
         Ok(response) => {
             println!("✅ AI analysis completed successfully");
@@ -27,7 +41,7 @@ pub async fn analyze_with_ai(
 /// Process Slack messages with AI for automation using new services
 #[tauri::command]
 pub async fn process_slack_messages_with_ai(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
     messages: Value,
     analysis_type: Option<String>,
@@ -37,7 +51,7 @@ pub async fn process_slack_messages_with_ai(
     println!("🔄 Processing Slack messages for project {}", project_id);
     
     // Use the new AI service for task analysis
-    let ai_client = crate::ai_service_client::AIServiceClient::new(None);
+    let ai_client = ai_client_for(&app).await;
     
     let request = crate::ai_service_client::TaskAnalysisRequest {
         messages: crate::ai_service_client::MessageInput::Text(messages.to_string()),
@@ -47,6 +61,7 @@ pub async fn process_slack_messages_with_ai(
             team_members: None,
         }),
         model: None,
+        prompt: None,
     };
     
     match ai_client.analyze_tasks(request).await {
@@ -72,15 +87,56 @@ pub async fn process_slack_messages_with_ai(
     }
 }
 
+/// Analyze messages in chunks so a partial failure can be retried chunk by chunk
+#[tauri::command]
+pub async fn analyze_messages_in_chunks(
+    app: AppHandle,
+    project_id: String,
+    messages: Vec<crate::ai_service_client::Message>,
+    chunk_size: Option<usize>,
+) -> Result<Value, String> {
+    println!("🧩 Chunked analysis of {} messages for project {}", messages.len(), project_id);
+    
+    let ai_client = ai_client_for(&app).await;
+    let chunk_size = crate::commands::settings::resolve_analysis_chunk_size(&app, chunk_size).await;
+    let context = crate::ai_service_client::ProjectContext {
+        project_id: Some(project_id),
+        project_name: None,
+        team_members: None,
+    };
+    
+    let analysis = ai_client.analyze_tasks_chunked(messages, Some(context), None, chunk_size).await;
+    
+    println!("✅ Chunked analysis {} finished with {} failed chunks",
+        analysis.analysis_id, analysis.failed_chunk_indices().len());
+    Ok(analysis.to_json())
+}
+
+/// Re-run only the chunks of a previous chunked analysis that failed
+#[tauri::command]
+pub async fn reanalyze_failed_chunks(app: AppHandle, analysis_id: String) -> Result<Value, String> {
+    println!("🔁 Re-analyzing failed chunks for {}", analysis_id);
+    
+    let ai_client = ai_client_for(&app).await;
+    match ai_client.reanalyze_failed_chunks(&analysis_id).await {
+        Ok(analysis) => Ok(analysis.to_json()),
+        Err(e) => {
+            println!("❌ Failed to re-analyze chunks: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 /// Analyze text for insights using the new AI service
 #[tauri::command]
 pub async fn analyze_text_for_insights(
+    app: AppHandle,
     text: String,
     context: Option<Value>,
 ) -> Result<Value, String> {
     println!("🔍 Analyzing text for insights with AI service");
     
-    let ai_client = crate::ai_service_client::AIServiceClient::new(None);
+    let ai_client = ai_client_for(&app).await;
     match ai_client.summarize(text, None).await {
         Ok(response) => {
             println!("✅ AI insights analysis completed");
@@ -98,9 +154,30 @@ pub async fn analyze_text_for_insights(
     }
 }
 
+/// Confidence assumed for items whose analysis didn't report one
+const DEFAULT_ITEM_CONFIDENCE: f64 = 0.8;
+
+/// Keep items whose `confidence` is at least `min_confidence`, optionally strongest first
+pub fn filter_by_confidence(items: Vec<Value>, min_confidence: f32, sort_by_confidence: bool) -> Vec<Value> {
+    let confidence = |item: &Value| item.get("confidence").and_then(|c| c.as_f64()).unwrap_or(0.0);
+    
+    let mut items: Vec<Value> = items
+        .into_iter()
+        .filter(|item| confidence(item) >= min_confidence as f64)
+        .collect();
+    if sort_by_confidence {
+        items.sort_by(|a, b| confidence(b).partial_cmp(&confidence(a)).unwrap_or(std::cmp::Ordering::Equal));
+    }
+    items
+}
+
 /// Extract actionable items from analysis results
 #[tauri::command]
-pub async fn extract_actionable_items(analysis_result: Value) -> Result<Vec<Value>, String> {
+pub async fn extract_actionable_items(
+    analysis_result: Value,
+    min_confidence: Option<f32>,
+    sort_by_confidence: Option<bool>,
+) -> Result<Vec<Value>, String> {
     println!("🎯 Extracting actionable items from analysis");
     
     // Extract tasks from the analysis result
@@ -116,32 +193,197 @@ pub async fn extract_actionable_items(analysis_result: Value) -> Result<Vec<Valu
                 "status": task.get("status").and_then(|s| s.as_str()).unwrap_or("pending"),
                 "assignee": task.get("assignee").and_then(|a| a.as_str()),
                 "source_message": task.get("source_message").and_then(|s| s.as_str()).unwrap_or(""),
-                "confidence": 0.8
+                "confidence": task.get("confidence")
+                    .or_else(|| task.get("confidence_score"))
+                    .and_then(|c| c.as_f64())
+                    .unwrap_or(DEFAULT_ITEM_CONFIDENCE)
             }));
         }
     }
     
+    let items = filter_by_confidence(items, min_confidence.unwrap_or(0.0), sort_by_confidence.unwrap_or(false));
     println!("✅ Extracted {} actionable items", items.len());
     Ok(items)
 }
 
-/// Create a task from AI suggestion using new services
+/// Idempotency key for applying a suggestion: its source message timestamp within the project
+pub fn suggestion_idempotency_key(project_id: &str, suggestion: &Value) -> Option<String> {
+    ["source_message_ts", "source_timestamp", "source_message_timestamp"]
+        .iter()
+        .find_map(|field| suggestion.get(*field).and_then(|v| v.as_str()))
+        .filter(|ts| !ts.is_empty())
+        .map(|ts| format!("{}:{}", project_id, ts))
+}
+
+/// Whether a suggestion has already been turned into a task
+pub fn is_suggestion_applied(conn: &rusqlite::Connection, project_id: &str, suggestion: &Value) -> bool {
+    suggestion_idempotency_key(project_id, suggestion).map_or(false, |key| {
+        conn.query_row("SELECT 1 FROM tasks WHERE suggestionKey = ?1", [&key], |_| Ok(()))
+            .is_ok()
+    })
+}
+
+/// Create a task from AI suggestion using new services. Repeated calls for the same source
+/// message (double clicks, retries) return the task created the first time. A new task counts
+/// as accepted feedback for `prompt_key`, or the suggestion's own `prompt_key`.
 #[tauri::command]
 pub async fn create_task_from_ai_suggestion(
-    _app: AppHandle,
+    app: AppHandle,
     suggestion: Value,
     project_id: String,
+    include_source: Option<bool>,
+    idempotency_key: Option<String>,
+    prompt_key: Option<String>,
 ) -> Result<String, String> {
     println!("📝 Creating task from AI suggestion for project {}", project_id);
     
-    // For now, generate a task ID - in production, this would create via database service
     let task_id = format!("task_{}_{}", project_id, chrono::Utc::now().timestamp_millis());
     
-    // Log the task creation for debugging
-    println!("📋 Task suggestion: {:?}", suggestion);
-    println!("✅ Task created with ID: {}", task_id);
+    let key = idempotency_key.or_else(|| suggestion_idempotency_key(&project_id, &suggestion));
+    let task = build_task_from_suggestion(&task_id, &project_id, &suggestion, include_source.unwrap_or(true));
+    let prompt_key = prompt_key.or_else(|| suggestion.get("prompt_key").and_then(|k| k.as_str()).map(str::to_string));
+    
+    let now = chrono::Utc::now().to_rfc3339();
+    // The unique index on suggestionKey decides which of several concurrent clicks wins;
+    // a failed insert leaves nothing behind, so the suggestion can simply be retried
+    let saved = crate::app_db::with_app_db(&app, move |conn| {
+        save_suggested_task(conn, &task, key.as_deref(), &now)
+    }).await;
+    
+    match saved {
+        Ok(saved_id) if saved_id != task_id => {
+            println!("↩️ Suggestion already applied as task {}", saved_id);
+            Ok(saved_id)
+        }
+        Ok(saved_id) => {
+            println!("✅ Task created with ID: {}", saved_id);
+            let feedback = serde_json::json!({
+                "type": "acceptance",
+                "prompt_key": prompt_key,
+                "task_id": saved_id,
+            });
+            if let Err(e) = capture_task_modification_feedback(app, feedback).await {
+                println!("⚠️ Failed to record acceptance feedback: {}", e);
+            }
+            Ok(saved_id)
+        }
+        Err(e) => {
+            println!("❌ Failed to create task from suggestion: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Task priorities accepted by the `tasks` table; anything else is stored as medium
+fn normalize_priority(priority: Option<&str>) -> &'static str {
+    match priority.map(|p| p.trim().to_lowercase()).as_deref() {
+        Some("low") => "low",
+        Some("high") | Some("urgent") | Some("critical") => "high",
+        _ => "medium",
+    }
+}
+
+/// Insert a task built by `build_task_from_suggestion` into the `tasks` table. When
+/// `suggestion_key` already belongs to a task nothing is inserted and that task's id is
+/// returned instead.
+pub fn save_suggested_task(
+    conn: &rusqlite::Connection,
+    task: &Value,
+    suggestion_key: Option<&str>,
+    now: &str,
+) -> Result<String, String> {
+    for column in ["sourceText", "sourcePermalink", "suggestionKey"] {
+        if !crate::slack_sync::has_column(conn, "tasks", column)? {
+            conn.execute(&format!("ALTER TABLE tasks ADD COLUMN {} TEXT", column), [])
+                .map_err(|e| format!("Failed to add column {}: {}", column, e))?;
+        }
+    }
+    conn.execute("CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_suggestion_key ON tasks(suggestionKey)", [])
+        .map_err(|e| format!("Failed to index suggestion keys: {}", e))?;
+    
+    let title = task["name"].as_str().map(str::trim).filter(|t| !t.is_empty())
+        .ok_or_else(|| "Suggestion has no title".to_string())?;
+    let inserted = conn.execute(
+        "INSERT INTO tasks (id, projectId, title, description, completed, priority, sourceText, sourcePermalink, suggestionKey, createdAt, updatedAt, syncStatus)
+         VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6, ?7, ?8, ?9, ?9, 'local')
+         ON CONFLICT(suggestionKey) DO NOTHING",
+        rusqlite::params![
+            task["id"].as_str(),
+            task["project_id"].as_str(),
+            title,
+            task["description"].as_str(),
+            normalize_priority(task["priority"].as_str()),
+            task.get("source_text").and_then(|v| v.as_str()),
+            task.get("source_permalink").and_then(|v| v.as_str()),
+            suggestion_key,
+            now,
+        ],
+    )
+    .map_err(|e| format!("Failed to save task: {}", e))?;
+    
+    match (inserted, suggestion_key) {
+        (0, Some(key)) => conn
+            .query_row("SELECT id FROM tasks WHERE suggestionKey = ?1", [key], |row| row.get(0))
+            .map_err(|e| format!("Failed to load existing task: {}", e)),
+        _ => Ok(task["id"].as_str().unwrap_or_default().to_string()),
+    }
+}
+
+/// Bring the tasks applied from the Slack message at `ts` in line with a later edit or
+/// deletion. With `current_text` the message was edited and tasks keeping their source text
+/// get the new wording; with `None` it was deleted and its open tasks are removed, while
+/// completed ones stay as a record. Returns how many tasks were linked to the message.
+pub fn reconcile_message_tasks(
+    conn: &rusqlite::Connection,
+    project_id: &str,
+    ts: &str,
+    current_text: Option<&str>,
+    now: &str,
+) -> Result<usize, String> {
+    // Nothing was ever applied from a suggestion
+    if !crate::slack_sync::has_column(conn, "tasks", "suggestionKey")? {
+        return Ok(0);
+    }
+
+    let key = format!("{}:{}", project_id, ts);
+    let changed = match current_text {
+        Some(text) => conn.execute(
+            "UPDATE tasks SET sourceText = CASE WHEN sourceText IS NULL THEN NULL ELSE ?1 END, updatedAt = ?2
+             WHERE suggestionKey = ?3",
+            rusqlite::params![text, now, key],
+        ),
+        None => conn.execute("DELETE FROM tasks WHERE suggestionKey = ?1 AND completed = 0", [&key]),
+    };
+    changed.map_err(|e| format!("Failed to update tasks from message {}: {}", ts, e))
+}
+
+/// Build the task record for an applied suggestion. When `include_source` is set the
+/// verbatim message text and permalink are kept so users can always see where it came from.
+pub fn build_task_from_suggestion(task_id: &str, project_id: &str, suggestion: &Value, include_source: bool) -> Value {
+    let mut task = serde_json::json!({
+        "id": task_id,
+        "project_id": project_id,
+        "name": suggestion.get("title").or_else(|| suggestion.get("name")).cloned().unwrap_or(Value::Null),
+        "description": suggestion.get("description").cloned().unwrap_or(Value::Null),
+        "priority": suggestion.get("priority").cloned().unwrap_or(Value::Null),
+        "source": "ai_suggestion",
+    });
+    
+    if include_source {
+        let original_text = suggestion.get("original_text")
+            .or_else(|| suggestion.get("source_message").and_then(|m| m.get("text")))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let permalink = suggestion.get("permalink")
+            .or_else(|| suggestion.get("source_message").and_then(|m| m.get("permalink")))
+            .cloned()
+            .unwrap_or(Value::Null);
+        
+        task["source_text"] = original_text;
+        task["source_permalink"] = permalink;
+    }
     
-    Ok(task_id)
+    task
 }
 
 /// Health check for AI automation using new services
@@ -171,6 +413,60 @@ pub async fn ai_automation_health_check(_app: AppHandle) -> Result<Value, String
     }
 }
 
+/// Store the API key used by the AI service
+#[tauri::command]
+pub async fn set_ai_credentials(app: AppHandle, key: String) -> Result<String, String> {
+    println!("🔑 Storing AI service API key");
+    crate::credentials::store_ai_api_key(app, key).await
+}
+
+/// Check the stored AI API key with a cheap authenticated call
+#[tauri::command]
+pub async fn test_ai_credentials(app: AppHandle) -> Result<Value, String> {
+    println!("🔑 Testing AI service API key");
+    
+    let api_key = match crate::credentials::get_ai_api_key(app).await? {
+        Some(key) => key,
+        None => {
+            return Ok(serde_json::json!({
+                "success": false,
+                "valid": false,
+                "error": "No AI API key configured"
+            }));
+        }
+    };
+    
+    let ai_client = crate::ai_service_client::AIServiceClient::new(None);
+    match ai_client.validate_api_key(&api_key, "gemini").await {
+        Ok(validation) => Ok(ai_credentials_report(&validation)),
+        Err(e) => {
+            println!("❌ AI credentials test failed: {}", e);
+            Ok(serde_json::json!({
+                "success": false,
+                "valid": false,
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+/// Shape an API key validation into the response returned to the frontend
+pub fn ai_credentials_report(validation: &crate::ai_service_client::ApiKeyValidation) -> Value {
+    if validation.is_valid {
+        println!("✅ AI API key is valid");
+    } else {
+        println!("❌ AI API key rejected: {:?}", validation.error_message);
+    }
+    
+    serde_json::json!({
+        "success": validation.is_valid,
+        "valid": validation.is_valid,
+        "provider": validation.provider,
+        "error": validation.error_message,
+        "checked_at": validation.timestamp
+    })
+}
+
 /// Store analysis results for future reference
 #[tauri::command]
 pub async fn store_analysis_result(
@@ -213,9 +509,159 @@ pub async fn capture_behavioral_feedback_advanced(_feedback: Value) -> Result<Va
     Ok(serde_json::json!({"status": "not_implemented"}))
 }
 
+/// Run `f` against the app database once the feedback tables exist
+async fn with_feedback_db<T, F>(app: &AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut rusqlite::Connection) -> Result<T, String> + Send + 'static,
+{
+    crate::app_db::with_app_db(app, move |conn| {
+        ensure_feedback_tables(conn)?;
+        f(conn)
+    }).await
+}
+
+pub fn ensure_feedback_tables(conn: &rusqlite::Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ai_feedback (
+            id TEXT PRIMARY KEY,
+            feedbackType TEXT NOT NULL,
+            promptKey TEXT,
+            itemCount INTEGER NOT NULL,
+            payload TEXT NOT NULL,
+            capturedAt TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ai_feedback_prompt ON ai_feedback (promptKey);
+        CREATE TABLE IF NOT EXISTS rejected_ai_items (
+            itemId TEXT PRIMARY KEY,
+            reason TEXT,
+            rejectedAt TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("Failed to create feedback tables: {}", e))
+}
+
+/// Store a feedback entry, then drop the oldest beyond `MAX_FEEDBACK_ENTRIES`. Its `type` and
+/// `prompt_key` fields file it per prompt; `item_ids`, when present, is how many items it covers.
+pub fn save_feedback(conn: &rusqlite::Connection, id: &str, feedback: &Value, captured_at: &str) -> Result<(), String> {
+    let item_count = feedback.get("item_ids").and_then(|ids| ids.as_array()).map_or(1, |ids| ids.len());
+    conn.execute(
+        "INSERT INTO ai_feedback (id, feedbackType, promptKey, itemCount, payload, capturedAt)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            id,
+            feedback.get("type").and_then(|t| t.as_str()).unwrap_or("modification"),
+            feedback.get("prompt_key").and_then(|k| k.as_str()),
+            item_count as i64,
+            feedback.to_string(),
+            captured_at,
+        ],
+    )
+    .map_err(|e| format!("Failed to save feedback: {}", e))?;
+    
+    conn.execute(
+        "DELETE FROM ai_feedback WHERE id NOT IN (
+            SELECT id FROM ai_feedback ORDER BY capturedAt DESC, rowid DESC LIMIT ?1
+        )",
+        [MAX_FEEDBACK_ENTRIES as i64],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to prune feedback: {}", e))
+}
+
 #[tauri::command]
-pub async fn capture_task_modification_feedback(_feedback: Value) -> Result<Value, String> {
-    Ok(serde_json::json!({"status": "not_implemented"}))
+pub async fn capture_task_modification_feedback(app: AppHandle, feedback: Value) -> Result<Value, String> {
+    let feedback_id = format!("feedback_{}", uuid::Uuid::new_v4().simple());
+    let captured_at = chrono::Utc::now().to_rfc3339();
+    let mut entry = feedback;
+    if let Some(obj) = entry.as_object_mut() {
+        obj.insert("id".to_string(), serde_json::json!(feedback_id));
+        obj.insert("captured_at".to_string(), serde_json::json!(captured_at));
+    }
+    
+    let id = feedback_id.clone();
+    with_feedback_db(&app, move |conn| save_feedback(conn, &id, &entry, &captured_at)).await?;
+    println!("🧠 Captured task feedback {}", feedback_id);
+    Ok(serde_json::json!({"status": "captured", "feedback_id": feedback_id}))
+}
+
+/// Mark `ids` rejected in one transaction, keeping the newest `MAX_REJECTED_ITEMS`. Returns
+/// the ids newly rejected and those that already were.
+fn reject_items(
+    conn: &mut rusqlite::Connection,
+    ids: &[String],
+    reason: Option<&str>,
+    rejected_at: &str,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut newly = Vec::new();
+    let mut already = Vec::new();
+    for id in ids {
+        let inserted = tx.execute(
+            "INSERT INTO rejected_ai_items (itemId, reason, rejectedAt) VALUES (?1, ?2, ?3)
+             ON CONFLICT(itemId) DO NOTHING",
+            rusqlite::params![id, reason, rejected_at],
+        )
+        .map_err(|e| format!("Failed to reject item {}: {}", id, e))?;
+        if inserted == 0 {
+            already.push(id.clone());
+        } else {
+            newly.push(id.clone());
+        }
+    }
+    tx.execute(
+        "DELETE FROM rejected_ai_items WHERE itemId NOT IN (
+            SELECT itemId FROM rejected_ai_items ORDER BY rejectedAt DESC, rowid DESC LIMIT ?1
+        )",
+        [MAX_REJECTED_ITEMS as i64],
+    )
+    .map_err(|e| format!("Failed to prune rejected items: {}", e))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit rejections: {}", e))?;
+    Ok((newly, already))
+}
+
+/// Whether an AI item has been rejected
+fn is_item_rejected(conn: &rusqlite::Connection, item_id: &str) -> bool {
+    conn.query_row("SELECT 1 FROM rejected_ai_items WHERE itemId = ?1", [item_id], |_| Ok(()))
+        .is_ok()
+}
+
+/// Reject many pending AI items at once and record why, so false-positive waves feed back
+/// into prompt improvement. `prompt_key` names the prompt that produced the items.
+#[tauri::command]
+pub async fn bulk_reject_ai_items(
+    app: AppHandle,
+    ids: Vec<String>,
+    reason: Option<String>,
+    prompt_key: Option<String>,
+) -> Result<Value, String> {
+    println!("🚫 Bulk rejecting {} AI items", ids.len());
+    
+    if ids.is_empty() {
+        return Err("No AI items to reject".to_string());
+    }
+    
+    let rejected_reason = reason.clone();
+    let (rejected, already_rejected) = with_feedback_db(&app, move |conn| {
+        reject_items(conn, &ids, rejected_reason.as_deref(), &chrono::Utc::now().to_rfc3339())
+    })
+    .await?;
+    
+    let feedback = capture_task_modification_feedback(app, serde_json::json!({
+        "type": "rejection",
+        "prompt_key": prompt_key,
+        "item_ids": rejected,
+        "reason": reason,
+    })).await?;
+    
+    println!("✅ Rejected {} AI items ({} already rejected)", rejected.len(), already_rejected.len());
+    Ok(serde_json::json!({
+        "rejected": rejected,
+        "already_rejected": already_rejected,
+        "feedback_id": feedback.get("feedback_id").cloned().unwrap_or(Value::Null),
+    }))
 }
 
 #[tauri::command]
@@ -224,8 +670,67 @@ pub async fn extract_high_confidence_items(_analysis: Value) -> Result<Vec<Value
 }
 
 #[tauri::command]
-pub async fn get_pending_ai_items() -> Result<Vec<Value>, String> {
-    Ok(vec![])
+pub async fn get_pending_ai_items(
+    app: AppHandle,
+    project_id: Option<String>,
+    min_confidence: Option<f32>,
+    sort_by_confidence: Option<bool>,
+) -> Result<Vec<Value>, String> {
+    println!("📥 Getting pending AI items (project: {:?}, min confidence: {:?})", project_id, min_confidence);
+    
+    with_feedback_db(&app, move |conn| {
+        pending_ai_items(conn, project_id.as_deref(), min_confidence.unwrap_or(0.0), sort_by_confidence.unwrap_or(false))
+    })
+    .await
+}
+
+/// Task suggestions still waiting for review, optionally one project's, without those already
+/// rejected or applied, and below `min_confidence`. Returns nothing before the suggestions
+/// table exists.
+pub fn pending_ai_items(
+    conn: &rusqlite::Connection,
+    project_id: Option<&str>,
+    min_confidence: f32,
+    sort_by_confidence: bool,
+) -> Result<Vec<Value>, String> {
+    let table_exists = conn
+        .query_row("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'slack_task_suggestions'", [], |_| Ok(()))
+        .is_ok();
+    if !table_exists {
+        return Ok(vec![]);
+    }
+    
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, projectId, channelId, title, description, reasoning, confidence, priority, createdAt
+             FROM slack_task_suggestions
+             WHERE status = 'pending_review' AND (?1 IS NULL OR projectId = ?1)
+             ORDER BY createdAt",
+        )
+        .map_err(|e| format!("Failed to prepare pending item query: {}", e))?;
+    let pending = stmt
+        .query_map([project_id], |row| {
+            Ok(serde_json::json!({
+                "id": row.get::<_, String>(0)?,
+                "project_id": row.get::<_, String>(1)?,
+                "channel_id": row.get::<_, String>(2)?,
+                "title": row.get::<_, String>(3)?,
+                "description": row.get::<_, String>(4)?,
+                "reasoning": row.get::<_, String>(5)?,
+                "confidence": row.get::<_, f64>(6)?,
+                "priority": row.get::<_, String>(7)?,
+                "created_at": row.get::<_, String>(8)?,
+            }))
+        })
+        .map_err(|e| format!("Failed to load pending items: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to load pending items: {}", e))?;
+    
+    let pending = pending.into_iter()
+        .filter(|item| item["id"].as_str().map_or(true, |id| !is_item_rejected(conn, id)))
+        .filter(|item| item["project_id"].as_str().map_or(true, |project_id| !is_suggestion_applied(conn, project_id, item)))
+        .collect();
+    Ok(filter_by_confidence(pending, min_confidence, sort_by_confidence))
 }
 
 #[tauri::command]
@@ -266,4 +771,217 @@ pub async fn store_project_insight(_insight: Value) -> Result<String, String> {
 #[tauri::command]
 pub async fn store_task_update_detection(_detection: Value) -> Result<String, String> {
     Ok("not_implemented".to_string())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion() -> Value {
+        serde_json::json!({
+            "title": "Send the Q3 report",
+            "description": "Share the report with finance",
+            "original_text": "@ana can you send the Q3 report to finance by Friday?",
+            "permalink": "https://example.slack.com/archives/C1/p1700000000000100"
+        })
+    }
+
+    fn feedback_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        ensure_feedback_tables(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn bulk_reject_skips_items_already_rejected() {
+        let mut conn = feedback_db();
+        let ids = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let (newly, already) = reject_items(&mut conn, &ids(&["a", "b"]), Some("channel noise"), "2024-05-01T00:00:00Z").unwrap();
+        assert_eq!(newly, ids(&["a", "b"]));
+        assert!(already.is_empty());
+
+        let (newly, already) = reject_items(&mut conn, &ids(&["b", "c", "c"]), None, "2024-05-02T00:00:00Z").unwrap();
+        assert_eq!(newly, ids(&["c"]));
+        assert_eq!(already, ids(&["b", "c"]));
+        assert!(is_item_rejected(&conn, "a"));
+        assert!(!is_item_rejected(&conn, "d"));
+
+        let reason: String = conn
+            .query_row("SELECT reason FROM rejected_ai_items WHERE itemId = 'a'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(reason, "channel noise");
+    }
+
+    #[test]
+    fn feedback_is_stored_and_capped() {
+        let conn = feedback_db();
+        let rejection = serde_json::json!({"type": "rejection", "prompt_key": "task_extraction", "item_ids": ["a", "b"]});
+        save_feedback(&conn, "f0", &rejection, "2024-05-01T00:00:00Z").unwrap();
+
+        let (kind, key, count): (String, String, i64) = conn
+            .query_row("SELECT feedbackType, promptKey, itemCount FROM ai_feedback WHERE id = 'f0'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((kind.as_str(), key.as_str(), count), ("rejection", "task_extraction", 2));
+
+        for i in 1..=MAX_FEEDBACK_ENTRIES {
+            let captured_at = format!("2024-05-02T00:00:{:02}Z", i % 60);
+            save_feedback(&conn, &format!("f{}", i), &serde_json::json!({}), &captured_at).unwrap();
+        }
+        let total: i64 = conn.query_row("SELECT COUNT(*) FROM ai_feedback", [], |row| row.get(0)).unwrap();
+        assert_eq!(total, MAX_FEEDBACK_ENTRIES as i64);
+        let oldest_kept: i64 = conn.query_row("SELECT COUNT(*) FROM ai_feedback WHERE id = 'f0'", [], |row| row.get(0)).unwrap();
+        assert_eq!(oldest_kept, 0);
+    }
+
+    #[test]
+    fn repeated_suggestion_returns_the_first_task() {
+        let suggestion = serde_json::json!({"title": "Ship it", "source_message_ts": "1700000000.000100"});
+        let key = suggestion_idempotency_key("p1", &suggestion).unwrap();
+        assert_eq!(key, "p1:1700000000.000100");
+        assert!(suggestion_idempotency_key("p1", &serde_json::json!({"title": "no source"})).is_none());
+
+        let conn = tasks_db();
+        assert!(!is_suggestion_applied(&conn, "p1", &suggestion));
+        let first = build_task_from_suggestion("task_a", "p1", &suggestion, false);
+        let second = build_task_from_suggestion("task_b", "p1", &suggestion, false);
+        assert_eq!(save_suggested_task(&conn, &first, Some(&key), "2024-05-01T00:00:00Z").unwrap(), "task_a");
+        assert_eq!(save_suggested_task(&conn, &second, Some(&key), "2024-05-01T00:00:01Z").unwrap(), "task_a");
+        assert!(is_suggestion_applied(&conn, "p1", &suggestion));
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    fn tasks_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE tasks (id TEXT PRIMARY KEY, projectId TEXT NOT NULL, title TEXT NOT NULL, description TEXT,
+             completed INTEGER DEFAULT 0, priority TEXT DEFAULT 'medium' CHECK (priority IN ('low', 'medium', 'high')),
+             createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL, syncStatus TEXT DEFAULT 'local');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn applied_suggestion_is_saved_as_a_task() {
+        let conn = tasks_db();
+
+        let mut with_priority = suggestion();
+        with_priority["priority"] = serde_json::json!("Urgent");
+        let task = build_task_from_suggestion("task_1", "p1", &with_priority, true);
+        assert_eq!(save_suggested_task(&conn, &task, None, "2024-05-01T00:00:00Z").unwrap(), "task_1");
+
+        let (title, priority, source): (String, String, String) = conn
+            .query_row("SELECT title, priority, sourceText FROM tasks WHERE id = 'task_1'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(title, "Send the Q3 report");
+        assert_eq!(priority, "high");
+        assert_eq!(source, "@ana can you send the Q3 report to finance by Friday?");
+
+        let untitled = build_task_from_suggestion("task_2", "p1", &serde_json::json!({}), false);
+        assert!(save_suggested_task(&conn, &untitled, None, "2024-05-01T00:00:00Z").is_err());
+    }
+
+    #[test]
+    fn edited_and_deleted_messages_update_their_tasks() {
+        let conn = tasks_db();
+        assert_eq!(reconcile_message_tasks(&conn, "p1", "1.0", None, "2024-05-01T00:00:00Z").unwrap(), 0);
+
+        let task = build_task_from_suggestion("task_1", "p1", &suggestion(), true);
+        save_suggested_task(&conn, &task, Some("p1:1.0"), "2024-05-01T00:00:00Z").unwrap();
+        let done = build_task_from_suggestion("task_2", "p1", &suggestion(), false);
+        save_suggested_task(&conn, &done, Some("p1:2.0"), "2024-05-01T00:00:00Z").unwrap();
+        conn.execute("UPDATE tasks SET completed = 1 WHERE id = 'task_2'", []).unwrap();
+
+        assert_eq!(reconcile_message_tasks(&conn, "p1", "1.0", Some("send the Q4 report"), "2024-05-02T00:00:00Z").unwrap(), 1);
+        let source: String = conn.query_row("SELECT sourceText FROM tasks WHERE id = 'task_1'", [], |row| row.get(0)).unwrap();
+        assert_eq!(source, "send the Q4 report");
+        assert_eq!(reconcile_message_tasks(&conn, "p2", "1.0", None, "2024-05-02T00:00:00Z").unwrap(), 0);
+
+        assert_eq!(reconcile_message_tasks(&conn, "p1", "1.0", None, "2024-05-03T00:00:00Z").unwrap(), 1);
+        assert_eq!(reconcile_message_tasks(&conn, "p1", "2.0", None, "2024-05-03T00:00:00Z").unwrap(), 0);
+        let remaining: Vec<String> = conn.prepare("SELECT id FROM tasks").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(remaining, vec!["task_2".to_string()]);
+    }
+
+    #[test]
+    fn weak_items_are_filtered_and_sorted_by_confidence() {
+        let items = vec![
+            serde_json::json!({"title": "noise", "confidence": 0.3}),
+            serde_json::json!({"title": "likely", "confidence": 0.7}),
+            serde_json::json!({"title": "certain", "confidence": 0.95}),
+            serde_json::json!({"title": "unscored"}),
+        ];
+
+        let kept = filter_by_confidence(items, 0.5, true);
+        let titles: Vec<&str> = kept.iter().map(|i| i["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["certain", "likely"]);
+    }
+
+    #[test]
+    fn applied_task_carries_original_text_when_enabled() {
+        let task = build_task_from_suggestion("task_1", "p1", &suggestion(), true);
+
+        assert_eq!(task["source_text"], "@ana can you send the Q3 report to finance by Friday?");
+        assert_eq!(task["source_permalink"], "https://example.slack.com/archives/C1/p1700000000000100");
+    }
+
+    #[test]
+    fn applied_task_omits_original_text_when_disabled() {
+        let task = build_task_from_suggestion("task_1", "p1", &suggestion(), false);
+
+        assert!(task.get("source_text").is_none());
+        assert!(task.get("source_permalink").is_none());
+        assert_eq!(task["name"], "Send the Q3 report");
+    }
+
+    #[test]
+    fn invalid_api_key_reports_failure() {
+        let validation: crate::ai_service_client::ApiKeyValidation = serde_json::from_value(serde_json::json!({
+            "isValid": false,
+            "provider": "gemini",
+            "errorMessage": "Invalid API key",
+            "timestamp": "2024-01-01T00:00:00.000Z"
+        })).unwrap();
+
+        let report = ai_credentials_report(&validation);
+        assert_eq!(report["success"], false);
+        assert_eq!(report["valid"], false);
+        assert_eq!(report["error"], "Invalid API key");
+    }
+
+    #[test]
+    fn pending_items_below_the_threshold_are_dropped() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        assert!(pending_ai_items(&conn, None, 0.0, false).unwrap().is_empty());
+
+        conn.execute_batch(
+            "CREATE TABLE slack_task_suggestions (
+                 id TEXT PRIMARY KEY, projectId TEXT NOT NULL, channelId TEXT NOT NULL, conversationId TEXT NOT NULL,
+                 title TEXT NOT NULL, description TEXT NOT NULL, reasoning TEXT NOT NULL, confidence REAL NOT NULL,
+                 priority TEXT NOT NULL, status TEXT NOT NULL, sourceMessages TEXT NOT NULL, participants TEXT NOT NULL,
+                 createdAt TEXT NOT NULL, reviewedAt TEXT, reviewedBy TEXT
+             );
+             INSERT INTO slack_task_suggestions VALUES
+                 ('low', 'p1', 'C1', 'c1', 'Maybe a task', '', '', 0.3, 'low', 'pending_review', '[]', '[]', '1', NULL, NULL),
+                 ('mid', 'p1', 'C1', 'c2', 'Likely a task', '', '', 0.6, 'medium', 'pending_review', '[]', '[]', '2', NULL, NULL),
+                 ('high', 'p1', 'C1', 'c3', 'Surely a task', '', '', 0.9, 'high', 'pending_review', '[]', '[]', '3', NULL, NULL),
+                 ('done', 'p1', 'C1', 'c4', 'Reviewed', '', '', 0.95, 'high', 'accepted', '[]', '[]', '4', NULL, NULL),
+                 ('other', 'p2', 'C2', 'c5', 'Elsewhere', '', '', 0.8, 'high', 'pending_review', '[]', '[]', '5', NULL, NULL);",
+        )
+        .unwrap();
+
+        let ids = |items: Vec<Value>| items.iter().map(|item| item["id"].as_str().unwrap().to_string()).collect::<Vec<_>>();
+        assert_eq!(ids(pending_ai_items(&conn, Some("p1"), 0.5, false).unwrap()), vec!["mid", "high"]);
+        assert_eq!(ids(pending_ai_items(&conn, Some("p1"), 0.5, true).unwrap()), vec!["high", "mid"]);
+        assert_eq!(pending_ai_items(&conn, None, 0.0, false).unwrap().len(), 4);
+    }
+}
@@ -4,13 +4,13 @@
 
 use serde_json;
 use chrono;
-use crate::queue_service_client::{QueueServiceClient, SlackSyncRequest, JobRequest, JobOptions};
+use crate::queue_service_client::{QueueServiceClient, SlackSyncRequest, JobRequest, JobOptions, BackoffSpec};
 
 // src-tauri/src/commands/background_sync_commands.rs
 
 #[tauri::command]
 pub async fn queue_background_sync(
-    _app_handle: tauri::AppHandle,
+    app_handle: tauri::AppHandle,
     project_id: String,
     channel_id: String,
     channel_name: String,
@@ -23,18 +23,25 @@ pub async fn queue_background_sync(
     
     match sync_type.as_str() {
         "slack" => {
-            // For Slack sync, we need access token - this would be retrieved from credentials
+            let access_token = crate::credentials::get_slack_credentials(app_handle).await?
+                .and_then(|credentials| credentials.access_token)
+                .ok_or_else(|| "Slack is not connected".to_string())?;
             let slack_request = SlackSyncRequest {
                 project_id: project_id.clone(),
                 channel_id: channel_id.clone(),
                 channel_name: channel_name.clone(),
-                access_token: "placeholder_token".to_string(), // TODO: Get from credentials
+                access_token,
                 last_timestamp: None,
             };
             
+            // Re-queueing a channel that already has a sync pending returns the existing job
             match queue_client.queue_slack_sync(slack_request).await {
                 Ok(job) => {
-                    println!("✅ Slack sync job queued: {}", job.id);
+                    if job.deduplicated {
+                        println!("⏭️ Slack sync already pending as job {}", job.id);
+                    } else {
+                        println!("✅ Slack sync job queued: {}", job.id);
+                    }
                     Ok(job.id)
                 }
                 Err(e) => {
@@ -71,6 +78,8 @@ pub async fn queue_background_sync(
                     delay: Some(0),
                     attempts: Some(3),
                     remove_on_complete: Some(true),
+                    backoff: Some(BackoffSpec::Exponential { delay: 2000 }),
+                    job_id: None,
                 }),
             };
             
@@ -160,10 +169,48 @@ pub async fn get_active_sync_jobs(_app_handle: tauri::AppHandle) -> Result<Vec<s
         }
     }
     
+    // Slack syncs the queue couldn't take, and on-demand ones, run in this process
+    for running in crate::slack::running_channel_syncs() {
+        all_jobs.push(serde_json::json!({
+            "id": format!("slack-sync:{}:{}", running.project_id, running.channel_id),
+            "queue": "in-process",
+            "type": "channel-sync",
+            "status": "active",
+            "data": {
+                "projectId": running.project_id,
+                "channelId": running.channel_id
+            },
+            "createdAt": running.started_at.to_rfc3339()
+        }));
+    }
+    
     println!("✅ Retrieved {} active sync jobs", all_jobs.len());
     Ok(all_jobs)
 }
 
+#[tauri::command]
+pub async fn get_failed_sync_jobs(_app_handle: tauri::AppHandle, queue: String) -> Result<Vec<serde_json::Value>, String> {
+    println!("💀 Getting failed jobs for queue: {}", queue);
+    
+    let queue_client = QueueServiceClient::new(None);
+    let failed = queue_client.get_failed_jobs(&queue).await
+        .map_err(|e| format!("Failed to get failed jobs: {}", e))?;
+    
+    let jobs: Vec<serde_json::Value> = failed.into_iter().map(|job| serde_json::json!({
+        "id": job.id,
+        "queue": queue,
+        "type": job.job_type,
+        "status": "failed",
+        "failedReason": job.failed_reason,
+        "data": job.data,
+        "createdAt": job.created_at,
+        "failedAt": job.failed_at
+    })).collect();
+    
+    println!("✅ Retrieved {} failed jobs", jobs.len());
+    Ok(jobs)
+}
+
 #[tauri::command]
 pub async fn cancel_sync_job(_app_handle: tauri::AppHandle, job_id: String) -> Result<(), String> {
     println!("🗑️ Cancelling sync job: {}", job_id);
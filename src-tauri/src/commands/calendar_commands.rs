@@ -1,13 +1,14 @@
 
-use crate::calendar_commands::{create_calendar_event as create_calendar_event_internal, get_event_by_id as get_event_by_id_internal, get_events_in_range as get_events_in_range_internal, update_event as update_event_internal, delete_event as delete_event_internal, store_event_detection as store_event_detection_internal,};
+use crate::calendar_commands::{create_calendar_event as create_calendar_event_internal, get_event_by_id as get_event_by_id_internal, get_events_in_range as get_events_in_range_internal, update_event as update_event_internal, delete_event as delete_event_internal, store_event_detection as store_event_detection_internal, validate_rrule as validate_rrule_internal, create_events_from_detections as create_events_from_detections_internal, DetectionConversion, NormalizedRrule,};
 // src-tauri/src/commands/calendar_commands.rs
 
 #[tauri::command]
 pub async fn create_calendar_event(
     app_handle: tauri::AppHandle,
     event: serde_json::Value,
+    force: Option<bool>,
 ) -> Result<serde_json::Value, String> {
-    create_calendar_event_internal(app_handle, event).await
+    create_calendar_event_internal(app_handle, event, force.unwrap_or(false)).await
 }
 
 #[tauri::command]
@@ -16,8 +17,8 @@ pub async fn get_event_by_id(app_handle: tauri::AppHandle, event_id: String) ->
 }
 
 #[tauri::command]
-pub async fn get_events_in_range(app_handle: tauri::AppHandle, start_date: String, end_date: String, project_id: Option<String>) -> Result<serde_json::Value, String> {
-    get_events_in_range_internal(app_handle, start_date, end_date, project_id).await.map(|events| serde_json::to_value(events).unwrap_or_default())
+pub async fn get_events_in_range(app_handle: tauri::AppHandle, start_date: String, end_date: String, project_id: Option<String>, timezone: Option<String>) -> Result<serde_json::Value, String> {
+    get_events_in_range_internal(app_handle, start_date, end_date, project_id, timezone).await.map(|events| serde_json::to_value(events).unwrap_or_default())
 }
 
 #[tauri::command]
@@ -41,3 +42,16 @@ pub async fn store_event_detection(
 ) -> Result<String, String> {
     store_event_detection_internal(app_handle, event).await
 }
+
+#[tauri::command]
+pub async fn validate_rrule(rrule: String) -> Result<NormalizedRrule, String> {
+    validate_rrule_internal(&rrule)
+}
+
+#[tauri::command]
+pub async fn create_events_from_detections(
+    app_handle: tauri::AppHandle,
+    detection_ids: Vec<String>,
+) -> Result<Vec<DetectionConversion>, String> {
+    create_events_from_detections_internal(app_handle, detection_ids).await
+}
@@ -0,0 +1,182 @@
+// src-tauri/src/commands/command_error.rs
+
+use serde::{Deserialize, Serialize};
+
+use crate::oauth_service_client::OAuthServiceError;
+use crate::slack::{AuthFailure, SlackError};
+use crate::slack_service_client::SlackServiceError;
+use crate::whatsapp_service_client::WhatsAppServiceError;
+
+/// Stable error codes the frontend can branch on. Codes in `REAUTH_CODES` mean the user has
+/// to authenticate again; `retryable` errors may succeed if the same call is repeated.
+pub mod codes {
+    pub const AUTH_REQUIRED: &str = "auth_required";
+    pub const MISSING_SCOPE: &str = "missing_scope";
+    pub const RATE_LIMITED: &str = "rate_limited";
+    pub const SERVICE_UNAVAILABLE: &str = "service_unavailable";
+    pub const NETWORK: &str = "network";
+    pub const NOT_FOUND: &str = "not_found";
+    pub const NOT_IN_CHANNEL: &str = "not_in_channel";
+    pub const CHANNEL_ARCHIVED: &str = "channel_archived";
+    pub const UNSUPPORTED_CHANNEL: &str = "unsupported_channel";
+    pub const INVALID_INPUT: &str = "invalid_input";
+    pub const INVALID_RESPONSE: &str = "invalid_response";
+    pub const SERVICE_ERROR: &str = "service_error";
+    pub const UNKNOWN: &str = "unknown";
+
+    pub const REAUTH_CODES: &[&str] = &[AUTH_REQUIRED, MISSING_SCOPE];
+}
+
+/// Error returned by commands that report a machine-readable cause alongside the message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+    pub retryable: bool,
+}
+
+impl CommandError {
+    pub fn new(code: &str, message: impl Into<String>, retryable: bool) -> Self {
+        CommandError { code: code.to_string(), message: message.into(), retryable }
+    }
+
+    /// Prefix the message with what the command was doing, e.g. "Connection failed"
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    /// Whether the user must authenticate again before retrying
+    pub fn needs_reauth(&self) -> bool {
+        codes::REAUTH_CODES.contains(&self.code.as_str())
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+/// Errors that were only ever strings carry no cause
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::new(codes::UNKNOWN, message, false)
+    }
+}
+
+/// Code and retryability of a failed request to one of the local services
+fn http_code(e: &reqwest::Error) -> (&'static str, bool) {
+    if e.is_connect() {
+        (codes::SERVICE_UNAVAILABLE, true)
+    } else if e.is_timeout() {
+        (codes::NETWORK, true)
+    } else if e.is_decode() {
+        (codes::INVALID_RESPONSE, false)
+    } else {
+        (codes::NETWORK, true)
+    }
+}
+
+impl From<SlackError> for CommandError {
+    fn from(e: SlackError) -> Self {
+        let code = match &e {
+            SlackError::Auth(AuthFailure::InvalidClientCredentials) => codes::INVALID_INPUT,
+            SlackError::Auth(_) => codes::AUTH_REQUIRED,
+            SlackError::RateLimited { .. } => codes::RATE_LIMITED,
+            SlackError::NotInChannel => codes::NOT_IN_CHANNEL,
+            SlackError::ChannelNotFound | SlackError::UserNotFound => codes::NOT_FOUND,
+            SlackError::ChannelArchived => codes::CHANNEL_ARCHIVED,
+            SlackError::UnsupportedChannelType => codes::UNSUPPORTED_CHANNEL,
+            SlackError::MissingScope(_) => codes::MISSING_SCOPE,
+            SlackError::EmptyInput(_) => codes::INVALID_INPUT,
+            SlackError::Http(_) => codes::NETWORK,
+            SlackError::Parse(_) => codes::INVALID_RESPONSE,
+            SlackError::Api(_) => codes::SERVICE_ERROR,
+        };
+        CommandError::new(code, e.to_string(), e.is_retryable())
+    }
+}
+
+/// Code and retryability of an error from the Slack service, looking through its request id
+fn slack_service_code(e: &SlackServiceError) -> (&'static str, bool) {
+    match e {
+        SlackServiceError::Http(http) => http_code(http),
+        SlackServiceError::ServiceUnavailable(_) => (codes::SERVICE_UNAVAILABLE, true),
+        SlackServiceError::InvalidResponse(_) => (codes::INVALID_RESPONSE, false),
+        SlackServiceError::SlackApiError(_) => (codes::SERVICE_ERROR, false),
+        SlackServiceError::AuthenticationFailed(_) => (codes::AUTH_REQUIRED, false),
+        SlackServiceError::Traced(traced) => slack_service_code(&traced.source),
+    }
+}
+
+impl From<SlackServiceError> for CommandError {
+    fn from(e: SlackServiceError) -> Self {
+        let (code, retryable) = slack_service_code(&e);
+        CommandError::new(code, e.to_string(), retryable)
+    }
+}
+
+/// Code and retryability of an error from the WhatsApp service, looking through its request id
+fn whatsapp_service_code(e: &WhatsAppServiceError) -> (&'static str, bool) {
+    match e {
+        WhatsAppServiceError::Http(http) => http_code(http),
+        WhatsAppServiceError::InvalidResponse(_) => (codes::INVALID_RESPONSE, false),
+        WhatsAppServiceError::ServiceError(_) => (codes::SERVICE_ERROR, false),
+        WhatsAppServiceError::InvalidLookback(_) => (codes::INVALID_INPUT, false),
+        WhatsAppServiceError::Traced(traced) => whatsapp_service_code(&traced.source),
+    }
+}
+
+impl From<WhatsAppServiceError> for CommandError {
+    fn from(e: WhatsAppServiceError) -> Self {
+        let (code, retryable) = whatsapp_service_code(&e);
+        CommandError::new(code, e.to_string(), retryable)
+    }
+}
+
+/// Code and retryability of an error from the OAuth service, looking through its request id
+fn oauth_service_code(e: &OAuthServiceError) -> (&'static str, bool) {
+    match e {
+        OAuthServiceError::Http(http) => http_code(http),
+        OAuthServiceError::ServiceUnavailable(_) => (codes::SERVICE_UNAVAILABLE, true),
+        OAuthServiceError::InvalidResponse(_) => (codes::INVALID_RESPONSE, false),
+        OAuthServiceError::AuthenticationFailed(_) => (codes::AUTH_REQUIRED, false),
+        OAuthServiceError::ServiceError(_) => (codes::SERVICE_ERROR, false),
+        OAuthServiceError::Traced(traced) => oauth_service_code(&traced.source),
+    }
+}
+
+impl From<OAuthServiceError> for CommandError {
+    fn from(e: OAuthServiceError) -> Self {
+        let (code, retryable) = oauth_service_code(&e);
+        CommandError::new(code, e.to_string(), retryable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slack_errors_map_to_stable_codes() {
+        let auth: CommandError = SlackError::Auth(AuthFailure::ExpiredToken).into();
+        assert_eq!(auth.code, codes::AUTH_REQUIRED);
+        assert!(auth.needs_reauth());
+        assert!(!auth.retryable);
+
+        let limited: CommandError = SlackError::RateLimited { retry_after: None }.into();
+        assert_eq!(limited.code, codes::RATE_LIMITED);
+        assert!(limited.retryable);
+    }
+
+    #[test]
+    fn traced_service_errors_keep_the_underlying_code() {
+        let err = WhatsAppServiceError::InvalidLookback(99999).with_request_id("req-1");
+        let command_error: CommandError = err.into();
+
+        assert_eq!(command_error.code, codes::INVALID_INPUT);
+        assert!(command_error.message.contains("req-1"));
+        assert!(!command_error.retryable);
+    }
+}
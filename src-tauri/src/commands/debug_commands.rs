@@ -3,6 +3,8 @@
 
 use tauri::Manager;
 
+use crate::log_sink::{recent_logs, LogLine};
+
 #[tauri::command]
 pub async fn open_devtools(window: tauri::Window) -> Result<(), String> {
     #[cfg(debug_assertions)]
@@ -19,3 +21,9 @@ pub async fn open_devtools(window: tauri::Window) -> Result<(), String> {
         Err("DevTools only available in debug mode".to_string())
     }
 }
+
+/// Last `limit` (default 200) log lines captured while debug logging was on
+#[tauri::command]
+pub async fn get_recent_logs(limit: Option<usize>) -> Result<Vec<LogLine>, String> {
+    Ok(recent_logs(limit.unwrap_or(200)))
+}
@@ -1,5 +1,5 @@
 
-use crate::document_commands::create_document as create_document_internal;
+use crate::document_commands::{create_document as create_document_internal, Document, DocumentVersion};
 
 // src-tauri/src/commands/document_commands.rs
 
@@ -16,3 +16,47 @@ pub async fn create_document(
     });
     create_document_internal(app_handle, project_id, document_data).await
 }
+
+#[tauri::command]
+pub async fn get_documents(
+    app_handle: tauri::AppHandle,
+    project_id: Option<String>,
+) -> Result<Vec<Document>, String> {
+    crate::document_commands::get_documents(app_handle, project_id).await
+}
+
+#[tauri::command]
+pub async fn get_document(app_handle: tauri::AppHandle, document_id: String) -> Result<Document, String> {
+    crate::document_commands::get_document(app_handle, document_id).await
+}
+
+#[tauri::command]
+pub async fn update_document(
+    app_handle: tauri::AppHandle,
+    document_id: String,
+    content: String,
+) -> Result<Document, String> {
+    crate::document_commands::update_document(app_handle, document_id, content).await
+}
+
+#[tauri::command]
+pub async fn delete_document(app_handle: tauri::AppHandle, document_id: String) -> Result<String, String> {
+    crate::document_commands::delete_document(app_handle, document_id).await
+}
+
+#[tauri::command]
+pub async fn get_document_versions(
+    app_handle: tauri::AppHandle,
+    document_id: String,
+) -> Result<Vec<DocumentVersion>, String> {
+    crate::document_commands::get_document_versions(app_handle, document_id).await
+}
+
+#[tauri::command]
+pub async fn restore_document_version(
+    app_handle: tauri::AppHandle,
+    document_id: String,
+    version_id: String,
+) -> Result<Document, String> {
+    crate::document_commands::restore_document_version(app_handle, document_id, version_id).await
+}
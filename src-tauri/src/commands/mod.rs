@@ -1,4 +1,3 @@
-use specta::Type;
 use tauri::command;
 
 // Module declarations
@@ -16,35 +15,21 @@ pub mod project_commands;
 pub mod document_commands;
 pub mod slack_commands;
 pub mod background_sync_commands;
+pub mod search_commands;
+pub mod command_error;
 
 // Re-export commonly used types
 #[allow(unused_imports)]
 pub use crate::commands::oauth_servers::OAuthServiceClientState;
 
-#[derive(Type, serde::Serialize, serde::Deserialize)]
-pub struct Project {
-    pub id: i32,
-    pub name: String,
-    pub description: String,
-    pub status: String,
-}
-
+/// A page of projects (`{ items, total }`), sorted by `sort_by` (name, created, updated or status)
 #[command]
-#[specta::specta(export = false)]
-pub fn get_projects() -> Vec<Project> {
-    // In a real application, you would fetch this from a database
-    vec![
-        Project {
-            id: 1,
-            name: "Project 1".to_string(),
-            description: "Description for project 1".to_string(),
-            status: "active".to_string(),
-        },
-        Project {
-            id: 2,
-            name: "Project 2".to_string(),
-            description: "Description for project 2".to_string(),
-            status: "archived".to_string(),
-        },
-    ]
-}
\ No newline at end of file
+pub async fn get_projects(
+    app: tauri::AppHandle,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort_by: Option<crate::project_commands::ProjectSort>,
+    status_filter: Option<String>,
+) -> Result<crate::project_commands::ProjectPage, String> {
+    crate::project_commands::list_projects(app, offset, limit, sort_by, status_filter).await
+}
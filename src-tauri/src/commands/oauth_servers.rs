@@ -1,43 +1,309 @@
 use tauri::State;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use crate::oauth_service_client::OAuthServiceClient;
+use crate::oauth_service_client::{OAuthServiceClient, OAuthServiceError};
+use crate::commands::command_error::CommandError;
+use crate::local_http::{read_request, write_response, READ_TIMEOUT};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use url::form_urlencoded;
 
 // State types for OAuth service client management
 pub type OAuthServiceClientState = Arc<Mutex<Option<OAuthServiceClient>>>;
 
+/// How long an issued OAuth `state` stays valid, and how long a loopback listener waits
+const OAUTH_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+/// How often expired OAuth states are pruned in the background
+const OAUTH_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
+// Configured time an OAuth `state` is kept before it's pruned, in seconds
+static OAUTH_STATE_RETENTION_SECS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(OAUTH_STATE_TTL.as_secs());
 
+/// How long issued states are kept; never shorter than they stay valid
+fn oauth_state_retention() -> Duration {
+    Duration::from_secs(OAUTH_STATE_RETENTION_SECS.load(std::sync::atomic::Ordering::SeqCst)).max(OAUTH_STATE_TTL)
+}
+
+// OAuth `state` values handed out with authorization URLs, kept for CSRF validation
+// together with the redirect URI the flow was started with
+static PENDING_OAUTH_STATES: Lazy<std::sync::Mutex<HashMap<String, (Instant, String)>>> = Lazy::new(|| {
+    std::sync::Mutex::new(HashMap::new())
+});
+
+// Redirect URI of the loopback listener still waiting for each provider's callback
+static LOOPBACK_LISTENERS: Lazy<std::sync::Mutex<HashMap<String, String>>> = Lazy::new(|| {
+    std::sync::Mutex::new(HashMap::new())
+});
+
+/// Remember a `state` value attached to an authorization URL
+pub fn register_oauth_state(state: &str, redirect_uri: &str) {
+    let mut states = PENDING_OAUTH_STATES.lock().unwrap_or_else(|e| e.into_inner());
+    prune_states(&mut states, oauth_state_retention());
+    states.insert(state.to_string(), (Instant::now(), redirect_uri.to_string()));
+}
+
+/// Check and consume a `state` returned by the provider, returning the redirect URI it was
+/// issued for. Each state can be used once, within `OAUTH_STATE_TTL` of being issued.
+pub fn consume_oauth_state(state: Option<&str>) -> Result<String, String> {
+    let state = state
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Missing OAuth state parameter".to_string())?;
+    
+    let mut states = PENDING_OAUTH_STATES.lock().unwrap_or_else(|e| e.into_inner());
+    prune_states(&mut states, oauth_state_retention());
+    
+    match states.remove(state) {
+        Some((issued, redirect_uri)) if issued.elapsed() < OAUTH_STATE_TTL => Ok(redirect_uri),
+        _ => Err("Invalid or expired OAuth state parameter".to_string()),
+    }
+}
+
+/// Drop states issued more than `retention` ago, returning how many were dropped
+fn prune_states(states: &mut HashMap<String, (Instant, String)>, retention: Duration) -> usize {
+    let before = states.len();
+    states.retain(|_, (issued, _)| issued.elapsed() < retention);
+    before - states.len()
+}
+
+/// Drop OAuth states from flows that were abandoned, returning how many were dropped
+pub fn cleanup_expired_oauth_states() -> usize {
+    let mut states = PENDING_OAUTH_STATES.lock().unwrap_or_else(|e| e.into_inner());
+    prune_states(&mut states, oauth_state_retention())
+}
+
+/// Prune abandoned OAuth states every hour, picking up the `oauth.state_retention_minutes`
+/// setting each time. Runs for the life of the app.
+pub async fn run_oauth_cleanup(app: tauri::AppHandle) {
+    use crate::commands::settings::{get_setting_as, OAUTH_STATE_RETENTION_MINUTES};
+    
+    let mut interval = tokio::time::interval(OAUTH_CLEANUP_INTERVAL);
+    loop {
+        interval.tick().await;
+        
+        match get_setting_as::<u64>(&app, OAUTH_STATE_RETENTION_MINUTES).await {
+            Ok(minutes) => OAUTH_STATE_RETENTION_SECS.store(minutes.max(1) * 60, std::sync::atomic::Ordering::SeqCst),
+            Err(e) => println!("⚠️ Could not read {}: {}", OAUTH_STATE_RETENTION_MINUTES, e),
+        }
+        
+        let pruned = cleanup_expired_oauth_states();
+        println!("🧹 OAuth cleanup pruned {} expired states", pruned);
+    }
+}
+
+/// Callback URI handled by the OAuth service for a provider
+pub fn oauth_redirect_uri(provider: &str) -> String {
+    format!("https://localhost:3003/api/oauth/{}/callback", provider)
+}
+
+/// Redirect URI of the provider's waiting loopback listener, if there is one
+fn active_loopback_listener(provider: &str) -> Option<String> {
+    LOOPBACK_LISTENERS.lock().unwrap_or_else(|e| e.into_inner()).get(provider).cloned()
+}
+
+/// Record `redirect_uri` as the provider's listener unless another one got there first, in
+/// which case that one's redirect URI is returned
+fn claim_loopback_listener(provider: &str, redirect_uri: &str) -> Result<(), String> {
+    let mut listeners = LOOPBACK_LISTENERS.lock().unwrap_or_else(|e| e.into_inner());
+    match listeners.get(provider) {
+        Some(existing) => Err(existing.clone()),
+        None => {
+            listeners.insert(provider.to_string(), redirect_uri.to_string());
+            Ok(())
+        }
+    }
+}
+
+fn release_loopback_listener(provider: &str) {
+    LOOPBACK_LISTENERS.lock().unwrap_or_else(|e| e.into_inner()).remove(provider);
+}
+
+/// Start a one-shot HTTP listener on 127.0.0.1 that receives the provider redirect and
+/// forwards `code`/`state` to the frontend as a `<provider>-oauth-callback` event.
+/// Returns the redirect URI to use for the flow. While a listener for the provider is still
+/// waiting, retries reuse it instead of starting another.
+pub async fn start_loopback_callback_listener(app: tauri::AppHandle, provider: &str) -> Result<String, String> {
+    if let Some(redirect_uri) = active_loopback_listener(provider) {
+        println!("♻️ Reusing loopback OAuth listener on {}", redirect_uri);
+        return Ok(redirect_uri);
+    }
+    
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await
+        .map_err(|e| format!("Failed to bind loopback listener: {}", e))?;
+    let port = listener.local_addr()
+        .map_err(|e| format!("Failed to read loopback address: {}", e))?
+        .port();
+    
+    let redirect_uri = format!("http://127.0.0.1:{}/oauth/{}/callback", port, provider);
+    // A concurrent attempt bound first: drop this listener and share that one
+    if let Err(existing) = claim_loopback_listener(provider, &redirect_uri) {
+        println!("♻️ Reusing loopback OAuth listener on {}", existing);
+        return Ok(existing);
+    }
+    
+    let event_name = format!("{}-oauth-callback", provider);
+    let provider = provider.to_string();
+    println!("👂 Loopback OAuth listener waiting on {}", redirect_uri);
+    
+    tokio::spawn(async move {
+        let received = tokio::time::timeout(OAUTH_STATE_TTL, async {
+            loop {
+                let mut stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        println!("❌ Loopback OAuth listener failed: {}", e);
+                        return;
+                    }
+                };
+                
+                if let Some(params) = read_loopback_callback(&mut stream).await {
+                    use tauri::Emitter;
+                    if let Err(e) = app.emit(&event_name, &params) {
+                        println!("⚠️ Failed to emit OAuth callback event: {}", e);
+                    }
+                    println!("✅ Received OAuth callback on loopback listener");
+                    return;
+                }
+            }
+        }).await;
+        
+        release_loopback_listener(&provider);
+        if received.is_err() {
+            println!("⏰ Loopback OAuth listener timed out without a callback");
+        }
+    });
+    
+    Ok(redirect_uri)
+}
+
+/// Read one HTTP request and, if it carries OAuth callback parameters, answer it and return them
+async fn read_loopback_callback(stream: &mut tokio::net::TcpStream) -> Option<HashMap<String, String>> {
+    // "GET /oauth/<provider>/callback?code=...&state=... HTTP/1.1"; the redirect has no body
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(stream, 0)).await.ok()??;
+    let params = request.query_params();
+
+    if !params.contains_key("code") && !params.contains_key("error") {
+        write_response(stream, 404, "text/plain", "").await;
+        return None;
+    }
+
+    let body = "<html><body><h3>Authorization received. You can close this window and return to the app.</h3></body></html>";
+    write_response(stream, 200, "text/html; charset=utf-8", body).await;
 
-/// Initialize the OAuth service client
+    Some(params)
+}
+
+/// Authorization endpoint and scope separator for each supported provider
+fn provider_authorize_endpoint(provider: &str) -> Result<(&'static str, &'static str), String> {
+    match provider {
+        "slack" => Ok(("https://slack.com/oauth/v2/authorize", ",")),
+        "google" => Ok(("https://accounts.google.com/o/oauth2/v2/auth", " ")),
+        other => Err(format!("Unsupported OAuth provider: {}", other)),
+    }
+}
+
+/// Start an OAuth flow for any supported provider and open the authorization URL
 #[tauri::command]
-pub async fn start_https_oauth_server(_app: tauri::AppHandle, state: State<'_, OAuthServiceClientState>) -> Result<String, String> {
-    println!("🚀 Initializing OAuth service client...");
+pub async fn start_oauth(
+    app: tauri::AppHandle,
+    oauth_server_state: State<'_, OAuthServiceClientState>,
+    provider: String,
+    client_id: String,
+    scopes: Vec<String>,
+) -> Result<serde_json::Value, String> {
+    println!("🚀 Starting {} OAuth flow", provider);
     
-    let mut client_guard = state.lock().await;
+    let (authorize_endpoint, scope_separator) = provider_authorize_endpoint(&provider)?;
+    
+    // Prefer the OAuth service callback; fall back to a local loopback listener when it's down
+    let service_healthy = match ensure_oauth_client(&mut *oauth_server_state.lock().await).await {
+        Ok(healthy) => healthy,
+        Err(e) => {
+            println!("⚠️ OAuth service unavailable: {}", e);
+            false
+        }
+    };
+    
+    let redirect_uri = if service_healthy {
+        println!("✅ OAuth service client started successfully");
+        oauth_redirect_uri(&provider)
+    } else {
+        println!("🔁 Falling back to loopback OAuth callback");
+        match start_loopback_callback_listener(app.clone(), &provider).await {
+            Ok(uri) => uri,
+            Err(e) => {
+                println!("❌ Failed to start loopback OAuth listener: {}", e);
+                return Ok(serde_json::json!({
+                    "success": false,
+                    "error": format!("Failed to start OAuth server: {}", e)
+                }));
+            }
+        }
+    };
+    
+    // Remember the state so the callback can be checked against it
+    let oauth_state = format!("state_{}", uuid::Uuid::new_v4().simple());
+    register_oauth_state(&oauth_state, &redirect_uri);
+    
+    let query: String = form_urlencoded::Serializer::new(String::new())
+        .append_pair("client_id", &client_id)
+        .append_pair("scope", &scopes.join(scope_separator))
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("state", &oauth_state)
+        .append_pair("response_type", "code")
+        .finish();
+    let oauth_url = format!("{}?{}", authorize_endpoint, query);
+    
+    println!("✅ OAuth URL generated successfully");
+    
+    // Open the OAuth URL in the user's default browser
+    if let Err(e) = open::that(&oauth_url) {
+        println!("⚠️ Failed to open browser automatically: {}", e);
+        // Don't fail the whole operation if browser opening fails
+    }
     
-    if client_guard.is_some() {
-        println!("⚠️ OAuth service client is already initialized");
-        return Ok("OAuth service client is already initialized".to_string());
+    Ok(serde_json::json!({
+        "success": true,
+        "url": oauth_url,
+        "redirect_uri": redirect_uri
+    }))
+}
+
+/// Reuse the client in `client_slot` or create one, returning whether the service is healthy.
+/// Callers hold the `OAuthServiceClientState` lock, so concurrent starts share one client.
+pub async fn ensure_oauth_client(client_slot: &mut Option<OAuthServiceClient>) -> Result<bool, OAuthServiceError> {
+    if let Some(client) = client_slot.as_ref() {
+        println!("♻️ Reusing initialized OAuth service client");
+        return client.health_check().await;
     }
     
     let client = OAuthServiceClient::new(None); // Uses default localhost:3003
+    let healthy = client.health_check().await?;
+    *client_slot = Some(client);
+    Ok(healthy)
+}
+
+/// Initialize the OAuth service client. Calling it again while a client exists reuses it.
+#[tauri::command]
+pub async fn start_https_oauth_server(_app: tauri::AppHandle, state: State<'_, OAuthServiceClientState>) -> Result<String, CommandError> {
+    println!("🚀 Initializing OAuth service client...");
     
-    // Test health check
-    match client.health_check().await {
+    let mut client_guard = state.lock().await;
+    let already_initialized = client_guard.is_some();
+    
+    match ensure_oauth_client(&mut client_guard).await {
+        Ok(true) if already_initialized => Ok("OAuth service client is already initialized".to_string()),
         Ok(true) => {
-            *client_guard = Some(client);
             println!("✅ OAuth service client initialized and health check passed");
             Ok("OAuth service client initialized successfully".to_string())
         }
         Ok(false) => {
             println!("⚠️ OAuth service is not healthy, but client initialized");
-            *client_guard = Some(client);
             Ok("OAuth service client initialized (service not healthy)".to_string())
         }
         Err(e) => {
             println!("❌ Failed to initialize OAuth service client: {}", e);
-            Err(format!("Failed to initialize OAuth service client: {}", e))
+            Err(CommandError::from(e).with_context("Failed to initialize OAuth service client"))
         }
     }
 }
@@ -72,9 +338,13 @@ pub async fn https_oauth_server_status(state: State<'_, OAuthServiceClientState>
     Ok(is_healthy)
 }
 
-/// Test OAuth service connection
+/// Prune expired OAuth states and test the OAuth service connection. Stored tokens expire
+/// in the service on their own.
 #[tauri::command]
-pub async fn cleanup_oauth_tokens(state: State<'_, OAuthServiceClientState>) -> Result<String, String> {
+pub async fn cleanup_oauth_tokens(state: State<'_, OAuthServiceClientState>) -> Result<String, CommandError> {
+    let pruned = cleanup_expired_oauth_states();
+    println!("🧹 Pruned {} expired OAuth states", pruned);
+    
     let client_guard = state.lock().await;
     if let Some(client) = client_guard.as_ref() {
         match client.health_check().await {
@@ -88,11 +358,59 @@ pub async fn cleanup_oauth_tokens(state: State<'_, OAuthServiceClientState>) ->
             }
             Err(e) => {
                 println!("❌ OAuth service connection test failed: {}", e);
-                Err(format!("OAuth service connection test failed: {}", e))
+                Err(CommandError::from(e).with_context("OAuth service connection test failed"))
             }
         }
     } else {
         println!("⚠️ OAuth service client is not initialized");
         Ok("OAuth service client is not initialized".to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Held by tests that touch the pending states, their retention or the loopback listeners
+    static GLOBAL_OAUTH_STATE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn states_older_than_the_retention_are_pruned() {
+        let mut states = HashMap::new();
+        states.insert("fresh".to_string(), (Instant::now(), "uri".to_string()));
+        states.insert("stale".to_string(), (Instant::now() - Duration::from_secs(120), "uri".to_string()));
+
+        assert_eq!(prune_states(&mut states, Duration::from_secs(60)), 1);
+        assert!(states.contains_key("fresh"));
+        assert_eq!(prune_states(&mut states, Duration::from_secs(60)), 0);
+    }
+
+    #[test]
+    fn retained_states_expire_after_the_ttl() {
+        let _guard = GLOBAL_OAUTH_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        OAUTH_STATE_RETENTION_SECS.store(24 * 60 * 60, std::sync::atomic::Ordering::SeqCst);
+        PENDING_OAUTH_STATES.lock().unwrap().insert(
+            "retained-state".to_string(),
+            (Instant::now() - OAUTH_STATE_TTL - Duration::from_secs(1), "uri".to_string()),
+        );
+        register_oauth_state("fresh-state", "uri");
+
+        assert!(consume_oauth_state(Some("retained-state")).is_err());
+        assert_eq!(consume_oauth_state(Some("fresh-state")), Ok("uri".to_string()));
+        assert!(consume_oauth_state(Some("fresh-state")).is_err());
+    }
+
+    #[test]
+    fn only_one_loopback_listener_is_claimed_per_provider() {
+        let _guard = GLOBAL_OAUTH_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        assert!(claim_loopback_listener("test-provider", "http://127.0.0.1:1/cb").is_ok());
+        assert_eq!(
+            claim_loopback_listener("test-provider", "http://127.0.0.1:2/cb"),
+            Err("http://127.0.0.1:1/cb".to_string())
+        );
+        assert_eq!(active_loopback_listener("test-provider").as_deref(), Some("http://127.0.0.1:1/cb"));
+
+        release_loopback_listener("test-provider");
+        assert!(active_loopback_listener("test-provider").is_none());
+    }
+}
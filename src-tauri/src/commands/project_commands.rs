@@ -1,7 +1,7 @@
 
 // src-tauri/src/commands/project_commands.rs
 
-use crate::project_commands::{get_all_projects as get_all_projects_internal, get_project as get_project_internal};
+use crate::project_commands::{get_all_projects as get_all_projects_internal, get_project as get_project_internal, ProjectUpdate};
 // src-tauri/src/commands/project_commands.rs
 
 #[tauri::command]
@@ -29,3 +29,22 @@ pub async fn update_project_field(
 ) -> Result<serde_json::Value, String> {
     crate::project_commands::update_project_field(app_handle, project_id, field, value).await.map(|s| serde_json::Value::String(s))
 }
+
+#[tauri::command]
+pub async fn update_project(
+    app_handle: tauri::AppHandle,
+    project_id: String,
+    update: ProjectUpdate,
+) -> Result<serde_json::Value, String> {
+    crate::project_commands::update_project(app_handle, project_id, update).await
+}
+
+#[tauri::command]
+pub async fn archive_project(app_handle: tauri::AppHandle, project_id: String) -> Result<serde_json::Value, String> {
+    crate::project_commands::archive_project(app_handle, project_id).await
+}
+
+#[tauri::command]
+pub async fn delete_project(app_handle: tauri::AppHandle, project_id: String, cascade: bool) -> Result<String, String> {
+    crate::project_commands::delete_project(app_handle, project_id, cascade).await
+}
@@ -1,5 +1,62 @@
 // Simplified prompt commands using new AI service
 
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Aggregated usage for one prompt key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptUsageStat {
+    pub key: String,
+    pub invocation_count: i64,
+    pub last_used_at: String,
+    /// Accepted / (accepted + rejected) items in this prompt's feedback; `None` without feedback
+    pub acceptance_rate: Option<f64>,
+    pub avg_execution_time_ms: f64,
+    pub total_tokens: i64,
+}
+
+/// Two prompt variants served side by side; `split` is the share of buckets that get variant B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptExperiment {
+    pub key: String,
+    pub variant_a: String,
+    pub variant_b: String,
+    pub split: f64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PromptVariant {
+    A,
+    B,
+}
+
+impl PromptVariant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptVariant::A => "A",
+            PromptVariant::B => "B",
+        }
+    }
+}
+
+/// Usage of one experiment variant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantResult {
+    pub variant: String,
+    pub invocation_count: i64,
+    /// Share of this variant's runs recorded as successful; feedback isn't tied to a variant
+    pub success_rate: f64,
+    pub avg_execution_time_ms: f64,
+    pub total_tokens: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentResults {
+    pub experiment: PromptExperiment,
+    pub variants: Vec<VariantResult>,
+}
+
 #[tauri::command]
 pub async fn get_all_prompts(_app_handle: tauri::AppHandle) -> Result<serde_json::Value, String> {
     println!("📋 Getting all prompts (simplified)");
@@ -12,13 +69,65 @@ pub async fn get_all_prompts(_app_handle: tauri::AppHandle) -> Result<serde_json
 }
 
 #[tauri::command]
-pub async fn get_effective_prompt(_app_handle: tauri::AppHandle, key: String) -> Result<String, String> {
+pub async fn get_effective_prompt(
+    app_handle: tauri::AppHandle,
+    key: String,
+    bucket_key: Option<String>,
+) -> Result<String, String> {
     println!("🔍 Getting effective prompt for key: {}", key);
     
+    if let Some(bucket_key) = bucket_key {
+        let experiment_key = key.clone();
+        let experiment = with_prompt_usage_db(&app_handle, move |conn| {
+            find_prompt_experiment(conn, &experiment_key)
+        }).await?;
+        if let Some(experiment) = experiment {
+            let variant = assign_variant(&key, &bucket_key, experiment.split);
+            println!("🧪 Serving variant {} of {} to bucket {}", variant.as_str(), key, bucket_key);
+            return Ok(match variant {
+                PromptVariant::A => experiment.variant_a,
+                PromptVariant::B => experiment.variant_b,
+            });
+        }
+    }
+    
     // Return default prompt
     Ok(format!("Default prompt for {}", key))
 }
 
+#[tauri::command]
+pub async fn set_prompt_experiment(
+    app_handle: tauri::AppHandle,
+    key: String,
+    variant_a: String,
+    variant_b: String,
+    split: f64,
+) -> Result<PromptExperiment, String> {
+    println!("🧪 Setting prompt experiment for {} (split: {})", key, split);
+    
+    if !(0.0..=1.0).contains(&split) {
+        return Err(format!("Experiment split must be between 0 and 1, got {}", split));
+    }
+    
+    let experiment = PromptExperiment {
+        key,
+        variant_a,
+        variant_b,
+        split,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let saved = experiment.clone();
+    with_prompt_usage_db(&app_handle, move |conn| save_prompt_experiment(conn, &saved)).await?;
+    Ok(experiment)
+}
+
+#[tauri::command]
+pub async fn get_experiment_results(app_handle: tauri::AppHandle, key: String) -> Result<ExperimentResults, String> {
+    println!("🧪 Getting experiment results for {}", key);
+    
+    with_prompt_usage_db(&app_handle, move |conn| experiment_results(conn, &key)).await
+}
+
 #[tauri::command]
 pub async fn initialize_default_prompts(_app_handle: tauri::AppHandle) -> Result<(), String> {
     println!("🚀 Initializing default prompts (managed by AI service)");
@@ -27,17 +136,210 @@ pub async fn initialize_default_prompts(_app_handle: tauri::AppHandle) -> Result
 
 #[tauri::command]
 pub async fn record_prompt_usage(
-    _app_handle: tauri::AppHandle, 
+    app_handle: tauri::AppHandle, 
     key: String, 
     success: bool, 
     execution_time_ms: i32, 
-    token_count: Option<i32>
+    token_count: Option<i32>,
+    bucket_key: Option<String>,
 ) -> Result<(), String> {
     println!("📊 Recording prompt usage: {} (success: {}, time: {}ms)", key, success, execution_time_ms);
     if let Some(tokens) = token_count {
         println!("  Token count: {}", tokens);
     }
-    Ok(())
+    
+    with_prompt_usage_db(&app_handle, move |conn| {
+        // The bucket hash is deterministic, so the served variant can be recomputed here
+        let variant = match &bucket_key {
+            Some(bucket_key) => find_prompt_experiment(conn, &key)?
+                .map(|experiment| assign_variant(&key, bucket_key, experiment.split)),
+            None => None,
+        };
+        insert_prompt_usage(conn, &key, success, execution_time_ms, token_count, variant, &chrono::Utc::now().to_rfc3339())
+    }).await
+}
+
+#[tauri::command]
+pub async fn get_prompt_usage_stats(
+    app_handle: tauri::AppHandle,
+    key: Option<String>,
+) -> Result<Vec<PromptUsageStat>, String> {
+    println!("📊 Getting prompt usage stats{}", key.as_ref().map(|k| format!(" for {}", k)).unwrap_or_default());
+    
+    with_prompt_usage_db(&app_handle, move |conn| prompt_usage_stats(conn, key.as_deref())).await
+}
+
+/// Run `f` against the app database once the prompt usage and feedback tables exist
+async fn with_prompt_usage_db<T, F>(app_handle: &tauri::AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> Result<T, String> + Send + 'static,
+{
+    crate::app_db::with_app_db(app_handle, move |conn| {
+        ensure_prompt_usage_table(conn)?;
+        crate::commands::ai_automation::ensure_feedback_tables(conn)?;
+        f(conn)
+    }).await
+}
+
+pub fn ensure_prompt_usage_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS prompt_usage (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            promptKey TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            executionTimeMs INTEGER NOT NULL,
+            tokenCount INTEGER,
+            variant TEXT,
+            usedAt TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_prompt_usage_key ON prompt_usage (promptKey);
+        CREATE TABLE IF NOT EXISTS prompt_experiments (
+            promptKey TEXT PRIMARY KEY,
+            variantA TEXT NOT NULL,
+            variantB TEXT NOT NULL,
+            split REAL NOT NULL,
+            createdAt TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("Failed to create prompt usage tables: {}", e))
+}
+
+/// Stable FNV-1a hash, so a bucket keeps its variant across app versions
+fn bucket_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Deterministically place `bucket_key` in variant A or B for the prompt `key`
+pub fn assign_variant(key: &str, bucket_key: &str, split: f64) -> PromptVariant {
+    let position = (bucket_hash(&format!("{}:{}", key, bucket_key)) % 10_000) as f64 / 10_000.0;
+    if position < split {
+        PromptVariant::B
+    } else {
+        PromptVariant::A
+    }
+}
+
+fn save_prompt_experiment(conn: &Connection, experiment: &PromptExperiment) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO prompt_experiments (promptKey, variantA, variantB, split, createdAt)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![experiment.key, experiment.variant_a, experiment.variant_b, experiment.split, experiment.created_at],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to store prompt experiment: {}", e))
+}
+
+fn find_prompt_experiment(conn: &Connection, key: &str) -> Result<Option<PromptExperiment>, String> {
+    let result = conn.query_row(
+        "SELECT promptKey, variantA, variantB, split, createdAt FROM prompt_experiments WHERE promptKey = ?1",
+        params![key],
+        |row| {
+            Ok(PromptExperiment {
+                key: row.get(0)?,
+                variant_a: row.get(1)?,
+                variant_b: row.get(2)?,
+                split: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        },
+    );
+    match result {
+        Ok(experiment) => Ok(Some(experiment)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to read prompt experiment: {}", e)),
+    }
+}
+
+/// Usage per variant for the experiment on `key`
+pub fn experiment_results(conn: &Connection, key: &str) -> Result<ExperimentResults, String> {
+    let experiment = find_prompt_experiment(conn, key)?
+        .ok_or_else(|| format!("No experiment configured for prompt {}", key))?;
+    
+    let mut stmt = conn
+        .prepare(
+            "SELECT variant, COUNT(*), AVG(success), AVG(executionTimeMs), COALESCE(SUM(tokenCount), 0)
+             FROM prompt_usage
+             WHERE promptKey = ?1 AND variant IS NOT NULL AND usedAt >= ?2
+             GROUP BY variant
+             ORDER BY variant",
+        )
+        .map_err(|e| format!("Failed to read experiment results: {}", e))?;
+    
+    let rows = stmt
+        .query_map(params![key, experiment.created_at], |row| {
+            Ok(VariantResult {
+                variant: row.get(0)?,
+                invocation_count: row.get(1)?,
+                success_rate: row.get(2)?,
+                avg_execution_time_ms: row.get(3)?,
+                total_tokens: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read experiment results: {}", e))?;
+    
+    let variants = rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read experiment results: {}", e))?;
+    Ok(ExperimentResults { experiment, variants })
+}
+
+fn insert_prompt_usage(
+    conn: &Connection,
+    key: &str,
+    success: bool,
+    execution_time_ms: i32,
+    token_count: Option<i32>,
+    variant: Option<PromptVariant>,
+    used_at: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO prompt_usage (promptKey, success, executionTimeMs, tokenCount, variant, usedAt)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![key, success, execution_time_ms, token_count, variant.map(|v| v.as_str()), used_at],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to record prompt usage: {}", e))
+}
+
+/// Per-key usage aggregates, most used first. `key` narrows the result to one prompt.
+pub fn prompt_usage_stats(conn: &Connection, key: Option<&str>) -> Result<Vec<PromptUsageStat>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT u.promptKey, COUNT(*), MAX(u.usedAt), AVG(u.executionTimeMs), COALESCE(SUM(u.tokenCount), 0),
+                    COALESCE(MAX(f.accepted), 0), COALESCE(MAX(f.rejected), 0)
+             FROM prompt_usage u
+             LEFT JOIN (
+                 SELECT promptKey,
+                        SUM(CASE WHEN feedbackType = 'acceptance' THEN itemCount ELSE 0 END) AS accepted,
+                        SUM(CASE WHEN feedbackType = 'rejection' THEN itemCount ELSE 0 END) AS rejected
+                 FROM ai_feedback
+                 GROUP BY promptKey
+             ) f ON f.promptKey = u.promptKey
+             WHERE ?1 IS NULL OR u.promptKey = ?1
+             GROUP BY u.promptKey
+             ORDER BY COUNT(*) DESC, u.promptKey",
+        )
+        .map_err(|e| format!("Failed to read prompt usage: {}", e))?;
+    
+    let rows = stmt
+        .query_map(params![key], |row| {
+            let accepted: i64 = row.get(5)?;
+            let rejected: i64 = row.get(6)?;
+            Ok(PromptUsageStat {
+                key: row.get(0)?,
+                invocation_count: row.get(1)?,
+                last_used_at: row.get(2)?,
+                acceptance_rate: (accepted + rejected > 0).then(|| accepted as f64 / (accepted + rejected) as f64),
+                avg_execution_time_ms: row.get(3)?,
+                total_tokens: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read prompt usage: {}", e))?;
+    
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read prompt usage: {}", e))
 }
 
 #[tauri::command]
@@ -50,4 +352,83 @@ pub async fn get_prompt_by_key(_app_handle: tauri::AppHandle, key: String) -> Re
 pub async fn update_prompt(_app_handle: tauri::AppHandle, key: String, prompt: String) -> Result<(), String> {
     println!("✏️ Updating prompt for key: {} (length: {})", key, prompt.len());
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_aggregate_usage_per_key() {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_prompt_usage_table(&conn).unwrap();
+        crate::commands::ai_automation::ensure_feedback_tables(&conn).unwrap();
+        
+        insert_prompt_usage(&conn, "task_extraction", true, 100, Some(500), None, "2024-05-01T10:00:00Z").unwrap();
+        insert_prompt_usage(&conn, "task_extraction", false, 300, None, None, "2024-05-02T10:00:00Z").unwrap();
+        insert_prompt_usage(&conn, "summary", true, 50, Some(200), None, "2024-05-01T09:00:00Z").unwrap();
+        
+        let feedback = [
+            serde_json::json!({"type": "acceptance", "prompt_key": "task_extraction"}),
+            serde_json::json!({"type": "rejection", "prompt_key": "task_extraction", "item_ids": ["a", "b", "c"]}),
+            serde_json::json!({"type": "modification", "prompt_key": "task_extraction"}),
+        ];
+        for (i, entry) in feedback.iter().enumerate() {
+            crate::commands::ai_automation::save_feedback(&conn, &format!("f{}", i), entry, "2024-05-02T11:00:00Z").unwrap();
+        }
+        
+        let stats = prompt_usage_stats(&conn, None).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].key, "task_extraction");
+        assert_eq!(stats[0].invocation_count, 2);
+        assert_eq!(stats[0].last_used_at, "2024-05-02T10:00:00Z");
+        assert_eq!(stats[0].acceptance_rate, Some(0.25));
+        assert_eq!(stats[0].avg_execution_time_ms, 200.0);
+        assert_eq!(stats[0].total_tokens, 500);
+        
+        let summary = prompt_usage_stats(&conn, Some("summary")).unwrap();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].acceptance_rate, None);
+        assert!(prompt_usage_stats(&conn, Some("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn variant_assignment_is_deterministic_and_follows_split() {
+        let buckets: Vec<String> = (0..1000).map(|i| format!("user-{}", i)).collect();
+        let served_b = buckets.iter()
+            .filter(|bucket| assign_variant("summary", bucket, 0.3) == PromptVariant::B)
+            .count();
+        assert!((200..400).contains(&served_b), "served B {} times", served_b);
+        
+        assert_eq!(assign_variant("summary", "user-7", 0.3), assign_variant("summary", "user-7", 0.3));
+        assert_eq!(assign_variant("summary", "user-7", 0.0), PromptVariant::A);
+        assert_eq!(assign_variant("summary", "user-7", 1.0), PromptVariant::B);
+    }
+
+    #[test]
+    fn experiment_results_group_usage_by_variant() {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_prompt_usage_table(&conn).unwrap();
+        save_prompt_experiment(&conn, &PromptExperiment {
+            key: "summary".to_string(),
+            variant_a: "Summarize".to_string(),
+            variant_b: "Summarize briefly".to_string(),
+            split: 0.5,
+            created_at: "2024-05-01T00:00:00Z".to_string(),
+        }).unwrap();
+        
+        insert_prompt_usage(&conn, "summary", true, 100, None, None, "2024-04-30T00:00:00Z").unwrap();
+        insert_prompt_usage(&conn, "summary", true, 100, None, Some(PromptVariant::A), "2024-05-02T00:00:00Z").unwrap();
+        insert_prompt_usage(&conn, "summary", false, 300, None, Some(PromptVariant::A), "2024-05-02T00:00:00Z").unwrap();
+        insert_prompt_usage(&conn, "summary", true, 50, Some(10), Some(PromptVariant::B), "2024-05-02T00:00:00Z").unwrap();
+        
+        let results = experiment_results(&conn, "summary").unwrap();
+        assert_eq!(results.variants.len(), 2);
+        assert_eq!(results.variants[0].variant, "A");
+        assert_eq!(results.variants[0].invocation_count, 2);
+        assert_eq!(results.variants[0].success_rate, 0.5);
+        assert_eq!(results.variants[1].variant, "B");
+        assert_eq!(results.variants[1].success_rate, 1.0);
+        assert!(experiment_results(&conn, "missing").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+// src-tauri/src/commands/search_commands.rs
+
+use crate::search::{search as search_internal, SearchKind, SearchResult};
+
+#[tauri::command]
+pub async fn search(
+    app_handle: tauri::AppHandle,
+    query: String,
+    kinds: Vec<SearchKind>,
+) -> Result<Vec<SearchResult>, String> {
+    search_internal(app_handle, query, kinds).await
+}
@@ -1,5 +1,7 @@
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use once_cell::sync::Lazy;
 use std::fs;
 
 /// Get a setting value by key from the app data directory
@@ -51,7 +53,7 @@ pub async fn store_setting(app: AppHandle, key: String, value: Value) -> Result<
     };
     
     // Update the setting
-    settings.insert(key, value);
+    settings.insert(key.clone(), value.clone());
     
     // Write back to file
     let content = serde_json::to_string_pretty(&settings)
@@ -61,6 +63,273 @@ pub async fn store_setting(app: AppHandle, key: String, value: Value) -> Result<
         .map_err(|e| format!("Failed to write settings file: {}", e))?;
     
     println!("✅ Setting stored successfully");
+    
+    if let Err(e) = app.emit(SETTING_CHANGED_EVENT, SettingChanged { key, value }) {
+        println!("⚠️ Failed to emit {} event: {}", SETTING_CHANGED_EVENT, e);
+    }
     Ok(())
 }
 
+/// Event emitted (to the frontend and Rust listeners) after `store_setting` writes a value
+pub const SETTING_CHANGED_EVENT: &str = "setting-changed";
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub struct SettingChanged {
+    pub key: String,
+    pub value: Value,
+}
+
+/// Minutes between background Slack sync runs
+pub const SLACK_SYNC_INTERVAL_MINUTES: &str = "slack.sync_interval_minutes";
+/// Channels synced at the same time by the background Slack sync
+pub const SLACK_SYNC_CONCURRENCY: &str = "slack.sync_concurrency";
+/// Setting used when a Slack channel connection doesn't specify how far back to backfill
+pub const SLACK_DEFAULT_BACKFILL_DAYS: &str = "slack.default_backfill_days";
+/// Setting used when a WhatsApp connect/refetch doesn't specify a lookback
+pub const WHATSAPP_DEFAULT_LOOKBACK_DAYS: &str = "whatsapp.lookback_days";
+/// Number of document versions kept per document
+pub const DOCUMENT_VERSION_RETENTION: &str = "documents.version_retention";
+/// Per-operation request timeouts for the service clients, in seconds
+pub const SERVICE_TIMEOUTS_SECS: &str = "services.timeouts_secs";
+/// Local port the Slack Events API receiver listens on
+pub const SLACK_EVENTS_PORT: &str = "slack.events_port";
+/// Whether debug logs are written and kept for `get_recent_logs`
+pub const LOGGING_DEBUG_ENABLED: &str = "logging.debug_enabled";
+/// Minutes an unfinished OAuth flow's state is kept before it's pruned
+pub const OAUTH_STATE_RETENTION_MINUTES: &str = "oauth.state_retention_minutes";
+/// Messages sent to the AI service per request when analyzing in chunks
+pub const AI_ANALYSIS_CHUNK_SIZE: &str = "ai.analysis_chunk_size";
+
+/// A known setting and the value used when nothing is stored for it
+#[derive(Debug, Clone)]
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub default: Value,
+    pub description: &'static str,
+}
+
+/// Registry of known settings. A `null` default means "unset" and reads as `None`.
+pub static KNOWN_SETTINGS: Lazy<Vec<SettingDefinition>> = Lazy::new(|| vec![
+    SettingDefinition {
+        key: SLACK_SYNC_INTERVAL_MINUTES,
+        default: serde_json::json!(15),
+        description: "Minutes between background Slack sync runs",
+    },
+    SettingDefinition {
+        key: SLACK_SYNC_CONCURRENCY,
+        default: serde_json::json!(3),
+        description: "Channels synced at the same time by the background Slack sync",
+    },
+    SettingDefinition {
+        key: SLACK_DEFAULT_BACKFILL_DAYS,
+        default: Value::Null,
+        description: "Days of history to backfill when connecting a Slack channel",
+    },
+    SettingDefinition {
+        key: WHATSAPP_DEFAULT_LOOKBACK_DAYS,
+        default: Value::Null,
+        description: "Days of history to fetch when connecting or refetching WhatsApp",
+    },
+    SettingDefinition {
+        key: DOCUMENT_VERSION_RETENTION,
+        default: serde_json::json!(50),
+        description: "Document versions kept per document",
+    },
+    SettingDefinition {
+        key: SERVICE_TIMEOUTS_SECS,
+        default: serde_json::json!({"health": 2, "status": 10, "standard": 30, "history": 60, "analysis": 120}),
+        description: "Service request timeouts in seconds, per kind of operation",
+    },
+    SettingDefinition {
+        key: SLACK_EVENTS_PORT,
+        default: serde_json::json!(3010),
+        description: "Local port that receives Slack Events API requests (expose it through a tunnel)",
+    },
+    SettingDefinition {
+        key: LOGGING_DEBUG_ENABLED,
+        default: serde_json::json!(false),
+        description: "Capture debug logs so support can retrieve recent lines",
+    },
+    SettingDefinition {
+        key: OAUTH_STATE_RETENTION_MINUTES,
+        default: serde_json::json!(10),
+        description: "Minutes an unfinished OAuth sign-in stays valid before it's cleaned up",
+    },
+    SettingDefinition {
+        key: AI_ANALYSIS_CHUNK_SIZE,
+        default: serde_json::json!(crate::ai_service_client::DEFAULT_ANALYSIS_CHUNK_SIZE),
+        description: "Messages sent to the AI service per request when analyzing large batches",
+    },
+]);
+
+/// Default value for a known setting
+pub fn setting_default(key: &str) -> Option<Value> {
+    KNOWN_SETTINGS.iter()
+        .find(|definition| definition.key == key)
+        .map(|definition| definition.default.clone())
+}
+
+/// Decode a stored value, falling back to the registry default when it's missing or malformed
+pub fn resolve_setting<T: DeserializeOwned>(key: &str, stored: Option<Value>) -> Result<T, String> {
+    let default = setting_default(key);
+    
+    match stored {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(typed) => Ok(typed),
+            Err(e) => match default {
+                Some(default) => {
+                    println!("⚠️ Stored setting {} is invalid ({}), using default", key, e);
+                    serde_json::from_value(default)
+                        .map_err(|e| format!("Invalid default for setting {}: {}", key, e))
+                }
+                None => Err(format!("Invalid value for setting {}: {}", key, e)),
+            },
+        },
+        None => {
+            let default = default.ok_or_else(|| format!("Setting {} is not set and has no default", key))?;
+            serde_json::from_value(default)
+                .map_err(|e| format!("Invalid default for setting {}: {}", key, e))
+        }
+    }
+}
+
+/// Read a setting as `T`, returning the registry default when it isn't stored
+pub async fn get_setting_as<T: DeserializeOwned>(app: &AppHandle, key: &str) -> Result<T, String> {
+    let stored = get_setting(app.clone(), key.to_string()).await?;
+    resolve_setting(key, stored)
+}
+
+/// Store a typed setting value
+pub async fn set_setting_typed<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> Result<(), String> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| format!("Failed to serialize setting {}: {}", key, e))?;
+    store_setting(app.clone(), key.to_string(), value).await
+}
+
+/// Keys stored under an older name, as (old, current)
+const RENAMED_SETTINGS: &[(&str, &str)] = &[
+    ("slack_default_backfill_days", SLACK_DEFAULT_BACKFILL_DAYS),
+    ("whatsapp_default_lookback_days", WHATSAPP_DEFAULT_LOOKBACK_DAYS),
+    ("document_version_retention", DOCUMENT_VERSION_RETENTION),
+];
+
+/// Move values stored under renamed keys to their current key. A value already stored under
+/// the current key wins. Returns whether anything changed.
+pub fn migrate_setting_keys(settings: &mut serde_json::Map<String, Value>) -> bool {
+    let mut changed = false;
+    for (old, current) in RENAMED_SETTINGS {
+        if let Some(value) = settings.remove(*old) {
+            settings.entry(current.to_string()).or_insert(value);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Rewrite settings.json with renamed keys migrated; run at startup before settings are read
+pub fn migrate_settings_file(app: &AppHandle) -> Result<(), String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let settings_file = app_data_dir.join("settings.json");
+    if !settings_file.exists() {
+        return Ok(());
+    }
+    
+    let content = fs::read_to_string(&settings_file)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+    let mut settings: serde_json::Map<String, Value> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse settings: {}", e))?;
+    if !migrate_setting_keys(&mut settings) {
+        return Ok(());
+    }
+    
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&settings_file, content)
+        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+    println!("✅ Migrated renamed setting keys");
+    Ok(())
+}
+
+/// An explicit day count wins; otherwise use the configured default if it's a positive number
+pub fn days_or_default(explicit: Option<i32>, configured: Option<&Value>) -> Option<i32> {
+    explicit.or_else(|| {
+        configured
+            .and_then(|v| v.as_i64())
+            .filter(|days| *days > 0)
+            .map(|days| days as i32)
+    })
+}
+
+/// Resolve a day count against the configured default stored under `key`
+pub async fn resolve_default_days(app: &AppHandle, explicit: Option<i32>, key: &str) -> Option<i32> {
+    if explicit.is_some() {
+        return explicit;
+    }
+    
+    match get_setting(app.clone(), key.to_string()).await {
+        Ok(configured) => days_or_default(None, configured.as_ref()),
+        Err(e) => {
+            println!("⚠️ Could not read setting {}: {}", key, e);
+            None
+        }
+    }
+}
+
+/// Messages per analysis chunk: `explicit` when given, otherwise the configured chunk size
+pub async fn resolve_analysis_chunk_size(app: &AppHandle, explicit: Option<usize>) -> usize {
+    if let Some(size) = explicit {
+        return size;
+    }
+    
+    get_setting_as::<usize>(app, AI_ANALYSIS_CHUNK_SIZE).await
+        .ok()
+        .filter(|size| *size > 0)
+        .unwrap_or(crate::ai_service_client::DEFAULT_ANALYSIS_CHUNK_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_value_uses_configured_default() {
+        assert_eq!(days_or_default(None, Some(&serde_json::json!(14))), Some(14));
+        assert_eq!(days_or_default(Some(3), Some(&serde_json::json!(14))), Some(3));
+        assert_eq!(days_or_default(None, Some(&serde_json::json!(0))), None);
+        assert_eq!(days_or_default(None, None), None);
+    }
+
+    #[test]
+    fn typed_settings_fall_back_to_registry_defaults() {
+        let interval: u64 = resolve_setting(SLACK_SYNC_INTERVAL_MINUTES, None).unwrap();
+        assert_eq!(interval, 15);
+        
+        let interval: u64 = resolve_setting(SLACK_SYNC_INTERVAL_MINUTES, Some(serde_json::json!(5))).unwrap();
+        assert_eq!(interval, 5);
+        
+        let interval: u64 = resolve_setting(SLACK_SYNC_INTERVAL_MINUTES, Some(serde_json::json!("often"))).unwrap();
+        assert_eq!(interval, 15);
+        
+        let lookback: Option<i32> = resolve_setting(WHATSAPP_DEFAULT_LOOKBACK_DAYS, None).unwrap();
+        assert_eq!(lookback, None);
+        
+        assert!(resolve_setting::<String>("unknown.key", None).is_err());
+    }
+
+    #[test]
+    fn renamed_keys_move_to_their_current_name() {
+        let mut settings = serde_json::Map::new();
+        settings.insert("slack_default_backfill_days".to_string(), serde_json::json!(30));
+        settings.insert("document_version_retention".to_string(), serde_json::json!(5));
+        settings.insert(DOCUMENT_VERSION_RETENTION.to_string(), serde_json::json!(20));
+        
+        assert!(migrate_setting_keys(&mut settings));
+        assert_eq!(settings.get(SLACK_DEFAULT_BACKFILL_DAYS), Some(&serde_json::json!(30)));
+        assert_eq!(settings.get(DOCUMENT_VERSION_RETENTION), Some(&serde_json::json!(20)));
+        assert!(!settings.contains_key("slack_default_backfill_days"));
+        assert!(!settings.contains_key("document_version_retention"));
+        
+        assert!(!migrate_setting_keys(&mut settings));
+    }
+}
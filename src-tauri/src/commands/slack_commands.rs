@@ -1,6 +1,7 @@
-use crate::credentials::{store_slack_credentials as store_slack_credentials_internal, get_slack_credentials as get_slack_credentials_internal, update_slack_access_token as update_slack_access_token_internal, delete_slack_credentials as delete_slack_credentials_internal, force_slack_reconnection as force_slack_reconnection_internal, debug_slack_credentials_status as debug_slack_credentials_status_internal,};
-use crate::slack_api::{slack_list_channels as slack_list_channels_internal, slack_build_oauth_url as slack_build_oauth_url_internal, slack_set_token as slack_set_token_internal, slack_test_connection as slack_test_connection_internal, slack_join_channel as slack_join_channel_internal, slack_fetch_messages as slack_fetch_messages_internal, slack_estimate_sync_time as slack_estimate_sync_time_internal, slack_analyze_messages as slack_analyze_messages_internal, get_slack_team_info as get_slack_team_info_internal, get_slack_user_info as get_slack_user_info_internal, slack_fetch_messages_paginated as slack_fetch_messages_paginated_internal,};
+use crate::credentials::{store_slack_credentials as store_slack_credentials_internal, get_slack_credentials as get_slack_credentials_internal, update_slack_access_token as update_slack_access_token_internal, delete_slack_credentials as delete_slack_credentials_internal, force_slack_reconnection as force_slack_reconnection_internal, store_slack_signing_secret as store_slack_signing_secret_internal, debug_slack_credentials_status as debug_slack_credentials_status_internal,};
+use crate::slack_api::{slack_list_channels as slack_list_channels_internal, slack_build_oauth_url as slack_build_oauth_url_internal, slack_set_token as slack_set_token_internal, slack_test_connection as slack_test_connection_internal, slack_join_channel as slack_join_channel_internal, slack_fetch_messages as slack_fetch_messages_internal, slack_fetch_messages_typed as slack_fetch_messages_typed_internal, slack_estimate_sync_time as slack_estimate_sync_time_internal, slack_analyze_messages as slack_analyze_messages_internal, get_slack_team_info as get_slack_team_info_internal, get_slack_user_info as get_slack_user_info_internal, slack_fetch_messages_paginated as slack_fetch_messages_paginated_internal, slack_sync_updated_threads as slack_sync_updated_threads_internal, slack_check_clock_skew as slack_check_clock_skew_internal, recommend_sync_interval as recommend_sync_interval_internal, slack_get_reactions_summary as slack_get_reactions_summary_internal, slack_fetch_messages_streaming as slack_fetch_messages_streaming_internal, slack_find_duplicate_channel_names as slack_find_duplicate_channel_names_internal, DuplicateChannelName, MessageReactionsSummary,};
 use crate::commands::oauth_servers::OAuthServiceClientState;
+use crate::commands::command_error::CommandError;
 
 // src-tauri/src/commands/slack_commands.rs
 
@@ -39,14 +40,26 @@ pub async fn force_slack_reconnection(app_handle: tauri::AppHandle) -> Result<St
     force_slack_reconnection_internal(app_handle).await
 }
 
+/// Store the signing secret and start receiving Slack events, replacing periodic polling
+#[tauri::command]
+pub async fn store_slack_signing_secret(app_handle: tauri::AppHandle, signing_secret: String) -> Result<bool, String> {
+    store_slack_signing_secret_internal(app_handle.clone(), signing_secret).await?;
+    crate::slack_events::start_slack_events_server(app_handle).await
+}
+
 #[tauri::command]
 pub async fn debug_slack_credentials_status(app_handle: tauri::AppHandle) -> Result<serde_json::Value, String> {
     debug_slack_credentials_status_internal(app_handle).await
 }
 
 #[tauri::command]
-pub async fn slack_list_channels(access_token: String) -> Result<serde_json::Value, String> {
-    slack_list_channels_internal(access_token).await
+pub async fn slack_list_channels(
+    app_handle: tauri::AppHandle,
+    access_token: String,
+    team_id: Option<String>,
+    use_cache: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    slack_list_channels_internal(app_handle, access_token, team_id, use_cache).await
 }
 
 #[tauri::command]
@@ -69,18 +82,42 @@ pub async fn slack_test_connection(access_token: String) -> Result<serde_json::V
 }
 
 #[tauri::command]
-pub async fn slack_join_channel(access_token: String, channel_id: String) -> Result<bool, String> {
+pub async fn slack_join_channel(access_token: String, channel_id: String) -> Result<bool, CommandError> {
     slack_join_channel_internal(access_token, channel_id).await
 }
 
 #[tauri::command]
 pub async fn slack_fetch_messages(
+    app_handle: tauri::AppHandle,
+    access_token: String,
+    channel_id: String,
+    oldest_timestamp: Option<f64>,
+    latest_timestamp: Option<f64>,
+    limit: Option<u32>,
+) -> Result<Vec<serde_json::Value>, String> {
+    slack_fetch_messages_internal(Some(app_handle), access_token, channel_id, oldest_timestamp, latest_timestamp, limit).await
+}
+
+/// Same as `slack_fetch_messages`, but always in the `SlackMessage` shape
+#[tauri::command]
+pub async fn slack_fetch_messages_typed(
+    app_handle: tauri::AppHandle,
     access_token: String,
     channel_id: String,
     oldest_timestamp: Option<f64>,
+    latest_timestamp: Option<f64>,
     limit: Option<u32>,
+) -> Result<Vec<crate::slack::SlackMessage>, CommandError> {
+    slack_fetch_messages_typed_internal(Some(app_handle), access_token, channel_id, oldest_timestamp, latest_timestamp, limit).await
+}
+
+#[tauri::command]
+pub async fn slack_fetch_messages_streaming(
+    app: tauri::AppHandle,
+    channel_id: String,
+    limit: u32,
 ) -> Result<Vec<serde_json::Value>, String> {
-    slack_fetch_messages_internal(access_token, channel_id, oldest_timestamp, limit).await
+    slack_fetch_messages_streaming_internal(app, channel_id, limit).await
 }
 
 #[tauri::command]
@@ -95,8 +132,12 @@ pub async fn slack_estimate_sync_time(
 pub async fn slack_analyze_messages(
     app_handle: tauri::AppHandle,
     messages: Vec<serde_json::Value>,
-) -> Result<Vec<serde_json::Value>, String> {
-    slack_analyze_messages_internal(app_handle, messages).await
+    chunk_size: Option<usize>,
+    include_bots: Option<bool>,
+    text_options: Option<crate::slack::SlackTextOptions>,
+    sync_id: Option<String>,
+) -> Result<crate::slack_api::MessageAnalysis, String> {
+    slack_analyze_messages_internal(app_handle, messages, chunk_size, include_bots, text_options, sync_id).await
 }
 
 #[tauri::command]
@@ -118,4 +159,35 @@ pub async fn slack_fetch_messages_paginated(
     cursor: Option<String>,
 ) -> Result<serde_json::Value, String> {
     slack_fetch_messages_paginated_internal(access_token, channel_id, oldest_timestamp, limit, cursor).await
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn slack_sync_updated_threads(
+    channel_id: String,
+    since_ts: f64,
+) -> Result<serde_json::Value, String> {
+    slack_sync_updated_threads_internal(channel_id, since_ts).await
+}
+
+#[tauri::command]
+pub async fn slack_check_clock_skew() -> Result<serde_json::Value, String> {
+    slack_check_clock_skew_internal().await
+}
+
+#[tauri::command]
+pub async fn recommend_sync_interval(channel_id: String) -> Result<u32, String> {
+    recommend_sync_interval_internal(channel_id).await
+}
+
+#[tauri::command]
+pub async fn slack_get_reactions_summary(
+    channel_id: String,
+    since_ts: Option<f64>,
+) -> Result<Vec<MessageReactionsSummary>, String> {
+    slack_get_reactions_summary_internal(channel_id, since_ts).await
+}
+
+#[tauri::command]
+pub async fn slack_find_duplicate_channel_names() -> Result<Vec<DuplicateChannelName>, String> {
+    slack_find_duplicate_channel_names_internal().await
+}
@@ -1,18 +1,33 @@
 use tauri::{AppHandle, State};
 use serde_json::Value;
 use chrono;
-use url::form_urlencoded;
 use crate::credentials::{
-    get_slack_credentials, validate_slack_credentials, SlackCredentialsStatus,
-    store_slack_credentials as store_credentials_legacy,
+    get_slack_credentials, validate_slack_credentials, SlackCredentialsStatus, delete_slack_credentials,
+    clear_slack_access_token, store_slack_credentials as store_credentials_legacy,
 };
 use crate::slack::{SlackClient, SlackSyncScheduler, SlackSyncState};
 use crate::slack_sync::{
-    SlackSync, create_sync, update_sync, get_syncs_for_project, delete_sync,
-    disconnect_channel, get_connected_channels_for_project,
+    SlackSync, create_sync, update_sync, normalize_user_list, get_syncs_for_project, delete_sync,
+    disconnect_channel, ChannelOverlap,
+    find_overlapping_channel_syncs as find_overlapping_channel_syncs_internal,
+    get_all_slack_syncs as get_all_slack_syncs_internal,
+};
+use crate::commands::command_error::{codes, CommandError};
+use crate::commands::oauth_servers::{
+    OAuthServiceClientState, start_oauth, consume_oauth_state, ensure_oauth_client,
 };
-use crate::commands::oauth_servers::{OAuthServiceClientState, start_https_oauth_server};
 use crate::oauth_service_client::OAuthServiceClient;
+use crate::queue_service_client::{JobInfo, QueueServiceClient};
+use crate::commands::settings::{
+    resolve_setting, SettingChanged, SETTING_CHANGED_EVENT, SLACK_SYNC_CONCURRENCY, SLACK_SYNC_INTERVAL_MINUTES,
+};
+
+/// Bot scopes requested when connecting a Slack workspace
+const SLACK_OAUTH_SCOPES: [&str; 13] = [
+    "channels:history", "channels:read", "channels:join", "groups:history", "groups:read",
+    "im:history", "im:read", "mpim:history", "mpim:read", "chat:write", "team:read",
+    "users:read", "users:read.email",
+];
 
 /// Start Slack OAuth flow
 #[tauri::command]
@@ -30,11 +45,8 @@ pub async fn slack_start_oauth(
             
             // Ensure OAuth service client is initialized before syncing credentials
             let mut client_guard = oauth_server_state.lock().await;
-            if client_guard.is_none() {
-                println!("🔄 Initializing OAuth service client...");
-                let client = OAuthServiceClient::new(None);
-                *client_guard = Some(client);
-                println!("✅ OAuth service client initialized");
+            if let Err(e) = ensure_oauth_client(&mut client_guard).await {
+                println!("⚠️ OAuth service client unavailable: {}", e);
             }
             
             // Sync credentials to OAuth service
@@ -54,46 +66,8 @@ pub async fn slack_start_oauth(
         }
     }
     
-    // Start the OAuth service client
-    let server_result = start_https_oauth_server(app.clone(), oauth_server_state.clone()).await;
-    match server_result {
-        Ok(_) => {
-            println!("✅ OAuth service client started successfully");
-        }
-        Err(e) => {
-            println!("❌ Failed to start OAuth service client: {}", e);
-            return Ok(serde_json::json!({
-                "success": false,
-                "error": format!("Failed to start OAuth server: {}", e)
-            }));
-        }
-    };
-    
-    // Use OAuth service callback URI - works with existing OAuth service
-    let redirect_uri = "https://localhost:3003/api/oauth/slack/callback".to_string();
-    
-    // Build the OAuth URL manually since we're using HTTP server
-    let oauth_url = format!(
-        "https://slack.com/oauth/v2/authorize?client_id={}&scope={}&redirect_uri={}&state={}",
-        client_id,
-        "channels:history,channels:read,channels:join,groups:history,groups:read,im:history,im:read,mpim:history,mpim:read,chat:write,team:read,users:read,users:read.email",
-        form_urlencoded::byte_serialize(redirect_uri.as_bytes()).collect::<String>(),
-        format!("state_{}", std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos())
-    );
-    
-    println!("✅ OAuth URL generated successfully");
-    
-    // Open the OAuth URL in the user's default browser
-    if let Err(e) = open::that(&oauth_url) {
-        println!("⚠️ Failed to open browser automatically: {}", e);
-        // Don't fail the whole operation if browser opening fails
-    }
-    
-    Ok(serde_json::json!({
-        "success": true,
-        "url": oauth_url,
-        "redirect_uri": redirect_uri
-    }))
+    let scopes = SLACK_OAUTH_SCOPES.iter().map(|s| s.to_string()).collect();
+    start_oauth(app, oauth_server_state, "slack".to_string(), client_id, scopes).await
 }
 
 /// Store Slack credentials using the new interface
@@ -123,11 +97,8 @@ pub async fn slack_store_credentials(
             
             // Ensure OAuth service client is initialized before syncing credentials
             let mut client_guard = oauth_server_state.lock().await;
-            if client_guard.is_none() {
-                println!("🔄 Initializing OAuth service client...");
-                let client = OAuthServiceClient::new(None);
-                *client_guard = Some(client);
-                println!("✅ OAuth service client initialized");
+            if let Err(e) = ensure_oauth_client(&mut client_guard).await {
+                println!("⚠️ OAuth service client unavailable: {}", e);
             }
             
             // Sync credentials to OAuth service
@@ -176,39 +147,77 @@ pub async fn check_slack_config_status(app: AppHandle) -> Result<SlackCredential
     }
 }
 
-/// Exchange Slack OAuth code for access token (legacy endpoint)
+/// Store the token from a code exchange, with its refresh token and expiry when the Slack app
+/// rotates tokens
+async fn store_oauth_tokens(
+    app: &AppHandle,
+    response: &crate::slack::SlackOAuthResponse,
+    access_token: &str,
+    team_id: &str,
+    team_name: &str,
+) -> Result<(), String> {
+    crate::credentials::update_slack_access_token(
+        app.clone(),
+        access_token.to_string(),
+        team_id.to_string(),
+        team_name.to_string(),
+    ).await?;
+    if let Some(rotated) = response.rotated_token(chrono::Utc::now()) {
+        crate::credentials::store_refreshed_slack_token(app.clone(), &rotated).await?;
+    }
+    Ok(())
+}
+
+/// Store the token data from an OAuth service code exchange, including a refresh token when
+/// the Slack app rotates tokens
+async fn store_exchanged_tokens(
+    app: &AppHandle,
+    token_data: &crate::oauth_service_client::OAuthTokenData,
+) -> Result<(), String> {
+    crate::credentials::update_slack_access_token(
+        app.clone(),
+        token_data.access_token.clone(),
+        token_data.team_id.clone().unwrap_or_default(),
+        token_data.team_name.clone().unwrap_or_default(),
+    ).await?;
+    if let Some(refresh_token) = &token_data.refresh_token {
+        let rotated = crate::oauth_service_client::RefreshedToken {
+            access_token: token_data.access_token.clone(),
+            refresh_token: Some(refresh_token.clone()),
+            expires_at: None,
+        };
+        crate::credentials::store_refreshed_slack_token(app.clone(), &rotated).await?;
+    }
+    Ok(())
+}
+
+/// Exchange Slack OAuth code for access token through the OAuth service (legacy endpoint).
+/// The service holds the Slack app credentials.
 #[tauri::command]
 pub async fn slack_exchange_code(
     app: AppHandle,
     code: String,
-    client_id: String,
-    client_secret: String,
     redirect_uri: String,
 ) -> Result<Value, String> {
     println!("🔄 Exchanging Slack OAuth code (legacy)");
     
-    let slack_client = SlackClient::new();
+    let oauth_client = OAuthServiceClient::new(None);
     
-    match slack_client.exchange_code_for_token(&code, &client_id, &client_secret, &redirect_uri).await {
-        Ok(response) => {
+    match oauth_client.exchange_code("slack", &code, &redirect_uri).await {
+        Ok(result) => {
             println!("✅ Successfully exchanged OAuth code");
             
-            // Store credentials if successful
-            if let Some(access_token) = &response.access_token {
-                let team_id = response.team.as_ref().map(|t| t.id.as_str()).unwrap_or("");
-                let team_name = response.team.as_ref().map(|t| t.name.as_str()).unwrap_or("");
-                
-                if let Err(e) = crate::credentials::update_slack_access_token(
-                    app.clone(),
-                    access_token.clone(),
-                    team_id.to_string(),
-                    team_name.to_string(),
-                ).await {
-                    println!("⚠️ Failed to store access token: {}", e);
+            match result.token_data() {
+                Some(token_data) => {
+                    println!("🔑 Token for team {:?} with scope {:?}", token_data.team_id, token_data.scope);
+                    if let Err(e) = store_exchanged_tokens(&app, token_data).await {
+                        println!("⚠️ Failed to store access token: {}", e);
+                    }
                 }
+                None => println!("⚠️ Exchange response had no readable token data, nothing stored"),
             }
             
-            Ok(serde_json::to_value(response).unwrap_or_default())
+            Ok(serde_json::to_value(result).unwrap_or_default())
         }
         Err(e) => {
             println!("❌ Failed to exchange OAuth code: {}", e);
@@ -239,12 +248,7 @@ pub async fn slack_exchange_oauth_code(
                 let team_id = response.team.as_ref().map(|t| t.id.as_str()).unwrap_or("");
                 let team_name = response.team.as_ref().map(|t| t.name.as_str()).unwrap_or("");
                 
-                if let Err(e) = crate::credentials::update_slack_access_token(
-                    app.clone(),
-                    access_token.clone(),
-                    team_id.to_string(),
-                    team_name.to_string(),
-                ).await {
+                if let Err(e) = store_oauth_tokens(&app, &response, access_token, team_id, team_name).await {
                     println!("⚠️ Failed to store access token: {}", e);
                 }
             }
@@ -263,9 +267,22 @@ pub async fn slack_exchange_oauth_code(
 pub async fn slack_complete_oauth(
     app: AppHandle,
     code: String,
+    state: Option<String>,
 ) -> Result<serde_json::Value, String> {
     println!("🔄 Completing Slack OAuth with stored credentials");
     
+    // Reject callbacks whose state we didn't issue (CSRF protection)
+    let redirect_uri = match consume_oauth_state(state.as_deref()) {
+        Ok(redirect_uri) => redirect_uri,
+        Err(e) => {
+            println!("❌ OAuth state validation failed: {}", e);
+            return Ok(serde_json::json!({
+                "success": false,
+                "error": e
+            }));
+        }
+    };
+    
     // Get stored credentials
     let credentials = match get_slack_credentials(app.clone()).await {
         Ok(Some(creds)) => creds,
@@ -285,9 +302,7 @@ pub async fn slack_complete_oauth(
         }
     };
     
-    // Use HTTPS redirect URI (must match what was used in oauth flow)
-    let redirect_uri = "https://localhost:3003/api/oauth/slack/callback".to_string();
-    
+    // The redirect URI must match the one the flow was started with (service or loopback)
     let slack_client = SlackClient::new();
     
     match slack_client.exchange_code_for_token(
@@ -304,18 +319,21 @@ pub async fn slack_complete_oauth(
                 let team_id = response.team.as_ref().map(|t| t.id.as_str()).unwrap_or("");
                 let team_name = response.team.as_ref().map(|t| t.name.as_str()).unwrap_or("");
                 
-                if let Err(e) = crate::credentials::update_slack_access_token(
-                    app.clone(),
-                    access_token.clone(),
-                    team_id.to_string(),
-                    team_name.to_string(),
-                ).await {
+                if let Err(e) = store_oauth_tokens(&app, &response, access_token, team_id, team_name).await {
                     println!("⚠️ Failed to store access token: {}", e);
                     return Ok(serde_json::json!({
                         "success": false,
                         "error": format!("Failed to store access token: {}", e)
                     }));
                 }
+                
+                // Channels connected before a reconnection may no longer be readable
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = revalidate_slack_syncs(app).await {
+                        println!("⚠️ Failed to revalidate syncs after OAuth: {}", e);
+                    }
+                });
             }
             
             // Return success response in the expected format
@@ -347,6 +365,7 @@ pub async fn create_slack_sync(
     channel_id: String,
     channel_name: String,
     _metadata: Option<serde_json::Value>,
+    analysis_prompt_key: Option<String>,
 ) -> Result<SlackSync, String> {
     println!("🔗 Creating Slack sync for project {} <-> channel {}", project_id, channel_id);
     
@@ -362,6 +381,10 @@ pub async fn create_slack_sync(
         sync_status: Some("local".to_string()),
         last_sync_at: None,
         team_id: None, // Extract from metadata if needed
+        analysis_prompt_key,
+        included_users: vec![],
+        excluded_users: vec![],
+        paused_until: None,
         created_at: chrono::Utc::now().to_rfc3339(),
         updated_at: chrono::Utc::now().to_rfc3339(),
     };
@@ -372,21 +395,80 @@ pub async fn create_slack_sync(
     Ok(created_sync)
 }
 
-/// Update an existing Slack sync connection
+/// Update an existing Slack sync connection: its analysis prompt, author filters and, when
+/// given, sync progress. Returns the stored sync.
 #[tauri::command]
 pub async fn update_slack_sync(app: AppHandle, sync: SlackSync) -> Result<SlackSync, String> {
     println!("📝 Updating Slack sync: {}", sync.id);
     
-    let mut updated_sync = sync;
-    updated_sync.updated_at = chrono::Utc::now().to_rfc3339();
-    
-    let updates = std::collections::HashMap::new();
-    let updated_result = update_sync(app, updated_sync.id.clone(), updates).await?;
+    let mut updates = std::collections::HashMap::new();
+    updates.insert("analysisPromptKey".to_string(), serde_json::json!(sync.analysis_prompt_key));
+    updates.insert("includedUsers".to_string(), serde_json::json!(normalize_user_list(sync.included_users)));
+    updates.insert("excludedUsers".to_string(), serde_json::json!(normalize_user_list(sync.excluded_users)));
+    if let Some(last_sync_at) = &sync.last_sync_at {
+        updates.insert("lastSyncAt".to_string(), serde_json::json!(last_sync_at));
+    }
+    if let Some(last_message_timestamp) = &sync.last_message_timestamp {
+        updates.insert("lastMessageTimestamp".to_string(), serde_json::json!(last_message_timestamp));
+    }
+    let updated_result = update_sync(app, sync.id.clone(), updates).await?;
     
     println!("✅ Slack sync updated successfully");
     Ok(updated_result)
 }
 
+/// Longest a sync can be snoozed for; longer than this it should be disconnected instead
+const MAX_SYNC_PAUSE_MINUTES: i64 = 7 * 24 * 60;
+
+/// Store a sync's pause and apply it to the running scheduler and events receiver
+async fn set_sync_paused_until(app: AppHandle, sync_id: String, paused_until: Option<chrono::DateTime<chrono::Utc>>) -> Result<SlackSync, CommandError> {
+    let mut sync = get_all_slack_syncs_internal(app.clone()).await?
+        .into_iter()
+        .find(|s| s.id == sync_id)
+        .ok_or_else(|| CommandError::new(codes::NOT_FOUND, format!("Slack sync {} not found", sync_id), false))?;
+    
+    let mut updates = std::collections::HashMap::new();
+    updates.insert("pausedUntil".to_string(), serde_json::json!(paused_until.map(|until| until.to_rfc3339())));
+    update_sync(app, sync_id, updates).await?;
+    
+    // The events receiver reads the stored sync; the scheduler keeps its own copy
+    if let Some(scheduler) = get_sync_scheduler().lock().await.as_ref() {
+        scheduler.set_paused_until(&sync.project_id, &sync.channel_id, paused_until);
+    }
+    
+    sync.paused_until = paused_until;
+    sync.updated_at = chrono::Utc::now().to_rfc3339();
+    Ok(sync)
+}
+
+/// Snooze a sync for `duration_minutes` without disconnecting it, e.g. to mute a noisy channel
+/// during a release. The sync resumes on its own afterwards.
+#[tauri::command]
+pub async fn pause_sync(app: AppHandle, id: String, duration_minutes: i64) -> Result<SlackSync, CommandError> {
+    if !(1..=MAX_SYNC_PAUSE_MINUTES).contains(&duration_minutes) {
+        return Err(CommandError::new(
+            codes::INVALID_INPUT,
+            format!("Pause must be between 1 and {} minutes", MAX_SYNC_PAUSE_MINUTES),
+            false,
+        ));
+    }
+    
+    let paused_until = chrono::Utc::now() + chrono::Duration::minutes(duration_minutes);
+    let sync = set_sync_paused_until(app, id, Some(paused_until)).await?;
+    
+    println!("⏸️ Paused Slack sync {} until {}", sync.id, paused_until.to_rfc3339());
+    Ok(sync)
+}
+
+/// End a sync's pause early
+#[tauri::command]
+pub async fn resume_sync(app: AppHandle, id: String) -> Result<SlackSync, CommandError> {
+    let sync = set_sync_paused_until(app, id, None).await?;
+    
+    println!("▶️ Resumed Slack sync {}", sync.id);
+    Ok(sync)
+}
+
 /// Get Slack syncs for a project
 #[tauri::command]
 pub async fn get_slack_sync_for_project(app: AppHandle, project_id: String) -> Result<Vec<SlackSync>, String> {
@@ -398,6 +480,17 @@ pub async fn get_slack_sync_for_project(app: AppHandle, project_id: String) -> R
     Ok(syncs)
 }
 
+/// Get every Slack sync across all projects, for the connections dashboard
+#[tauri::command]
+pub async fn get_all_slack_syncs(app: AppHandle) -> Result<Vec<SlackSync>, String> {
+    println!("📋 Getting Slack syncs for all projects");
+    
+    let syncs = get_all_slack_syncs_internal(app).await?;
+    
+    println!("✅ Found {} Slack syncs", syncs.len());
+    Ok(syncs)
+}
+
 /// Delete a Slack sync connection
 #[tauri::command]
 pub async fn delete_slack_sync(app: AppHandle, project_id: String, channel_id: String) -> Result<(), String> {
@@ -430,7 +523,26 @@ pub async fn disconnect_slack_channel(
 ) -> Result<(), String> {
     println!("🔌 Disconnecting channel {} from project {}", channel_id, project_id);
     
-    disconnect_channel(app, project_id, channel_id).await?;
+    disconnect_channel(app, project_id, channel_id.clone()).await?;
+    
+    // Stop a backfill that is still paginating instead of letting it run to the end
+    if crate::slack::cancel_channel_fetches(&channel_id) {
+        println!("🛑 Cancelled in-flight message fetches for channel {}", channel_id);
+    }
+    
+    // Drop any sync jobs still pending for this channel
+    let queue_client = QueueServiceClient::new(None);
+    let is_channel_job = |job: &JobInfo| {
+        job.data.as_ref()
+            .and_then(|data| data.get("channelId"))
+            .and_then(|id| id.as_str())
+            .map(|id| id == channel_id)
+            .unwrap_or(false)
+    };
+    match queue_client.cancel_jobs_for("slack-sync", is_channel_job).await {
+        Ok(count) => println!("🗑️ Cancelled {} pending sync jobs for channel {}", count, channel_id),
+        Err(e) => println!("⚠️ Could not cancel pending sync jobs for channel {}: {}", channel_id, e),
+    }
     
     println!("✅ Slack channel disconnected successfully");
     Ok(())
@@ -441,24 +553,112 @@ pub async fn disconnect_slack_channel(
 pub async fn get_project_connected_channels(app: AppHandle, project_id: String) -> Result<Vec<SlackSync>, String> {
     println!("📡 Getting connected channels for project: {}", project_id);
     
-    let channels = get_connected_channels_for_project(app).await?;
+    let channels: Vec<SlackSync> = get_all_slack_syncs_internal(app).await?
+        .into_iter()
+        .filter(|sync| sync.project_id == project_id)
+        .collect();
     
     println!("✅ Found {} connected channels for project", channels.len());
-    Ok(channels.into_iter().map(|c| SlackSync {
-        id: format!("{}_{}_{}", c.project_id, c.channel_id, chrono::Utc::now().timestamp()),
-        project_id: c.project_id,
-        channel_id: c.channel_id,
-        channel_name: c.channel_name,
-        last_sync_timestamp: None,
-        last_message_timestamp: None,
-        is_active: c.is_active,
-        sync_interval_minutes: Some(15),
-        sync_status: Some("synced".to_string()),
-        last_sync_at: c.last_sync_at,
-        team_id: None,
-        created_at: chrono::Utc::now().to_rfc3339(),
-        updated_at: chrono::Utc::now().to_rfc3339(),
-    }).collect())
+    Ok(channels)
+}
+
+/// Whether a Slack error means the bot can't join the channel by itself (private channel or DM)
+fn requires_invite(slack_error: &str) -> bool {
+    ["method_not_supported_for_channel_type", "not_in_channel", "channel_not_found"]
+        .iter()
+        .any(|code| slack_error.contains(code))
+}
+
+fn invite_bot_message(channel_name: &str) -> String {
+    format!(
+        "#{} is a private channel, so the bot can't join it on its own. \
+         Invite it from Slack with `/invite @Project Boxes` in that channel, then connect again.",
+        channel_name
+    )
+}
+
+/// Make sure the bot is in the channel before the first history fetch. Public channels are
+/// joined; private ones can only be checked, since the bot has to be invited. Returns how the
+/// bot has access ("joined" or "member").
+async fn ensure_bot_in_channel(channel_id: &str, channel_name: &str, is_private: bool) -> Result<&'static str, String> {
+    let slack_client = &crate::slack_service_client::SlackServiceClient::new(None);
+    
+    check_channel_access(
+        channel_id,
+        channel_name,
+        is_private,
+        || async move {
+            slack_client.join_channel(channel_id).await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        },
+        || async move {
+            let probe = crate::slack_service_client::ChannelHistoryOptions {
+                limit: Some(1),
+                cursor: None,
+                oldest: None,
+                latest: None,
+            };
+            slack_client.get_channel_history(channel_id, Some(probe)).await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        },
+    ).await
+}
+
+/// `ensure_bot_in_channel` with the Slack calls passed in: `join` for public channels, then
+/// `probe` (a one-message history read) when joining isn't possible
+async fn check_channel_access<J, JoinFut, P, ProbeFut>(
+    channel_id: &str,
+    channel_name: &str,
+    is_private: bool,
+    join: J,
+    probe: P,
+) -> Result<&'static str, String>
+where
+    J: FnOnce() -> JoinFut,
+    JoinFut: std::future::Future<Output = Result<(), String>>,
+    P: FnOnce() -> ProbeFut,
+    ProbeFut: std::future::Future<Output = Result<(), String>>,
+{
+    if !is_private {
+        match join().await {
+            Ok(()) => {
+                println!("🚪 Bot joined channel {}", channel_id);
+                return Ok("joined");
+            }
+            Err(e) if requires_invite(&e) => {
+                println!("🔒 Channel {} can't be joined directly, checking membership", channel_id);
+            }
+            Err(e) => return Err(format!("Could not join #{}: {}", channel_name, e)),
+        }
+    }
+    
+    match probe().await {
+        Ok(()) => Ok("member"),
+        Err(e) if requires_invite(&e) => Err(invite_bot_message(channel_name)),
+        Err(e) => Err(format!("Could not verify access to #{}: {}", channel_name, e)),
+    }
+}
+
+/// A newly connected channel sync and how the bot got into the channel
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct ChannelConnection {
+    pub sync: SlackSync,
+    /// "joined" when the bot joined a public channel, "member" when it was already in it
+    pub channel_access: String,
+    /// Other projects the channel is already synced into
+    pub overlapping_projects: Vec<String>,
+}
+
+/// The projects other than `project_id` that `channel_id` is synced into
+fn other_projects_on_channel(overlaps: &[ChannelOverlap], channel_id: &str, project_id: &str) -> Vec<String> {
+    overlaps.iter()
+        .filter(|overlap| overlap.channel_id == channel_id)
+        .flat_map(|overlap| overlap.project_ids.iter())
+        .filter(|id| id.as_str() != project_id)
+        .cloned()
+        .collect()
 }
 
 /// Connect a project to a Slack channel
@@ -469,9 +669,37 @@ pub async fn connect_project_to_channel(
     channel_id: String,
     channel_name: String,
     sync_interval_minutes: Option<i32>,
-) -> Result<SlackSync, String> {
+    backfill_days: Option<i32>,
+    is_private: Option<bool>,
+    analysis_prompt_key: Option<String>,
+) -> Result<ChannelConnection, String> {
     println!("🔗 Connecting project {} to channel {} ({})", project_id, channel_id, channel_name);
     
+    // Without channel membership the first conversations.history call fails with not_in_channel
+    let channel_access = ensure_bot_in_channel(&channel_id, &channel_name, is_private.unwrap_or(false)).await?;
+    
+    // Default to an interval based on how busy the channel is
+    let sync_interval_minutes = match sync_interval_minutes {
+        Some(minutes) => Some(minutes),
+        None => match crate::slack_api::recommend_sync_interval(channel_id.clone()).await {
+            Ok(minutes) => Some(minutes as i32),
+            Err(e) => {
+                println!("⚠️ Could not compute recommended sync interval, using default: {}", e);
+                None
+            }
+        },
+    };
+    
+    // Backfill from the configured default when the caller doesn't say how far back to go
+    let backfill_days = crate::commands::settings::resolve_default_days(
+        &app,
+        backfill_days,
+        crate::commands::settings::SLACK_DEFAULT_BACKFILL_DAYS,
+    ).await;
+    let backfill_from = backfill_days.map(|days| {
+        (chrono::Utc::now() - chrono::Duration::days(days as i64)).timestamp().to_string()
+    });
+    
     // Create the sync connection
     let sync = SlackSync {
         id: uuid::Uuid::new_v4().to_string(),
@@ -479,20 +707,48 @@ pub async fn connect_project_to_channel(
         channel_id: channel_id.clone(),
         channel_name: channel_name.clone(),
         last_sync_timestamp: None,
-        last_message_timestamp: None,
+        last_message_timestamp: backfill_from,
         is_active: true,
         sync_interval_minutes: sync_interval_minutes.or(Some(15)),
-        sync_status: Some("connected".to_string()),
+        sync_status: Some("local".to_string()),
         last_sync_at: None,
         team_id: None,
+        analysis_prompt_key,
+        included_users: vec![],
+        excluded_users: vec![],
+        paused_until: None,
         created_at: chrono::Utc::now().to_rfc3339(),
         updated_at: chrono::Utc::now().to_rfc3339(),
     };
     
     let created_sync = create_sync(app.clone(), sync).await?;
     
+    // Flag channels that already feed another project - it doubles API load and duplicates tasks
+    let overlapping_projects = match find_overlapping_channel_syncs_internal(app.clone()).await {
+        Ok(overlaps) => other_projects_on_channel(&overlaps, &channel_id, &project_id),
+        Err(e) => {
+            println!("⚠️ Could not check for overlapping channel syncs: {}", e);
+            vec![]
+        }
+    };
+    
     println!("✅ Project {} connected to channel {} successfully", project_id, channel_id);
-    Ok(created_sync)
+    Ok(ChannelConnection {
+        sync: created_sync,
+        channel_access: channel_access.to_string(),
+        overlapping_projects,
+    })
+}
+
+/// Find channels that are synced into more than one project
+#[tauri::command]
+pub async fn find_overlapping_channel_syncs(app: AppHandle) -> Result<Vec<ChannelOverlap>, String> {
+    println!("🔍 Checking for channels connected to multiple projects");
+    
+    let overlaps = find_overlapping_channel_syncs_internal(app).await?;
+    
+    println!("✅ Found {} overlapping channel syncs", overlaps.len());
+    Ok(overlaps)
 }
 
 // Global state for sync scheduler
@@ -506,12 +762,39 @@ fn get_sync_scheduler() -> &'static Arc<Mutex<Option<SlackSyncScheduler>>> {
     SYNC_SCHEDULER.get_or_init(|| Arc::new(Mutex::new(None)))
 }
 
+/// Scheduler state for a stored sync. A sync that never ran starts from its backfill point
+/// (stored as epoch seconds in `last_message_timestamp`), or from now without one.
+fn sync_state(sync: SlackSync) -> SlackSyncState {
+    let last_sync_at = sync.last_sync_at.as_deref()
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&chrono::Utc));
+    let backfill_from = sync.last_message_timestamp.as_deref()
+        .and_then(crate::slack::parse_slack_ts)
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0));
+    
+    SlackSyncState {
+        last_sync: last_sync_at.or(backfill_from).unwrap_or_else(chrono::Utc::now),
+        project_id: sync.project_id,
+        channel_id: sync.channel_id,
+        paused_until: sync.paused_until,
+        is_active: sync.is_active,
+        included_users: sync.included_users,
+        excluded_users: sync.excluded_users,
+    }
+}
+
 /// Start the Slack sync scheduler
 #[tauri::command]
 pub async fn start_slack_sync_scheduler(app: AppHandle, interval_minutes: Option<u64>) -> Result<String, String> {
     println!("🔄 Starting Slack sync scheduler...");
     
-    let interval = interval_minutes.unwrap_or(15); // Default 15 minutes
+    let interval = match interval_minutes {
+        Some(minutes) => minutes,
+        None => crate::commands::settings::get_setting_as::<u64>(
+            &app,
+            SLACK_SYNC_INTERVAL_MINUTES,
+        ).await.unwrap_or(15),
+    };
     
     // Get Slack credentials
     let credentials = match get_slack_credentials(app.clone()).await {
@@ -525,16 +808,18 @@ pub async fn start_slack_sync_scheduler(app: AppHandle, interval_minutes: Option
     if let Some(token) = credentials.access_token {
         client.set_token(token);
     }
-    let scheduler = SlackSyncScheduler::new(client, interval);
+    let concurrency = crate::commands::settings::get_setting_as::<usize>(&app, SLACK_SYNC_CONCURRENCY)
+        .await
+        .unwrap_or(crate::slack::DEFAULT_SYNC_CONCURRENCY);
+    let mut scheduler = SlackSyncScheduler::new(client, interval).with_concurrency(concurrency);
+    match crate::app_db::app_db_path(&app) {
+        Ok(db_path) => scheduler = scheduler.with_message_store(db_path),
+        Err(e) => println!("⚠️ Synced messages won't be stored locally: {}", e),
+    }
     
     // Get active sync configs
-    let sync_configs = match get_syncs_for_project(app.clone(), "".to_string()).await {
-        Ok(syncs) => syncs.into_iter().map(|s| SlackSyncState {
-            project_id: s.project_id,
-            channel_id: s.channel_id,
-            is_active: s.is_active,
-            last_sync: chrono::Utc::now(), // Use current time as default
-        }).collect(),
+    let sync_configs = match get_all_slack_syncs_internal(app.clone()).await {
+        Ok(syncs) => syncs.into_iter().map(sync_state).collect(),
         Err(e) => {
             println!("⚠️ No sync configs found: {}", e);
             vec![]
@@ -573,6 +858,41 @@ pub async fn stop_slack_sync_scheduler() -> Result<String, String> {
     }
 }
 
+/// New scheduler interval carried by a `setting-changed` event, if it is one
+fn interval_from_setting_change(change: &SettingChanged) -> Option<u64> {
+    if change.key != SLACK_SYNC_INTERVAL_MINUTES {
+        return None;
+    }
+    resolve_setting::<u64>(&change.key, Some(change.value.clone()))
+        .ok()
+        .filter(|minutes| *minutes > 0)
+}
+
+/// Subscribe the running scheduler to `slack.sync_interval_minutes` changes so a new interval
+/// applies without restarting the app
+pub fn watch_sync_interval_setting(app: &AppHandle) {
+    use tauri::Listener;
+    
+    app.listen(SETTING_CHANGED_EVENT, |event| {
+        let change: SettingChanged = match serde_json::from_str(event.payload()) {
+            Ok(change) => change,
+            Err(e) => {
+                println!("⚠️ Ignoring malformed {} payload: {}", SETTING_CHANGED_EVENT, e);
+                return;
+            }
+        };
+        
+        if let Some(minutes) = interval_from_setting_change(&change) {
+            tauri::async_runtime::spawn(async move {
+                let guard = get_sync_scheduler().lock().await;
+                if let Some(scheduler) = guard.as_ref() {
+                    scheduler.set_interval_minutes(minutes);
+                }
+            });
+        }
+    });
+}
+
 /// Get the status of the Slack sync scheduler
 #[tauri::command]
 pub async fn slack_sync_scheduler_status() -> Result<bool, String> {
@@ -589,9 +909,350 @@ pub async fn slack_sync_scheduler_status() -> Result<bool, String> {
     Ok(is_running)
 }
 
-/// Check Slack connection status
+/// Outcome of a full Slack integration reset
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SlackResetReport {
+    pub scheduler_stopped: bool,
+    pub token_revoked: bool,
+    pub credentials_cleared: bool,
+    pub syncs_disconnected: usize,
+}
+
+/// Stop the scheduler held in `scheduler_state`, then revoke and clear credentials through the
+/// given callbacks. Revocation is best-effort; clearing must succeed for the reset to count.
+async fn reset_slack_state<R, RFut, C, CFut>(
+    scheduler_state: &Mutex<Option<SlackSyncScheduler>>,
+    revoke: R,
+    clear_credentials: C,
+) -> Result<SlackResetReport, String>
+where
+    R: FnOnce() -> RFut,
+    RFut: std::future::Future<Output = Result<bool, String>>,
+    C: FnOnce() -> CFut,
+    CFut: std::future::Future<Output = Result<(), String>>,
+{
+    let scheduler_stopped = {
+        let mut guard = scheduler_state.lock().await;
+        match guard.take() {
+            Some(scheduler) => {
+                scheduler.stop().await;
+                true
+            }
+            None => false,
+        }
+    };
+    
+    let token_revoked = match revoke().await {
+        Ok(revoked) => revoked,
+        Err(e) => {
+            println!("⚠️ Failed to revoke Slack token: {}", e);
+            false
+        }
+    };
+    
+    clear_credentials().await?;
+    
+    Ok(SlackResetReport {
+        scheduler_stopped,
+        token_revoked,
+        credentials_cleared: true,
+        syncs_disconnected: 0,
+    })
+}
+
+/// Turn off every enabled sync (`isEnabled = 0`) and return the ids of those turned off. A
+/// sync that can't be updated is logged and left out.
+async fn disable_all_syncs(app: &AppHandle) -> Result<Vec<String>, String> {
+    let mut disabled = Vec::new();
+    for sync in get_all_slack_syncs_internal(app.clone()).await?.into_iter().filter(|s| s.is_active) {
+        let mut updates = std::collections::HashMap::new();
+        updates.insert("isEnabled".to_string(), serde_json::json!(false));
+        match update_sync(app.clone(), sync.id.clone(), updates).await {
+            Ok(_) => disabled.push(sync.id),
+            Err(e) => println!("⚠️ Failed to disable sync {}: {}", sync.id, e),
+        }
+    }
+    Ok(disabled)
+}
+
+/// Reset the Slack integration to a clean "needs setup" state: stop the scheduler, revoke and
+/// clear credentials, and disable every sync
+#[tauri::command]
+pub async fn reset_slack_integration(app: AppHandle) -> Result<SlackResetReport, String> {
+    println!("🧹 Resetting Slack integration...");
+    
+    let credentials = get_slack_credentials(app.clone()).await.ok().flatten();
+    
+    let revoke = || async move {
+        let credentials = match credentials {
+            Some(creds) => creds,
+            None => return Ok(false),
+        };
+        match (credentials.team_id, credentials.access_token) {
+            (Some(team_id), Some(access_token)) => {
+                OAuthServiceClient::new(None)
+                    .revoke_tokens("slack", &team_id, &access_token)
+                    .await
+                    .map(|_| true)
+                    .map_err(|e| e.to_string())
+            }
+            _ => Ok(false),
+        }
+    };
+    
+    let clear_app = app.clone();
+    let clear_credentials = || async move {
+        if let Err(e) = delete_slack_credentials(clear_app.clone()).await {
+            println!("ℹ️ Credential deletion reported: {}", e);
+        }
+        match get_slack_credentials(clear_app).await {
+            Ok(None) => Ok(()),
+            Ok(Some(_)) => Err("Slack credentials still present after reset".to_string()),
+            Err(e) => Err(format!("Failed to verify credential removal: {}", e)),
+        }
+    };
+    
+    let mut report = reset_slack_state(get_sync_scheduler(), revoke, clear_credentials).await?;
+    
+    report.syncs_disconnected = disable_all_syncs(&app).await
+        .unwrap_or_else(|e| {
+            println!("⚠️ Failed to load syncs to disable: {}", e);
+            vec![]
+        })
+        .len();
+    
+    println!("✅ Slack integration reset: {:?}", report);
+    Ok(report)
+}
+
+/// Re-authorize Slack without losing connected channels: only the access token is cleared, the
+/// app credentials and every sync config are kept, and a new OAuth flow is started. Once it
+/// completes, `slack_complete_oauth` revalidates the syncs against the new token.
+#[tauri::command]
+pub async fn reconnect_preserving_syncs(
+    app: AppHandle,
+    oauth_server_state: State<'_, OAuthServiceClientState>,
+) -> Result<serde_json::Value, String> {
+    println!("🔄 Reconnecting Slack while keeping channel syncs...");
+    
+    let client_id = match get_slack_credentials(app.clone()).await? {
+        Some(credentials) => credentials.client_id,
+        None => return Err(crate::credentials::CredentialError::NotConfigured.to_string()),
+    };
+    
+    clear_slack_access_token(app.clone()).await?;
+    
+    slack_start_oauth(app, oauth_server_state, client_id).await
+}
+
+/// Disconnect Slack: revoke the access token on Slack's side, then stop the scheduler, delete
+/// the stored credentials and disable every sync. If the revoke fails nothing is removed, so the
+/// token is never left valid without a way to revoke it from here.
+#[tauri::command]
+pub async fn slack_disconnect(app: AppHandle) -> Result<serde_json::Value, String> {
+    println!("🔌 Disconnecting Slack...");
+    
+    let access_token = get_slack_credentials(app.clone()).await?.and_then(|c| c.access_token);
+    let revoked = match access_token {
+        Some(token) => {
+            let mut client = SlackClient::new();
+            client.set_token(token);
+            client.revoke_token().await.map_err(|e| format!("Failed to revoke Slack token: {}", e))?
+        }
+        None => false,
+    };
+    println!("🔑 Slack token {}", if revoked { "revoked" } else { "was not active" });
+    
+    // Nothing should poll Slack once the token is gone
+    stop_slack_sync_scheduler().await?;
+    delete_slack_credentials(app.clone()).await?;
+    
+    let deactivated = disable_all_syncs(&app).await?;
+    
+    println!("✅ Slack disconnected, {} syncs deactivated", deactivated.len());
+    Ok(serde_json::json!({
+        "revoked": revoked,
+        "deactivated_sync_ids": deactivated
+    }))
+}
+
+/// Locally stored messages of a channel, newest first, for browsing history offline. Pass the
+/// oldest `ts` already shown as `before_ts` to page back.
+#[tauri::command]
+pub async fn get_stored_messages(
+    app: AppHandle,
+    channel_id: String,
+    limit: Option<u32>,
+    before_ts: Option<String>,
+) -> Result<Vec<crate::slack_message_store::StoredSlackMessage>, String> {
+    let db_path = crate::app_db::app_db_path(&app)?;
+    let limit = limit.unwrap_or(crate::slack_message_store::DEFAULT_STORED_MESSAGE_LIMIT);
+    
+    let messages = crate::slack_message_store::read_messages(db_path, channel_id.clone(), limit, before_ts).await?;
+    println!("📚 Loaded {} stored messages for channel {}", messages.len(), channel_id);
+    Ok(messages)
+}
+
+/// Refresh the Slack access token with Slack's `oauth.v2.access` and store the result, including
+/// the rotated refresh token. Only apps with token rotation get a refresh token. Returns when
+/// the new token expires, never the tokens themselves.
+#[tauri::command]
+pub async fn slack_refresh_token(app: AppHandle) -> Result<serde_json::Value, CommandError> {
+    println!("🔄 Refreshing Slack access token...");
+    
+    let credentials = get_slack_credentials(app.clone()).await?
+        .ok_or_else(|| CommandError::new(codes::AUTH_REQUIRED, "Slack is not connected", false))?;
+    let refresh_token = credentials.refresh_token
+        .ok_or_else(|| CommandError::new(codes::AUTH_REQUIRED, "Slack token rotation is not enabled; reconnect to get a refresh token", false))?;
+    
+    let response = SlackClient::new()
+        .refresh_access_token(&refresh_token, &credentials.client_id, &credentials.client_secret).await
+        .map_err(|e| CommandError::from(e).with_context("Token refresh failed"))?;
+    let refreshed = response.rotated_token(chrono::Utc::now())
+        .ok_or_else(|| CommandError::new(codes::AUTH_REQUIRED, "Slack returned no rotated token", false))?;
+    crate::credentials::store_refreshed_slack_token(app, &refreshed).await?;
+    
+    Ok(serde_json::json!({
+        "expires_at": refreshed.expires_at.map(|at| at.to_rfc3339()),
+        "refresh_token_rotated": refreshed.refresh_token.is_some()
+    }))
+}
+
+/// What an on-demand channel sync fetched
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct SyncNowResult {
+    pub sync_id: String,
+    pub message_count: u32,
+    pub task_count: u32,
+    /// New `last_sync_at` of the sync, for the frontend to store
+    pub last_sync_at: String,
+}
+
+/// Sync one project's channel right away instead of waiting for the scheduler, e.g. just after
+/// connecting it. Fetches messages since the last sync, runs task detection and moves
+/// `last_sync_at` forward.
 #[tauri::command]
-pub async fn slack_check_connection(app: AppHandle) -> Result<serde_json::Value, String> {
+pub async fn sync_channel_now(app: AppHandle, project_id: String, channel_id: String) -> Result<SyncNowResult, CommandError> {
+    println!("⚡ Syncing channel {} for project {} now", channel_id, project_id);
+    
+    let sync = get_all_slack_syncs_internal(app.clone()).await?
+        .into_iter()
+        .find(|s| s.project_id == project_id && s.channel_id == channel_id)
+        .ok_or_else(|| CommandError::new(codes::NOT_FOUND, format!("Channel {} is not connected to project {}", channel_id, project_id), false))?;
+    let access_token = get_slack_credentials(app.clone()).await?
+        .and_then(|credentials| credentials.access_token)
+        .ok_or_else(|| CommandError::new(codes::AUTH_REQUIRED, "Slack is not connected", false))?;
+    
+    let mut client = SlackClient::new();
+    client.set_token(access_token);
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let sync_id = sync.id.clone();
+    let message_store = crate::app_db::app_db_path(&app).ok();
+    let users = client.list_users().await.unwrap_or_else(|e| {
+        println!("⚠️ Could not list workspace members, mentions stay as ids: {}", e);
+        vec![]
+    });
+    let outcome = crate::slack::sync_channel_once(&client, &sync_state(sync), message_store.as_deref(), &users).await
+        .map_err(|e| CommandError::from(e).with_context("Sync failed"))?;
+    
+    // Only move last_sync_at once the fetch succeeded, so a failed sync is retried from the same point
+    let mut updates = std::collections::HashMap::new();
+    updates.insert("lastSyncAt".to_string(), serde_json::json!(started_at));
+    updates.insert("syncStatus".to_string(), serde_json::json!("synced"));
+    update_sync(app, sync_id.clone(), updates).await?;
+    
+    println!("✅ Synced channel {}: {} messages, {} tasks", channel_id, outcome.message_count, outcome.tasks.len());
+    Ok(SyncNowResult {
+        sync_id,
+        message_count: outcome.message_count as u32,
+        task_count: outcome.tasks.len() as u32,
+        last_sync_at: started_at,
+    })
+}
+
+/// Whether a sync still works with the current token
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct SyncRevalidation {
+    pub sync_id: String,
+    pub channel_id: String,
+    /// "joined" or "member" when the bot has access, otherwise "needs_reconnect"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Check every active sync with `check_access`, which returns how the bot has access or why
+/// the channel can't be read. Inactive syncs are left alone.
+async fn revalidate_syncs<F, Fut>(syncs: Vec<SlackSync>, check_access: F) -> Vec<SyncRevalidation>
+where
+    F: Fn(SlackSync) -> Fut,
+    Fut: std::future::Future<Output = Result<&'static str, String>>,
+{
+    let mut results = Vec::new();
+    for sync in syncs.into_iter().filter(|s| s.is_active) {
+        let (sync_id, channel_id) = (sync.id.clone(), sync.channel_id.clone());
+        let (status, error) = match check_access(sync).await {
+            Ok(status) => (status.to_string(), None),
+            Err(e) => {
+                println!("⚠️ Sync {} lost access to channel {}: {}", sync_id, channel_id, e);
+                ("needs_reconnect".to_string(), Some(e))
+            }
+        };
+        results.push(SyncRevalidation { sync_id, channel_id, status, error });
+    }
+    results
+}
+
+/// Re-check the stored syncs after a reconnection, rejoining public channels the bot left.
+/// Each checked sync gets its `lastSyncError` set to why it lost access, or cleared. Runs
+/// after every completed OAuth flow.
+#[tauri::command]
+pub async fn revalidate_slack_syncs(app: AppHandle) -> Result<Vec<SyncRevalidation>, String> {
+    let has_token = get_slack_credentials(app.clone()).await?
+        .map_or(false, |credentials| credentials.access_token.is_some());
+    if !has_token {
+        return Err("Slack is not connected yet. Complete the OAuth flow before revalidating syncs.".to_string());
+    }
+    
+    let syncs = get_all_slack_syncs_internal(app.clone()).await?;
+    println!("🔍 Revalidating {} Slack syncs against the current token", syncs.len());
+    
+    // Private channels fail the join and fall back to a membership check
+    let results = revalidate_syncs(syncs, |sync| async move {
+        ensure_bot_in_channel(&sync.channel_id, &sync.channel_name, false).await
+    }).await;
+    
+    for result in &results {
+        let mut updates = std::collections::HashMap::new();
+        updates.insert("lastSyncError".to_string(), serde_json::json!(result.error));
+        if let Err(e) = update_sync(app.clone(), result.sync_id.clone(), updates).await {
+            println!("⚠️ Failed to record revalidation of sync {}: {}", result.sync_id, e);
+        }
+    }
+    
+    let lost = results.iter().filter(|r| r.error.is_some()).count();
+    println!("✅ Revalidated {} syncs, {} need to be reconnected", results.len(), lost);
+    Ok(results)
+}
+
+/// How long a successful connection check is reused before calling auth.test again
+const CONNECTION_CHECK_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+// Last successful auth.test result per token hash; the connection indicator polls often
+static CONNECTION_CHECKS: OnceLock<std::sync::Mutex<std::collections::HashMap<u64, (std::time::Instant, Value)>>> = OnceLock::new();
+
+fn token_hash(token: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    token.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check Slack connection status. A check that succeeded in the last minute is reused
+/// unless `force` is set.
+#[tauri::command]
+pub async fn slack_check_connection(app: AppHandle, force: Option<bool>) -> Result<serde_json::Value, String> {
     
     // Get stored credentials
     let credentials = match get_slack_credentials(app.clone()).await {
@@ -620,12 +1281,35 @@ pub async fn slack_check_connection(app: AppHandle) -> Result<serde_json::Value,
     
     // Check if we have an access token
     if let Some(access_token) = credentials.access_token {
+        let checks = CONNECTION_CHECKS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+        let token_key = token_hash(&access_token);
+        if !force.unwrap_or(false) {
+            let cached = checks.lock().unwrap_or_else(|e| e.into_inner())
+                .get(&token_key)
+                .filter(|(checked_at, _)| checked_at.elapsed() < CONNECTION_CHECK_TTL)
+                .map(|(_, team_info)| team_info.clone());
+            if let Some(team_info) = cached {
+                return Ok(serde_json::json!({
+                    "success": true,
+                    "data": {
+                        "connected": true,
+                        "teamInfo": team_info
+                    }
+                }));
+            }
+        }
+        
         // Test connection using existing Slack client
         let mut slack_client = SlackClient::new();
         slack_client.set_token(access_token);
         
         match slack_client.test_slack_connection().await {
             Ok(team_info) => {
+                let mut checks = checks.lock().unwrap_or_else(|e| e.into_inner());
+                checks.retain(|_, (checked_at, _)| checked_at.elapsed() < CONNECTION_CHECK_TTL);
+                checks.insert(token_key, (std::time::Instant::now(), team_info.clone()));
+                drop(checks);
+                
                 Ok(serde_json::json!({
                     "success": true,
                     "data": {
@@ -636,6 +1320,7 @@ pub async fn slack_check_connection(app: AppHandle) -> Result<serde_json::Value,
             }
             Err(e) => {
                 println!("❌ Slack connection test failed: {}", e);
+                checks.lock().unwrap_or_else(|e| e.into_inner()).remove(&token_key);
                 Ok(serde_json::json!({
                     "success": false,
                     "error": format!("Connection test failed: {}", e),
@@ -695,4 +1380,196 @@ pub async fn slack_get_users_list(app: AppHandle) -> Result<Vec<crate::slack::Sl
         println!("❌ No access token found");
         Err("No access token configured. Please complete OAuth flow.".to_string())
     }
-}
\ No newline at end of file
+}
+
+/// Get a Slack user's presence (active/away)
+#[tauri::command]
+pub async fn slack_get_user_presence(app: AppHandle, user_id: String) -> Result<crate::slack::SlackPresence, String> {
+    println!("🟢 Getting Slack presence for user: {}", user_id);
+    
+    let access_token = match get_slack_credentials(app.clone()).await {
+        Ok(Some(creds)) => creds.access_token,
+        Ok(None) => return Err("No Slack credentials configured".to_string()),
+        Err(e) => return Err(format!("Failed to get credentials: {}", e)),
+    }
+    .ok_or_else(|| "No access token configured. Please complete OAuth flow.".to_string())?;
+    
+    let mut slack_client = SlackClient::new();
+    slack_client.set_token(access_token);
+    
+    slack_client.get_presence(&user_id).await
+        .map_err(|e| format!("Failed to get user presence: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::SlackCredentials;
+
+    #[test]
+    fn private_channel_errors_ask_for_an_invite() {
+        assert!(requires_invite("Slack API error: method_not_supported_for_channel_type"));
+        assert!(requires_invite("Slack API error: not_in_channel"));
+        assert!(!requires_invite("Service unavailable: connection refused"));
+        assert!(invite_bot_message("design").contains("/invite"));
+    }
+
+    #[tokio::test]
+    async fn private_channels_without_the_bot_ask_for_an_invite() {
+        let result = check_channel_access(
+            "C-private",
+            "design",
+            true,
+            || async { Ok(()) },
+            || async { Err("Slack API error: not_in_channel".to_string()) },
+        ).await;
+
+        let error = result.unwrap_err();
+        assert!(error.contains("#design"));
+        assert!(error.contains("/invite"));
+    }
+
+    #[tokio::test]
+    async fn unjoinable_public_channels_fall_back_to_a_membership_check() {
+        let access = check_channel_access(
+            "C-public",
+            "general",
+            false,
+            || async { Err("Slack API error: method_not_supported_for_channel_type".to_string()) },
+            || async { Ok(()) },
+        ).await;
+
+        assert_eq!(access.unwrap(), "member");
+    }
+
+    #[test]
+    fn connecting_a_second_project_reports_the_overlap() {
+        let connected = |channel_id: &str, project_id: &str| crate::slack_sync::ConnectedChannel {
+            channel_id: channel_id.to_string(),
+            channel_name: "general".to_string(),
+            project_id: project_id.to_string(),
+            project_name: None,
+            is_active: true,
+            last_sync_at: None,
+        };
+
+        let first = vec![connected("C1", "project-a")];
+        let overlaps = crate::slack_sync::detect_overlapping_channels(&first);
+        assert!(other_projects_on_channel(&overlaps, "C1", "project-a").is_empty());
+
+        let second = vec![connected("C1", "project-a"), connected("C2", "project-c"), connected("C1", "project-b")];
+        let overlaps = crate::slack_sync::detect_overlapping_channels(&second);
+        assert_eq!(other_projects_on_channel(&overlaps, "C1", "project-b"), vec!["project-a".to_string()]);
+        assert!(other_projects_on_channel(&overlaps, "C2", "project-c").is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_stops_scheduler_and_clears_credentials() {
+        let scheduler = SlackSyncScheduler::new(SlackClient::new(), 15);
+        scheduler.start(vec![]).await.unwrap();
+        assert!(scheduler.is_running().await);
+        let scheduler_state = Mutex::new(Some(scheduler));
+
+        let stored = Arc::new(Mutex::new(Some(SlackCredentials {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            access_token: Some("xoxb-token".to_string()),
+            team_id: Some("T123".to_string()),
+            team_name: None,
+            refresh_token: None,
+            token_expires_at: None,
+        })));
+
+        let clear_store = Arc::clone(&stored);
+        let report = reset_slack_state(
+            &scheduler_state,
+            || async { Err("service unavailable".to_string()) },
+            || async move {
+                *clear_store.lock().await = None;
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(report.scheduler_stopped);
+        assert!(!report.token_revoked);
+        assert!(report.credentials_cleared);
+        assert!(scheduler_state.lock().await.is_none());
+        assert!(stored.lock().await.is_none());
+    }
+
+    fn sync(id: &str, channel_id: &str, is_active: bool) -> SlackSync {
+        SlackSync {
+            id: id.to_string(),
+            project_id: "project-a".to_string(),
+            channel_id: channel_id.to_string(),
+            channel_name: "general".to_string(),
+            last_sync_timestamp: None,
+            last_message_timestamp: None,
+            is_active,
+            sync_interval_minutes: Some(15),
+            sync_status: Some("local".to_string()),
+            last_sync_at: None,
+            team_id: None,
+            analysis_prompt_key: None,
+            included_users: vec![],
+            excluded_users: vec![],
+            paused_until: None,
+            created_at: "2024-05-01T00:00:00Z".to_string(),
+            updated_at: "2024-05-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn revalidation_flags_syncs_that_lost_access() {
+        let syncs = vec![sync("s1", "C1", true), sync("s2", "C2", true), sync("s3", "C3", false)];
+
+        let results = revalidate_syncs(syncs, |sync| async move {
+            match sync.channel_id.as_str() {
+                "C1" => Ok("joined"),
+                _ => Err(invite_bot_message(&sync.channel_name)),
+            }
+        }).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].status, "joined");
+        assert!(results[0].error.is_none());
+        assert_eq!(results[1].sync_id, "s2");
+        assert_eq!(results[1].status, "needs_reconnect");
+        assert!(results[1].error.as_deref().unwrap().contains("/invite"));
+    }
+
+    #[test]
+    fn never_synced_channels_start_from_their_backfill_point() {
+        let mut backfilled = sync("s1", "C1", true);
+        backfilled.last_message_timestamp = Some("1714521600".to_string());
+        assert_eq!(sync_state(backfilled.clone()).last_sync.to_rfc3339(), "2024-05-01T00:00:00+00:00");
+
+        backfilled.last_sync_at = Some("2024-05-02T00:00:00Z".to_string());
+        assert_eq!(sync_state(backfilled).last_sync.to_rfc3339(), "2024-05-02T00:00:00+00:00");
+    }
+
+    #[test]
+    fn paused_syncs_resume_once_the_pause_ends() {
+        let now = chrono::Utc::now();
+        let mut paused = sync("s1", "C-paused", true);
+        paused.paused_until = Some(now + chrono::Duration::hours(2));
+        let mut state = sync_state(paused);
+
+        assert!(state.is_paused(now));
+        assert!(!state.is_paused(now + chrono::Duration::hours(3)));
+
+        state.paused_until = None;
+        assert!(!state.is_paused(now));
+    }
+
+    #[test]
+    fn only_valid_interval_changes_reach_the_scheduler() {
+        let change = |key: &str, value: Value| SettingChanged { key: key.to_string(), value };
+        
+        assert_eq!(interval_from_setting_change(&change(SLACK_SYNC_INTERVAL_MINUTES, serde_json::json!(5))), Some(5));
+        assert_eq!(interval_from_setting_change(&change(SLACK_SYNC_INTERVAL_MINUTES, serde_json::json!(0))), None);
+        assert_eq!(interval_from_setting_change(&change("whatsapp.lookback_days", serde_json::json!(5))), None);
+    }
+}
@@ -1,5 +1,8 @@
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use rusqlite::Connection;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 
 // src-tauri/src/commands/system_commands.rs
 
@@ -26,30 +29,547 @@ pub async fn get_system_user_info() -> Result<serde_json::Value, String> {
     }))
 }
 
+/// Where a notification leads when clicked
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationAction {
+    OpenProject { project_id: String },
+    OpenTask { project_id: String, task_id: String },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    pub action: Option<NotificationAction>,
+    pub icon: Option<String>,
+    /// Notifications sharing a tag replace each other instead of stacking
+    pub tag: Option<String>,
+    pub created_at: String,
+}
+
+// Notifications currently shown, keyed by notification id
+static ACTIVE_NOTIFICATIONS: Lazy<std::sync::Mutex<HashMap<String, Notification>>> = Lazy::new(|| {
+    std::sync::Mutex::new(HashMap::new())
+});
+
+/// Notifications nobody dismissed are forgotten after this long
+const NOTIFICATION_TTL: chrono::Duration = chrono::Duration::hours(1);
+/// Most notifications tracked at once; the oldest are forgotten first
+const MAX_ACTIVE_NOTIFICATIONS: usize = 50;
+
+/// Add `notification` to `active`, removing any earlier one with the same tag as well as
+/// expired ones and, past `MAX_ACTIVE_NOTIFICATIONS`, the oldest. Returns the removed ids.
+fn register_notification(
+    active: &mut HashMap<String, Notification>,
+    notification: Notification,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<String> {
+    let created_at = |n: &Notification| chrono::DateTime::parse_from_rfc3339(&n.created_at)
+        .map(|at| at.with_timezone(&chrono::Utc))
+        .unwrap_or(now);
+    let mut removed: Vec<String> = active.values()
+        .filter(|n| {
+            (notification.tag.is_some() && n.tag == notification.tag) || now - created_at(n) > NOTIFICATION_TTL
+        })
+            .map(|n| n.id.clone())
+        .collect();
+    for id in &removed {
+        active.remove(id);
+    }
+
+    active.insert(notification.id.clone(), notification);
+    while active.len() > MAX_ACTIVE_NOTIFICATIONS {
+        let oldest = active.values().min_by_key(|n| created_at(n)).map(|n| n.id.clone());
+        match oldest {
+            Some(id) => {
+                active.remove(&id);
+                removed.push(id);
+            }
+            None => break,
+        }
+    }
+    removed
+}
+
+/// Show a notification. The frontend renders `notification-shown` events and routes clicks
+/// through the attached action. Returns the notification id.
 #[tauri::command]
-pub async fn send_notification(title: String, body: String) -> Result<(), String> {
+pub async fn send_notification(
+    app: tauri::AppHandle,
+    title: String,
+    body: String,
+    action: Option<NotificationAction>,
+    icon: Option<String>,
+    tag: Option<String>,
+) -> Result<String, String> {
     println!("🔔 Sending notification: {} - {}", title, body);
-    // Placeholder implementation
-    Ok(())
+    
+    if title.trim().is_empty() {
+        return Err("Notification title cannot be empty".to_string());
+    }
+    
+    let notification = Notification {
+        id: uuid::Uuid::new_v4().to_string(),
+        title,
+        body,
+        action,
+        icon,
+        tag,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    
+    let replaced = register_notification(
+        &mut ACTIVE_NOTIFICATIONS.lock().unwrap_or_else(|e| e.into_inner()),
+        notification.clone(),
+        chrono::Utc::now(),
+    );
+    for id in replaced {
+        if let Err(e) = app.emit("notification-cleared", &id) {
+            println!("⚠️ Failed to emit notification-cleared: {}", e);
+        }
+    }
+    
+    app.emit("notification-shown", &notification)
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    
+    println!("✅ Notification {} shown", notification.id);
+    Ok(notification.id)
+}
+
+/// Dismiss a notification previously returned by `send_notification`
+#[tauri::command]
+pub async fn clear_notification(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    println!("🔕 Clearing notification: {}", id);
+    
+    ACTIVE_NOTIFICATIONS.lock().unwrap_or_else(|e| e.into_inner())
+        .remove(&id)
+        .ok_or_else(|| format!("Notification not found: {}", id))?;
+    
+    app.emit("notification-cleared", &id)
+        .map_err(|e| format!("Failed to clear notification: {}", e))
+}
+
+/// Per-service budget for `services_health`; a slower service is reported as down
+pub const SERVICE_HEALTH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ServiceHealth {
+    pub up: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Run one health check under `timeout`, timing it
+async fn measure_health<F, E>(timeout: std::time::Duration, check: F) -> ServiceHealth
+where
+    F: std::future::Future<Output = Result<bool, E>>,
+    E: std::fmt::Display,
+{
+    let started = std::time::Instant::now();
+    let outcome = tokio::time::timeout(timeout, check).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    
+    match outcome {
+        Ok(Ok(true)) => ServiceHealth { up: true, latency_ms, error: None },
+        Ok(Ok(false)) => ServiceHealth { up: false, latency_ms, error: Some("Service reported unhealthy".to_string()) },
+        Ok(Err(e)) => ServiceHealth { up: false, latency_ms, error: Some(e.to_string()) },
+        Err(_) => ServiceHealth {
+            up: false,
+            latency_ms,
+            error: Some(format!("Timed out after {}ms", timeout.as_millis())),
+        },
+    }
 }
 
+/// Check every backing service concurrently for the settings status screen
 #[tauri::command]
-pub async fn reset_database(app: tauri::AppHandle) -> Result<String, String> {
-    println!("🗑️ Resetting database...");
+pub async fn services_health() -> Result<HashMap<String, ServiceHealth>, String> {
+    println!("🩺 Checking health of all services...");
+    
+    let slack = crate::slack_service_client::SlackServiceClient::new(None);
+    let whatsapp = crate::whatsapp_service_client::WhatsAppServiceClient::new(None);
+    let queue = crate::queue_service_client::QueueServiceClient::new(None);
+    let oauth = crate::oauth_service_client::OAuthServiceClient::new(None);
+    let ai = crate::ai_service_client::AIServiceClient::new(None);
+    
+    let (slack, whatsapp, queue, oauth, ai) = tokio::join!(
+        measure_health(SERVICE_HEALTH_TIMEOUT, slack.health_check()),
+        measure_health(SERVICE_HEALTH_TIMEOUT, whatsapp.health_check()),
+        measure_health(SERVICE_HEALTH_TIMEOUT, queue.health_check()),
+        measure_health(SERVICE_HEALTH_TIMEOUT, oauth.health_check()),
+        measure_health(SERVICE_HEALTH_TIMEOUT, ai.health_check()),
+    );
+    
+    let report: HashMap<String, ServiceHealth> = [
+        ("slack", slack),
+        ("whatsapp", whatsapp),
+        ("queue", queue),
+        ("oauth", oauth),
+        ("ai", ai),
+    ]
+    .into_iter()
+    .map(|(name, health)| (name.to_string(), health))
+    .collect();
+    
+    let up = report.values().filter(|h| h.up).count();
+    println!("✅ Service health: {}/{} up", up, report.len());
+    Ok(report)
+}
+
+/// Which subsystems `reset_database` should clear
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ResetOptions {
+    pub slack: bool,
+    pub whatsapp: bool,
+    pub projects: bool,
+    pub documents: bool,
+    pub settings: bool,
+}
+
+impl ResetOptions {
+    pub fn all() -> Self {
+        Self { slack: true, whatsapp: true, projects: true, documents: true, settings: true }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.slack || self.whatsapp || self.projects || self.documents || self.settings)
+    }
+}
+
+/// Rows removed from one table by a reset (or that would be, for a dry run)
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ResetSummary {
+    pub table: String,
+    pub rows_deleted: usize,
+}
+
+/// What a reset removed, or would remove when `dry_run` is set. Tables are sorted by name.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ResetReport {
+    pub dry_run: bool,
+    pub tables: Vec<ResetSummary>,
+    pub total_rows: usize,
+    pub files: Vec<String>,
+}
+
+impl ResetReport {
+    fn add_table(&mut self, table: String, rows_deleted: usize) {
+        self.total_rows += rows_deleted;
+        self.tables.push(ResetSummary { table, rows_deleted });
+    }
+}
+
+/// Every table in `conn`, sorted by name
+fn all_tables(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+        .map_err(|e| format!("Failed to list tables: {}", e))?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to list tables: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to list tables: {}", e))?;
+    Ok(tables)
+}
+
+fn count_rows(conn: &Connection, table: &str) -> Result<usize, String> {
+    conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", table), [], |row| row.get::<_, i64>(0))
+        .map(|count| count as usize)
+        .map_err(|e| format!("Failed to count rows in {}: {}", table, e))
+}
+
+/// Row counts of every table, for reporting what deleting the whole database removes. A
+/// corrupt or locked database is the usual reason for a full reset, so tables that can't be
+/// read are left out of the report instead of failing it.
+pub fn count_all_tables(conn: &Connection, dry_run: bool) -> ResetReport {
+    let mut report = ResetReport { dry_run, ..Default::default() };
+    let tables = all_tables(conn).unwrap_or_else(|e| {
+        println!("⚠️ {}; the reset summary will be empty", e);
+        vec![]
+    });
+    for table in tables {
+        match count_rows(conn, &table) {
+            Ok(count) => report.add_table(table, count),
+            Err(e) => println!("⚠️ {}; left out of the reset summary", e),
+        }
+    }
+    report
+}
+
+/// Tables owned by the selected subsystems that exist in `conn`. Clearing projects does not
+/// clear documents; select both for a full project wipe.
+fn tables_for_reset(conn: &Connection, options: &ResetOptions) -> Result<Vec<String>, String> {
+    Ok(all_tables(conn)?
+        .into_iter()
+        .filter(|table| {
+            (options.slack && (table.starts_with("slack_") || table == "project_slack_connections"))
+                || (options.whatsapp && table.starts_with("whatsapp_"))
+                || (options.projects
+                    && ["projects", "tasks", "events", "event_detections", "activity_logs", "project_checkins"].contains(&table.as_str()))
+                || (options.documents && (table == "documents" || table == "document_versions"))
+                || (options.settings && table == "settings")
+        })
+        .collect())
+}
+
+/// Files owned by the selected subsystems, deleted along with their tables. The WhatsApp
+/// monitor keeps its browser profile and message store next to the app, not in app data.
+fn files_for_reset(app_data_dir: &std::path::Path, options: &ResetOptions) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    if options.settings {
+        files.push(app_data_dir.join("settings.json"));
+    }
+    if options.whatsapp {
+        files.push(std::path::PathBuf::from("./whatsapp_profile"));
+        files.push(std::path::PathBuf::from("./whatsapp_messages.db"));
+    }
+    files
+}
+
+/// Count (and unless `dry_run`, delete) the rows of every table selected by `options`
+pub fn reset_tables(conn: &mut Connection, options: &ResetOptions, dry_run: bool) -> Result<ResetReport, String> {
+    let tables = tables_for_reset(conn, options)?;
+    let mut report = ResetReport { dry_run, ..Default::default() };
+
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start reset transaction: {}", e))?;
+    for table in tables {
+        let count = count_rows(&tx, &table)?;
+        if !dry_run {
+            tx.execute(&format!("DELETE FROM \"{}\"", table), [])
+                .map_err(|e| format!("Failed to clear {}: {}", table, e))?;
+        }
+        report.add_table(table, count);
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit reset: {}", e))?;
+
+    Ok(report)
+}
+
+/// Reset app data. Without `options` the whole database file is deleted (the old behaviour);
+/// with options only the selected subsystems are cleared. `dry_run` reports row counts only.
+/// Either way the report lists the rows removed per table.
+#[tauri::command]
+pub async fn reset_database(
+    app: tauri::AppHandle,
+    options: Option<ResetOptions>,
+    dry_run: Option<bool>,
+) -> Result<ResetReport, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    println!("🗑️ Resetting database (options: {:?}, dry run: {})...", options, dry_run);
     
     let app_data_dir = app.path().app_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
     
     let db_path = app_data_dir.join("project_boxes.db");
     
-    if db_path.exists() {
-        std::fs::remove_file(&db_path)
-            .map_err(|e| format!("Failed to delete database file: {}", e))?;
-        println!("✅ Database file deleted");
+    if options.is_none() && !dry_run {
+        let report = if db_path.exists() {
+            // Count while the file is still there; the connection closes before deleting it
+            let counted = crate::app_db::with_db_at(db_path.clone(), |conn| Ok(count_all_tables(conn, false))).await;
+            let report = counted.unwrap_or_else(|e| {
+                println!("⚠️ Failed to open database, deleting it without a summary: {}", e);
+                ResetReport::default()
+            });
+            std::fs::remove_file(&db_path)
+                .map_err(|e| format!("Failed to delete database file: {}", e))?;
+            println!("✅ Database file deleted");
+            report
+        } else {
+            println!("ℹ️ Database file doesn't exist");
+            ResetReport::default()
+        };
+        
+        println!("✅ Database reset completed: {} rows across {} tables", report.total_rows, report.tables.len());
+        return Ok(report);
+    }
+    
+    let options = options.unwrap_or_else(ResetOptions::all);
+    if options.is_empty() {
+        return Err("Select at least one subsystem to reset".to_string());
+    }
+    
+    let mut report = if db_path.exists() {
+        let table_options = options.clone();
+        crate::app_db::with_db_at(db_path.clone(), move |conn| reset_tables(conn, &table_options, dry_run)).await?
     } else {
         println!("ℹ️ Database file doesn't exist");
+        ResetReport { dry_run, ..Default::default() }
+    };
+    
+    for path in files_for_reset(&app_data_dir, &options).into_iter().filter(|p| p.exists()) {
+        if !dry_run {
+            let removed = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            removed.map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        }
+        report.files.push(path.display().to_string());
     }
     
-    println!("✅ Database reset completed");
-    Ok("Database reset successfully".to_string())
+    println!(
+        "✅ Database reset {}: {} rows across {} tables, {} files",
+        if dry_run { "preview" } else { "completed" },
+        report.total_rows, report.tables.len(), report.files.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE projects (id TEXT PRIMARY KEY);
+             CREATE TABLE slack_messages (id TEXT PRIMARY KEY);
+             CREATE TABLE project_slack_connections (id TEXT PRIMARY KEY);
+             INSERT INTO projects VALUES ('p1');
+             INSERT INTO slack_messages VALUES ('m1'), ('m2');
+             INSERT INTO project_slack_connections VALUES ('c1');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn notification(id: &str, tag: Option<&str>) -> Notification {
+        Notification {
+            id: id.to_string(),
+            title: "Slack sync".to_string(),
+            body: "3 new tasks".to_string(),
+            action: Some(NotificationAction::OpenProject { project_id: "p1".to_string() }),
+            icon: None,
+            tag: tag.map(|t| t.to_string()),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[tokio::test]
+    async fn health_check_reports_latency_errors_and_timeouts() {
+        let timeout = std::time::Duration::from_millis(20);
+
+        let up = measure_health(timeout, async { Ok::<bool, String>(true) }).await;
+        assert!(up.up);
+        assert!(up.error.is_none());
+
+        let failed = measure_health(timeout, async { Err::<bool, String>("connection refused".to_string()) }).await;
+        assert!(!failed.up);
+        assert_eq!(failed.error.as_deref(), Some("connection refused"));
+
+        let slow = measure_health(timeout, std::future::pending::<Result<bool, String>>()).await;
+        assert!(!slow.up);
+        assert!(slow.latency_ms >= 20);
+        assert!(slow.error.unwrap().starts_with("Timed out"));
+    }
+
+    #[test]
+    fn tagged_notifications_coalesce() {
+        let now = chrono::Utc::now();
+        let mut active = HashMap::new();
+        assert!(register_notification(&mut active, notification("n1", Some("slack-sync")), now).is_empty());
+        assert!(register_notification(&mut active, notification("n2", None), now).is_empty());
+
+        let replaced = register_notification(&mut active, notification("n3", Some("slack-sync")), now);
+        assert_eq!(replaced, vec!["n1".to_string()]);
+        assert_eq!(active.len(), 2);
+        assert!(active.contains_key("n3"));
+    }
+
+    #[test]
+    fn expired_and_excess_notifications_are_pruned() {
+        let now = chrono::Utc::now();
+        let mut active = HashMap::new();
+        let mut stale = notification("stale", None);
+        stale.created_at = (now - NOTIFICATION_TTL - chrono::Duration::minutes(1)).to_rfc3339();
+        register_notification(&mut active, stale, now);
+
+        let removed = register_notification(&mut active, notification("fresh", None), now);
+        assert_eq!(removed, vec!["stale".to_string()]);
+
+        for i in 0..MAX_ACTIVE_NOTIFICATIONS {
+            let mut newer = notification(&format!("n{}", i), None);
+            newer.created_at = (now + chrono::Duration::seconds(i as i64 + 1)).to_rfc3339();
+            register_notification(&mut active, newer, now);
+        }
+        assert_eq!(active.len(), MAX_ACTIVE_NOTIFICATIONS);
+        assert!(!active.contains_key("fresh"));
+    }
+
+    #[test]
+    fn dry_run_counts_without_deleting() {
+        let mut conn = seeded_connection();
+        let options = ResetOptions { slack: true, ..Default::default() };
+
+        let report = reset_tables(&mut conn, &options, true).unwrap();
+        assert_eq!(report.total_rows, 3);
+        assert_eq!(report.tables, vec![
+            ResetSummary { table: "project_slack_connections".to_string(), rows_deleted: 1 },
+            ResetSummary { table: "slack_messages".to_string(), rows_deleted: 2 },
+        ]);
+
+        let remaining: i64 = conn.query_row("SELECT COUNT(*) FROM slack_messages", [], |row| row.get(0)).unwrap();
+        assert_eq!(remaining, 2);
+    }
+
+    #[test]
+    fn selective_reset_leaves_other_subsystems() {
+        let mut conn = seeded_connection();
+        let options = ResetOptions { slack: true, ..Default::default() };
+
+        let report = reset_tables(&mut conn, &options, false).unwrap();
+        assert_eq!(report.total_rows, 3);
+
+        let slack: i64 = conn.query_row("SELECT COUNT(*) FROM slack_messages", [], |row| row.get(0)).unwrap();
+        let projects: i64 = conn.query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0)).unwrap();
+        assert_eq!(slack, 0);
+        assert_eq!(projects, 1);
+    }
+
+    #[test]
+    fn documents_and_whatsapp_resets_cover_their_stores() {
+        let conn = seeded_connection();
+        conn.execute_batch(
+            "CREATE TABLE documents (id TEXT PRIMARY KEY);
+             CREATE TABLE document_versions (id TEXT PRIMARY KEY);",
+        )
+        .unwrap();
+
+        let documents = ResetOptions { documents: true, ..Default::default() };
+        assert_eq!(tables_for_reset(&conn, &documents).unwrap(), vec!["document_versions", "documents"]);
+
+        let whatsapp = ResetOptions { whatsapp: true, ..Default::default() };
+        let files = files_for_reset(std::path::Path::new("/data"), &whatsapp);
+        assert!(files.contains(&std::path::PathBuf::from("./whatsapp_profile")));
+        assert!(files.contains(&std::path::PathBuf::from("./whatsapp_messages.db")));
+        assert!(!files.contains(&std::path::PathBuf::from("/data/settings.json")));
+    }
+
+    #[test]
+    fn full_reset_summary_covers_every_table() {
+        let conn = seeded_connection();
+
+        let report = count_all_tables(&conn, false);
+        assert_eq!(report.total_rows, 4);
+        assert_eq!(report.tables.iter().map(|t| t.table.as_str()).collect::<Vec<_>>(),
+            vec!["project_slack_connections", "projects", "slack_messages"]);
+    }
+
+    #[test]
+    fn corrupt_database_gives_an_empty_summary() {
+        let db_path = std::env::temp_dir().join(format!("corrupt-{}.db", uuid::Uuid::new_v4()));
+        std::fs::write(&db_path, b"not a sqlite database, just some bytes that fill a header").unwrap();
+
+        let report = count_all_tables(&Connection::open(&db_path).unwrap(), false);
+        assert_eq!(report.total_rows, 0);
+        assert!(report.tables.is_empty());
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
 }
@@ -1,6 +1,7 @@
 use tauri::AppHandle;
 use tauri_plugin_keyring::KeyringExt;
 use serde::{Deserialize, Serialize};
+use crate::locale::Locale;
 
 // Slack credentials structure
 #[derive(Debug, Serialize, Deserialize)]
@@ -10,81 +11,273 @@ pub struct SlackCredentials {
     pub access_token: Option<String>,
     pub team_id: Option<String>,
     pub team_name: Option<String>,
+    /// Present when token rotation is enabled for the Slack app
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// When `access_token` expires (RFC 3339), for rotating tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_expires_at: Option<String>,
+}
+
+impl SlackCredentials {
+    /// Take the tokens from a refresh. A rotated refresh token replaces the stored one, which
+    /// the provider has invalidated; without one the stored token is kept.
+    pub fn apply_refresh(&mut self, refreshed: &crate::oauth_service_client::RefreshedToken) {
+        self.access_token = Some(refreshed.access_token.clone());
+        if let Some(refresh_token) = &refreshed.refresh_token {
+            self.refresh_token = Some(refresh_token.clone());
+        }
+        self.token_expires_at = refreshed.expires_at.map(|at| at.to_rfc3339());
+    }
+}
+
+/// Credential fields checked before they are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialField {
+    ClientId,
+    ClientSecret,
+    AccessToken,
+    TeamId,
+    TeamName,
+    ApiKey,
+    SigningSecret,
+}
+
+impl CredentialField {
+    fn label(&self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (CredentialField::ClientId, _) => "Client ID",
+            (CredentialField::ClientSecret, _) => "Client Secret",
+            (CredentialField::TeamId, _) => "Team ID",
+            (CredentialField::SigningSecret, _) => "Signing Secret",
+            (CredentialField::AccessToken, Locale::Portuguese) => "Token de acesso",
+            (CredentialField::AccessToken, Locale::English) => "Access token",
+            (CredentialField::TeamName, Locale::Portuguese) => "Nome da equipe",
+            (CredentialField::TeamName, Locale::English) => "Team name",
+            (CredentialField::ApiKey, Locale::Portuguese) => "Chave de API",
+            (CredentialField::ApiKey, Locale::English) => "API key",
+        }
+    }
+
+    /// Portuguese adjectives agree with the field's gender ("Chave ... vazia")
+    fn is_feminine(&self) -> bool {
+        matches!(self, CredentialField::ApiKey)
+    }
+}
+
+/// Why a credential could not be validated or stored. `Display` uses the system locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialError {
+    Empty(CredentialField),
+    TooShort(CredentialField, usize),
+    TooLong(CredentialField, usize),
+    InvalidCharacters(CredentialField),
+    InvalidFormat(CredentialField),
+    /// No Slack app credentials have been configured yet
+    NotConfigured,
+    Serialization(String),
+    Keychain(String),
+    /// Saving to the keychain failed, which usually means the app's signature or the
+    /// keychain permissions are off
+    KeychainWrite(String),
+}
+
+impl CredentialError {
+    /// User-facing description of the error in `locale`
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (CredentialError::Empty(field), Locale::Portuguese) => format!(
+                "{} não pode estar {}", field.label(locale), if field.is_feminine() { "vazia" } else { "vazio" }
+            ),
+            (CredentialError::Empty(field), Locale::English) => format!("{} cannot be empty", field.label(locale)),
+            (CredentialError::TooShort(field, min), Locale::Portuguese) => format!(
+                "{} muito {} (mínimo {} caracteres)", field.label(locale), if field.is_feminine() { "curta" } else { "curto" }, min
+            ),
+            (CredentialError::TooShort(field, min), Locale::English) => format!(
+                "{} is too short (minimum {} characters)", field.label(locale), min
+            ),
+            (CredentialError::TooLong(field, max), Locale::Portuguese) => format!(
+                "{} muito {} (máximo {} caracteres)", field.label(locale), if field.is_feminine() { "longa" } else { "longo" }, max
+            ),
+            (CredentialError::TooLong(field, max), Locale::English) => format!(
+                "{} is too long (maximum {} characters)", field.label(locale), max
+            ),
+            (CredentialError::InvalidCharacters(field), Locale::Portuguese) => format!("{} contém caracteres inválidos", field.label(locale)),
+            (CredentialError::InvalidCharacters(field), Locale::English) => format!("{} contains invalid characters", field.label(locale)),
+            (CredentialError::InvalidFormat(field), Locale::Portuguese) => format!("Formato de {} inválido", field.label(locale)),
+            (CredentialError::InvalidFormat(field), Locale::English) => format!("Invalid {} format", field.label(locale)),
+            (CredentialError::NotConfigured, Locale::Portuguese) => {
+                "Credenciais não encontradas. Configure primeiro o Client ID e Client Secret.".to_string()
+            }
+            (CredentialError::NotConfigured, Locale::English) => {
+                "Credentials not found. Configure the Client ID and Client Secret first.".to_string()
+            }
+            (CredentialError::Serialization(detail), Locale::Portuguese) => format!("Erro ao processar credenciais: {}", detail),
+            (CredentialError::Serialization(detail), Locale::English) => format!("Could not process credentials: {}", detail),
+            (CredentialError::Keychain(detail), Locale::Portuguese) => format!("Erro ao acessar o keychain: {}", detail),
+            (CredentialError::Keychain(detail), Locale::English) => format!("Could not access the keychain: {}", detail),
+            (CredentialError::KeychainWrite(detail), Locale::Portuguese) => format!(
+                "Erro ao armazenar credenciais no keychain: {}. Isso pode indicar um problema de assinatura do app ou permissões do keychain.", detail
+            ),
+            (CredentialError::KeychainWrite(detail), Locale::English) => format!(
+                "Could not store credentials in the keychain: {}. This may indicate an app signing or keychain permission problem.", detail
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for CredentialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.localized_message(Locale::system()))
+    }
+}
+
+impl std::error::Error for CredentialError {}
+
+impl From<CredentialError> for String {
+    fn from(e: CredentialError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Confirmation of a credential change, shown to the user. `Display` uses the system locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialNotice {
+    Stored(CredentialField),
+    /// The access token was dropped and the workspace needs to be re-authorized
+    AccessTokenCleared,
+}
+
+impl CredentialNotice {
+    /// User-facing description of the change in `locale`
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (CredentialNotice::Stored(field), Locale::Portuguese) => format!(
+                "{} {} com sucesso", field.label(locale), if field.is_feminine() { "armazenada" } else { "armazenado" }
+            ),
+            (CredentialNotice::Stored(field), Locale::English) => format!("{} stored successfully", field.label(locale)),
+            (CredentialNotice::AccessTokenCleared, Locale::Portuguese) => {
+                "Token de acesso removido. Execute a autenticação OAuth novamente para reconectar.".to_string()
+            }
+            (CredentialNotice::AccessTokenCleared, Locale::English) => {
+                "Access token removed. Run the OAuth authorization again to reconnect.".to_string()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CredentialNotice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.localized_message(Locale::system()))
+    }
 }
 
 // Input validation helper functions
-pub fn validate_client_id(client_id: &str) -> Result<(), String> {
+pub fn validate_client_id(client_id: &str) -> Result<(), CredentialError> {
     if client_id.trim().is_empty() {
-        return Err("Client ID não pode estar vazio".to_string());
+        return Err(CredentialError::Empty(CredentialField::ClientId));
     }
     if client_id.len() > 255 {
-        return Err("Client ID muito longo (máximo 255 caracteres)".to_string());
+        return Err(CredentialError::TooLong(CredentialField::ClientId, 255));
     }
     // Slack client IDs typically follow a pattern like "1234567890.1234567890"
     if !client_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-') {
-        return Err("Client ID contém caracteres inválidos".to_string());
+        return Err(CredentialError::InvalidCharacters(CredentialField::ClientId));
     }
     Ok(())
 }
 
-pub fn validate_client_secret(client_secret: &str) -> Result<(), String> {
+pub fn validate_client_secret(client_secret: &str) -> Result<(), CredentialError> {
     if client_secret.trim().is_empty() {
-        return Err("Client Secret não pode estar vazio".to_string());
+        return Err(CredentialError::Empty(CredentialField::ClientSecret));
     }
     if client_secret.len() < 8 {
-        return Err("Client Secret muito curto (mínimo 8 caracteres)".to_string());
+        return Err(CredentialError::TooShort(CredentialField::ClientSecret, 8));
     }
     if client_secret.len() > 255 {
-        return Err("Client Secret muito longo (máximo 255 caracteres)".to_string());
+        return Err(CredentialError::TooLong(CredentialField::ClientSecret, 255));
     }
     // Basic validation for printable ASCII characters
     if !client_secret.chars().all(|c| c.is_ascii() && !c.is_control()) {
-        return Err("Client Secret contém caracteres inválidos".to_string());
+        return Err(CredentialError::InvalidCharacters(CredentialField::ClientSecret));
     }
     Ok(())
 }
 
-pub fn validate_access_token(token: &str) -> Result<(), String> {
+pub fn validate_access_token(token: &str) -> Result<(), CredentialError> {
     if token.trim().is_empty() {
-        return Err("Token de acesso não pode estar vazio".to_string());
+        return Err(CredentialError::Empty(CredentialField::AccessToken));
     }
     if token.len() > 500 {
-        return Err("Token de acesso muito longo".to_string());
+        return Err(CredentialError::TooLong(CredentialField::AccessToken, 500));
     }
     // Slack tokens typically start with xoxb-, xoxp-, or xoxa-
     if !token.starts_with("xox") {
-        return Err("Formato de token inválido".to_string());
+        return Err(CredentialError::InvalidFormat(CredentialField::AccessToken));
     }
     if !token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
-        return Err("Token contém caracteres inválidos".to_string());
+        return Err(CredentialError::InvalidCharacters(CredentialField::AccessToken));
     }
     Ok(())
 }
 
-pub fn validate_team_id(team_id: &str) -> Result<(), String> {
+pub fn validate_team_id(team_id: &str) -> Result<(), CredentialError> {
     if team_id.trim().is_empty() {
-        return Err("Team ID não pode estar vazio".to_string());
+        return Err(CredentialError::Empty(CredentialField::TeamId));
     }
     if team_id.len() > 50 {
-        return Err("Team ID muito longo".to_string());
+        return Err(CredentialError::TooLong(CredentialField::TeamId, 50));
     }
     // Slack team IDs typically start with 'T'
     if !team_id.starts_with('T') || !team_id.chars().all(|c| c.is_ascii_alphanumeric()) {
-        return Err("Formato de Team ID inválido".to_string());
+        return Err(CredentialError::InvalidFormat(CredentialField::TeamId));
     }
     Ok(())
 }
 
-pub fn validate_team_name(team_name: &str) -> Result<(), String> {
+pub fn validate_team_name(team_name: &str) -> Result<(), CredentialError> {
     if team_name.trim().is_empty() {
-        return Err("Nome da equipe não pode estar vazio".to_string());
+        return Err(CredentialError::Empty(CredentialField::TeamName));
     }
     if team_name.len() > 100 {
-        return Err("Nome da equipe muito longo (máximo 100 caracteres)".to_string());
+        return Err(CredentialError::TooLong(CredentialField::TeamName, 100));
     }
     // Allow most characters but prevent control characters
     if team_name.chars().any(|c| c.is_control()) {
-        return Err("Nome da equipe contém caracteres inválidos".to_string());
+        return Err(CredentialError::InvalidCharacters(CredentialField::TeamName));
+    }
+    Ok(())
+}
+
+pub fn validate_ai_api_key(api_key: &str) -> Result<(), CredentialError> {
+    if api_key.trim().is_empty() {
+        return Err(CredentialError::Empty(CredentialField::ApiKey));
+    }
+    if api_key.len() < 20 {
+        return Err(CredentialError::TooShort(CredentialField::ApiKey, 20));
+    }
+    if api_key.len() > 255 {
+        return Err(CredentialError::TooLong(CredentialField::ApiKey, 255));
+    }
+    if !api_key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(CredentialError::InvalidCharacters(CredentialField::ApiKey));
+    }
+    Ok(())
+}
+
+/// Slack signing secrets are 32 hex characters
+pub fn validate_signing_secret(signing_secret: &str) -> Result<(), CredentialError> {
+    if signing_secret.trim().is_empty() {
+        return Err(CredentialError::Empty(CredentialField::SigningSecret));
+    }
+    if signing_secret.len() < 32 {
+        return Err(CredentialError::TooShort(CredentialField::SigningSecret, 32));
+    }
+    if signing_secret.len() > 64 {
+        return Err(CredentialError::TooLong(CredentialField::SigningSecret, 64));
+    }
+    if !signing_secret.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(CredentialError::InvalidCharacters(CredentialField::SigningSecret));
     }
     Ok(())
 }
@@ -118,11 +311,13 @@ pub async fn store_slack_credentials(
         access_token: None,
         team_id: None,
         team_name: None,
+        refresh_token: None,
+        token_expires_at: None,
     };
     
     let credentials_json = serde_json::to_string(&credentials)
         .map_err(|e| {
-            let error = format!("Erro ao serializar credenciais: {}", e);
+            let error = CredentialError::Serialization(e.to_string()).to_string();
             println!("❌ [STORE] Serialization failed: {}", error);
             error
         })?;
@@ -131,7 +326,7 @@ pub async fn store_slack_credentials(
     
     keyring.set_password("project_boxes", "slack_credentials", &credentials_json)
         .map_err(|e| {
-            let error = format!("Erro ao armazenar credenciais no keychain: {}. Isso pode indicar um problema de assinatura do app ou permissões do keychain.", e);
+            let error = CredentialError::KeychainWrite(e.to_string()).to_string();
             println!("❌ [STORE] Keychain storage failed: {}", error);
             error
         })?;
@@ -155,7 +350,7 @@ pub async fn get_slack_credentials(app: AppHandle) -> Result<Option<SlackCredent
                     Ok(Some(credentials))
                 }
                 Err(e) => {
-                    let error = format!("Erro ao deserializar credenciais: {}", e);
+                    let error = CredentialError::Serialization(e.to_string()).to_string();
                     println!("❌ [GET] Deserialization failed: {}", error);
                     Err(error)
                 }
@@ -208,18 +403,18 @@ pub async fn update_slack_access_token(
             println!("✅ [UPDATE] Found existing credentials");
             serde_json::from_str::<SlackCredentials>(&credentials_json)
                 .map_err(|e| {
-                    let error = format!("Erro ao deserializar credenciais existentes: {}", e);
+                    let error = CredentialError::Serialization(e.to_string()).to_string();
                     println!("❌ [UPDATE] Deserialization failed: {}", error);
                     error
                 })?
         }
         Ok(None) => {
-            let error = "Credenciais não encontradas. Configure primeiro o Client ID e Client Secret.".to_string();
+            let error = CredentialError::NotConfigured.to_string();
             println!("❌ [UPDATE] {}", error);
             return Err(error);
         }
         Err(e) => {
-            let error = format!("Erro ao acessar credenciais existentes: {}. Configure primeiro o Client ID e Client Secret.", e);
+            let error = CredentialError::Keychain(e.to_string()).to_string();
             println!("❌ [UPDATE] {}", error);
             return Err(error);
         }
@@ -227,14 +422,16 @@ pub async fn update_slack_access_token(
     
     println!("✅ [UPDATE] Existing credentials loaded, updating with OAuth data...");
     
-    // Update with new access token
+    // Update with new access token; rotation data of the previous token no longer applies
     credentials.access_token = Some(access_token.clone());
     credentials.team_id = Some(team_id.clone());
     credentials.team_name = Some(team_name.clone());
+    credentials.refresh_token = None;
+    credentials.token_expires_at = None;
     
     let credentials_json = serde_json::to_string(&credentials)
         .map_err(|e| {
-            let error = format!("Erro ao serializar credenciais atualizadas: {}", e);
+            let error = CredentialError::Serialization(e.to_string()).to_string();
             println!("❌ [UPDATE] Serialization failed: {}", error);
             error
         })?;
@@ -243,7 +440,7 @@ pub async fn update_slack_access_token(
     
     keyring.set_password("project_boxes", "slack_credentials", &credentials_json)
         .map_err(|e| {
-            let error = format!("Erro ao atualizar credenciais no keychain: {}", e);
+            let error = CredentialError::KeychainWrite(e.to_string()).to_string();
             println!("❌ [UPDATE] Keychain update failed: {}", error);
             error
         })?;
@@ -254,12 +451,56 @@ pub async fn update_slack_access_token(
     Ok("Token de acesso atualizado com sucesso".to_string())
 }
 
+// Store the AI service API key securely
+pub async fn store_ai_api_key(app: AppHandle, api_key: String) -> Result<String, String> {
+    validate_ai_api_key(&api_key)?;
+    
+    app.keyring().set_password("project_boxes", "ai_api_key", &api_key)
+        .map_err(|e| CredentialError::KeychainWrite(e.to_string()))?;
+    
+    println!("✅ [STORE] AI API key stored successfully in keychain");
+    Ok(CredentialNotice::Stored(CredentialField::ApiKey).to_string())
+}
+
+// Retrieve the AI service API key
+pub async fn get_ai_api_key(app: AppHandle) -> Result<Option<String>, String> {
+    match app.keyring().get_password("project_boxes", "ai_api_key") {
+        Ok(api_key) => Ok(api_key),
+        Err(e) => {
+            println!("❌ [GET] Keychain access error: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+// Store the Slack app signing secret used to verify Events API requests
+pub async fn store_slack_signing_secret(app: AppHandle, signing_secret: String) -> Result<String, String> {
+    validate_signing_secret(&signing_secret)?;
+    
+    app.keyring().set_password("project_boxes", "slack_signing_secret", &signing_secret)
+        .map_err(|e| CredentialError::KeychainWrite(e.to_string()))?;
+    
+    println!("✅ [STORE] Slack signing secret stored successfully in keychain");
+    Ok(CredentialNotice::Stored(CredentialField::SigningSecret).to_string())
+}
+
+// Retrieve the Slack signing secret, if events are configured
+pub async fn get_slack_signing_secret(app: AppHandle) -> Result<Option<String>, String> {
+    match app.keyring().get_password("project_boxes", "slack_signing_secret") {
+        Ok(signing_secret) => Ok(signing_secret),
+        Err(e) => {
+            println!("❌ [GET] Keychain access error: {}", e);
+            Ok(None)
+        }
+    }
+}
+
 // Delete Slack credentials
 pub async fn delete_slack_credentials(app: AppHandle) -> Result<String, String> {
     let keyring = app.keyring();
     
     keyring.delete_password("project_boxes", "slack_credentials")
-        .map_err(|e| format!("Erro ao deletar credenciais: {}", e))?;
+        .map_err(|e| CredentialError::Keychain(e.to_string()))?;
     
     Ok("Credenciais removidas com sucesso".to_string())
 }
@@ -295,6 +536,55 @@ pub async fn force_slack_reconnection(app: AppHandle) -> Result<String, String>
     Ok("Credenciais do Slack limpas completamente. Execute a autenticação OAuth novamente para obter acesso com os scopes atualizados.".to_string())
 }
 
+// Clear only the Slack access token, keeping the app credentials and team so the workspace can
+// be re-authorized (e.g. for new scopes) without reconnecting every channel
+pub async fn clear_slack_access_token(app: AppHandle) -> Result<String, String> {
+    println!("🔄 [RECONNECT] Clearing Slack access token, keeping app credentials...");
+    let keyring = app.keyring();
+    
+    let mut credentials = match keyring.get_password("project_boxes", "slack_credentials") {
+        Ok(Some(credentials_json)) => serde_json::from_str::<SlackCredentials>(&credentials_json)
+            .map_err(|e| CredentialError::Serialization(e.to_string()))?,
+        Ok(None) => return Err(CredentialError::NotConfigured.to_string()),
+        Err(e) => return Err(CredentialError::Keychain(e.to_string()).to_string()),
+    };
+    
+    credentials.access_token = None;
+    
+    let credentials_json = serde_json::to_string(&credentials)
+        .map_err(|e| CredentialError::Serialization(e.to_string()))?;
+    keyring.set_password("project_boxes", "slack_credentials", &credentials_json)
+        .map_err(|e| CredentialError::KeychainWrite(e.to_string()))?;
+    
+    println!("✅ [RECONNECT] Access token cleared");
+    Ok(CredentialNotice::AccessTokenCleared.to_string())
+}
+
+/// Store the tokens from a refresh, including a rotated refresh token
+pub async fn store_refreshed_slack_token(
+    app: AppHandle,
+    refreshed: &crate::oauth_service_client::RefreshedToken,
+) -> Result<(), String> {
+    let keyring = app.keyring();
+    
+    let mut credentials = match keyring.get_password("project_boxes", "slack_credentials") {
+        Ok(Some(credentials_json)) => serde_json::from_str::<SlackCredentials>(&credentials_json)
+            .map_err(|e| CredentialError::Serialization(e.to_string()))?,
+        Ok(None) => return Err(CredentialError::NotConfigured.to_string()),
+        Err(e) => return Err(CredentialError::Keychain(e.to_string()).to_string()),
+    };
+    
+    credentials.apply_refresh(refreshed);
+    
+    let credentials_json = serde_json::to_string(&credentials)
+        .map_err(|e| CredentialError::Serialization(e.to_string()))?;
+    keyring.set_password("project_boxes", "slack_credentials", &credentials_json)
+        .map_err(|e| CredentialError::KeychainWrite(e.to_string()))?;
+    
+    println!("✅ [REFRESH] Slack tokens updated{}", if refreshed.refresh_token.is_some() { ", refresh token rotated" } else { "" });
+    Ok(())
+}
+
 // Debug command to check credential status
 pub async fn debug_slack_credentials_status(app: AppHandle) -> Result<serde_json::Value, String> {
     println!("🔍 [DEBUG] Starting comprehensive credential status check...");
@@ -365,7 +655,7 @@ pub async fn debug_slack_credentials_status(app: AppHandle) -> Result<serde_json
 
 
 // Status enum for Slack credentials
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub enum SlackCredentialsStatus {
     Configured,
     PartiallyConfigured,
@@ -1,7 +1,41 @@
 use tauri::AppHandle;
 use chrono::Utc;
+use rusqlite::{params, Connection};
 use serde_json;
 use uuid::Uuid;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub content: String,
+    #[serde(rename = "type")]
+    pub doc_type: String,
+    pub author: String,
+    pub version: String,
+    pub tags: Vec<String>,
+    pub is_public: bool,
+    pub metadata: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A snapshot of a document's content taken before it was overwritten
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentVersion {
+    pub id: String,
+    pub document_id: String,
+    pub version_number: i64,
+    pub title: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+/// Versions kept per document when the retention setting is missing or invalid
+pub const DEFAULT_DOCUMENT_VERSION_RETENTION: usize = 50;
 
 // Validation helper functions
 pub fn validate_project_id(project_id: &str) -> Result<(), String> {
@@ -11,6 +45,13 @@ pub fn validate_project_id(project_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub fn validate_document_id(document_id: &str) -> Result<(), String> {
+    if document_id.trim().is_empty() {
+        return Err("Document ID cannot be empty".to_string());
+    }
+    Ok(())
+}
+
 pub fn validate_document_type(doc_type: &str) -> Result<(), String> {
     let allowed_types = ["ai_kickoff", "meeting_notes", "requirements", "design", "technical", "user_guide", "general"];
     if !allowed_types.contains(&doc_type) {
@@ -20,7 +61,7 @@ pub fn validate_document_type(doc_type: &str) -> Result<(), String> {
 }
 
 pub async fn create_document(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
     document_data: serde_json::Value,
 ) -> Result<serde_json::Value, String> {
@@ -75,7 +116,22 @@ pub async fn create_document(
         .cloned()
         .unwrap_or(serde_json::json!({}));
 
-    // This would normally insert into the documents table
+    let document = Document {
+        id: document_id.clone(),
+        project_id: project_id.clone(),
+        title: title.to_string(),
+        content: content.to_string(),
+        doc_type: doc_type.to_string(),
+        author: author.to_string(),
+        version: version.to_string(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        is_public,
+        metadata: metadata.clone(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+    };
+    with_documents_db(&app, move |conn| insert_document(conn, &document)).await?;
+
     let created_document = serde_json::json!({
         "id": document_id,
         "project_id": project_id,
@@ -96,4 +152,520 @@ pub async fn create_document(
     
     println!("✅ [create_document] Document '{}' created with ID: {}", title, document_id);
     Ok(created_document)
-}
\ No newline at end of file
+}
+
+/// List documents, newest first, optionally restricted to one project
+pub async fn get_documents(
+    app: AppHandle,
+    project_id: Option<String>,
+) -> Result<Vec<Document>, String> {
+    println!("📄 [get_documents] Fetching documents for project: {:?}", project_id);
+    
+    if let Some(project_id) = &project_id {
+        validate_project_id(project_id)?;
+    }
+    
+    with_documents_db(&app, move |conn| load_documents(conn, project_id.as_deref())).await
+}
+
+pub async fn get_document(
+    app: AppHandle,
+    document_id: String,
+) -> Result<Document, String> {
+    println!("📄 [get_document] Fetching document: {}", document_id);
+    
+    validate_document_id(&document_id)?;
+    
+    let id = document_id.clone();
+    with_documents_db(&app, move |conn| load_document(conn, &id))
+        .await?
+        .ok_or_else(|| format!("Document not found: {}", document_id))
+}
+
+/// Replace a document's content and bump its `updated_at`
+pub async fn update_document(
+    app: AppHandle,
+    document_id: String,
+    content: String,
+) -> Result<Document, String> {
+    println!("📝 [update_document] Updating document: {}", document_id);
+    
+    validate_document_id(&document_id)?;
+    
+    if content.trim().is_empty() {
+        return Err("Document content cannot be empty".to_string());
+    }
+    
+    let retention = document_version_retention(&app).await;
+    let id = document_id.clone();
+    let document = with_documents_db(&app, move |conn| {
+        replace_document_content(conn, &id, &content, retention, &Utc::now().to_rfc3339())
+    }).await?;
+    
+    println!("✅ [update_document] Document {} updated", document_id);
+    Ok(document)
+}
+
+/// Version history for a document, newest first
+pub async fn get_document_versions(
+    app: AppHandle,
+    document_id: String,
+) -> Result<Vec<DocumentVersion>, String> {
+    println!("📜 [get_document_versions] Fetching versions for document: {}", document_id);
+    
+    validate_document_id(&document_id)?;
+    
+    with_documents_db(&app, move |conn| list_document_versions(conn, &document_id)).await
+}
+
+/// Put a previous version's content back. The current content is snapshotted first, so a
+/// restore can itself be undone.
+pub async fn restore_document_version(
+    app: AppHandle,
+    document_id: String,
+    version_id: String,
+) -> Result<Document, String> {
+    println!("⏪ [restore_document_version] Restoring document {} to version {}", document_id, version_id);
+    
+    validate_document_id(&document_id)?;
+    
+    let retention = document_version_retention(&app).await;
+    let id = document_id.clone();
+    let (version_number, document) = with_documents_db(&app, move |conn| {
+        let version = find_document_version(conn, &id, &version_id)?;
+        let document = replace_document_content(conn, &id, &version.content, retention, &Utc::now().to_rfc3339())?;
+        Ok((version.version_number, document))
+    }).await?;
+    
+    println!("✅ [restore_document_version] Document {} restored to version {}", document_id, version_number);
+    Ok(document)
+}
+
+pub async fn delete_document(
+    app: AppHandle,
+    document_id: String,
+) -> Result<String, String> {
+    println!("🗑️ [delete_document] Deleting document: {}", document_id);
+    
+    validate_document_id(&document_id)?;
+    
+    let id = document_id.clone();
+    with_documents_db(&app, move |conn| {
+        remove_document(conn, &id)?;
+        match delete_document_versions(conn, &id) {
+            Ok(removed) => println!("🗑️ [delete_document] Removed {} versions", removed),
+            Err(e) => println!("⚠️ [delete_document] Failed to remove versions: {}", e),
+        }
+        Ok(())
+    }).await?;
+    
+    println!("✅ [delete_document] Document {} deleted", document_id);
+    Ok(format!("Document {} deleted", document_id))
+}
+
+/// Retention cap from the `documents.version_retention` setting
+async fn document_version_retention(app: &AppHandle) -> usize {
+    match crate::commands::settings::get_setting(
+        app.clone(),
+        crate::commands::settings::DOCUMENT_VERSION_RETENTION.to_string(),
+    ).await {
+        Ok(configured) => retention_or_default(configured.as_ref()),
+        Err(e) => {
+            println!("⚠️ Could not read document version retention: {}", e);
+            DEFAULT_DOCUMENT_VERSION_RETENTION
+        }
+    }
+}
+
+pub fn retention_or_default(configured: Option<&serde_json::Value>) -> usize {
+    crate::commands::settings::resolve_setting::<usize>(
+        crate::commands::settings::DOCUMENT_VERSION_RETENTION,
+        configured.cloned(),
+    )
+    .ok()
+    .filter(|keep| *keep > 0)
+    .unwrap_or(DEFAULT_DOCUMENT_VERSION_RETENTION)
+}
+
+/// Run `f` against the app database once the document and version tables exist
+async fn with_documents_db<T, F>(app: &AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> Result<T, String> + Send + 'static,
+{
+    crate::app_db::with_app_db(app, move |conn| {
+        ensure_documents_table(conn)?;
+        ensure_document_versions_table(conn)?;
+        f(conn)
+    }).await
+}
+
+/// Document fields the frontend's `documents` table has no column for, added on first use
+const OPTIONAL_DOCUMENT_COLUMNS: &[(&str, &str)] = &[
+    ("author", "TEXT"),
+    ("version", "TEXT"),
+    ("isPublic", "INTEGER DEFAULT 0"),
+    ("metadata", "TEXT"),
+];
+
+/// The frontend creates `documents`; create it here too (same definition) in case the
+/// backend writes first, and add the columns only the backend uses
+pub fn ensure_documents_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS documents (
+            id TEXT PRIMARY KEY,
+            projectId TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            type TEXT NOT NULL,
+            content TEXT,
+            url TEXT,
+            tags TEXT,
+            createdAt TEXT NOT NULL,
+            updatedAt TEXT NOT NULL,
+            syncStatus TEXT DEFAULT 'local' CHECK (syncStatus IN ('local', 'synced', 'conflict')),
+            lastSyncAt TEXT,
+            teamId TEXT,
+            FOREIGN KEY (projectId) REFERENCES projects (id) ON DELETE CASCADE
+        );",
+    )
+    .map_err(|e| format!("Failed to create documents table: {}", e))?;
+    
+    for (column, sql_type) in OPTIONAL_DOCUMENT_COLUMNS {
+        if !crate::slack_sync::has_column(conn, "documents", column)? {
+            conn.execute(&format!("ALTER TABLE documents ADD COLUMN {} {}", column, sql_type), [])
+                .map_err(|e| format!("Failed to add column {}: {}", column, e))?;
+        }
+    }
+    Ok(())
+}
+
+const DOCUMENT_COLUMNS: &str = "id, projectId, title, content, type, author, version, tags, isPublic, metadata, createdAt, updatedAt";
+
+fn row_to_document(row: &rusqlite::Row) -> rusqlite::Result<Document> {
+    let json_column = |index: usize| -> rusqlite::Result<Option<serde_json::Value>> {
+        Ok(row.get::<_, Option<String>>(index)?.and_then(|text| serde_json::from_str(&text).ok()))
+    };
+    Ok(Document {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+        doc_type: row.get(4)?,
+        author: row.get::<_, Option<String>>(5)?.unwrap_or_else(|| "system".to_string()),
+        version: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "1.0".to_string()),
+        tags: json_column(7)?.and_then(|tags| serde_json::from_value(tags).ok()).unwrap_or_default(),
+        is_public: row.get::<_, Option<i64>>(8)?.unwrap_or(0) != 0,
+        metadata: json_column(9)?.unwrap_or_else(|| serde_json::json!({})),
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+pub fn insert_document(conn: &Connection, document: &Document) -> Result<(), String> {
+    conn.execute(
+        &format!("INSERT INTO documents ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)", DOCUMENT_COLUMNS),
+        params![
+            document.id,
+            document.project_id,
+            document.title,
+            document.content,
+            document.doc_type,
+            document.author,
+            document.version,
+            serde_json::json!(document.tags).to_string(),
+            document.is_public,
+            document.metadata.to_string(),
+            document.created_at,
+            document.updated_at,
+        ],
+    )
+    .map_err(|e| format!("Failed to store document: {}", e))?;
+    Ok(())
+}
+
+/// Documents newest first, optionally only one project's
+pub fn load_documents(conn: &Connection, project_id: Option<&str>) -> Result<Vec<Document>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM documents WHERE ?1 IS NULL OR projectId = ?1 ORDER BY updatedAt DESC",
+            DOCUMENT_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to read documents: {}", e))?;
+    
+    let rows = stmt
+        .query_map(params![project_id], row_to_document)
+        .map_err(|e| format!("Failed to read documents: {}", e))?;
+    
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read documents: {}", e))
+}
+
+pub fn load_document(conn: &Connection, document_id: &str) -> Result<Option<Document>, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM documents WHERE id = ?1", DOCUMENT_COLUMNS),
+        params![document_id],
+        row_to_document,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to read document: {}", e))
+}
+
+/// Replace a stored document's content and return the updated document
+pub fn save_document_content(conn: &Connection, document_id: &str, content: &str, now: &str) -> Result<Document, String> {
+    let changed = conn
+        .execute(
+            "UPDATE documents SET content = ?1, updatedAt = ?2 WHERE id = ?3",
+            params![content, now, document_id],
+        )
+        .map_err(|e| format!("Failed to update document: {}", e))?;
+    if changed == 0 {
+        return Err(format!("Document not found: {}", document_id));
+    }
+    load_document(conn, document_id)?
+        .ok_or_else(|| format!("Document not found: {}", document_id))
+}
+
+/// Snapshot the stored content as a version and write `content` over it, in one transaction
+pub fn replace_document_content(
+    conn: &Connection,
+    document_id: &str,
+    content: &str,
+    retention: usize,
+    now: &str,
+) -> Result<Document, String> {
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let previous = load_document(&tx, document_id)?
+        .ok_or_else(|| format!("Document not found: {}", document_id))?;
+    snapshot_document_version(&tx, &previous, retention)?;
+    let document = save_document_content(&tx, document_id, content, now)?;
+    tx.commit().map_err(|e| format!("Failed to save document: {}", e))?;
+    Ok(document)
+}
+
+fn remove_document(conn: &Connection, document_id: &str) -> Result<(), String> {
+    let changed = conn
+        .execute("DELETE FROM documents WHERE id = ?1", params![document_id])
+        .map_err(|e| format!("Failed to delete document: {}", e))?;
+    if changed == 0 {
+        return Err(format!("Document not found: {}", document_id));
+    }
+    Ok(())
+}
+
+pub fn ensure_document_versions_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS document_versions (
+            id TEXT PRIMARY KEY,
+            documentId TEXT NOT NULL,
+            versionNumber INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            content TEXT NOT NULL,
+            createdAt TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_document_versions_document
+            ON document_versions (documentId, versionNumber);",
+    )
+    .map_err(|e| format!("Failed to create document_versions table: {}", e))
+}
+
+/// Store the document's current content as a new version, then prune to `retention`
+pub fn snapshot_document_version(
+    conn: &Connection,
+    document: &Document,
+    retention: usize,
+) -> Result<DocumentVersion, String> {
+    let next_number: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(versionNumber), 0) + 1 FROM document_versions WHERE documentId = ?1",
+            params![document.id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to read document versions: {}", e))?;
+    
+    let version = DocumentVersion {
+        id: Uuid::new_v4().to_string(),
+        document_id: document.id.clone(),
+        version_number: next_number,
+        title: document.title.clone(),
+        content: document.content.clone(),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    
+    conn.execute(
+        "INSERT INTO document_versions (id, documentId, versionNumber, title, content, createdAt)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![version.id, version.document_id, version.version_number, version.title, version.content, version.created_at],
+    )
+    .map_err(|e| format!("Failed to store document version: {}", e))?;
+    
+    conn.execute(
+        "DELETE FROM document_versions WHERE documentId = ?1 AND id NOT IN (
+            SELECT id FROM document_versions WHERE documentId = ?1
+            ORDER BY versionNumber DESC LIMIT ?2
+        )",
+        params![document.id, retention as i64],
+    )
+    .map_err(|e| format!("Failed to prune document versions: {}", e))?;
+    
+    Ok(version)
+}
+
+pub fn list_document_versions(conn: &Connection, document_id: &str) -> Result<Vec<DocumentVersion>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, documentId, versionNumber, title, content, createdAt FROM document_versions
+             WHERE documentId = ?1 ORDER BY versionNumber DESC",
+        )
+        .map_err(|e| format!("Failed to read document versions: {}", e))?;
+    
+    let rows = stmt
+        .query_map(params![document_id], row_to_version)
+        .map_err(|e| format!("Failed to read document versions: {}", e))?;
+    
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read document versions: {}", e))
+}
+
+pub fn find_document_version(
+    conn: &Connection,
+    document_id: &str,
+    version_id: &str,
+) -> Result<DocumentVersion, String> {
+    conn.query_row(
+        "SELECT id, documentId, versionNumber, title, content, createdAt FROM document_versions
+         WHERE documentId = ?1 AND id = ?2",
+        params![document_id, version_id],
+        row_to_version,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            format!("Version {} not found for document {}", version_id, document_id)
+        }
+        other => format!("Failed to read document version: {}", other),
+    })
+}
+
+fn delete_document_versions(conn: &Connection, document_id: &str) -> Result<usize, String> {
+    conn.execute("DELETE FROM document_versions WHERE documentId = ?1", params![document_id])
+        .map_err(|e| format!("Failed to delete document versions: {}", e))
+}
+
+fn row_to_version(row: &rusqlite::Row) -> rusqlite::Result<DocumentVersion> {
+    Ok(DocumentVersion {
+        id: row.get(0)?,
+        document_id: row.get(1)?,
+        version_number: row.get(2)?,
+        title: row.get(3)?,
+        content: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(content: &str) -> Document {
+        Document {
+            id: "doc-1".to_string(),
+            project_id: "proj-1".to_string(),
+            title: "Weekly sync".to_string(),
+            content: content.to_string(),
+            doc_type: "meeting_notes".to_string(),
+            author: "system".to_string(),
+            version: "1.0".to_string(),
+            tags: vec![],
+            is_public: false,
+            metadata: serde_json::json!({}),
+            created_at: Utc::now().to_rfc3339(),
+            updated_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn documents_are_stored_and_updated_in_the_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE projects (id TEXT PRIMARY KEY); INSERT INTO projects VALUES ('proj-1');").unwrap();
+        ensure_documents_table(&conn).unwrap();
+        // Running it again, as every command does, leaves the table as it is
+        ensure_documents_table(&conn).unwrap();
+
+        let mut stored = document("first");
+        stored.tags = vec!["weekly".to_string()];
+        stored.metadata = serde_json::json!({ "source": "slack" });
+        insert_document(&conn, &stored).unwrap();
+
+        let loaded = load_document(&conn, "doc-1").unwrap().unwrap();
+        assert_eq!(loaded.tags, vec!["weekly"]);
+        assert_eq!(loaded.metadata["source"], "slack");
+        assert_eq!(load_documents(&conn, Some("proj-1")).unwrap().len(), 1);
+        assert!(load_documents(&conn, Some("proj-2")).unwrap().is_empty());
+
+        let updated = save_document_content(&conn, "doc-1", "second", "2030-01-01T00:00:00Z").unwrap();
+        assert_eq!(updated.content, "second");
+        assert_eq!(updated.updated_at, "2030-01-01T00:00:00Z");
+
+        remove_document(&conn, "doc-1").unwrap();
+        assert!(load_document(&conn, "doc-1").unwrap().is_none());
+        assert!(save_document_content(&conn, "doc-1", "third", "2030-01-02T00:00:00Z").is_err());
+    }
+
+    #[test]
+    fn versions_can_be_restored_after_reopening_the_database() {
+        let db_path = std::env::temp_dir().join(format!("documents-{}.db", Uuid::new_v4()));
+        let open = || {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch("CREATE TABLE IF NOT EXISTS projects (id TEXT PRIMARY KEY); INSERT OR IGNORE INTO projects VALUES ('proj-1');").unwrap();
+            ensure_documents_table(&conn).unwrap();
+            ensure_document_versions_table(&conn).unwrap();
+            conn
+        };
+        
+        {
+            let conn = open();
+            insert_document(&conn, &document("draft")).unwrap();
+            replace_document_content(&conn, "doc-1", "final", 10, "2030-01-01T00:00:00Z").unwrap();
+        }
+        
+        // A new connection stands in for an app restart
+        let conn = open();
+        let draft = list_document_versions(&conn, "doc-1").unwrap().remove(0);
+        assert_eq!(draft.content, "draft");
+        let restored = replace_document_content(&conn, "doc-1", &draft.content, 10, "2030-01-02T00:00:00Z").unwrap();
+        assert_eq!(restored.content, "draft");
+        assert_eq!(list_document_versions(&conn, "doc-1").unwrap()[0].content, "final");
+        
+        assert!(replace_document_content(&conn, "doc-2", "x", 10, "2030-01-03T00:00:00Z").is_err());
+        drop(conn);
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[test]
+    fn snapshots_are_numbered_and_pruned_to_retention() {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_document_versions_table(&conn).unwrap();
+        
+        for content in ["first", "second", "third"] {
+            snapshot_document_version(&conn, &document(content), 2).unwrap();
+        }
+        
+        let versions = list_document_versions(&conn, "doc-1").unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version_number, 3);
+        assert_eq!(versions[0].content, "third");
+        assert_eq!(versions[1].content, "second");
+        
+        let found = find_document_version(&conn, "doc-1", &versions[1].id).unwrap();
+        assert_eq!(found.content, "second");
+        assert!(find_document_version(&conn, "doc-2", &versions[1].id).is_err());
+    }
+
+    #[test]
+    fn retention_falls_back_to_default() {
+        assert_eq!(retention_or_default(Some(&serde_json::json!(10))), 10);
+        assert_eq!(retention_or_default(Some(&serde_json::json!(0))), DEFAULT_DOCUMENT_VERSION_RETENTION);
+        assert_eq!(retention_or_default(None), DEFAULT_DOCUMENT_VERSION_RETENTION);
+    }
+}
@@ -0,0 +1,99 @@
+use once_cell::sync::Lazy;
+use reqwest::{Client, RequestBuilder, Response};
+use std::time::Duration;
+
+/// Default request timeout. Service clients override it per request through their `TimeoutPolicy`.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Idle keep-alive connections kept per host; the app talks to a handful of hosts at most
+const MAX_IDLE_PER_HOST: usize = 8;
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+fn builder() -> reqwest::ClientBuilder {
+    Client::builder()
+        .timeout(DEFAULT_REQUEST_TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_max_idle_per_host(MAX_IDLE_PER_HOST)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .user_agent("ProjectBoxes/1.0")
+}
+
+// Failing to build means the TLS backend couldn't initialize; no request could succeed anyway
+static SHARED_CLIENT: Lazy<Client> = Lazy::new(|| {
+    builder().build().expect("Failed to create shared HTTP client")
+});
+
+// The OAuth service runs locally over HTTPS with a self-signed certificate
+static LOCAL_TLS_CLIENT: Lazy<Client> = Lazy::new(|| {
+    builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .expect("Failed to create local TLS HTTP client")
+});
+
+/// Connection-pooled client shared by the Slack and service clients. Cloning is cheap and
+/// reuses the same pool.
+pub fn shared_client() -> Client {
+    SHARED_CLIENT.clone()
+}
+
+/// Shared client that accepts self-signed certificates, for services on localhost only
+pub fn local_tls_client() -> Client {
+    LOCAL_TLS_CLIENT.clone()
+}
+
+/// Header that correlates one logical request across the app and the Node services
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Send `request` tagged with a fresh `X-Request-Id`, logging the id on send and on failure.
+/// The id is returned so callers can attach it to the errors they report.
+pub async fn send_traced(service: &str, request: RequestBuilder) -> (String, reqwest::Result<Response>) {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    log::debug!("➡️ [{}] Sending request {}", service, request_id);
+
+    let result = request.header(REQUEST_ID_HEADER, &request_id).send().await;
+    match &result {
+        Ok(response) if !response.status().is_success() => {
+            log::warn!("⚠️ [{}] Request {} returned {}", service, request_id, response.status());
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("❌ [{}] Request {} failed: {}", service, request_id, e),
+    }
+    (request_id, result)
+}
+
+/// A service client error tagged with the `X-Request-Id` of the request that failed
+#[derive(Debug, thiserror::Error)]
+#[error("{source} (request id: {request_id})")]
+pub struct Traced<E: std::error::Error + 'static> {
+    pub request_id: String,
+    pub source: Box<E>,
+}
+
+/// Service client errors whose `Traced` variant carries the id of the failed request
+pub trait RequestTraced: Sized + std::error::Error + From<Traced<Self>> + 'static {
+    /// The request id wrapper, when this is the `Traced` variant
+    fn traced(&self) -> Option<&Traced<Self>>;
+
+    /// Attach the `X-Request-Id` of the request that failed. An error that already carries
+    /// an id keeps it, since that is the request that actually failed.
+    fn with_request_id(self, request_id: &str) -> Self {
+        if self.traced().is_some() {
+            return self;
+        }
+        Traced { request_id: request_id.to_string(), source: Box::new(self) }.into()
+    }
+
+    /// Correlation id of the failed request, when known
+    fn request_id(&self) -> Option<&str> {
+        self.traced().map(|traced| traced.request_id.as_str())
+    }
+
+    /// The error without its request id
+    fn untraced(&self) -> &Self {
+        match self.traced() {
+            Some(traced) => traced.source.untraced(),
+            None => self,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod slack;
 pub mod credentials;
+pub mod locale;
 pub mod slack_api;
 pub mod slack_sync;
 pub mod calendar_commands;
@@ -9,11 +10,21 @@ pub mod whatsapp;
 pub mod whatsapp_service_client;
 pub mod whatsapp_commands;
 pub mod whatsapp_process_manager;
+pub mod log_sink;
 pub mod ai_service_client;
 pub mod oauth_service_client;
 pub mod queue_service_client;
 pub mod slack_service_client;
 pub mod socket_service_client;
+pub mod service_timeouts;
+pub mod http_client;
+pub mod search;
+pub mod app_db;
+pub mod local_http;
+pub mod slack_events;
+pub mod slack_message_store;
+pub mod service_supervisor;
+pub mod bindings;
 pub mod commands;
 
 #[cfg(test)]
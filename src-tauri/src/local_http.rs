@@ -0,0 +1,159 @@
+// Just enough HTTP/1.1 for the app's local receivers: the OAuth loopback callback and the
+// Slack Events API endpoint. Requests may arrive split across any number of TCP reads.
+
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use url::form_urlencoded;
+
+/// Request line plus headers beyond this are refused
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// A client that hasn't sent its whole request by then is dropped
+pub const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A request as read off the socket
+#[derive(Debug, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Raw query string, empty without one
+    pub query: String,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Decoded query string parameters
+    pub fn query_params(&self) -> HashMap<String, String> {
+        form_urlencoded::parse(self.query.as_bytes()).into_owned().collect()
+    }
+}
+
+/// Read one request: everything up to the blank line after the headers, then a
+/// `Content-Length` body. `None` when the client disconnects early or sends more than the
+/// limits allow.
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S, max_body_bytes: usize) -> Option<HttpRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return None;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = HttpRequest {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        ..Default::default()
+    };
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            request.headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length: usize = request.headers.get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    if content_length > max_body_bytes {
+        return None;
+    }
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    request.body = body;
+    Some(request)
+}
+
+/// Write a complete response and ask the client to close the connection
+pub async fn write_response<S: AsyncWrite + Unpin>(stream: &mut S, status: u16, content_type: &str, body: &str) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        _ => "Not Found",
+    };
+    let raw = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(raw.as_bytes()).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `raw` to `read_request` a few bytes at a time, as a slow client would
+    async fn read_split(raw: &'static [u8], max_body_bytes: usize) -> Option<HttpRequest> {
+        let (mut client, mut server) = tokio::io::duplex(7);
+        tokio::spawn(async move {
+            for piece in raw.chunks(5) {
+                if client.write_all(piece).await.is_err() {
+                    return;
+                }
+            }
+        });
+        read_request(&mut server, max_body_bytes).await
+    }
+
+    #[tokio::test]
+    async fn requests_split_across_reads_are_reassembled() {
+        let request = read_split(
+            b"GET /oauth/slack/callback?code=abc%20123&state=xyz HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n",
+            0,
+        ).await.unwrap();
+
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/oauth/slack/callback");
+        assert_eq!(request.headers.get("host").map(String::as_str), Some("127.0.0.1"));
+        let params = request.query_params();
+        assert_eq!(params.get("code").map(String::as_str), Some("abc 123"));
+        assert_eq!(params.get("state").map(String::as_str), Some("xyz"));
+    }
+
+    #[tokio::test]
+    async fn bodies_are_read_up_to_the_content_length() {
+        let request = read_split(
+            b"POST /slack/events HTTP/1.1\r\nContent-Length: 17\r\nContent-Type: application/json\r\n\r\n{\"type\":\"event\"}\n",
+            1024,
+        ).await.unwrap();
+
+        assert_eq!(request.body, b"{\"type\":\"event\"}\n");
+        assert!(request.query.is_empty());
+    }
+
+    #[tokio::test]
+    async fn oversized_and_truncated_requests_are_refused() {
+        assert!(read_split(b"POST /slack/events HTTP/1.1\r\nContent-Length: 2048\r\n\r\n{}", 1024).await.is_none());
+        assert!(read_split(b"POST /slack/events HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}", 1024).await.is_none());
+        assert!(read_split(b"GET / HTTP/1.1\r\nHost: 127.0.0.1\r\n", 0).await.is_none());
+    }
+}
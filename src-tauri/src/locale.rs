@@ -0,0 +1,44 @@
+use once_cell::sync::Lazy;
+
+/// Language used for user-facing error messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Portuguese,
+}
+
+// Apps launched from Finder or the Start menu have no LANG, so ask the OS for its language
+static SYSTEM_LOCALE: Lazy<Locale> = Lazy::new(|| {
+    sys_locale::get_locale()
+        .map(|tag| Locale::from_tag(&tag))
+        .unwrap_or(Locale::English)
+});
+
+impl Locale {
+    /// Locale of the running system, read once from the OS language settings
+    pub fn system() -> Self {
+        *SYSTEM_LOCALE
+    }
+
+    /// Parse a language tag such as `pt_BR.UTF-8` or `en-US`. Anything that isn't Portuguese is English.
+    pub fn from_tag(tag: &str) -> Self {
+        if tag.to_lowercase().starts_with("pt") {
+            Locale::Portuguese
+        } else {
+            Locale::English
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_tags_are_parsed() {
+        assert_eq!(Locale::from_tag("pt_BR.UTF-8"), Locale::Portuguese);
+        assert_eq!(Locale::from_tag("pt-PT"), Locale::Portuguese);
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Locale::English);
+        assert_eq!(Locale::from_tag("C"), Locale::English);
+    }
+}
@@ -0,0 +1,174 @@
+// Process-wide logger: forwards to env_logger and keeps the most recent lines in memory
+// so support can pull them from a user's machine with `get_recent_logs`.
+//
+// Logging is off unless BOXES_DEBUG_LOG is set or the `logging.debug_enabled` setting is on.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::commands::settings::{get_setting_as, SettingChanged, LOGGING_DEBUG_ENABLED, SETTING_CHANGED_EVENT};
+
+/// Environment variable that turns logging on regardless of the setting
+pub const DEBUG_LOG_ENV_VAR: &str = "BOXES_DEBUG_LOG";
+/// Log lines kept in memory; older lines are dropped first
+pub const RECENT_LOG_CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct LogLine {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+static RECENT_LOGS: Lazy<Mutex<VecDeque<LogLine>>> = Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_LOG_CAPACITY)));
+
+fn push_line(buffer: &mut VecDeque<LogLine>, line: LogLine, capacity: usize) {
+    while buffer.len() >= capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back(line);
+}
+
+/// The newest `limit` lines, oldest first
+fn tail(buffer: &VecDeque<LogLine>, limit: usize) -> Vec<LogLine> {
+    buffer.iter().skip(buffer.len().saturating_sub(limit)).cloned().collect()
+}
+
+/// Debug lines are only kept for this crate; dependencies (hyper, reqwest, tauri) log at
+/// Debug far too much and would push everything else out of the buffer
+fn is_captured(target: &str, level: Level) -> bool {
+    const CRATE_NAME: &str = env!("CARGO_CRATE_NAME");
+
+    level <= Level::Info
+        || target == CRATE_NAME
+        || target.strip_prefix(CRATE_NAME).is_some_and(|rest| rest.starts_with("::"))
+}
+
+struct SinkLogger {
+    console: env_logger::Logger,
+}
+
+impl Log for SinkLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && is_captured(metadata.target(), metadata.level())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = LogLine {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        push_line(&mut RECENT_LOGS.lock().unwrap_or_else(|e| e.into_inner()), line, RECENT_LOG_CAPACITY);
+
+        if self.console.enabled(record.metadata()) {
+            self.console.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+    }
+}
+
+fn env_enabled() -> bool {
+    std::env::var(DEBUG_LOG_ENV_VAR).map(|v| !v.is_empty() && v != "0").unwrap_or(false)
+}
+
+/// Install the logger. Console output still follows RUST_LOG (default "info").
+pub fn init() {
+    let console = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).build();
+    if log::set_boxed_logger(Box::new(SinkLogger { console })).is_err() {
+        println!("⚠️ A logger was already installed, recent logs will not be captured");
+        return;
+    }
+    set_enabled(env_enabled());
+}
+
+/// Turn logging on or off; the environment variable keeps it on
+pub fn set_enabled(enabled: bool) {
+    let enabled = enabled || env_enabled();
+    log::set_max_level(if enabled { LevelFilter::Debug } else { LevelFilter::Off });
+}
+
+/// Apply the `logging.debug_enabled` setting now and whenever it changes
+pub fn watch_debug_log_setting(app: &AppHandle) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match get_setting_as::<bool>(&handle, LOGGING_DEBUG_ENABLED).await {
+            Ok(enabled) => set_enabled(enabled),
+            Err(e) => println!("⚠️ Could not read {}: {}", LOGGING_DEBUG_ENABLED, e),
+        }
+    });
+
+    app.listen(SETTING_CHANGED_EVENT, |event| {
+        let change: SettingChanged = match serde_json::from_str(event.payload()) {
+            Ok(change) => change,
+            Err(_) => return,
+        };
+        if change.key == LOGGING_DEBUG_ENABLED {
+            let enabled = matches!(change.value, Value::Bool(true));
+            println!("📝 Debug logging {}", if enabled { "enabled" } else { "disabled" });
+            set_enabled(enabled);
+        }
+    });
+}
+
+/// The newest `limit` captured log lines, oldest first
+pub fn recent_logs(limit: usize) -> Vec<LogLine> {
+    let buffer = RECENT_LOGS.lock().unwrap_or_else(|e| e.into_inner());
+    tail(&buffer, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(message: &str) -> LogLine {
+        LogLine {
+            timestamp: String::new(),
+            level: "INFO".to_string(),
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn debug_lines_are_kept_for_this_crate_only() {
+        let own_module = module_path!();
+
+        assert!(is_captured(own_module, Level::Debug));
+        assert!(is_captured(env!("CARGO_CRATE_NAME"), Level::Debug));
+        assert!(!is_captured("hyper::proto::h1", Level::Debug));
+        assert!(!is_captured(&format!("{}_other::module", env!("CARGO_CRATE_NAME")), Level::Debug));
+        assert!(is_captured("hyper::proto::h1", Level::Warn));
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest_and_tails_newest() {
+        let mut buffer = VecDeque::new();
+        for i in 0..5 {
+            push_line(&mut buffer, line(&i.to_string()), 3);
+        }
+
+        let messages: Vec<String> = tail(&buffer, 10).into_iter().map(|l| l.message).collect();
+        assert_eq!(messages, vec!["2", "3", "4"]);
+
+        let messages: Vec<String> = tail(&buffer, 2).into_iter().map(|l| l.message).collect();
+        assert_eq!(messages, vec!["3", "4"]);
+    }
+}
@@ -31,6 +31,17 @@ mod ai_service_client;
 mod oauth_service_client;
 mod queue_service_client;
 mod slack_service_client;
+mod service_timeouts;
+mod http_client;
+mod app_db;
+mod search;
+mod locale;
+mod service_supervisor;
+mod local_http;
+mod slack_events;
+mod slack_message_store;
+mod log_sink;
+mod bindings;
 
 // Modular command structure
 mod commands;
@@ -41,52 +52,68 @@ mod tests;
 // Import command modules
 use commands::{
     ai_automation::{
-        analyze_behavioral_patterns, analyze_text_for_insights, analyze_with_ai,
+        analyze_behavioral_patterns, analyze_messages_in_chunks, analyze_text_for_insights, analyze_with_ai,
         ai_automation_health_check, apply_project_update_suggestion,
-        bulk_process_task_suggestions, capture_behavioral_feedback_advanced,
+        bulk_process_task_suggestions, bulk_reject_ai_items, capture_behavioral_feedback_advanced,
         capture_task_modification_feedback, create_task_from_ai_suggestion,
         extract_actionable_items, extract_high_confidence_items, get_pending_ai_items,
         improve_prompts_from_feedback, improve_prompts_with_analysis, init_ai_automation,
         initialize_advanced_prompt_improvement, initialize_prompt_improvement_service,
-        process_slack_messages_with_ai,
-        reject_project_update_suggestion, store_project_insight, store_task_update_detection,
+        process_slack_messages_with_ai, reanalyze_failed_chunks,
+        reject_project_update_suggestion, set_ai_credentials, store_project_insight,
+        store_task_update_detection, test_ai_credentials,
     },
     background_sync_commands::{
-        cancel_sync_job, get_active_sync_jobs, get_sync_job_status, queue_background_sync,
+        cancel_sync_job, get_active_sync_jobs, get_failed_sync_jobs, get_sync_job_status,
+        queue_background_sync,
     },
     calendar_commands::{
-        create_calendar_event, delete_event, get_event_by_id, get_events_in_range,
-        store_event_detection, update_event,
+        create_calendar_event, create_events_from_detections, delete_event, get_event_by_id, get_events_in_range,
+        store_event_detection, update_event, validate_rrule,
+    },
+    debug_commands::{open_devtools, get_recent_logs},
+    document_commands::{
+        create_document, delete_document, get_document, get_document_versions, get_documents,
+        restore_document_version, update_document,
     },
-    debug_commands::{open_devtools},
-    document_commands::create_document,
     oauth_servers::{
-        cleanup_oauth_tokens, https_oauth_server_status, start_https_oauth_server,
+        cleanup_oauth_tokens, https_oauth_server_status, start_https_oauth_server, start_oauth,
         stop_https_oauth_server, OAuthServiceClientState,
     },
-    project_commands::{create_project, get_all_projects, get_project, update_project_field},
+    project_commands::{
+        archive_project, create_project, delete_project, get_all_projects, get_project,
+        update_project, update_project_field,
+    },
     prompt_commands::{
-        get_all_prompts, get_effective_prompt, get_prompt_by_key, initialize_default_prompts,
-        record_prompt_usage, update_prompt,
+        get_all_prompts, get_effective_prompt, get_experiment_results, get_prompt_by_key, get_prompt_usage_stats, initialize_default_prompts,
+        record_prompt_usage, set_prompt_experiment, update_prompt,
     },
+    search_commands::search,
     settings::{get_setting, store_setting},
     slack_commands::{
-        debug_slack_credentials_status, delete_slack_credentials, force_slack_reconnection,
+        debug_slack_credentials_status, delete_slack_credentials, force_slack_reconnection, store_slack_signing_secret,
         get_slack_credentials, get_slack_team_info, get_slack_user_info, slack_analyze_messages,
-        slack_build_oauth_url, slack_estimate_sync_time, slack_fetch_messages,
+        slack_build_oauth_url, slack_check_clock_skew, slack_estimate_sync_time,
+        slack_get_reactions_summary, slack_fetch_messages, slack_fetch_messages_typed, slack_fetch_messages_streaming,
+        slack_find_duplicate_channel_names,
         slack_fetch_messages_paginated, slack_join_channel, slack_list_channels, slack_set_token,
-        slack_test_connection, store_slack_credentials,
+        slack_sync_updated_threads, slack_test_connection, recommend_sync_interval, store_slack_credentials,
         update_slack_access_token,
     },
     slack_integration::{
         check_slack_config_status, connect_project_to_channel, create_slack_sync,
-        delete_slack_sync, disconnect_slack_channel, get_project_connected_channels,
-        get_slack_sync_for_project, slack_check_connection, slack_complete_oauth,
-        slack_exchange_code, slack_exchange_oauth_code, slack_get_users_list, slack_start_oauth,
+        delete_slack_sync, disconnect_slack_channel, find_overlapping_channel_syncs,
+        get_project_connected_channels, get_slack_sync_for_project, slack_check_connection, slack_complete_oauth,
+        slack_exchange_code, slack_exchange_oauth_code, slack_get_user_presence, slack_get_users_list,
+        slack_start_oauth,
         slack_store_credentials, slack_sync_scheduler_status, start_slack_sync_scheduler,
-        stop_slack_sync_scheduler, update_slack_sync,
+        stop_slack_sync_scheduler, reset_slack_integration, update_slack_sync,
+        reconnect_preserving_syncs, revalidate_slack_syncs, get_all_slack_syncs, slack_disconnect, sync_channel_now, pause_sync, resume_sync, get_stored_messages, slack_refresh_token,
+    },
+    system_commands::{
+        clear_notification, get_platform_info, get_system_user_info, reset_database, send_notification,
+        services_health,
     },
-    system_commands::{get_platform_info, get_system_user_info, reset_database, send_notification},
     task_commands::{
         apply_task_update,
     },
@@ -105,16 +132,16 @@ use whatsapp::{
 // Import new WhatsApp service commands
 use whatsapp_commands::{
     whatsapp_connect_v2, whatsapp_disconnect_v2, whatsapp_get_status_v2, whatsapp_start_monitoring_v2,
-    whatsapp_get_unprocessed_messages_v2, whatsapp_mark_processed_v2, whatsapp_check_login_v2,
-    whatsapp_refetch_messages_v2,
+    whatsapp_get_unprocessed_messages_v2, whatsapp_mark_processed_v2, whatsapp_mark_processed_batch_v2, whatsapp_get_chats_v2, whatsapp_check_login_v2,
+    whatsapp_refetch_messages_v2, whatsapp_list_gaps_v2, whatsapp_recover_gap_v2, whatsapp_selftest_v2,
 };
 
 // Import WhatsApp process management commands
 use whatsapp_process_manager::{
     whatsapp_service_start, whatsapp_service_stop, whatsapp_service_status, whatsapp_service_restart,
+    whatsapp_service_health_check,
 };
 
-use std::process::Command;
 use tauri::AppHandle;
 use chrono::Utc;
 
@@ -127,12 +154,9 @@ async fn start_embedded_services(_app_handle: AppHandle) {
     println!("🔧 Using concurrent startup with orchestrated fallback...");
     println!("[{}] ⏰ Starting service startup", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"));
     
-    // Start with concurrent method (more stable) and fall back to orchestrated if needed
-    let result = Command::new("npm")
-        .arg("run")
-        .arg("services:start")
-        .current_dir("../") // Go up one directory from src-tauri to project root
-        .spawn();
+    // Start with concurrent method (more stable) and fall back to orchestrated if needed.
+    // Spawned processes are tracked so they can be stopped when the app exits.
+    let result = service_supervisor::spawn_npm_script("services:start");
     
     match result {
         Ok(_) => {
@@ -144,11 +168,7 @@ async fn start_embedded_services(_app_handle: AppHandle) {
             println!("🔄 Falling back to orchestrated startup...");
             
             // Fallback to orchestrated method if concurrent startup fails
-            let fallback_result = Command::new("npm")
-                .arg("run")
-                .arg("services:start:orchestrated")
-                .current_dir("../")
-                .spawn();
+            let fallback_result = service_supervisor::spawn_npm_script("services:start:orchestrated");
                 
             match fallback_result {
                 Ok(_) => println!("✅ Orchestrated services startup initiated!"),
@@ -161,6 +181,7 @@ async fn start_embedded_services(_app_handle: AppHandle) {
 #[tokio::main]
 async fn main() {
     println!("🚀 Starting Tauri application...");
+    log_sink::init();
 
     #[cfg(debug_assertions)]
     let builder = tauri::Builder::default();
@@ -182,6 +203,7 @@ async fn main() {
         .invoke_handler(tauri::generate_handler![
             // Debug commands
             open_devtools,
+            get_recent_logs,
             
             // New modular commands from commands module
             get_projects,
@@ -194,35 +216,41 @@ async fn main() {
             
             // OAuth server commands
             start_https_oauth_server, stop_https_oauth_server, https_oauth_server_status,
-            cleanup_oauth_tokens,
+            cleanup_oauth_tokens, start_oauth,
             
             // Slack integration commands
             slack_start_oauth, slack_store_credentials, check_slack_config_status, 
             slack_exchange_code, slack_exchange_oauth_code, slack_complete_oauth,
             create_slack_sync, update_slack_sync, get_slack_sync_for_project,
             delete_slack_sync, disconnect_slack_channel, get_project_connected_channels,
-            connect_project_to_channel, start_slack_sync_scheduler, stop_slack_sync_scheduler,
+            connect_project_to_channel, start_slack_sync_scheduler, stop_slack_sync_scheduler, reset_slack_integration,
             slack_sync_scheduler_status, slack_check_connection, slack_get_users_list,
+            find_overlapping_channel_syncs, slack_get_user_presence,
+            reconnect_preserving_syncs, revalidate_slack_syncs, get_all_slack_syncs, slack_disconnect, sync_channel_now, pause_sync, resume_sync, get_stored_messages, slack_refresh_token,
             
             // AI automation commands  
             analyze_with_ai, process_slack_messages_with_ai, 
             get_pending_ai_items, apply_project_update_suggestion, reject_project_update_suggestion,
-            bulk_process_task_suggestions, create_task_from_ai_suggestion,
+            bulk_process_task_suggestions, bulk_reject_ai_items, create_task_from_ai_suggestion,
             ai_automation_health_check, init_ai_automation, extract_actionable_items,
             extract_high_confidence_items, analyze_behavioral_patterns,
             improve_prompts_from_feedback, initialize_prompt_improvement_service,
             capture_task_modification_feedback, store_task_update_detection,
             store_project_insight, analyze_text_for_insights, improve_prompts_with_analysis,
             capture_behavioral_feedback_advanced, initialize_advanced_prompt_improvement,
+            analyze_messages_in_chunks, reanalyze_failed_chunks,
+            set_ai_credentials, test_ai_credentials,
             
             // Background sync commands
             queue_background_sync, get_sync_job_status, get_active_sync_jobs,
-            cancel_sync_job,
+            cancel_sync_job, get_failed_sync_jobs,
             
             // System commands
             get_platform_info,
             get_system_user_info,
             send_notification,
+            clear_notification,
+            services_health,
             reset_database,
             
             // Task management commands
@@ -234,6 +262,9 @@ async fn main() {
             get_prompt_by_key,
             initialize_default_prompts,
             record_prompt_usage,
+            get_prompt_usage_stats,
+            set_prompt_experiment,
+            get_experiment_results,
             update_prompt,
             
             // Calendar management commands
@@ -243,15 +274,27 @@ async fn main() {
             update_event,
             delete_event,
             store_event_detection,
+            validate_rrule,
+            create_events_from_detections,
             
             // Project management commands
             get_all_projects,
             get_project,
             create_project,
+            update_project,
             update_project_field,
+            archive_project,
+            delete_project,
+            search,
             
             // Document management commands
             create_document,
+            get_documents,
+            get_document,
+            update_document,
+            delete_document,
+            get_document_versions,
+            restore_document_version,
             
             // WhatsApp commands (legacy - headless Chrome)
             whatsapp_connect,
@@ -269,14 +312,20 @@ async fn main() {
             whatsapp_start_monitoring_v2,
             whatsapp_get_unprocessed_messages_v2,
             whatsapp_mark_processed_v2,
+            whatsapp_mark_processed_batch_v2,
+            whatsapp_get_chats_v2,
             whatsapp_check_login_v2,
             whatsapp_refetch_messages_v2,
+            whatsapp_list_gaps_v2,
+            whatsapp_recover_gap_v2,
+            whatsapp_selftest_v2,
             
             // WhatsApp process management commands
             whatsapp_service_start,
             whatsapp_service_stop,
             whatsapp_service_status,
             whatsapp_service_restart,
+            whatsapp_service_health_check,
 
             // Slack commands
             store_slack_credentials,
@@ -284,6 +333,7 @@ async fn main() {
             update_slack_access_token,
             delete_slack_credentials,
             force_slack_reconnection,
+            store_slack_signing_secret,
             debug_slack_credentials_status,
             slack_list_channels,
             slack_build_oauth_url,
@@ -291,21 +341,60 @@ async fn main() {
             slack_test_connection,
             slack_join_channel,
             slack_fetch_messages,
+            slack_fetch_messages_typed,
             slack_estimate_sync_time,
             slack_analyze_messages,
             get_slack_team_info,
             get_slack_user_info,
             slack_fetch_messages_paginated,
+            slack_sync_updated_threads,
+            slack_check_clock_skew,
+            recommend_sync_interval,
+            slack_get_reactions_summary,
+            slack_fetch_messages_streaming,
+            slack_find_duplicate_channel_names,
         ])
         .setup(|app| {
             println!("✅ Tauri application setup started");
             
+            // Settings renamed since earlier versions keep their stored values
+            if let Err(e) = commands::settings::migrate_settings_file(app.handle()) {
+                println!("⚠️ Settings not migrated: {}", e);
+            }
+            
+            // Let the Slack scheduler pick up interval changes from settings
+            commands::slack_integration::watch_sync_interval_setting(app.handle());
+            // Service clients read their request timeouts from settings
+            service_timeouts::watch_timeout_setting(app.handle());
+            // Debug logging can be switched on from settings for support sessions
+            log_sink::watch_debug_log_setting(app.handle());
+            
+            // Keep the frontend's generated types in step with the command structs
+            #[cfg(debug_assertions)]
+            if let Err(e) = bindings::export_bindings(std::path::Path::new(bindings::BINDINGS_PATH)) {
+                println!("⚠️ TypeScript bindings not exported: {}", e);
+            }
+            
             // Auto-start all Node.js services for distribution
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 start_embedded_services(app_handle).await;
             });
             
+            // Receive Slack messages as they are posted when events are configured
+            let events_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = slack_events::start_slack_events_server(events_handle).await {
+                    println!("⚠️ Slack events receiver not started, channels will be polled: {}", e);
+                }
+            });
+            
+            // Restart services that stop responding to health checks
+            tauri::async_runtime::spawn(service_supervisor::run_service_supervisor());
+            
+            // Abandoned OAuth sign-ins would otherwise pile up until the app restarts
+            tauri::async_runtime::spawn(commands::oauth_servers::run_oauth_cleanup(app.handle().clone()));
+            
             Ok(())
         })
         .build(tauri::generate_context!())
@@ -315,8 +404,14 @@ async fn main() {
     
     
     app.run(|_app_handle, event| match event {
-        tauri::RunEvent::ExitRequested { api, .. } => {
-            api.prevent_exit();
+        // Closing the last window keeps the app alive; an explicit exit(code) goes through
+        tauri::RunEvent::ExitRequested { api, code, .. } => {
+            if code.is_none() {
+                api.prevent_exit();
+            }
+        }
+        tauri::RunEvent::Exit => {
+            service_supervisor::shutdown_services(service_supervisor::SHUTDOWN_GRACE_PERIOD);
         }
         _ => {}
     });
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use reqwest;
+use crate::service_timeouts::TimeoutPolicy;
 use log::{info, warn, error, debug};
 use thiserror::Error;
+use crate::http_client::{send_traced, RequestTraced, Traced};
 
 #[derive(Error, Debug)]
 pub enum OAuthServiceError {
@@ -15,6 +17,17 @@ pub enum OAuthServiceError {
     AuthenticationFailed(String),
     #[error("Service error: {0}")]
     ServiceError(String),
+    #[error(transparent)]
+    Traced(#[from] Traced<OAuthServiceError>),
+}
+
+impl RequestTraced for OAuthServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            OAuthServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,10 +79,84 @@ pub struct OAuthTokenData {
     pub scope: Option<String>,
 }
 
+/// Tokens returned by a refresh. Providers that rotate refresh tokens (Slack, Google) send a new
+/// one that replaces the old; `None` means the old refresh token is still valid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshedToken {
+    pub access_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Read a refresh response. The access token may come as `access_token` or `token`, and the
+/// expiry as an RFC 3339 `expires_at` or as `expires_in` seconds from `now`.
+fn parse_refresh_response(result: &serde_json::Value, now: chrono::DateTime<chrono::Utc>) -> Option<RefreshedToken> {
+    let access_token = result["access_token"].as_str().or_else(|| result["token"].as_str())?;
+    let expires_at = result["expires_at"].as_str()
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&chrono::Utc))
+        .or_else(|| result["expires_in"].as_i64().map(|secs| now + chrono::Duration::seconds(secs)));
+    
+    Some(RefreshedToken {
+        access_token: access_token.to_string(),
+        refresh_token: result["refresh_token"].as_str().filter(|t| !t.is_empty()).map(String::from),
+        expires_at,
+    })
+}
+
+/// Result of a code exchange: typed token data when the payload could be read, otherwise
+/// the raw response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CodeExchangeResult {
+    Token(OAuthTokenData),
+    Raw(serde_json::Value),
+}
+
+impl CodeExchangeResult {
+    pub fn token_data(&self) -> Option<&OAuthTokenData> {
+        match self {
+            CodeExchangeResult::Token(data) => Some(data),
+            CodeExchangeResult::Raw(_) => None,
+        }
+    }
+}
+
+/// Read token data from an exchange response, accepting both the flat `OAuthTokenData`
+/// shape and the raw Slack `oauth.v2.access` payload (nested `team`/`authed_user`)
+pub fn parse_token_data(provider: &str, response: &serde_json::Value) -> Option<OAuthTokenData> {
+    let payload = response.get("data").unwrap_or(response);
+    
+    if let Ok(data) = serde_json::from_value::<OAuthTokenData>(payload.clone()) {
+        return Some(data);
+    }
+    
+    let str_at = |path: &[&str]| -> Option<String> {
+        let mut value = payload;
+        for key in path {
+            value = value.get(*key)?;
+        }
+        value.as_str().map(|s| s.to_string())
+    };
+    
+    Some(OAuthTokenData {
+        provider: str_at(&["provider"]).unwrap_or_else(|| provider.to_string()),
+        access_token: str_at(&["access_token"])?,
+        refresh_token: str_at(&["refresh_token"]),
+        team_id: str_at(&["team_id"]).or_else(|| str_at(&["team", "id"])),
+        team_name: str_at(&["team_name"]).or_else(|| str_at(&["team", "name"])),
+        user_id: str_at(&["user_id"]).or_else(|| str_at(&["authed_user", "id"])),
+        scope: str_at(&["scope"]),
+    })
+}
+
 #[derive(Clone)]
 pub struct OAuthServiceClient {
     base_url: String,
     client: reqwest::Client,
+    timeouts: TimeoutPolicy,
 }
 
 impl OAuthServiceClient {
@@ -79,23 +166,30 @@ impl OAuthServiceClient {
             "https://localhost:3003".to_string()
         });
         
-        let client = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true) // Allow self-signed certificates for local development
-            .build()
-            .expect("Failed to create HTTP client");
-        
         Self {
             base_url,
-            client,
+            // Allow self-signed certificates for local development
+            client: crate::http_client::local_tls_client(),
+            timeouts: TimeoutPolicy::configured(),
         }
     }
     
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+    
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
+    }
+    
     pub async fn health_check(&self) -> Result<bool, OAuthServiceError> {
         debug!("💓 Performing OAuth service health check");
         
         let url = format!("{}/health", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("oauth", self.client.get(&url).timeout(self.timeouts.health)).await;
+        match response {
             Ok(response) => {
                 if response.status().is_success() {
                     info!("✅ OAuth service health check passed");
@@ -107,7 +201,7 @@ impl OAuthServiceClient {
             }
             Err(e) => {
                 error!("❌ OAuth service health check request failed: {}", e);
-                Err(OAuthServiceError::Http(e))
+                Err(OAuthServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -122,26 +216,26 @@ impl OAuthServiceClient {
             redirect_uri: redirect_uri.to_string(),
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let oauth_response: OAuthUrlResponse = response.json().await
-                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             info!("✅ OAuth URL generated successfully");
             Ok(oauth_response)
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to generate OAuth URL: {}", error_text);
-            Err(OAuthServiceError::ServiceError(error_text))
+            Err(OAuthServiceError::ServiceError(error_text).with_request_id(&request_id))
         }
     }
     
-    pub async fn exchange_code(&self, provider: &str, code: &str, redirect_uri: &str) -> Result<serde_json::Value, OAuthServiceError> {
+    pub async fn exchange_code(&self, provider: &str, code: &str, redirect_uri: &str) -> Result<CodeExchangeResult, OAuthServiceError> {
         info!("🔄 Exchanging OAuth code for tokens");
         
         let url = format!("{}/api/oauth/exchange-code", self.base_url);
@@ -152,22 +246,28 @@ impl OAuthServiceClient {
             redirect_uri: redirect_uri.to_string(),
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let result: serde_json::Value = response.json().await
-                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             info!("✅ OAuth code exchange successful");
-            Ok(result)
+            match parse_token_data(provider, &result) {
+                Some(token_data) => Ok(CodeExchangeResult::Token(token_data)),
+                None => {
+                    warn!("⚠️ Could not read token data from exchange response, returning raw payload");
+                    Ok(CodeExchangeResult::Raw(result))
+                }
+            }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ OAuth code exchange failed: {}", error_text);
-            Err(OAuthServiceError::AuthenticationFailed(error_text))
+            Err(OAuthServiceError::AuthenticationFailed(error_text).with_request_id(&request_id))
         }
     }
     
@@ -180,15 +280,15 @@ impl OAuthServiceClient {
             token: token.to_string(),
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.post(&url).json(&request).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() || response.status() == 401 {
             let verify_response: TokenVerifyResponse = response.json().await
-                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if verify_response.valid {
                 info!("✅ Token verification successful");
@@ -200,11 +300,13 @@ impl OAuthServiceClient {
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Token verification request failed: {}", error_text);
-            Err(OAuthServiceError::ServiceError(error_text))
+            Err(OAuthServiceError::ServiceError(error_text).with_request_id(&request_id))
         }
     }
     
-    pub async fn refresh_token(&self, token: &str) -> Result<String, OAuthServiceError> {
+    /// Exchange `token` for a fresh access token, returning any rotated refresh token with it.
+    /// Callers must store the rotated refresh token, since the old one stops working.
+    pub async fn refresh_token(&self, token: &str) -> Result<RefreshedToken, OAuthServiceError> {
         info!("🔄 Refreshing OAuth token");
         
         let url = format!("{}/api/auth/refresh", self.base_url);
@@ -213,27 +315,27 @@ impl OAuthServiceClient {
             token: token.to_string(),
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let result: serde_json::Value = response.json().await
-                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
-            if let Some(new_token) = result["token"].as_str() {
-                info!("✅ Token refresh successful");
-                Ok(new_token.to_string())
+            if let Some(refreshed) = parse_refresh_response(&result, chrono::Utc::now()) {
+                info!("✅ Token refresh successful{}", if refreshed.refresh_token.is_some() { " (refresh token rotated)" } else { "" });
+                Ok(refreshed)
             } else {
                 error!("❌ No token in refresh response");
-                Err(OAuthServiceError::InvalidResponse("No token in response".to_string()))
+                Err(OAuthServiceError::InvalidResponse("No token in response".to_string()).with_request_id(&request_id))
             }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Token refresh failed: {}", error_text);
-            Err(OAuthServiceError::AuthenticationFailed(error_text))
+            Err(OAuthServiceError::AuthenticationFailed(error_text).with_request_id(&request_id))
         }
     }
     
@@ -242,33 +344,33 @@ impl OAuthServiceClient {
         
         let url = format!("{}/api/auth/tokens/{}/{}", self.base_url, provider, identifier);
         
-        let response = self.client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", auth_token))
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.get(&url).header("Authorization", format!("Bearer {}", auth_token)).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let result: serde_json::Value = response.json().await
-                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if let Some(tokens) = result["tokens"].as_object() {
                 let token_data: OAuthTokenData = serde_json::from_value(serde_json::Value::Object(tokens.clone()))
-                    .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()))?;
+                    .map_err(|e| OAuthServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
                 
                 info!("✅ Retrieved stored tokens successfully");
                 Ok(token_data)
             } else {
                 error!("❌ No tokens in response");
-                Err(OAuthServiceError::InvalidResponse("No tokens in response".to_string()))
+                Err(OAuthServiceError::InvalidResponse("No tokens in response".to_string()).with_request_id(&request_id))
             }
         } else if response.status() == 404 {
             warn!("⚠️ No stored tokens found");
-            Err(OAuthServiceError::ServiceError("Tokens not found".to_string()))
+            Err(OAuthServiceError::ServiceError("Tokens not found".to_string()).with_request_id(&request_id))
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to retrieve tokens: {}", error_text);
-            Err(OAuthServiceError::ServiceError(error_text))
+            Err(OAuthServiceError::ServiceError(error_text).with_request_id(&request_id))
         }
     }
     
@@ -277,11 +379,11 @@ impl OAuthServiceClient {
         
         let url = format!("{}/api/auth/tokens/{}/{}", self.base_url, provider, identifier);
         
-        let response = self.client
-            .delete(&url)
-            .header("Authorization", format!("Bearer {}", auth_token))
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.delete(&url).header("Authorization", format!("Bearer {}", auth_token)).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             info!("✅ Tokens revoked successfully");
@@ -289,7 +391,7 @@ impl OAuthServiceClient {
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to revoke tokens: {}", error_text);
-            Err(OAuthServiceError::ServiceError(error_text))
+            Err(OAuthServiceError::ServiceError(error_text).with_request_id(&request_id))
         }
     }
     
@@ -304,11 +406,11 @@ impl OAuthServiceClient {
             "client_secret": client_secret
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "oauth",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| OAuthServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             info!("✅ {} credentials configured successfully", provider);
@@ -316,7 +418,78 @@ impl OAuthServiceClient {
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to configure {} credentials: {}", provider, error_text);
-            Err(OAuthServiceError::ServiceError(error_text))
+            Err(OAuthServiceError::ServiceError(error_text).with_request_id(&request_id))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_responses_keep_rotated_tokens_and_expiry() {
+        let now = chrono::Utc::now();
+
+        let rotated = parse_refresh_response(&serde_json::json!({
+            "access_token": "xoxe.xoxb-new",
+            "refresh_token": "xoxe-1-new",
+            "expires_in": 43200
+        }), now).unwrap();
+        assert_eq!(rotated.access_token, "xoxe.xoxb-new");
+        assert_eq!(rotated.refresh_token.as_deref(), Some("xoxe-1-new"));
+        assert_eq!(rotated.expires_at, Some(now + chrono::Duration::seconds(43200)));
+
+        let legacy = parse_refresh_response(&serde_json::json!({ "token": "jwt" }), now).unwrap();
+        assert_eq!(legacy.access_token, "jwt");
+        assert!(legacy.refresh_token.is_none() && legacy.expires_at.is_none());
+
+        assert!(parse_refresh_response(&serde_json::json!({ "error": "nope" }), now).is_none());
+    }
+
+    #[test]
+    fn flat_token_data_is_read_as_is() {
+        let data = parse_token_data("slack", &serde_json::json!({
+            "data": {
+                "provider": "slack",
+                "access_token": "xoxb-flat",
+                "refresh_token": "xoxe-1-flat",
+                "team_id": "T1",
+                "team_name": "Acme",
+                "user_id": "U1",
+                "scope": "channels:history,chat:write"
+            }
+        })).unwrap();
+
+        assert_eq!(data.access_token, "xoxb-flat");
+        assert_eq!(data.refresh_token.as_deref(), Some("xoxe-1-flat"));
+        assert_eq!(data.team_id.as_deref(), Some("T1"));
+        assert_eq!(data.scope.as_deref(), Some("channels:history,chat:write"));
+    }
+
+    #[test]
+    fn nested_slack_payloads_are_flattened() {
+        let data = parse_token_data("slack", &serde_json::json!({
+            "ok": true,
+            "access_token": "xoxb-nested",
+            "scope": "channels:read",
+            "team": { "id": "T2", "name": "Globex" },
+            "authed_user": { "id": "U2" }
+        })).unwrap();
+
+        assert_eq!(data.provider, "slack");
+        assert_eq!(data.access_token, "xoxb-nested");
+        assert_eq!(data.team_id.as_deref(), Some("T2"));
+        assert_eq!(data.team_name.as_deref(), Some("Globex"));
+        assert_eq!(data.user_id.as_deref(), Some("U2"));
+        assert_eq!(data.scope.as_deref(), Some("channels:read"));
+        assert!(data.refresh_token.is_none());
+    }
+
+    #[test]
+    fn malformed_exchange_responses_have_no_token_data() {
+        assert!(parse_token_data("slack", &serde_json::json!({ "ok": false, "error": "invalid_code" })).is_none());
+        assert!(parse_token_data("slack", &serde_json::json!({ "access_token": 42 })).is_none());
+        assert!(parse_token_data("slack", &serde_json::json!("not an object")).is_none());
+    }
+}
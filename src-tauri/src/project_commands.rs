@@ -1,6 +1,8 @@
 use tauri::AppHandle;
 use chrono::Utc;
 use serde_json;
+use rusqlite::OptionalExtension;
+use crate::app_db::with_existing_app_db;
 // DatabaseService now handled by separate microservice
 
 // Validation helper functions
@@ -35,31 +37,175 @@ pub fn validate_metric_type(metric_type: &str) -> Result<(), String> {
     Ok(())
 }
 
-// Simplified project commands using database service
+/// Projects returned per page when the caller doesn't ask for a size
+pub const DEFAULT_PROJECT_PAGE_SIZE: u32 = 50;
+/// Largest page `list_projects` will return
+pub const MAX_PROJECT_PAGE_SIZE: u32 = 500;
+
+/// Order for `list_projects`; names sort A-Z, dates newest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectSort {
+    #[default]
+    Name,
+    Created,
+    Updated,
+    Status,
+}
+
+impl ProjectSort {
+    fn order_by(self) -> &'static str {
+        match self {
+            ProjectSort::Name => "name COLLATE NOCASE ASC, id",
+            ProjectSort::Created => "createdAt DESC, id",
+            ProjectSort::Updated => "updatedAt DESC, id",
+            ProjectSort::Status => "status ASC, name COLLATE NOCASE ASC, id",
+        }
+    }
+}
+
+/// One page of projects and how many match the filter in total
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct ProjectPage {
+    pub items: Vec<serde_json::Value>,
+    pub total: i64,
+}
+
+const PROJECT_COLUMNS: &str = "id, name, description, status, isNextUp, strategicGoal, archivedAt, createdAt, updatedAt";
+
+/// A project row selected with `PROJECT_COLUMNS`, in the frontend's field names
+fn project_from_row(row: &rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    Ok(serde_json::json!({
+        "id": row.get::<_, String>(0)?,
+        "name": row.get::<_, String>(1)?,
+        "description": row.get::<_, String>(2)?,
+        "status": row.get::<_, String>(3)?,
+        "isNextUp": row.get::<_, Option<i64>>(4)?.unwrap_or(0) != 0,
+        "strategicGoal": row.get::<_, Option<String>>(5)?,
+        "archivedAt": row.get::<_, Option<String>>(6)?,
+        "createdAt": row.get::<_, String>(7)?,
+        "updatedAt": row.get::<_, String>(8)?,
+    }))
+}
+
+fn projects_table_exists(conn: &rusqlite::Connection) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'projects'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(|e| format!("Failed to inspect database: {}", e))
+}
+
+/// The project with `project_id`, if it exists
+pub fn load_project(conn: &rusqlite::Connection, project_id: &str) -> Result<Option<serde_json::Value>, String> {
+    if !projects_table_exists(conn)? {
+        return Ok(None);
+    }
+    
+    conn.query_row(
+        &format!("SELECT {} FROM projects WHERE id = ?1", PROJECT_COLUMNS),
+        [project_id],
+        project_from_row,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to query project: {}", e))
+}
+
+/// Read projects from the frontend's database. `limit: None` returns every matching project.
+pub fn load_projects_page(
+    conn: &rusqlite::Connection,
+    status_filter: Option<&str>,
+    sort_by: ProjectSort,
+    offset: u32,
+    limit: Option<u32>,
+) -> Result<ProjectPage, String> {
+    if !projects_table_exists(conn)? {
+        return Ok(ProjectPage { items: vec![], total: 0 });
+    }
+    
+    let total: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM projects WHERE ?1 IS NULL OR status = ?1",
+            rusqlite::params![status_filter],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count projects: {}", e))?;
+    
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}
+             FROM projects
+             WHERE ?1 IS NULL OR status = ?1
+             ORDER BY {}
+             LIMIT ?2 OFFSET ?3",
+            PROJECT_COLUMNS,
+            sort_by.order_by(),
+        ))
+        .map_err(|e| format!("Failed to prepare project query: {}", e))?;
+    
+    // SQLite treats a negative LIMIT as "no limit"
+    let limit = limit.map(i64::from).unwrap_or(-1);
+    let items = stmt
+        .query_map(rusqlite::params![status_filter, limit, offset], project_from_row)
+        .map_err(|e| format!("Failed to query projects: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read project row: {}", e))?;
+    
+    Ok(ProjectPage { items, total })
+}
+
+async fn query_projects(
+    app: AppHandle,
+    status_filter: Option<String>,
+    sort_by: ProjectSort,
+    offset: u32,
+    limit: Option<u32>,
+) -> Result<ProjectPage, String> {
+    let page = with_existing_app_db(&app, move |conn| {
+        load_projects_page(conn, status_filter.as_deref(), sort_by, offset, limit)
+    })
+    .await?;
+    Ok(page.unwrap_or(ProjectPage { items: vec![], total: 0 }))
+}
+
+/// A page of projects, optionally only those with `status_filter`
+pub async fn list_projects(
+    app: AppHandle,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort_by: Option<ProjectSort>,
+    status_filter: Option<String>,
+) -> Result<ProjectPage, String> {
+    let limit = limit.unwrap_or(DEFAULT_PROJECT_PAGE_SIZE).clamp(1, MAX_PROJECT_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+    println!("📋 [list_projects] Listing projects {}..{} (status: {:?})", offset, offset + limit, status_filter);
+    
+    query_projects(app, status_filter, sort_by.unwrap_or_default(), offset, Some(limit)).await
+}
+
 pub async fn get_all_projects(
-    _app: AppHandle,
+    app: AppHandle,
 ) -> Result<Vec<serde_json::Value>, String> {
-    println!("📋 [get_all_projects] Fetching all projects from database service");
+    println!("📋 [get_all_projects] Fetching all projects");
     
-    // In production, this would call the database service
-    Ok(vec![])
+    Ok(query_projects(app, None, ProjectSort::default(), 0, None).await?.items)
 }
 
 pub async fn get_project(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
 ) -> Result<serde_json::Value, String> {
     println!("🔍 [get_project] Fetching project: {}", project_id);
     
     validate_project_id(&project_id)?;
     
-    // In production, this would call the database service
-    Ok(serde_json::json!({
-        "id": project_id,
-        "name": "Sample Project",
-        "description": "Project data from database service",
-        "status": "active"
-    }))
+    let id = project_id.clone();
+    with_existing_app_db(&app, move |conn| load_project(conn, &id))
+        .await?
+        .flatten()
+        .ok_or_else(|| format!("Project not found: {}", project_id))
 }
 
 pub async fn create_project(
@@ -88,31 +234,301 @@ pub async fn create_project(
     Ok(project_id)
 }
 
+/// Lets a nullable field tell "sent as null" (`Some(None)`, clear it) apart from "not sent"
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+/// Optional-field patch for a project; only the fields that are set get written. Nullable
+/// fields set to `Some(None)` are cleared.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ProjectUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub team: Option<Option<String>>,
+}
+
+/// Values allowed by the table's CHECK constraint on `status`
+const PROJECT_STATUSES: &[&str] = &["active", "archived", "shelf"];
+
+/// `ProjectUpdate` fields stored in columns the frontend schema doesn't create, with their
+/// column name and type; they are added on first write
+const OPTIONAL_PROJECT_COLUMNS: &[(&str, &str, &str)] = &[
+    ("priority", "priority", "TEXT"),
+    ("due_date", "dueDate", "TEXT"),
+    ("progress", "progress", "REAL"),
+    ("budget", "budget", "REAL"),
+    ("team", "team", "TEXT"),
+];
+
+impl ProjectUpdate {
+    /// Build a single-field update, as sent by `update_project_field`
+    pub fn from_field(field: &str, value: serde_json::Value) -> Result<Self, String> {
+        validate_field_name(field)?;
+        if value.is_null() && !OPTIONAL_PROJECT_COLUMNS.iter().any(|(name, _, _)| *name == field) {
+            return Err(format!("Field {} cannot be cleared", field));
+        }
+        
+        let mut patch = serde_json::Map::new();
+        patch.insert(field.to_string(), value);
+        serde_json::from_value(serde_json::Value::Object(patch))
+            .map_err(|e| format!("Invalid value for field {}: {}", field, e))
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields().is_empty()
+    }
+    
+    /// Names of the fields this update will write
+    pub fn changed_fields(&self) -> Vec<String> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Write every set field of `update` to the project in one UPDATE, bumping `updatedAt`.
+/// Archiving stamps `archivedAt`; any other status clears it.
+pub fn apply_project_update(
+    conn: &rusqlite::Connection,
+    project_id: &str,
+    update: &ProjectUpdate,
+    now: &str,
+) -> Result<(), String> {
+    use rusqlite::types::Value as Sql;
+    
+    let text = |value: &Option<String>| value.clone().map(Sql::Text);
+    let nullable_text = |value: &Option<Option<String>>| value.clone().map(|v| v.map_or(Sql::Null, Sql::Text));
+    let nullable_real = |value: Option<Option<f64>>| value.map(|v| v.map_or(Sql::Null, Sql::Real));
+    let mut fields: Vec<(&str, Sql)> = [
+        ("name", text(&update.name)),
+        ("description", text(&update.description)),
+        ("status", text(&update.status)),
+        ("priority", nullable_text(&update.priority)),
+        ("due_date", nullable_text(&update.due_date)),
+        ("progress", nullable_real(update.progress)),
+        ("budget", nullable_real(update.budget)),
+        ("team", nullable_text(&update.team)),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| value.map(|value| (field, value)))
+    .collect();
+    if fields.is_empty() {
+        return Err("No fields to update".to_string());
+    }
+    
+    if let Some(status) = &update.status {
+        if !PROJECT_STATUSES.contains(&status.as_str()) {
+            return Err(format!("Invalid project status {}, expected one of {:?}", status, PROJECT_STATUSES));
+        }
+        let archived_at = if status == "archived" { Sql::Text(now.to_string()) } else { Sql::Null };
+        fields.push(("archivedAt", archived_at));
+    }
+    
+    let mut assignments = vec!["updatedAt = ?1".to_string()];
+    let mut values = vec![Sql::Text(now.to_string())];
+    for (field, value) in fields {
+        let column = match OPTIONAL_PROJECT_COLUMNS.iter().find(|(name, _, _)| *name == field) {
+            Some((_, column, sql_type)) => {
+                if !crate::slack_sync::has_column(conn, "projects", column)? {
+                    conn.execute(&format!("ALTER TABLE projects ADD COLUMN {} {}", column, sql_type), [])
+                        .map_err(|e| format!("Failed to add column {}: {}", column, e))?;
+                }
+                *column
+            }
+            None => field,
+        };
+        values.push(value);
+        assignments.push(format!("{} = ?{}", column, values.len()));
+    }
+    
+    values.push(Sql::Text(project_id.to_string()));
+    let changed = conn
+        .execute(
+            &format!("UPDATE projects SET {} WHERE id = ?{}", assignments.join(", "), values.len()),
+            rusqlite::params_from_iter(values),
+        )
+        .map_err(|e| format!("Failed to update project {}: {}", project_id, e))?;
+    if changed == 0 {
+        return Err(format!("Project not found: {}", project_id));
+    }
+    Ok(())
+}
+
+/// Delete the project row. With `cascade`, tables referencing it with `ON DELETE CASCADE`
+/// follow; without it foreign keys stay off and the delete is refused while the project still
+/// has tasks or events.
+pub fn delete_project_row(conn: &rusqlite::Connection, project_id: &str, cascade: bool) -> Result<(), String> {
+    if !cascade {
+        let mut dependents = Vec::new();
+        for table in ["tasks", "events"] {
+            if !crate::slack_sync::has_column(conn, table, "projectId")? {
+                continue;
+            }
+            let count: i64 = conn
+                .query_row(&format!("SELECT COUNT(*) FROM {} WHERE projectId = ?1", table), [project_id], |row| row.get(0))
+                .map_err(|e| format!("Failed to count {} of project {}: {}", table, project_id, e))?;
+            if count > 0 {
+                dependents.push(format!("{} {}", count, table));
+            }
+        }
+        if !dependents.is_empty() {
+            return Err(format!(
+                "Project {} has {}; delete them first or use cascade",
+                project_id, dependents.join(" and ")
+            ));
+        }
+    }
+    
+    let pragma = if cascade { "PRAGMA foreign_keys = ON" } else { "PRAGMA foreign_keys = OFF" };
+    conn.execute_batch(pragma)
+        .map_err(|e| format!("Failed to set foreign keys: {}", e))?;
+    let changed = conn
+        .execute("DELETE FROM projects WHERE id = ?1", [project_id])
+        .map_err(|e| format!("Failed to delete project {}: {}", project_id, e))?;
+    if changed == 0 {
+        return Err(format!("Project not found: {}", project_id));
+    }
+    Ok(())
+}
+
+/// Apply every set field of `update` to the project in a single write
 pub async fn update_project(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
-    field: String,
-    value: serde_json::Value,
-) -> Result<String, String> {
-    println!("📝 [update_project] Updating project {} field {}", project_id, field);
+    update: ProjectUpdate,
+) -> Result<serde_json::Value, String> {
+    validate_project_id(&project_id)?;
+    
+    if let Some(name) = &update.name {
+        if name.trim().is_empty() {
+            return Err("Project name cannot be empty".to_string());
+        }
+    }
+    
+    let fields = update.changed_fields();
+    if fields.is_empty() {
+        return Err("No fields to update".to_string());
+    }
+    
+    println!("📝 [update_project] Updating project {} fields {:?}", project_id, fields);
+    
+    let now = Utc::now().to_rfc3339();
+    let (id, updated_at) = (project_id.clone(), now.clone());
+    with_existing_app_db(&app, move |conn| apply_project_update(conn, &id, &update, &now))
+        .await?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    
+    println!("✅ Project {} updated: {:?}", project_id, fields);
+    Ok(serde_json::json!({
+        "id": project_id,
+        "updated_fields": fields,
+        "updated_at": updated_at
+    }))
+}
+
+/// Deactivate the project's Slack syncs and archive it in one transaction, so a failure leaves
+/// neither half applied. Returns how many syncs were deactivated.
+pub fn archive_project_rows(conn: &mut rusqlite::Connection, project_id: &str, now: &str) -> Result<usize, String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let deactivated = if crate::slack_sync::has_column(&tx, "slack_sync_metadata", "projectId")? {
+        tx.execute(
+            "UPDATE slack_sync_metadata SET isEnabled = 0, updatedAt = ?1
+             WHERE projectId = ?2 AND isEnabled = 1 AND isDeleted = 0",
+            [now, project_id],
+        )
+        .map_err(|e| format!("Failed to deactivate syncs of project {}: {}", project_id, e))?
+    } else {
+        0
+    };
+    
+    let update = ProjectUpdate {
+        status: Some("archived".to_string()),
+        ..Default::default()
+    };
+    apply_project_update(&tx, project_id, &update, now)?;
+    
+    tx.commit()
+        .map_err(|e| format!("Failed to archive project {}: {}", project_id, e))?;
+    Ok(deactivated)
+}
+
+/// Archive a project and deactivate its Slack syncs so it stops pulling in messages
+pub async fn archive_project(
+    app: AppHandle,
+    project_id: String,
+) -> Result<serde_json::Value, String> {
+    println!("📦 [archive_project] Archiving project: {}", project_id);
     
     validate_project_id(&project_id)?;
-    validate_field_name(&field)?;
     
-    // In production, this would call the database service
-    println!("✅ Project {} updated: {} = {:?}", project_id, field, value);
-    Ok("Update successful".to_string())
+    let now = Utc::now().to_rfc3339();
+    let id = project_id.clone();
+    let deactivated = with_existing_app_db(&app, move |conn| archive_project_rows(conn, &id, &now))
+        .await?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    
+    println!("✅ Project {} archived, {} syncs deactivated", project_id, deactivated);
+    Ok(serde_json::json!({
+        "project_id": project_id,
+        "status": "archived",
+        "deactivated_syncs": deactivated
+    }))
 }
 
+/// Delete a project. With `cascade` its Slack syncs, documents, tasks and events are removed
+/// too; without it the delete is refused while any of them exist.
 pub async fn delete_project(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
+    cascade: bool,
 ) -> Result<String, String> {
-    println!("🗑️ [delete_project] Deleting project: {}", project_id);
+    println!("🗑️ [delete_project] Deleting project: {} (cascade: {})", project_id, cascade);
     
     validate_project_id(&project_id)?;
     
-    // In production, this would call the database service
+    let syncs = crate::slack_sync::get_syncs_for_project(app.clone(), project_id.clone()).await?;
+    let documents = crate::document_commands::get_documents(app.clone(), Some(project_id.clone())).await?;
+    
+    if !cascade && (!syncs.is_empty() || !documents.is_empty()) {
+        return Err(format!(
+            "Project {} has {} Slack syncs and {} documents; delete them first or use cascade",
+            project_id, syncs.len(), documents.len()
+        ));
+    }
+    
+    for sync in syncs {
+        crate::slack_sync::delete_sync(app.clone(), sync.id).await?;
+    }
+    for document in documents {
+        crate::document_commands::delete_document(app.clone(), document.id).await?;
+    }
+    
+    let id = project_id.clone();
+    with_existing_app_db(&app, move |conn| delete_project_row(conn, &id, cascade))
+        .await?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    
     println!("✅ Project {} deleted", project_id);
     Ok("Project deleted successfully".to_string())
 }
@@ -151,6 +567,172 @@ pub async fn update_project_field(
     field: String,
     value: serde_json::Value,
 ) -> Result<String, String> {
-    // Alias for update_project
-    update_project(app, project_id, field, value).await
-}
\ No newline at end of file
+    println!("📝 [update_project_field] Updating project {} field {}", project_id, field);
+    
+    let update = ProjectUpdate::from_field(&field, value)?;
+    update_project(app, project_id, update).await?;
+    Ok("Update successful".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE projects (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL, description TEXT NOT NULL, status TEXT NOT NULL,
+                isNextUp INTEGER DEFAULT 0, strategicGoal TEXT, archivedAt TEXT,
+                createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL
+             );
+             INSERT INTO projects (id, name, description, status, createdAt, updatedAt) VALUES
+                ('p1', 'beta', '', 'active', '2026-01-01', '2026-03-01'),
+                ('p2', 'Alpha', '', 'archived', '2026-02-01', '2026-02-01'),
+                ('p3', 'gamma', '', 'active', '2026-03-01', '2026-01-01');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn ids(page: &ProjectPage) -> Vec<&str> {
+        page.items.iter().map(|p| p["id"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn pages_are_sorted_and_counted_before_the_limit() {
+        let conn = seeded_db();
+
+        let page = load_projects_page(&conn, None, ProjectSort::Name, 0, Some(2)).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(ids(&page), vec!["p2", "p1"]);
+
+        let page = load_projects_page(&conn, None, ProjectSort::Created, 2, Some(2)).unwrap();
+        assert_eq!(ids(&page), vec!["p1"]);
+
+        let page = load_projects_page(&conn, None, ProjectSort::Updated, 0, None).unwrap();
+        assert_eq!(ids(&page), vec!["p1", "p2", "p3"]);
+    }
+
+    #[test]
+    fn single_project_lookup_reads_the_row() {
+        let conn = seeded_db();
+
+        let project = load_project(&conn, "p2").unwrap().unwrap();
+        assert_eq!(project["name"], "Alpha");
+        assert_eq!(project["status"], "archived");
+        assert!(load_project(&conn, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn updates_are_written_and_archiving_is_stamped() {
+        let conn = seeded_db();
+
+        let update = ProjectUpdate {
+            name: Some("Beta launch".to_string()),
+            status: Some("archived".to_string()),
+            budget: Some(Some(1500.0)),
+            ..Default::default()
+        };
+        apply_project_update(&conn, "p1", &update, "2026-04-01").unwrap();
+
+        let project = load_project(&conn, "p1").unwrap().unwrap();
+        assert_eq!(project["name"], "Beta launch");
+        assert_eq!(project["status"], "archived");
+        assert_eq!(project["archivedAt"], "2026-04-01");
+        assert_eq!(project["updatedAt"], "2026-04-01");
+        let budget: f64 = conn.query_row("SELECT budget FROM projects WHERE id = 'p1'", [], |row| row.get(0)).unwrap();
+        assert_eq!(budget, 1500.0);
+
+        let reopen = ProjectUpdate { status: Some("active".to_string()), ..Default::default() };
+        apply_project_update(&conn, "p1", &reopen, "2026-04-02").unwrap();
+        assert!(load_project(&conn, "p1").unwrap().unwrap()["archivedAt"].is_null());
+
+        let invalid = ProjectUpdate { status: Some("paused".to_string()), ..Default::default() };
+        assert!(apply_project_update(&conn, "p1", &invalid, "2026-04-03").is_err());
+        assert!(apply_project_update(&conn, "missing", &reopen, "2026-04-03").is_err());
+    }
+
+    #[test]
+    fn nullable_fields_can_be_cleared() {
+        let conn = seeded_db();
+        let set = ProjectUpdate::from_field("team", serde_json::json!("Platform")).unwrap();
+        apply_project_update(&conn, "p1", &set, "2026-04-01").unwrap();
+
+        let clear = ProjectUpdate::from_field("team", serde_json::Value::Null).unwrap();
+        assert_eq!(clear.changed_fields(), vec!["team"]);
+        apply_project_update(&conn, "p1", &clear, "2026-04-02").unwrap();
+        let team: Option<String> = conn.query_row("SELECT team FROM projects WHERE id = 'p1'", [], |row| row.get(0)).unwrap();
+        assert!(team.is_none());
+
+        assert!(ProjectUpdate::from_field("name", serde_json::Value::Null).is_err());
+        assert!(ProjectUpdate::from_field("budget", serde_json::json!("lots")).is_err());
+    }
+
+    #[test]
+    fn deleting_a_project_removes_the_row() {
+        let conn = seeded_db();
+
+        delete_project_row(&conn, "p2", false).unwrap();
+        assert!(load_project(&conn, "p2").unwrap().is_none());
+        assert!(delete_project_row(&conn, "p2", false).is_err());
+    }
+
+    #[test]
+    fn projects_with_tasks_are_only_deleted_with_cascade() {
+        let conn = seeded_db();
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id TEXT PRIMARY KEY, projectId TEXT NOT NULL,
+                FOREIGN KEY (projectId) REFERENCES projects (id) ON DELETE CASCADE
+             );
+             INSERT INTO tasks (id, projectId) VALUES ('t1', 'p1'), ('t2', 'p1');",
+        )
+        .unwrap();
+
+        let error = delete_project_row(&conn, "p1", false).unwrap_err();
+        assert!(error.contains("2 tasks"));
+        assert!(load_project(&conn, "p1").unwrap().is_some());
+        let tasks: i64 = conn.query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0)).unwrap();
+        assert_eq!(tasks, 2);
+
+        delete_project_row(&conn, "p1", true).unwrap();
+        assert!(load_project(&conn, "p1").unwrap().is_none());
+        let tasks: i64 = conn.query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0)).unwrap();
+        assert_eq!(tasks, 0);
+    }
+
+    #[test]
+    fn archiving_deactivates_syncs_in_the_same_transaction() {
+        let mut conn = seeded_db();
+        conn.execute_batch(
+            "CREATE TABLE slack_sync_metadata (
+                id TEXT PRIMARY KEY, projectId TEXT NOT NULL, isEnabled INTEGER NOT NULL,
+                isDeleted INTEGER NOT NULL DEFAULT 0, updatedAt TEXT
+             );
+             INSERT INTO slack_sync_metadata (id, projectId, isEnabled) VALUES
+                ('s1', 'p1', 1), ('s2', 'p1', 1), ('s3', 'p3', 1), ('s4', 'gone', 1);",
+        )
+        .unwrap();
+        let enabled = |conn: &rusqlite::Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM slack_sync_metadata WHERE isEnabled = 1", [], |row| row.get(0)).unwrap()
+        };
+
+        assert_eq!(archive_project_rows(&mut conn, "p1", "2026-04-01").unwrap(), 2);
+        assert_eq!(load_project(&conn, "p1").unwrap().unwrap()["status"], "archived");
+        assert_eq!(enabled(&conn), 2);
+
+        // The project row is missing, so the sync deactivation is rolled back
+        assert!(archive_project_rows(&mut conn, "gone", "2026-04-02").is_err());
+        assert_eq!(enabled(&conn), 2);
+    }
+
+    #[test]
+    fn status_filter_applies_to_items_and_total() {
+        let conn = seeded_db();
+
+        let page = load_projects_page(&conn, Some("active"), ProjectSort::Name, 0, None).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(ids(&page), vec!["p1", "p3"]);
+    }
+}
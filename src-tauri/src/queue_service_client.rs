@@ -3,6 +3,9 @@ use reqwest;
 use log::{info, warn, error, debug};
 use thiserror::Error;
 use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use crate::service_timeouts::TimeoutPolicy;
+use crate::http_client::{send_traced, RequestTraced, Traced};
 
 #[derive(Error, Debug)]
 pub enum QueueServiceError {
@@ -16,6 +19,34 @@ pub enum QueueServiceError {
     QueueError(String),
     #[error("Job not found: {0}")]
     JobNotFound(String),
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    #[error(transparent)]
+    Traced(#[from] Traced<QueueServiceError>),
+}
+
+impl RequestTraced for QueueServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            QueueServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
+}
+
+/// How long `wait_for_job` keeps retrying a job the queue doesn't know about yet
+const JOB_REGISTRATION_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Default interval between polls when streaming job progress to the frontend
+pub const DEFAULT_PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Payload of the `queue-job-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgressEvent {
+    pub job_id: String,
+    pub queue: String,
+    pub progress: serde_json::Value,
+    pub status: String,
 }
 
 // Request/Response types
@@ -39,6 +70,19 @@ pub struct JobOptions {
     pub attempts: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remove_on_complete: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<BackoffSpec>,
+    /// Dedup key: while a job with this id is waiting or running, adding another is a no-op
+    #[serde(rename = "jobId", skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+}
+
+/// Delay between retry attempts, in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BackoffSpec {
+    Fixed { delay: u64 },
+    Exponential { delay: u64 },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,9 +94,12 @@ pub struct JobResponse {
     pub status: String,
     #[serde(rename = "createdAt")]
     pub created_at: String,
+    /// True when the job already existed and nothing new was queued
+    #[serde(default)]
+    pub deduplicated: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct JobStatus {
     pub id: String,
     pub queue: String,
@@ -63,7 +110,7 @@ pub struct JobStatus {
     pub data: serde_json::Value,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<serde_json::Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "failedReason", skip_serializing_if = "Option::is_none")]
     pub failed_reason: Option<String>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
@@ -81,7 +128,7 @@ pub struct QueueJobs {
     pub failed: Vec<JobInfo>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct JobInfo {
     pub id: String,
     #[serde(rename = "type")]
@@ -92,12 +139,15 @@ pub struct JobInfo {
     pub progress: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<serde_json::Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "failedReason", skip_serializing_if = "Option::is_none")]
     pub failed_reason: Option<String>,
+    /// Not reported for completed and failed jobs
     #[serde(rename = "createdAt")]
-    pub created_at: String,
+    pub created_at: Option<String>,
     #[serde(rename = "finishedAt")]
     pub finished_at: Option<String>,
+    #[serde(rename = "failedAt")]
+    pub failed_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +173,11 @@ pub struct SlackSyncRequest {
     pub last_timestamp: Option<u64>,
 }
 
+/// Job id shared by every sync of one project's channel, so only one can be pending at a time
+pub fn slack_sync_job_key(project_id: &str, channel_id: &str) -> String {
+    format!("slack-sync_{}_{}", project_id, channel_id)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageAnalysisRequest {
     pub messages: serde_json::Value,
@@ -149,10 +204,23 @@ pub struct ServiceResponse<T> {
     pub error: Option<String>,
 }
 
+fn priority_options(priority: i32) -> Option<JobOptions> {
+    Some(JobOptions {
+        priority: Some(priority),
+        delay: Some(0),
+        attempts: None,
+        remove_on_complete: None,
+        backoff: None,
+        job_id: None,
+    })
+}
+
 #[derive(Clone)]
 pub struct QueueServiceClient {
     base_url: String,
     client: reqwest::Client,
+    progress_poll_interval: Duration,
+    timeouts: TimeoutPolicy,
 }
 
 impl QueueServiceClient {
@@ -161,12 +229,27 @@ impl QueueServiceClient {
         
         info!("🚀 Initializing Queue Service Client at {}", base_url);
         
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
-        Self { base_url, client }
+        Self {
+            base_url,
+            client: crate::http_client::shared_client(),
+            progress_poll_interval: DEFAULT_PROGRESS_POLL_INTERVAL,
+            timeouts: TimeoutPolicy::configured(),
+        }
+    }
+    
+    /// Override how often `stream_job_progress` polls the queue service
+    pub fn with_progress_poll_interval(mut self, interval: Duration) -> Self {
+        self.progress_poll_interval = interval;
+        self
+    }
+    
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+    
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
     }
     
     pub async fn health_check(&self) -> Result<bool, QueueServiceError> {
@@ -174,7 +257,8 @@ impl QueueServiceClient {
         
         let url = format!("{}/health", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("queue", self.client.get(&url).timeout(self.timeouts.health)).await;
+        match response {
             Ok(response) => {
                 if response.status().is_success() {
                     info!("✅ Queue service health check passed");
@@ -186,7 +270,7 @@ impl QueueServiceClient {
             }
             Err(e) => {
                 error!("❌ Queue service health check request failed: {}", e);
-                Err(QueueServiceError::Http(e))
+                Err(QueueServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -196,13 +280,13 @@ impl QueueServiceClient {
         
         let url = format!("{}/api/queue/jobs", self.base_url);
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "queue",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| QueueServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<JobResponse>(response).await
+        self.handle_response::<JobResponse>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn get_job_status(&self, queue: &str, job_id: &str) -> Result<JobStatus, QueueServiceError> {
@@ -210,12 +294,105 @@ impl QueueServiceClient {
         
         let url = format!("{}/api/queue/jobs/{}/{}", self.base_url, queue, job_id);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "queue",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| QueueServiceError::from(e).with_request_id(&request_id))?;
+        
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(QueueServiceError::JobNotFound(format!("{} in queue {}", job_id, queue)).with_request_id(&request_id));
+        }
         
-        self.handle_response::<JobStatus>(response).await
+        let job = self.handle_response::<serde_json::Value>(response).await.map_err(|e| e.with_request_id(&request_id))?;
+        parse_job_status(job, queue, job_id).map_err(|e| e.with_request_id(&request_id))
+    }
+    
+    /// Poll a job until it is `completed` or `failed`, or until `timeout` elapses.
+    /// Jobs that the queue hasn't registered yet are retried for a short grace period.
+    pub async fn wait_for_job(
+        &self,
+        queue: &str,
+        job_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<JobStatus, QueueServiceError> {
+        info!("⏳ Waiting for job {} in queue {} (timeout: {:?})", job_id, queue, timeout);
+        self.poll_job(queue, job_id, poll_interval, timeout, |status| {
+            debug!("⏳ Job {} is {} (progress: {})", job_id, status.status, status.progress);
+        }).await
+    }
+    
+    /// Like `wait_for_job`, but emits `queue-job-progress` events along the way.
+    /// Only changes in progress or status are emitted.
+    pub async fn stream_job_progress(
+        &self,
+        app: &AppHandle,
+        queue: &str,
+        job_id: &str,
+        timeout: Duration,
+    ) -> Result<JobStatus, QueueServiceError> {
+        info!("📡 Streaming progress for job {} in queue {}", job_id, queue);
+        
+        let mut last_emitted: Option<(serde_json::Value, String)> = None;
+        self.poll_job(queue, job_id, self.progress_poll_interval, timeout, |status| {
+            let snapshot = (status.progress.clone(), status.status.clone());
+            if last_emitted.as_ref() == Some(&snapshot) {
+                return;
+            }
+            let event = JobProgressEvent {
+                job_id: job_id.to_string(),
+                queue: queue.to_string(),
+                progress: status.progress.clone(),
+                status: status.status.clone(),
+            };
+            if let Err(e) = app.emit("queue-job-progress", &event) {
+                warn!("⚠️ Failed to emit job progress event: {}", e);
+            }
+            last_emitted = Some(snapshot);
+        }).await
+    }
+    
+    /// Poll a job every `poll_interval`, handing each status to `on_status`, until it
+    /// finishes or `timeout` elapses
+    async fn poll_job(
+        &self,
+        queue: &str,
+        job_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+        mut on_status: impl FnMut(&JobStatus),
+    ) -> Result<JobStatus, QueueServiceError> {
+        let started = std::time::Instant::now();
+        
+        loop {
+            match self.get_job_status(queue, job_id).await {
+                Ok(status) => {
+                    on_status(&status);
+                    if status.status == "completed" || status.status == "failed" {
+                        info!("🏁 Job {} finished with status: {}", job_id, status.status);
+                        return Ok(status);
+                    }
+                }
+                Err(e) if matches!(e.untraced(), QueueServiceError::JobNotFound(_)) => {
+                    if started.elapsed() >= JOB_REGISTRATION_GRACE_PERIOD {
+                        warn!("⚠️ Job {} still not found after grace period", job_id);
+                        return Err(e);
+                    }
+                    debug!("🔍 Job {} not registered yet, retrying", job_id);
+                }
+                Err(e) => return Err(e),
+            }
+            
+            if started.elapsed() + poll_interval > timeout {
+                warn!("⏰ Timed out waiting for job {} in queue {}", job_id, queue);
+                return Err(QueueServiceError::Timeout(format!(
+                    "job {} in queue {} did not finish within {:?}", job_id, queue, timeout
+                )));
+            }
+            
+            tokio::time::sleep(poll_interval).await;
+        }
     }
     
     pub async fn get_queue_jobs(&self, queue: &str) -> Result<QueueJobs, QueueServiceError> {
@@ -223,12 +400,13 @@ impl QueueServiceClient {
         
         let url = format!("{}/api/queue/jobs/{}", self.base_url, queue);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "queue",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| QueueServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<QueueJobs>(response).await
+        self.handle_response::<QueueJobs>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn cancel_job(&self, queue: &str, job_id: &str) -> Result<(), QueueServiceError> {
@@ -236,10 +414,11 @@ impl QueueServiceClient {
         
         let url = format!("{}/api/queue/jobs/{}/{}", self.base_url, queue, job_id);
         
-        let response = self.client
-            .delete(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "queue",
+            self.client.delete(&url).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| QueueServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             info!("✅ Job cancelled successfully");
@@ -247,8 +426,35 @@ impl QueueServiceClient {
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to cancel job: {}", error_text);
-            Err(QueueServiceError::QueueError(error_text))
+            Err(QueueServiceError::QueueError(error_text).with_request_id(&request_id))
+        }
+    }
+    
+    /// Jobs that exhausted their retries, with their `failed_reason`
+    pub async fn get_failed_jobs(&self, queue: &str) -> Result<Vec<JobInfo>, QueueServiceError> {
+        debug!("💀 Getting failed jobs for queue: {}", queue);
+        
+        let jobs = self.get_queue_jobs(queue).await?;
+        Ok(jobs.failed)
+    }
+    
+    /// Cancel every waiting or active job in `queue` that matches `predicate`.
+    /// Returns how many jobs were cancelled; individual failures are logged and skipped.
+    pub async fn cancel_jobs_for(&self, queue: &str, predicate: impl Fn(&JobInfo) -> bool) -> Result<usize, QueueServiceError> {
+        info!("🗑️ Cancelling matching jobs in queue {}", queue);
+        
+        let jobs = self.get_queue_jobs(queue).await?;
+        let mut cancelled = 0;
+        
+        for job in cancellable_jobs(&jobs, predicate) {
+            match self.cancel_job(queue, &job.id).await {
+                Ok(_) => cancelled += 1,
+                Err(e) => warn!("⚠️ Failed to cancel job {} in queue {}: {}", job.id, queue, e),
+            }
         }
+        
+        info!("✅ Cancelled {} jobs in queue {}", cancelled, queue);
+        Ok(cancelled)
     }
     
     pub async fn get_queue_stats(&self) -> Result<std::collections::HashMap<String, QueueStats>, QueueServiceError> {
@@ -256,50 +462,73 @@ impl QueueServiceClient {
         
         let url = format!("{}/api/queue/stats", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "queue",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| QueueServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<std::collections::HashMap<String, QueueStats>>(response).await
+        self.handle_response::<std::collections::HashMap<String, QueueStats>>(response).await.map_err(|e| e.with_request_id(&request_id))
+    }
+    
+    /// Queue a job with a typed payload through the generic `/api/queue/jobs` endpoint
+    pub async fn enqueue<T: Serialize>(
+        &self,
+        queue: &str,
+        job_type: &str,
+        data: T,
+        options: Option<JobOptions>,
+    ) -> Result<JobResponse, QueueServiceError> {
+        let data = serde_json::to_value(data)
+            .map_err(|e| QueueServiceError::QueueError(format!("Failed to serialize job data: {}", e)))?;
+        
+        self.add_job(JobRequest {
+            queue: queue.to_string(),
+            job_type: job_type.to_string(),
+            data,
+            options,
+        }).await
     }
     
     // Slack-specific methods
+    /// Queue a channel sync keyed by project and channel. If that channel already has a sync
+    /// waiting or running, the existing job is returned with `deduplicated` set.
     pub async fn queue_slack_sync(&self, request: SlackSyncRequest) -> Result<JobResponse, QueueServiceError> {
+        self.queue_slack_sync_after(request, Duration::ZERO).await
+    }
+    
+    /// Like `queue_slack_sync`, but the job only becomes runnable after `delay`
+    pub async fn queue_slack_sync_after(&self, request: SlackSyncRequest, delay: Duration) -> Result<JobResponse, QueueServiceError> {
         info!("🔄 Queueing Slack channel sync for {}", request.channel_name);
         
-        let url = format!("{}/api/queue/slack/sync-channel", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let request = SlackSyncRequest {
+            last_timestamp: request.last_timestamp.or(Some(0)),
+            ..request
+        };
+        let options = priority_options(5).map(|options| JobOptions {
+            delay: Some(delay.as_millis() as u64),
+            job_id: Some(slack_sync_job_key(&request.project_id, &request.channel_id)),
+            ..options
+        });
         
-        self.handle_response::<JobResponse>(response).await
+        let job = self.enqueue("slack-sync", "channel-sync", request, options).await?;
+        if job.deduplicated {
+            debug!("⏭️ Slack sync already pending as job {}", job.id);
+        }
+        Ok(job)
     }
     
     pub async fn queue_slack_analysis(&self, request: MessageAnalysisRequest) -> Result<JobResponse, QueueServiceError> {
         info!("🤖 Queueing Slack message analysis: {}", request.analysis_type);
         
-        let url = format!("{}/api/queue/slack/analyze-messages", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response::<JobResponse>(response).await
+        self.enqueue("slack-sync", "message-analysis", request, priority_options(3)).await
     }
     
     // AI analysis methods
     pub async fn queue_task_detection(&self, messages: serde_json::Value, project_context: Option<serde_json::Value>) -> Result<JobResponse, QueueServiceError> {
         info!("🎯 Queueing AI task detection");
         
-        let url = format!("{}/api/queue/ai/detect-tasks", self.base_url);
-        
-        let request = serde_json::json!({
+        let data = serde_json::json!({
             "messages": messages,
             "projectContext": project_context,
             "options": {
@@ -307,74 +536,44 @@ impl QueueServiceClient {
             }
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response::<JobResponse>(response).await
+        self.enqueue("ai-analysis", "task-detection", data, priority_options(2)).await
     }
     
     pub async fn queue_project_analysis(&self, messages: serde_json::Value, project_context: serde_json::Value) -> Result<JobResponse, QueueServiceError> {
         info!("📊 Queueing project update analysis");
         
-        let url = format!("{}/api/queue/ai/analyze-project-updates", self.base_url);
-        
-        let request = serde_json::json!({
+        let data = serde_json::json!({
             "messages": messages,
             "projectContext": project_context,
             "updateType": "general"
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response::<JobResponse>(response).await
+        self.enqueue("ai-analysis", "project-update", data, priority_options(2)).await
     }
     
     // WhatsApp methods
     pub async fn queue_whatsapp_sync(&self, chat_id: &str, last_timestamp: Option<u64>) -> Result<JobResponse, QueueServiceError> {
         info!("📱 Queueing WhatsApp message sync for chat: {}", chat_id);
         
-        let url = format!("{}/api/queue/whatsapp/sync-messages", self.base_url);
-        
-        let request = serde_json::json!({
+        let data = serde_json::json!({
             "chatId": chat_id,
             "lastTimestamp": last_timestamp.unwrap_or(0),
             "syncType": "incremental"
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response::<JobResponse>(response).await
+        self.enqueue("whatsapp-sync", "message-sync", data, priority_options(4)).await
     }
     
     pub async fn queue_whatsapp_analysis(&self, messages: serde_json::Value, analysis_type: &str) -> Result<JobResponse, QueueServiceError> {
         info!("🔍 Queueing WhatsApp analysis: {}", analysis_type);
         
-        let url = format!("{}/api/queue/whatsapp/analyze", self.base_url);
-        
-        let request = serde_json::json!({
+        let data = serde_json::json!({
             "messages": messages,
             "analysisType": analysis_type,
             "context": {}
         });
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response::<JobResponse>(response).await
+        self.enqueue("whatsapp-sync", "ai-analysis", data, priority_options(3)).await
     }
     
     async fn handle_response<T>(&self, response: reqwest::Response) -> Result<T, QueueServiceError>
@@ -419,4 +618,100 @@ impl QueueServiceClient {
             Err(QueueServiceError::ServiceUnavailable(format!("HTTP {}: {}", status, response_text)))
         }
     }
-}
\ No newline at end of file
+}
+
+/// The service answers unknown jobs with HTTP 200 and `{ "status": "not_found" }`
+fn parse_job_status(job: serde_json::Value, queue: &str, job_id: &str) -> Result<JobStatus, QueueServiceError> {
+    if job.get("status").and_then(|status| status.as_str()) == Some("not_found") {
+        return Err(QueueServiceError::JobNotFound(format!("{} in queue {}", job_id, queue)));
+    }
+    serde_json::from_value(job).map_err(|e| QueueServiceError::InvalidResponse(e.to_string()))
+}
+
+/// Waiting and active jobs matching `predicate`; finished jobs can't be cancelled
+fn cancellable_jobs<'a>(jobs: &'a QueueJobs, predicate: impl Fn(&JobInfo) -> bool) -> Vec<&'a JobInfo> {
+    jobs.waiting.iter().chain(jobs.active.iter()).filter(|job| predicate(job)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slack_sync_jobs_are_keyed_by_project_and_channel() {
+        assert_eq!(slack_sync_job_key("p1", "C1"), slack_sync_job_key("p1", "C1"));
+        assert_ne!(slack_sync_job_key("p1", "C1"), slack_sync_job_key("p2", "C1"));
+
+        let options = serde_json::to_value(JobOptions {
+            job_id: Some(slack_sync_job_key("p1", "C1")),
+            ..priority_options(5).unwrap()
+        }).unwrap();
+        assert_eq!(options["jobId"], "slack-sync_p1_C1");
+    }
+
+    #[test]
+    fn job_responses_without_dedup_flag_are_new_jobs() {
+        let job: JobResponse = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "queue": "slack-sync",
+            "type": "channel-sync",
+            "status": "queued",
+            "createdAt": "2026-01-01T00:00:00Z"
+        })).unwrap();
+        assert!(!job.deduplicated);
+    }
+
+    #[test]
+    fn finished_job_lists_parse_without_creation_times() {
+        let jobs: QueueJobs = serde_json::from_value(serde_json::json!({
+            "waiting": [{ "id": "1", "type": "channel-sync", "data": {}, "createdAt": "2026-01-01T00:00:00Z" }],
+            "active": [],
+            "completed": [{ "id": "2", "type": "channel-sync", "result": {}, "finishedAt": "2026-01-01T00:05:00Z" }],
+            "failed": [{
+                "id": "3",
+                "type": "channel-sync",
+                "failedReason": "Slack rate limited",
+                "failedAt": "2026-01-01T00:06:00Z"
+            }]
+        })).unwrap();
+
+        assert_eq!(jobs.waiting[0].created_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+        assert!(jobs.completed[0].created_at.is_none());
+        assert_eq!(jobs.failed[0].failed_reason.as_deref(), Some("Slack rate limited"));
+        assert_eq!(jobs.failed[0].failed_at.as_deref(), Some("2026-01-01T00:06:00Z"));
+    }
+
+    #[test]
+    fn only_pending_jobs_for_the_channel_are_cancelled() {
+        let job = |id: &str, channel: &str| serde_json::json!({ "id": id, "type": "channel-sync", "data": { "channelId": channel } });
+        let jobs: QueueJobs = serde_json::from_value(serde_json::json!({
+            "waiting": [job("1", "C1"), job("2", "C2")],
+            "active": [job("3", "C1")],
+            "completed": [job("4", "C1")],
+            "failed": [job("5", "C1")]
+        })).unwrap();
+
+        let for_c1 = |job: &JobInfo| job.data.as_ref().and_then(|data| data["channelId"].as_str()) == Some("C1");
+        let ids: Vec<&str> = cancellable_jobs(&jobs, for_c1).iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn unknown_jobs_are_reported_as_not_found() {
+        let missing = parse_job_status(serde_json::json!({ "status": "not_found" }), "slack-sync", "7");
+        assert!(matches!(missing, Err(QueueServiceError::JobNotFound(_))));
+
+        let job = parse_job_status(serde_json::json!({
+            "id": "7",
+            "queue": "slack-sync",
+            "type": "channel-sync",
+            "status": "active",
+            "progress": 40,
+            "data": {},
+            "createdAt": "2026-01-01T00:00:00Z",
+            "processedAt": null,
+            "finishedAt": null
+        }), "slack-sync", "7").unwrap();
+        assert_eq!(job.status, "active");
+    }
+}
@@ -0,0 +1,424 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Maximum number of results returned by a single search
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Characters of context kept on each side of a match in LIKE-fallback snippets
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Project,
+    Document,
+}
+
+impl SearchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Project => "project",
+            SearchKind::Document => "document",
+        }
+    }
+
+    /// Table and searchable columns, in ranking order (earlier fields weigh more)
+    fn source(&self) -> (&'static str, &'static str, &'static str, &'static [&'static str]) {
+        match self {
+            SearchKind::Project => ("projects", "id", "name", &["name", "description"]),
+            SearchKind::Document => ("documents", "projectId", "title", &["title", "description", "content"]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub matched_field: String,
+    pub snippet: String,
+    /// Higher is more relevant
+    pub score: f64,
+}
+
+/// Search project names/descriptions and document contents in the app database
+pub async fn search(
+    app: AppHandle,
+    query: String,
+    kinds: Vec<SearchKind>,
+) -> Result<Vec<SearchResult>, String> {
+    println!("🔎 [search] Searching for '{}' in {:?}", query, kinds);
+
+    let results = crate::app_db::with_existing_app_db(&app, move |conn| {
+        let use_fts = fts5_available(conn);
+        search_connection(conn, &query, &kinds, DEFAULT_SEARCH_LIMIT, use_fts)
+    })
+    .await?;
+
+    if results.is_none() {
+        println!("ℹ️ Database file doesn't exist, nothing to search");
+    }
+    Ok(results.unwrap_or_default())
+}
+
+/// Whether this SQLite build has the FTS5 extension compiled in
+pub fn fts5_available(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT sqlite_compileoption_used('ENABLE_FTS5')",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|used| used == 1)
+    .unwrap_or(false)
+}
+
+/// Run a search against `conn`. An empty `kinds` searches everything.
+pub fn search_connection(
+    conn: &Connection,
+    query: &str,
+    kinds: &[SearchKind],
+    limit: usize,
+    use_fts: bool,
+) -> Result<Vec<SearchResult>, String> {
+    if query.trim().is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+
+    let kinds: Vec<SearchKind> = if kinds.is_empty() {
+        vec![SearchKind::Project, SearchKind::Document]
+    } else {
+        kinds.to_vec()
+    };
+
+    let mut results = if use_fts {
+        search_fts(conn, query, &kinds)?
+    } else {
+        search_like(conn, query, &kinds)?
+    };
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    // An item matching in several fields is listed once, under its best-scoring field
+    let mut seen = std::collections::HashSet::new();
+    results.retain(|result| seen.insert((result.kind, result.id.clone())));
+    results.truncate(limit);
+    Ok(results)
+}
+
+/// Turn free text into an FTS5 query: every term quoted and prefix-matched, all required
+fn fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sqlite_object_exists(conn: &Connection, object_type: &str, name: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = ?1 AND name = ?2",
+        params![object_type, name],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(|e| format!("Failed to inspect database: {}", e))
+}
+
+/// Statements indexing every searchable field of `row`: `new` inside a trigger, or an alias
+/// defined by `from` when indexing existing rows
+fn index_row_sql(kind: SearchKind, row: &str, from: &str) -> String {
+    let (_, project_column, title_column, fields) = kind.source();
+    fields
+        .iter()
+        .enumerate()
+        .map(|(position, field)| {
+            format!(
+                "INSERT INTO search_index (kind, id, project_id, title, field, weight, body)
+                 SELECT '{kind}', {row}.id, {row}.{project}, {row}.{title}, '{field}', {weight}, {row}.{field} {from}
+                 WHERE {row}.{field} IS NOT NULL AND {row}.{field} != '';",
+                kind = kind.as_str(),
+                row = row,
+                project = project_column,
+                title = title_column,
+                field = field,
+                weight = field_weight(position),
+                from = from,
+            )
+        })
+        .collect()
+}
+
+/// Create the FTS5 index the first time it's needed and keep it current with triggers, so
+/// searches don't re-index everything. A kind whose table doesn't exist yet is indexed once
+/// it does.
+pub fn ensure_search_index(conn: &Connection, kinds: &[SearchKind]) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+             kind UNINDEXED, id UNINDEXED, project_id UNINDEXED, title UNINDEXED,
+             field UNINDEXED, weight UNINDEXED, body
+         );",
+    )
+    .map_err(|e| format!("Failed to create search index: {}", e))?;
+
+    for kind in kinds {
+        let (table, ..) = kind.source();
+        let trigger = format!("search_index_{}", table);
+        if !sqlite_object_exists(conn, "table", table)? || sqlite_object_exists(conn, "trigger", &format!("{}_insert", trigger))? {
+            continue;
+        }
+
+        let remove_old = format!("DELETE FROM search_index WHERE kind = '{}' AND id = old.id;", kind.as_str());
+        let sql = format!(
+            "BEGIN;
+             DELETE FROM search_index WHERE kind = '{kind}';
+             {backfill}
+             CREATE TRIGGER {trigger}_insert AFTER INSERT ON {table} BEGIN {index_new} END;
+             CREATE TRIGGER {trigger}_update AFTER UPDATE ON {table} BEGIN {remove_old} {index_new} END;
+             CREATE TRIGGER {trigger}_delete AFTER DELETE ON {table} BEGIN {remove_old} END;
+             COMMIT;",
+            kind = kind.as_str(),
+            backfill = index_row_sql(*kind, "source", &format!("FROM {} AS source", table)),
+            trigger = trigger,
+            table = table,
+            index_new = index_row_sql(*kind, "new", ""),
+            remove_old = remove_old,
+        );
+        if let Err(e) = conn.execute_batch(&sql) {
+            let _ = conn.execute_batch("ROLLBACK");
+            return Err(format!("Failed to index {}: {}", table, e));
+        }
+    }
+    Ok(())
+}
+
+/// Rank matches in the FTS5 index with bm25
+fn search_fts(conn: &Connection, query: &str, kinds: &[SearchKind]) -> Result<Vec<SearchResult>, String> {
+    ensure_search_index(conn, kinds)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT kind, id, project_id, title, field, weight,
+                    snippet(search_index, 6, '[', ']', '…', 12), bm25(search_index)
+             FROM search_index WHERE search_index MATCH ?1",
+        )
+        .map_err(|e| format!("Failed to prepare search: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![fts_query(query)], |row| {
+            let kind: String = row.get(0)?;
+            let weight: f64 = row.get(5)?;
+            let rank: f64 = row.get(7)?;
+            Ok(SearchResult {
+                kind: if kind == "project" { SearchKind::Project } else { SearchKind::Document },
+                id: row.get(1)?,
+                project_id: row.get(2)?,
+                title: row.get(3)?,
+                matched_field: row.get(4)?,
+                snippet: row.get(6)?,
+                // bm25 is lower-is-better and negative for matches
+                score: -rank * weight,
+            })
+        })
+        .map_err(|e| format!("Search failed: {}", e))?;
+
+    let results = rows.collect::<Result<Vec<SearchResult>, _>>()
+        .map_err(|e| format!("Failed to read search results: {}", e))?;
+    Ok(results.into_iter().filter(|result| kinds.contains(&result.kind)).collect())
+}
+
+/// Plain LIKE search, used when FTS5 is not compiled in
+fn search_like(conn: &Connection, query: &str, kinds: &[SearchKind]) -> Result<Vec<SearchResult>, String> {
+    let needle = query.trim().to_lowercase();
+    let pattern = format!(
+        "%{}%",
+        needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+    let mut results = Vec::new();
+
+    for kind in kinds {
+        let (table, project_column, title_column, fields) = kind.source();
+        for (position, field) in fields.iter().enumerate() {
+            let sql = format!(
+                "SELECT id, {project}, {title}, {field} FROM {table}
+                 WHERE lower({field}) LIKE ?1 ESCAPE '\\'",
+                project = project_column,
+                title = title_column,
+                field = field,
+                table = table,
+            );
+            let mut stmt = conn
+                .prepare(&sql)
+                .map_err(|e| format!("Failed to prepare search on {}: {}", table, e))?;
+            let rows = stmt
+                .query_map(params![pattern], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                })
+                .map_err(|e| format!("Search failed on {}: {}", table, e))?;
+
+            for row in rows {
+                let (id, project_id, title, text) =
+                    row.map_err(|e| format!("Failed to read search results: {}", e))?;
+                let occurrences = text.to_lowercase().matches(&needle).count().max(1);
+                results.push(SearchResult {
+                    kind: *kind,
+                    id,
+                    project_id,
+                    title,
+                    matched_field: field.to_string(),
+                    snippet: like_snippet(&text, &needle),
+                    score: occurrences as f64 * field_weight(position),
+                });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Titles/names outrank descriptions, which outrank body content
+fn field_weight(position: usize) -> f64 {
+    match position {
+        0 => 3.0,
+        1 => 2.0,
+        _ => 1.0,
+    }
+}
+
+/// Cut a window of text around the first occurrence of `needle` (already lowercased),
+/// bracketing the match. Matching is done per character, since lowercasing can change
+/// how many bytes or characters a piece of text takes.
+fn like_snippet(text: &str, needle: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    // Lowercased characters, each with the index of the original character it came from
+    let lowered: Vec<(char, usize)> = chars
+        .iter()
+        .enumerate()
+        .flat_map(|(index, c)| c.to_lowercase().map(move |lower| (lower, index)))
+        .collect();
+    let needle: Vec<char> = needle.chars().collect();
+    let found = (!needle.is_empty())
+        .then(|| lowered.windows(needle.len()).position(|window| window.iter().map(|(c, _)| *c).eq(needle.iter().copied())))
+        .flatten();
+    let (start, end) = match found {
+        Some(position) => (lowered[position].1, lowered[position + needle.len() - 1].1 + 1),
+        None => return chars.iter().take(SNIPPET_CONTEXT_CHARS * 2).collect(),
+    };
+    let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (end + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    format!(
+        "{}{}[{}]{}{}",
+        if from > 0 { "…" } else { "" },
+        chars[from..start].iter().collect::<String>(),
+        chars[start..end].iter().collect::<String>(),
+        chars[end..to].iter().collect::<String>(),
+        if to < chars.len() { "…" } else { "" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE projects (id TEXT PRIMARY KEY, name TEXT NOT NULL, description TEXT NOT NULL);
+             CREATE TABLE documents (id TEXT PRIMARY KEY, projectId TEXT NOT NULL, title TEXT NOT NULL,
+                                     description TEXT, content TEXT);
+             INSERT INTO projects VALUES ('p1', 'Website redesign', 'New landing page');
+             INSERT INTO projects VALUES ('p2', 'Hiring', 'Plan the website launch hiring');
+             INSERT INTO documents VALUES ('d1', 'p2', 'Kickoff notes', NULL,
+                                           'We agreed the website copy is owned by marketing');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn like_fallback_ranks_names_above_content() {
+        let conn = seeded_connection();
+        let results = search_connection(&conn, "Website", &[], 10, false).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].id, "p1");
+        assert_eq!(results[0].matched_field, "name");
+        assert!(results[0].snippet.contains("[Website]"));
+
+        let document = results.iter().find(|r| r.kind == SearchKind::Document).unwrap();
+        assert_eq!(document.project_id, "p2");
+        assert_eq!(document.matched_field, "content");
+    }
+
+    #[test]
+    fn kinds_filter_limits_sources() {
+        let conn = seeded_connection();
+        let results = search_connection(&conn, "website", &[SearchKind::Document], 10, false).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "d1");
+    }
+
+    #[test]
+    fn fts_search_matches_prefixes_when_available() {
+        let conn = seeded_connection();
+        if !fts5_available(&conn) {
+            return;
+        }
+        let results = search_connection(&conn, "launc", &[SearchKind::Project], 10, true).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "p2");
+        assert!(results[0].snippet.contains("[launch]"));
+    }
+
+    #[test]
+    fn items_matching_in_several_fields_are_listed_once() {
+        let conn = seeded_connection();
+        conn.execute("INSERT INTO projects VALUES ('p3', 'Launch', 'Launch checklist')", []).unwrap();
+
+        for use_fts in [false, fts5_available(&conn)] {
+            let results = search_connection(&conn, "launch", &[SearchKind::Project], 10, use_fts).unwrap();
+            let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+            assert_eq!(ids, vec!["p3", "p2"]);
+            assert_eq!(results[0].matched_field, "name");
+        }
+    }
+
+    #[test]
+    fn fts_index_follows_table_changes() {
+        let conn = seeded_connection();
+        if !fts5_available(&conn) {
+            return;
+        }
+        assert_eq!(search_connection(&conn, "roadmap", &[], 10, true).unwrap().len(), 0);
+
+        conn.execute("INSERT INTO projects VALUES ('p3', 'Roadmap', '')", []).unwrap();
+        conn.execute("UPDATE documents SET title = 'Roadmap notes' WHERE id = 'd1'", []).unwrap();
+        let results = search_connection(&conn, "roadmap", &[], 10, true).unwrap();
+        assert_eq!(results.len(), 2);
+
+        conn.execute("DELETE FROM projects WHERE id = 'p3'", []).unwrap();
+        let results = search_connection(&conn, "roadmap", &[], 10, true).unwrap();
+        assert_eq!(results.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["d1"]);
+    }
+
+    #[test]
+    fn snippets_handle_text_that_changes_length_when_lowercased() {
+        assert_eq!(like_snippet("İstanbul office", "office"), "İstanbul [office]");
+        assert_eq!(like_snippet("Ünïcode ÄRGER", "ärger"), "Ünïcode [ÄRGER]");
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        let conn = seeded_connection();
+        assert!(search_connection(&conn, "  ", &[], 10, false).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+/// How often the supervisor polls `services_health`
+pub const SUPERVISOR_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Consecutive failed checks before a service is restarted
+pub const FAILURES_BEFORE_RESTART: u32 = 3;
+/// Restarts allowed per service within `RESTART_WINDOW`
+pub const MAX_RESTARTS_PER_HOUR: usize = 3;
+const RESTART_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// How long services get to exit after SIGTERM before they are killed
+pub const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// Port each supervised service listens on, matching the service clients' defaults
+const SERVICE_PORTS: &[(&str, u16)] = &[
+    ("whatsapp", 3001),
+    ("ai", 3002),
+    ("oauth", 3003),
+    ("queue", 3004),
+    ("slack", 3005),
+];
+
+// npm processes started for the embedded services, with the script that started them
+static SERVICE_PROCESSES: Lazy<std::sync::Mutex<Vec<(String, Child)>>> = Lazy::new(|| {
+    std::sync::Mutex::new(Vec::new())
+});
+
+/// Per-service failure streaks and recent restart times
+#[derive(Debug, Default)]
+pub struct SupervisorState {
+    consecutive_failures: HashMap<String, u32>,
+    restarts: HashMap<String, VecDeque<Instant>>,
+}
+
+impl SupervisorState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a health result and decide whether the service should be restarted now.
+    /// A restart resets the failure streak; restarts past the hourly cap are skipped.
+    pub fn record(&mut self, service: &str, up: bool, now: Instant) -> bool {
+        if up {
+            self.consecutive_failures.remove(service);
+            return false;
+        }
+
+        let failures = self.consecutive_failures.entry(service.to_string()).or_insert(0);
+        *failures += 1;
+        if *failures < FAILURES_BEFORE_RESTART {
+            return false;
+        }
+
+        let restarts = self.restarts.entry(service.to_string()).or_default();
+        while restarts.front().is_some_and(|at| now.duration_since(*at) >= RESTART_WINDOW) {
+            restarts.pop_front();
+        }
+        if restarts.len() >= MAX_RESTARTS_PER_HOUR {
+            println!("⚠️ [SUPERVISOR] {} is down but hit {} restarts this hour, not restarting", service, MAX_RESTARTS_PER_HOUR);
+            return false;
+        }
+
+        restarts.push_back(now);
+        *failures = 0;
+        true
+    }
+}
+
+/// Run an npm script from the project root and keep its handle so it can be stopped on exit.
+/// On unix the script gets its own process group, so signals reach the node processes too.
+pub fn spawn_npm_script(script: &str) -> std::io::Result<()> {
+    let mut command = Command::new("npm");
+    command.arg("run").arg(script).current_dir("../");
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    
+    let child = command.spawn()?;
+    SERVICE_PROCESSES.lock().unwrap_or_else(|e| e.into_inner())
+        .push((script.to_string(), child));
+    Ok(())
+}
+
+/// Remove the process started by `script` from the tracked processes
+fn take_service_process(script: &str) -> Option<(String, Child)> {
+    let mut processes = SERVICE_PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    let index = processes.iter().position(|(s, _)| s == script)?;
+    Some(processes.remove(index))
+}
+
+/// Kill whatever still listens on `port`. Services started together by `services:start`
+/// share one process group, so a single hung service can only be found by its port.
+#[cfg(unix)]
+fn free_port(port: u16) {
+    let pids = match Command::new("lsof").args(["-t", "-sTCP:LISTEN", &format!("-iTCP:{}", port)]).output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(e) => {
+            println!("⚠️ [SUPERVISOR] Could not look up the process on port {}: {}", port, e);
+            return;
+        }
+    };
+
+    for pid in pids.split_whitespace() {
+        println!("🛑 [SUPERVISOR] Killing process {} on port {}", pid, port);
+        let _ = Command::new("kill").arg("-KILL").arg(pid).status();
+    }
+}
+
+#[cfg(not(unix))]
+fn free_port(port: u16) {
+    let _ = Command::new("cmd")
+        .args(["/C", &format!("for /f \"tokens=5\" %p in ('netstat -ano ^| findstr LISTENING ^| findstr :{}') do taskkill /F /PID %p", port)])
+        .status();
+}
+
+/// Stop a service before it is restarted, so the new process can bind its port
+fn stop_service(service: &str) {
+    if let Some(child) = take_service_process(&format!("service:{}", service)) {
+        shutdown_children(vec![child], SHUTDOWN_GRACE_PERIOD);
+    }
+    if let Some((_, port)) = SERVICE_PORTS.iter().find(|(name, _)| *name == service) {
+        free_port(*port);
+    }
+}
+
+/// Stop a single Node service and start it again through its `service:<name>` npm script
+fn restart_service(service: &str) {
+    println!("🔁 [SUPERVISOR] Restarting {} service...", service);
+    stop_service(service);
+
+    match spawn_npm_script(&format!("service:{}", service)) {
+        Ok(()) => println!("✅ [SUPERVISOR] {} service restart initiated", service),
+        Err(e) => println!("❌ [SUPERVISOR] Failed to restart {} service: {}", service, e),
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(child: &Child, signal: &str) {
+    let _ = Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg("--")
+        .arg(format!("-{}", child.id()))
+        .status();
+}
+
+/// Ask a service to exit (SIGTERM to its process group on unix)
+fn terminate(child: &mut Child) {
+    #[cfg(unix)]
+    signal_process_group(child, "TERM");
+    #[cfg(not(unix))]
+    let _ = child.kill();
+}
+
+/// Kill a service that ignored `terminate`
+fn force_kill(child: &mut Child) {
+    #[cfg(unix)]
+    signal_process_group(child, "KILL");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Terminate `children`, wait up to `grace` for them to exit, then force-kill the rest.
+/// Returns how many exited gracefully and how many had to be killed.
+fn shutdown_children(mut children: Vec<(String, Child)>, grace: Duration) -> (usize, usize) {
+    for (_, child) in children.iter_mut() {
+        terminate(child);
+    }
+
+    let deadline = Instant::now() + grace;
+    let mut graceful = 0;
+    while !children.is_empty() && Instant::now() < deadline {
+        children.retain_mut(|(_, child)| match child.try_wait() {
+            Ok(Some(_)) | Err(_) => {
+                graceful += 1;
+                false
+            }
+            Ok(None) => true,
+        });
+        if !children.is_empty() {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    let forced = children.len();
+    for (script, child) in children.iter_mut() {
+        println!("⚠️ [SUPERVISOR] {} did not exit in time, killing it", script);
+        force_kill(child);
+    }
+    (graceful, forced)
+}
+
+/// Stop every tracked service process. Called once the app is really exiting.
+pub fn shutdown_services(grace: Duration) {
+    let children: Vec<(String, Child)> = SERVICE_PROCESSES.lock().unwrap_or_else(|e| e.into_inner())
+        .drain(..)
+        .collect();
+    if children.is_empty() {
+        return;
+    }
+
+    println!("🛑 [SUPERVISOR] Stopping {} service processes...", children.len());
+    let (graceful, forced) = shutdown_children(children, grace);
+    println!("✅ [SUPERVISOR] Services stopped ({} gracefully, {} forced)", graceful, forced);
+}
+
+/// Poll service health forever and restart services that stay down
+pub async fn run_service_supervisor() {
+    println!("🩺 [SUPERVISOR] Service supervisor started");
+    let mut state = SupervisorState::new();
+
+    loop {
+        tokio::time::sleep(SUPERVISOR_CHECK_INTERVAL).await;
+
+        let health = match crate::commands::system_commands::services_health().await {
+            Ok(health) => health,
+            Err(e) => {
+                println!("⚠️ [SUPERVISOR] Health check failed: {}", e);
+                continue;
+            }
+        };
+
+        let now = Instant::now();
+        for (service, status) in health {
+            if state.record(&service, status.up, now) {
+                restart_service(&service);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restarts_after_consecutive_failures() {
+        let mut state = SupervisorState::new();
+        let now = Instant::now();
+
+        assert!(!state.record("slack", false, now));
+        assert!(!state.record("slack", false, now));
+        assert!(!state.record("slack", true, now));
+        assert!(!state.record("slack", false, now));
+        assert!(!state.record("slack", false, now));
+        assert!(state.record("slack", false, now));
+    }
+
+    #[cfg(unix)]
+    fn spawn_in_group(script: &str) -> Child {
+        use std::os::unix::process::CommandExt;
+        Command::new("sh").arg("-c").arg(script).process_group(0).spawn().unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shutdown_terminates_then_forces_stubborn_processes() {
+        let children = vec![
+            ("polite".to_string(), spawn_in_group("sleep 30")),
+            ("stubborn".to_string(), spawn_in_group("trap '' TERM; while true; do sleep 0.1; done")),
+        ];
+
+        let (graceful, forced) = shutdown_children(children, Duration::from_millis(500));
+        assert_eq!(graceful, 1);
+        assert_eq!(forced, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stopping_a_service_kills_its_tracked_process() {
+        let child = spawn_in_group("sleep 30");
+        let pid = child.id();
+        SERVICE_PROCESSES.lock().unwrap().push(("service:test".to_string(), child));
+
+        stop_service("test");
+
+        assert!(take_service_process("service:test").is_none());
+        let alive = Command::new("kill").arg("-0").arg(pid.to_string())
+            .stderr(std::process::Stdio::null())
+            .status().unwrap().success();
+        assert!(!alive);
+    }
+
+    #[test]
+    fn restarts_are_capped_per_hour() {
+        let mut state = SupervisorState::new();
+        let start = Instant::now();
+
+        let mut restarts = 0;
+        for _ in 0..(FAILURES_BEFORE_RESTART as usize * (MAX_RESTARTS_PER_HOUR + 2)) {
+            if state.record("slack", false, start) {
+                restarts += 1;
+            }
+        }
+        assert_eq!(restarts, MAX_RESTARTS_PER_HOUR);
+
+        let later = start + RESTART_WINDOW;
+        assert!(state.record("slack", false, later));
+    }
+}
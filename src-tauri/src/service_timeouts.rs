@@ -0,0 +1,113 @@
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::commands::settings::{get_setting_as, SettingChanged, SERVICE_TIMEOUTS_SECS, SETTING_CHANGED_EVENT};
+
+/// Request timeouts used by the service clients, chosen per operation so a quick health
+/// check doesn't wait as long as a heavy analysis call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutPolicy {
+    /// Health checks
+    pub health: Duration,
+    /// Status and metadata lookups (job status, team/user info, connection state)
+    pub status: Duration,
+    /// Regular requests that don't fit the other categories
+    pub standard: Duration,
+    /// Message/history fetches, which can page through a lot of data
+    pub history: Duration,
+    /// AI analysis requests
+    pub analysis: Duration,
+}
+
+impl Default for TimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            health: Duration::from_secs(2),
+            status: Duration::from_secs(10),
+            standard: Duration::from_secs(30),
+            history: Duration::from_secs(60),
+            analysis: Duration::from_secs(120),
+        }
+    }
+}
+
+// Policy built from the `services.timeouts_secs` setting, used by newly created clients
+static CONFIGURED_POLICY: Lazy<RwLock<TimeoutPolicy>> = Lazy::new(|| RwLock::new(TimeoutPolicy::default()));
+
+impl TimeoutPolicy {
+    /// Build a policy from a `{ "health": 2, "history": 90, ... }` object of seconds.
+    /// Missing, non-numeric or zero entries keep their default.
+    pub fn from_setting(value: &Value) -> Self {
+        let mut policy = Self::default();
+        let fields: [(&str, &mut Duration); 5] = [
+            ("health", &mut policy.health),
+            ("status", &mut policy.status),
+            ("standard", &mut policy.standard),
+            ("history", &mut policy.history),
+            ("analysis", &mut policy.analysis),
+        ];
+        for (name, field) in fields {
+            if let Some(secs) = value.get(name).and_then(|v| v.as_u64()).filter(|secs| *secs > 0) {
+                *field = Duration::from_secs(secs);
+            }
+        }
+        policy
+    }
+
+    /// The policy currently configured through settings
+    pub fn configured() -> Self {
+        *CONFIGURED_POLICY.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_configured(policy: TimeoutPolicy) {
+        *CONFIGURED_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+}
+
+/// Load the configured timeouts and keep them in sync with `services.timeouts_secs` changes.
+/// Clients created afterwards pick up the new values.
+pub fn watch_timeout_setting(app: &AppHandle) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match get_setting_as::<Value>(&handle, SERVICE_TIMEOUTS_SECS).await {
+            Ok(value) => TimeoutPolicy::set_configured(TimeoutPolicy::from_setting(&value)),
+            Err(e) => println!("⚠️ Could not read {}: {}", SERVICE_TIMEOUTS_SECS, e),
+        }
+    });
+
+    app.listen(SETTING_CHANGED_EVENT, |event| {
+        let change: SettingChanged = match serde_json::from_str(event.payload()) {
+            Ok(change) => change,
+            Err(_) => return,
+        };
+        if change.key == SERVICE_TIMEOUTS_SECS {
+            let policy = TimeoutPolicy::from_setting(&change.value);
+            println!("⏱️ Service timeouts updated: {:?}", policy);
+            TimeoutPolicy::set_configured(policy);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setting_overrides_only_valid_entries() {
+        let policy = TimeoutPolicy::from_setting(&serde_json::json!({
+            "health": 1, "history": 300, "status": 0, "analysis": "slow"
+        }));
+        let defaults = TimeoutPolicy::default();
+
+        assert_eq!(policy.health, Duration::from_secs(1));
+        assert_eq!(policy.history, Duration::from_secs(300));
+        assert_eq!(policy.status, defaults.status);
+        assert_eq!(policy.analysis, defaults.analysis);
+        assert_eq!(policy.standard, defaults.standard);
+    }
+}
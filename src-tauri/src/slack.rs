@@ -7,6 +7,230 @@ use url::Url;
 use std::sync::Mutex;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
+use crate::locale::Locale;
+
+/// Why Slack rejected our credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+    MissingToken,
+    InvalidToken,
+    ExpiredToken,
+    AccountInactive,
+    InvalidClientCredentials,
+}
+
+/// Transport-level failures talking to Slack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpFailure {
+    Timeout,
+    Connect,
+    Request(String),
+    Status(u16),
+    RetriesExhausted,
+}
+
+/// Request inputs that must not be empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlackInput {
+    ChannelId,
+    AuthorizationCode,
+    ClientId,
+    ClientSecret,
+    RedirectUri,
+    RefreshToken,
+}
+
+/// Errors from the Slack Web API client. Variants carry only the error kind; the text shown
+/// to the user comes from `localized_message`, and `Display` uses the system locale.
+#[derive(Debug)]
+pub enum SlackError {
+    Auth(AuthFailure),
+    RateLimited { retry_after: Option<std::time::Duration> },
+    NotInChannel,
+    ChannelNotFound,
+    ChannelArchived,
+    UnsupportedChannelType,
+    UserNotFound,
+    /// The token lacks a scope; `None` when Slack didn't say which one is needed
+    MissingScope(Option<&'static str>),
+    EmptyInput(SlackInput),
+    Http(HttpFailure),
+    Parse(String),
+    /// Any other `ok: false` error code, as returned by Slack
+    Api(String),
+}
+
+impl SlackError {
+    fn missing_token() -> Self {
+        SlackError::Auth(AuthFailure::MissingToken)
+    }
+
+    /// Whether the same request may succeed if tried again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, SlackError::RateLimited { .. } | SlackError::Http(_))
+    }
+
+    /// Map an `ok: false` error code. `required_scope` is the scope to report for `missing_scope`.
+    pub fn from_api_error(code: &str, required_scope: Option<&'static str>) -> Self {
+        match code {
+            "invalid_auth" | "not_authed" | "token_revoked" => SlackError::Auth(AuthFailure::InvalidToken),
+            "token_expired" => SlackError::Auth(AuthFailure::ExpiredToken),
+            "account_inactive" => SlackError::Auth(AuthFailure::AccountInactive),
+            "not_in_channel" => SlackError::NotInChannel,
+            "channel_not_found" => SlackError::ChannelNotFound,
+            "is_archived" => SlackError::ChannelArchived,
+            "method_not_supported_for_channel_type" => SlackError::UnsupportedChannelType,
+            "user_not_found" => SlackError::UserNotFound,
+            "rate_limited" | "ratelimited" => SlackError::RateLimited { retry_after: None },
+            "missing_scope" => SlackError::MissingScope(required_scope),
+            _ => SlackError::Api(code.to_string()),
+        }
+    }
+
+    /// Map an unsuccessful HTTP status
+    pub fn from_status(status: reqwest::StatusCode, retry_after: Option<std::time::Duration>) -> Self {
+        match status.as_u16() {
+            401 => SlackError::Auth(AuthFailure::ExpiredToken),
+            404 => SlackError::ChannelNotFound,
+            429 => SlackError::RateLimited { retry_after },
+            code => SlackError::Http(HttpFailure::Status(code)),
+        }
+    }
+
+    /// User-facing description of the error in `locale`
+    pub fn localized_message(&self, locale: Locale) -> String {
+        let pick = |pt: &str, en: &str| match locale {
+            Locale::Portuguese => pt.to_string(),
+            Locale::English => en.to_string(),
+        };
+        match self {
+            SlackError::Auth(AuthFailure::MissingToken) => pick(
+                "Token de acesso não configurado",
+                "Access token is not configured",
+            ),
+            SlackError::Auth(AuthFailure::InvalidToken) => pick(
+                "Token de acesso inválido. Execute a autenticação OAuth novamente",
+                "Invalid access token. Run the OAuth authentication again",
+            ),
+            SlackError::Auth(AuthFailure::ExpiredToken) => pick(
+                "Token de acesso inválido ou expirado",
+                "Access token is invalid or expired",
+            ),
+            SlackError::Auth(AuthFailure::AccountInactive) => pick("Conta Slack inativa", "Slack account is inactive"),
+            SlackError::Auth(AuthFailure::InvalidClientCredentials) => pick(
+                "Client ID ou Client Secret incorretos",
+                "Incorrect Client ID or Client Secret",
+            ),
+            SlackError::RateLimited { .. } => pick(
+                "Limite de requisições excedido. Tente novamente em alguns segundos",
+                "Rate limit exceeded. Try again in a few seconds",
+            ),
+            SlackError::NotInChannel => pick(
+                "Bot não tem acesso a este canal. Adicione o bot ao canal primeiro",
+                "The bot has no access to this channel. Add the bot to the channel first",
+            ),
+            SlackError::ChannelNotFound => pick("Canal não encontrado", "Channel not found"),
+            SlackError::ChannelArchived => pick(
+                "Não é possível entrar em canal arquivado",
+                "Cannot join an archived channel",
+            ),
+            SlackError::UnsupportedChannelType => pick(
+                "Não é possível entrar neste tipo de canal (privado ou DM)",
+                "Cannot join this kind of channel (private or DM)",
+            ),
+            SlackError::UserNotFound => pick("Usuário não encontrado", "User not found"),
+            SlackError::MissingScope(Some(scope)) => match locale {
+                Locale::Portuguese => format!("Permissões insuficientes. A aplicação precisa do escopo '{}'", scope),
+                Locale::English => format!("Insufficient permissions. The app needs the '{}' scope", scope),
+            },
+            SlackError::MissingScope(None) => pick(
+                "Permissões insuficientes. A aplicação precisa de escopos adicionais",
+                "Insufficient permissions. The app needs additional scopes",
+            ),
+            SlackError::EmptyInput(input) => {
+                let (pt, en) = match input {
+                    SlackInput::ChannelId => ("Channel ID", "Channel ID"),
+                    SlackInput::AuthorizationCode => ("Código de autorização", "Authorization code"),
+                    SlackInput::ClientId => ("Client ID", "Client ID"),
+                    SlackInput::ClientSecret => ("Client Secret", "Client Secret"),
+                    SlackInput::RedirectUri => ("URI de redirecionamento", "Redirect URI"),
+                    SlackInput::RefreshToken => ("Token de atualização", "Refresh token"),
+                };
+                match locale {
+                    Locale::Portuguese => format!("{} não pode estar vazio", pt),
+                    Locale::English => format!("{} cannot be empty", en),
+                }
+            }
+            SlackError::Http(HttpFailure::Timeout) => pick(
+                "Timeout na conexão com Slack. Verifique sua conexão com a internet.",
+                "Connection to Slack timed out. Check your internet connection.",
+            ),
+            SlackError::Http(HttpFailure::Connect) => pick(
+                "Erro de conexão com Slack. Verifique sua conexão com a internet.",
+                "Could not connect to Slack. Check your internet connection.",
+            ),
+            SlackError::Http(HttpFailure::Request(detail)) => match locale {
+                Locale::Portuguese => format!("Erro na requisição ao Slack: {}", detail),
+                Locale::English => format!("Slack request failed: {}", detail),
+            },
+            SlackError::Http(HttpFailure::Status(code)) => {
+                let reason = match code {
+                    403 => pick("Permissões insuficientes", "Insufficient permissions"),
+                    500..=599 => pick(
+                        "Erro interno do Slack. Tente novamente mais tarde",
+                        "Slack internal error. Try again later",
+                    ),
+                    _ => pick("Erro desconhecido", "Unknown error"),
+                };
+                match locale {
+                    Locale::Portuguese => format!("Erro HTTP {}: {}", code, reason),
+                    Locale::English => format!("HTTP error {}: {}", code, reason),
+                }
+            }
+            SlackError::Http(HttpFailure::RetriesExhausted) => pick(
+                "Falha após múltiplas tentativas",
+                "Failed after several attempts",
+            ),
+            SlackError::Parse(detail) => match locale {
+                Locale::Portuguese => format!("Erro ao processar resposta do Slack: {}", detail),
+                Locale::English => format!("Could not read Slack response: {}", detail),
+            },
+            SlackError::Api(code) => match locale {
+                Locale::Portuguese => format!("Erro do Slack: {}", code),
+                Locale::English => format!("Slack error: {}", code),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for SlackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.localized_message(Locale::system()))
+    }
+}
+
+impl Error for SlackError {}
+
+impl From<reqwest::Error> for SlackError {
+    fn from(e: reqwest::Error) -> Self {
+        SlackError::Http(if e.is_timeout() {
+            HttpFailure::Timeout
+        } else if e.is_connect() {
+            HttpFailure::Connect
+        } else {
+            HttpFailure::Request(e.to_string())
+        })
+    }
+}
+
+/// `Retry-After` of a response, in seconds
+fn retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response.headers()
+        .get("retry-after")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SlackOAuthResponse {
@@ -24,6 +248,23 @@ pub struct SlackOAuthResponse {
     pub bot_id: Option<String>,
     pub token_type: Option<String>,
     pub scope: Option<String>,
+    /// Sent when token rotation is enabled for the Slack app; stored, never passed on
+    #[serde(skip_serializing)]
+    pub refresh_token: Option<String>,
+    /// Seconds until `access_token` expires, with token rotation
+    pub expires_in: Option<i64>,
+}
+
+impl SlackOAuthResponse {
+    /// The rotation data of the returned token, when the app rotates tokens
+    pub fn rotated_token(&self, now: DateTime<Utc>) -> Option<crate::oauth_service_client::RefreshedToken> {
+        let refresh_token = self.refresh_token.clone().filter(|t| !t.is_empty())?;
+        Some(crate::oauth_service_client::RefreshedToken {
+            access_token: self.access_token.clone()?,
+            refresh_token: Some(refresh_token),
+            expires_at: self.expires_in.map(|secs| now + chrono::Duration::seconds(secs)),
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -115,16 +356,236 @@ pub struct SlackUserProfile {
     pub image_48: Option<String>,
 }
 
+impl SlackUser {
+    /// The name a mention of this member reads as: display name, else real name, else handle
+    pub fn mention_name(&self) -> &str {
+        let profile = self.profile.as_ref();
+        [
+            profile.and_then(|p| p.display_name.as_deref()),
+            self.display_name.as_deref(),
+            profile.and_then(|p| p.real_name.as_deref()),
+            self.real_name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|name| !name.is_empty())
+        .unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackPresence {
+    pub presence: String, // "active" | "away"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_away: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manual_away: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<i64>,
+}
+
+impl SlackPresence {
+    pub fn is_active(&self) -> bool {
+        self.presence == "active"
+    }
+}
+
+/// Parse a `users.getPresence` response body
+pub fn parse_presence_response(body: &str) -> Result<SlackPresence, SlackError> {
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| SlackError::Parse(e.to_string()))?;
+    
+    if !value.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let error_msg = value.get("error").and_then(|v| v.as_str()).unwrap_or("Erro desconhecido");
+        return Err(match error_msg {
+            _ => SlackError::from_api_error(error_msg, Some("users:read")),
+        });
+    }
+    
+    serde_json::from_value(value)
+        .map_err(|e| SlackError::Parse(format!("presença do usuário: {}", e)))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackMessage {
     pub ts: String,
     pub user: Option<String>,
+    #[serde(default)]
     pub text: String,
     pub channel: Option<String>, // Made optional since Slack API doesn't always include it
-    #[serde(rename = "type")]
+    /// The Slack service omits `type`; everything it returns is a regular message
+    #[serde(rename = "type", default = "default_message_type")]
     pub msg_type: String,
     pub thread_ts: Option<String>,
     pub attachments: Option<Vec<SlackAttachment>>,
+    /// Set for system events (joins, topic changes, ...) and bot posts
+    #[serde(default)]
+    pub subtype: Option<String>,
+    #[serde(default)]
+    pub bot_id: Option<String>,
+    /// Set once the message has been edited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited: Option<SlackEdited>,
+    /// For `message_changed` events, the message as it reads now
+    #[serde(default, rename = "message", skip_serializing_if = "Option::is_none")]
+    pub changed_message: Option<Box<SlackMessage>>,
+    /// For `message_deleted` events, the timestamp of the removed message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_ts: Option<String>,
+    /// Thread metadata, set on thread parents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_reply: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<Vec<SlackReaction>>,
+}
+
+fn default_message_type() -> String {
+    "message".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlackEdited {
+    #[serde(default)]
+    pub user: Option<String>,
+    pub ts: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackReaction {
+    pub name: String,
+    pub count: u32,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// A history entry seen as a change to the conversation, so tasks derived from earlier
+/// versions of a message can be updated or dropped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SlackMessageEvent {
+    New(SlackMessage),
+    /// `message` is the current version of the message posted at `ts`
+    Edited { ts: String, message: SlackMessage },
+    Deleted { ts: String },
+}
+
+impl SlackMessageEvent {
+    /// Timestamp of the original message this event is about
+    pub fn source_ts(&self) -> &str {
+        match self {
+            SlackMessageEvent::New(message) => &message.ts,
+            SlackMessageEvent::Edited { ts, .. } | SlackMessageEvent::Deleted { ts } => ts,
+        }
+    }
+}
+
+/// Split a history page into new messages, edits and deletions. Edits come either as
+/// `message_changed` events or as messages carrying an `edited` marker. Polled history only
+/// has the marker, on messages inside the sync window; `message_changed` and `message_deleted`
+/// are pushed to the Events API receiver.
+pub fn classify_message_events(messages: Vec<SlackMessage>) -> Vec<SlackMessageEvent> {
+    messages.into_iter()
+        .filter_map(|message| match message.subtype.as_deref() {
+            Some("message_changed") => match message.changed_message {
+                Some(current) => Some(SlackMessageEvent::Edited {
+                    ts: current.ts.clone(),
+                    message: SlackMessage { channel: current.channel.clone().or(message.channel), ..*current },
+                }),
+                None => {
+                    println!("⚠️ [SLACK_SYNC] message_changed event {} without a message, ignoring", message.ts);
+                    None
+                }
+            },
+            Some("message_deleted") => Some(SlackMessageEvent::Deleted {
+                ts: message.deleted_ts.unwrap_or(message.ts),
+            }),
+            _ if message.edited.is_some() => Some(SlackMessageEvent::Edited {
+                ts: message.ts.clone(),
+                message,
+            }),
+            _ => Some(SlackMessageEvent::New(message)),
+        })
+        .collect()
+}
+
+/// Parse a Slack timestamp such as "1699999999.000100" into seconds since the epoch.
+/// Returns `None` for empty, non-numeric, negative or non-finite input instead of zero.
+pub fn parse_slack_ts(ts: &str) -> Option<f64> {
+    ts.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+}
+
+/// Drop messages posted outside `(oldest, latest)`. Cursor pages are requested without the
+/// window bounds, so history keeps going back past `oldest`; returns `true` once a message
+/// older than `oldest` was seen and there is no point fetching further pages.
+pub fn trim_to_window(messages: &mut Vec<SlackMessage>, oldest: Option<f64>, latest: Option<f64>) -> bool {
+    let mut reached_oldest = false;
+    messages.retain(|message| match message.timestamp_secs() {
+        Some(secs) if oldest.map_or(false, |oldest| secs < oldest) => {
+            reached_oldest = true;
+            false
+        }
+        Some(secs) => latest.map_or(true, |latest| secs <= latest),
+        None => true,
+    });
+    reached_oldest
+}
+
+/// Convert a Slack timestamp to a UTC time, keeping microsecond precision
+pub fn slack_ts_to_datetime(ts: &str) -> Option<DateTime<Utc>> {
+    let secs = parse_slack_ts(ts)?;
+    let micros = (secs * 1_000_000.0).round() as i64;
+    DateTime::<Utc>::from_timestamp_micros(micros)
+}
+
+impl SlackMessage {
+    /// When the message was posted, in seconds since the epoch (`None` if `ts` is malformed)
+    pub fn timestamp_secs(&self) -> Option<f64> {
+        parse_slack_ts(&self.ts)
+    }
+
+    /// When the message was posted (`None` if `ts` is malformed)
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        slack_ts_to_datetime(&self.ts)
+    }
+
+    /// The message text followed by each attachment's author, title and text, so tasks in
+    /// link unfurls and bot attachments (Jira, GitHub, ...) are analyzed too
+    pub fn analyzable_text(&self) -> String {
+        let mut content = self.text.clone();
+        for attachment in self.attachments.iter().flatten() {
+            let parts: Vec<&str> = [&attachment.title, &attachment.text]
+                .into_iter()
+                .filter_map(|part| part.as_deref())
+                .map(|part| part.trim())
+                .filter(|part| !part.is_empty())
+                .collect();
+            if parts.is_empty() {
+                continue;
+            }
+            
+            let header = match attachment.author_name.as_deref().map(|a| a.trim()).filter(|a| !a.is_empty()) {
+                Some(author) => format!("[Attachment from {}]", author),
+                None => "[Attachment]".to_string(),
+            };
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&header);
+            for part in parts {
+                content.push('\n');
+                content.push_str(part);
+            }
+        }
+        content
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,11 +612,163 @@ pub struct SlackResponseMetadata {
     pub next_cursor: Option<String>,
 }
 
-// Global sync state to prevent concurrent fetches for the same channel
-static CHANNEL_SYNC_LOCKS: Lazy<Mutex<HashMap<String, Arc<Mutex<bool>>>>> = Lazy::new(|| {
+/// Clock skew (in seconds) beyond which timestamp-based sync windows become unreliable
+pub const CLOCK_SKEW_WARNING_SECONDS: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClockSkewReport {
+    pub skew_seconds: i64,
+    pub threshold_seconds: i64,
+    pub exceeds_threshold: bool,
+    pub server_time: String,
+    pub local_time: String,
+}
+
+/// Compare a Slack `Date` header against local time. Positive skew means the local clock is ahead.
+pub fn compute_clock_skew(date_header: &str, local_now: DateTime<Utc>) -> Result<ClockSkewReport, SlackError> {
+    let server_time = DateTime::parse_from_rfc2822(date_header)
+        .map_err(|e| SlackError::Parse(format!("cabeçalho Date inválido '{}': {}", date_header, e)))?
+        .with_timezone(&Utc);
+    
+    let skew_seconds = (local_now - server_time).num_seconds();
+    
+    Ok(ClockSkewReport {
+        skew_seconds,
+        threshold_seconds: CLOCK_SKEW_WARNING_SECONDS,
+        exceeds_threshold: skew_seconds.abs() > CLOCK_SKEW_WARNING_SECONDS,
+        server_time: server_time.to_rfc3339(),
+        local_time: local_now.to_rfc3339(),
+    })
+}
+
+/// A channel sync held for longer than this is assumed abandoned and can be taken over
+pub const CHANNEL_SYNC_STALE_AFTER: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+// Channels with a fetch in progress, and when it started. Entries only live as long as their
+// `ChannelSyncGuard`, so idle channels never accumulate here.
+static CHANNEL_SYNC_LOCKS: Lazy<Mutex<HashMap<String, std::time::Instant>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+// Cancellation tokens for in-flight fetches, so disconnecting a channel can stop a backfill
+static CHANNEL_FETCH_CANCELLATIONS: Lazy<Mutex<HashMap<String, tokio_util::sync::CancellationToken>>> = Lazy::new(|| {
     Mutex::new(HashMap::new())
 });
 
+/// The token fetches for `channel_id` should watch. Every fetch of the channel shares it until
+/// `cancel_channel_fetches` is called.
+pub fn channel_fetch_token(channel_id: &str) -> tokio_util::sync::CancellationToken {
+    CHANNEL_FETCH_CANCELLATIONS.lock().unwrap_or_else(|e| e.into_inner())
+        .entry(channel_id.to_string())
+        .or_default()
+        .clone()
+}
+
+/// Stop every running fetch for `channel_id`; they return what they have gathered so far
+pub fn cancel_channel_fetches(channel_id: &str) -> bool {
+    match CHANNEL_FETCH_CANCELLATIONS.lock().unwrap_or_else(|e| e.into_inner()).remove(channel_id) {
+        Some(token) => {
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Delay after the first 429 when Slack doesn't send `Retry-After`
+const INITIAL_RATE_LIMIT_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+/// Upper bound for the adaptive delay between pages
+const MAX_PAGE_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+/// Below this the delay snaps back to zero
+const MIN_PAGE_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Delay between history pages: zero while Slack keeps up, doubled on every 429 and
+/// halved again on each successful page
+#[derive(Debug, Clone, Default)]
+pub struct AdaptivePageDelay {
+    current: std::time::Duration,
+}
+
+impl AdaptivePageDelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long to wait before the next page
+    pub fn delay(&self) -> std::time::Duration {
+        self.current
+    }
+
+    pub fn on_success(&mut self) {
+        self.current /= 2;
+        if self.current < MIN_PAGE_DELAY {
+            self.current = std::time::Duration::ZERO;
+        }
+    }
+
+    /// Back off after a 429 and return how long to wait before retrying.
+    /// `Retry-After` wins when it asks for longer than the backoff.
+    pub fn on_rate_limited(&mut self, retry_after: Option<std::time::Duration>) -> std::time::Duration {
+        self.current = (self.current * 2).max(INITIAL_RATE_LIMIT_BACKOFF).min(MAX_PAGE_DELAY);
+        retry_after.map_or(self.current, |retry_after| retry_after.max(self.current))
+    }
+}
+
+/// Reported after each page of a channel history fetch, as the `fetch-progress` event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelFetchProgress {
+    pub channel_id: String,
+    pub pages_fetched: u32,
+    pub messages_so_far: usize,
+    /// Most messages the fetch will collect, when it is capped
+    pub limit: Option<u32>,
+    pub has_more: bool,
+}
+
+/// Marks a channel as syncing until dropped, including on early returns and panics
+#[derive(Debug)]
+pub struct ChannelSyncGuard {
+    channel_id: String,
+    // When this guard took the claim; a stale takeover replaces it, so it identifies the holder
+    acquired_at: std::time::Instant,
+}
+
+impl ChannelSyncGuard {
+    /// Claim `channel_id`, or `None` if another fetch for it is already running
+    pub fn try_acquire(channel_id: &str) -> Option<Self> {
+        Self::try_acquire_at(channel_id, std::time::Instant::now())
+    }
+
+    fn try_acquire_at(channel_id: &str, now: std::time::Instant) -> Option<Self> {
+        let mut locks = CHANNEL_SYNC_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+        
+        // Drop claims whose guard was leaked instead of dropped
+        locks.retain(|id, started| {
+            let stale = now.saturating_duration_since(*started) >= CHANNEL_SYNC_STALE_AFTER;
+            if stale {
+                println!("⚠️ [DEBUG] Releasing stale sync lock for channel {}", id);
+            }
+            !stale
+        });
+        
+        if locks.contains_key(channel_id) {
+            return None;
+        }
+        locks.insert(channel_id.to_string(), now);
+        Some(Self { channel_id: channel_id.to_string(), acquired_at: now })
+    }
+}
+
+impl Drop for ChannelSyncGuard {
+    fn drop(&mut self) {
+        let mut locks = CHANNEL_SYNC_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+        // A guard whose claim was taken over must not release the new holder's
+        if locks.get(&self.channel_id) == Some(&self.acquired_at) {
+            locks.remove(&self.channel_id);
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SlackClient {
     client: Client,
@@ -164,16 +777,8 @@ pub struct SlackClient {
 
 impl SlackClient {
     pub fn new() -> Self {
-        // Create a more robust HTTP client with timeouts and retries
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .connect_timeout(std::time::Duration::from_secs(10))
-            .user_agent("ProjectBoxes/1.0")
-            .build()
-            .unwrap_or_else(|_| Client::new());
-            
         Self {
-            client,
+            client: crate::http_client::shared_client(),
             access_token: None,
         }
     }
@@ -187,8 +792,8 @@ impl SlackClient {
     }
 
     /// Test the connection and validate required scopes
-    pub async fn test_slack_connection(&self) -> Result<serde_json::Value, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
+    pub async fn test_slack_connection(&self) -> Result<serde_json::Value, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
         // Simple retry logic for network issues
         let mut _last_error = None;
@@ -217,28 +822,45 @@ impl SlackClient {
         }
         
         // This should never be reached, but just in case
-        Err("Falha após múltiplas tentativas".into())
+        Err(SlackError::Http(HttpFailure::RetriesExhausted))
+    }
+    
+    /// Measure the difference between the local clock and Slack's servers
+    pub async fn check_clock_skew(&self) -> Result<ClockSkewReport, SlackError> {
+        let response = self.client
+            .get("https://slack.com/api/api.test")
+            .send()
+            .await?;
+        
+        let local_now = Utc::now();
+        
+        let date_header = response.headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| SlackError::Parse("resposta sem cabeçalho Date".to_string()))?
+            .to_string();
+        
+        let report = compute_clock_skew(&date_header, local_now)?;
+        
+        if report.exceeds_threshold {
+            println!("⚠️ [SLACK_CLOCK] Local clock is off by {}s (threshold {}s) - sync windows may miss or duplicate messages",
+                report.skew_seconds, report.threshold_seconds);
+        }
+        
+        Ok(report)
     }
     
-    async fn process_auth_response(&self, response: reqwest::Response) -> Result<serde_json::Value, Box<dyn Error + Send + Sync>> {
+    async fn process_auth_response(&self, response: reqwest::Response) -> Result<serde_json::Value, SlackError> {
 
         // Check HTTP status
         if !response.status().is_success() {
-            return Err(format!("Erro HTTP {}: {}", response.status().as_u16(), 
-                match response.status().as_u16() {
-                    401 => "Token de acesso inválido ou expirado",
-                    403 => "Permissões insuficientes",
-                    429 => "Muitas requisições. Tente novamente em alguns segundos",
-                    500..=599 => "Erro interno do Slack. Tente novamente mais tarde",
-                    _ => "Erro desconhecido"
-                }).into());
-        }
-
-        let response_text = response.text().await
-            .map_err(|e| format!("Erro ao ler resposta do Slack: {}", e))?;
+            return Err(SlackError::from_status(response.status(), retry_after(&response)));
+        }
+
+        let response_text = response.text().await?;
         
         let auth_response: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| format!("Erro ao processar resposta do Slack: {}", e))?;
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
         
         // Check if the response indicates success
         if !auth_response.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
@@ -246,12 +868,7 @@ impl SlackClient {
                 .and_then(|v| v.as_str())
                 .unwrap_or("Erro desconhecido");
             
-            return Err(match error_msg {
-                "invalid_auth" => "Token de acesso inválido. Execute a autenticação OAuth novamente".into(),
-                "account_inactive" => "Conta Slack inativa".into(),
-                "missing_scope" => "Permissões insuficientes. A aplicação precisa de escopos adicionais".into(),
-                _ => format!("Erro do Slack: {}", error_msg).into()
-            });
+            return Err(SlackError::from_api_error(error_msg, None));
         }
 
         Ok(auth_response)
@@ -264,19 +881,19 @@ impl SlackClient {
         client_id: &str,
         client_secret: &str,
         redirect_uri: &str,
-    ) -> Result<SlackOAuthResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<SlackOAuthResponse, SlackError> {
         // Validate inputs
         if code.trim().is_empty() {
-            return Err("Código de autorização não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::AuthorizationCode));
         }
         if client_id.trim().is_empty() {
-            return Err("Client ID não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::ClientId));
         }
         if client_secret.trim().is_empty() {
-            return Err("Client Secret não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::ClientSecret));
         }
         if redirect_uri.trim().is_empty() {
-            return Err("URI de redirecionamento não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::RedirectUri));
         }
 
         let params = [
@@ -290,38 +907,71 @@ impl SlackClient {
             .post("https://slack.com/api/oauth.v2.access")
             .form(&params)
             .send()
-            .await
-            .map_err(|e| -> String {
-                if e.is_timeout() {
-                    "Timeout na autenticação OAuth. Tente novamente.".to_string()
-                } else if e.is_connect() {
-                    "Erro de conexão durante OAuth. Verifique sua internet.".to_string()
-                } else {
-                    format!("Erro na requisição OAuth: {}", e)
-                }
-            })?;
+            .await?;
 
         // Check HTTP status
         if !response.status().is_success() {
-            return Err(format!("Erro HTTP na autenticação OAuth {}: {}", 
-                response.status().as_u16(),
-                match response.status().as_u16() {
-                    400 => "Dados de autenticação inválidos",
-                    401 => "Client ID ou Client Secret incorretos",
-                    403 => "Acesso negado pelo Slack",
-                    500..=599 => "Erro interno do Slack durante OAuth",
-                    _ => "Erro desconhecido na autenticação"
-                }).into());
+            return Err(match response.status().as_u16() {
+                401 => SlackError::Auth(AuthFailure::InvalidClientCredentials),
+                code => SlackError::Http(HttpFailure::Status(code)),
+            });
+        }
+
+        let oauth_response: SlackOAuthResponse = response.json().await
+            .map_err(|e| SlackError::Parse(format!("OAuth: {}", e)))?;
+        
+        Ok(oauth_response)
+    }
+
+    /// Trade a refresh token for a new access token (and a new refresh token, which replaces
+    /// the used one) through `oauth.v2.access`, for apps with token rotation
+    pub async fn refresh_access_token(
+        &self,
+        refresh_token: &str,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<SlackOAuthResponse, SlackError> {
+        if refresh_token.trim().is_empty() {
+            return Err(SlackError::EmptyInput(SlackInput::RefreshToken));
+        }
+        if client_id.trim().is_empty() {
+            return Err(SlackError::EmptyInput(SlackInput::ClientId));
+        }
+        if client_secret.trim().is_empty() {
+            return Err(SlackError::EmptyInput(SlackInput::ClientSecret));
+        }
+
+        let params = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ];
+
+        let response = self.client
+            .post("https://slack.com/api/oauth.v2.access")
+            .form(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(match response.status().as_u16() {
+                401 => SlackError::Auth(AuthFailure::InvalidClientCredentials),
+                code => SlackError::Http(HttpFailure::Status(code)),
+            });
         }
 
         let oauth_response: SlackOAuthResponse = response.json().await
-            .map_err(|e| format!("Erro ao processar resposta OAuth: {}", e))?;
+            .map_err(|e| SlackError::Parse(format!("OAuth: {}", e)))?;
+        if !oauth_response.ok {
+            return Err(SlackError::from_api_error(oauth_response.error.as_deref().unwrap_or("unknown_error"), None));
+        }
         
         Ok(oauth_response)
     }
 
-    pub async fn list_channels(&self) -> Result<Vec<SlackChannel>, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
+    pub async fn list_channels(&self) -> Result<Vec<SlackChannel>, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
         let mut all_channels = Vec::new();
         let mut cursor: Option<String> = None;
@@ -343,49 +993,26 @@ impl SlackClient {
                 .bearer_auth(token)
                 .query(&query_params)
                 .send()
-                .await
-                .map_err(|e| -> String {
-                    if e.is_timeout() {
-                        "Timeout na conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else if e.is_connect() {
-                        "Erro de conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else {
-                        format!("Erro na requisição ao Slack: {}", e)
-                    }
-                })?;
+                .await?;
 
             // Check HTTP status
             if !response.status().is_success() {
-                return Err(format!("Erro HTTP {}: {}", response.status().as_u16(), 
-                    match response.status().as_u16() {
-                        401 => "Token de acesso inválido ou expirado",
-                        403 => "Permissões insuficientes. Verifique os escopos da aplicação Slack",
-                        429 => "Muitas requisições. Tente novamente em alguns segundos",
-                        500..=599 => "Erro interno do Slack. Tente novamente mais tarde",
-                        _ => "Erro desconhecido"
-                    }).into());
+                return Err(SlackError::from_status(response.status(), retry_after(&response)));
             }
 
             // First get the raw response text for debugging
-            let response_text = response.text().await
-                .map_err(|e| format!("Erro ao ler resposta do Slack: {}", e))?;
+            let response_text = response.text().await?;
             
             // Try to parse the JSON and provide better error context
             let list_response: SlackListResponse<SlackChannel> = serde_json::from_str(&response_text)
                 .map_err(|e| {
                     eprintln!("Slack API Response: {}", response_text);
-                    format!("Erro ao processar resposta do Slack: {}. Response: {}", e, response_text.chars().take(500).collect::<String>())
+                    SlackError::Parse(format!("{}. Response: {}", e, response_text.chars().take(500).collect::<String>()))
                 })?;
             
             if !list_response.ok {
                 let error_msg = list_response.error.unwrap_or_else(|| "Erro desconhecido".to_string());
-                return Err(match error_msg.as_str() {
-                    "invalid_auth" => "Token de acesso inválido. Execute a autenticação OAuth novamente".into(),
-                    "account_inactive" => "Conta Slack inativa".into(),
-                    "missing_scope" => "Permissões insuficientes. A aplicação precisa do escopo 'channels:read'".into(),
-                    "rate_limited" => "Limite de requisições excedido. Tente novamente em alguns segundos".into(),
-                    _ => format!("Erro do Slack: {}", error_msg).into()
-                });
+                return Err(SlackError::from_api_error(&error_msg, Some("channels:read")));
             }
 
             // Add channels from this page
@@ -441,55 +1068,47 @@ impl SlackClient {
         &self,
         channel_id: &str,
         oldest_timestamp: Option<f64>,
-        limit: Option<u32>
-    ) -> Result<Vec<SlackMessage>, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
+        latest_timestamp: Option<f64>,
+        limit: Option<u32>,
+        cancel: &tokio_util::sync::CancellationToken,
+        on_progress: Option<&(dyn Fn(&ChannelFetchProgress) + Send + Sync)>,
+    ) -> Result<Vec<SlackMessage>, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
         // Validate channel_id
         if channel_id.trim().is_empty() {
-            return Err("Channel ID não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::ChannelId));
         }
         
-        // Get or create a lock for this channel to prevent concurrent fetches
-        let channel_lock = {
-            let mut locks = CHANNEL_SYNC_LOCKS.lock().unwrap();
-            locks.entry(channel_id.to_string())
-                .or_insert_with(|| Arc::new(Mutex::new(false)))
-                .clone()
-        };
-        
-        // Check if sync is already in progress
-        {
-            let mut is_syncing = match channel_lock.try_lock() {
-                Ok(guard) => guard,
-                Err(_) => {
-                    println!("⚠️ [DEBUG] Sync already in progress for channel {}, skipping duplicate request", channel_id);
-                    return Ok(vec![]); // Return empty to avoid duplicate fetches
-                }
-            };
-            
-            if *is_syncing {
-                println!("⚠️ [DEBUG] Sync already in progress for channel {} (locked), skipping duplicate request", channel_id);
-                return Ok(vec![]);
+        // Prevent concurrent fetches for the same channel; the guard releases the channel
+        // however this function exits
+        let _sync_guard = match ChannelSyncGuard::try_acquire(channel_id) {
+            Some(guard) => guard,
+            None => {
+                println!("⚠️ [DEBUG] Sync already in progress for channel {}, skipping duplicate request", channel_id);
+                return Ok(vec![]); // Return empty to avoid duplicate fetches
             }
-            
-            // Mark as syncing
-            *is_syncing = true;
-        } // Drop the lock here before any async operations
+        };
         
         let mut all_messages = Vec::new();
         let mut cursor: Option<String> = None;
         let requested_limit = limit.unwrap_or(15);
         let page_limit = requested_limit.min(15); // Non-Marketplace apps limited to 15 messages per request
-        let mut _consecutive_rate_limits = 0;
+        let mut page_delay = AdaptivePageDelay::new();
         let mut previous_cursors: std::collections::HashSet<String> = std::collections::HashSet::new();
         let mut page_count = 0;
+        let mut pages_fetched = 0u32;
         const MAX_PAGES: usize = 100; // Safety limit to prevent infinite loops
         
         // For small requests (widgets), disable pagination to prevent loops
         let enable_pagination = requested_limit > 15;
         
         loop {
+            if cancel.is_cancelled() {
+                println!("🛑 [DEBUG] Fetch cancelled for channel {}, returning {} messages gathered so far", channel_id, all_messages.len());
+                break;
+            }
+            
             println!("🔄 [DEBUG] Fetching page with cursor: {:?}", cursor);
             
             // Convert limit to string to avoid temporary value issues
@@ -499,19 +1118,26 @@ impl SlackClient {
                 ("limit", &limit_str),
             ];
             
-            // Add cursor for pagination OR oldest timestamp (but NEVER both)
+            // Add cursor for pagination OR the oldest/latest window (but NEVER both)
             // This is crucial - Slack API doesn't handle oldest + cursor properly
             let cursor_str;
             let oldest_str;
+            let latest_str;
             if let Some(ref cursor_val) = cursor {
-                // When paginating, only use cursor - no oldest timestamp
+                // When paginating, only use cursor - the window is enforced by trim_to_window
                 cursor_str = cursor_val.clone();
                 query_params.push(("cursor", &cursor_str));
-            } else if let Some(oldest) = oldest_timestamp {
-                // Only use oldest timestamp on first request (when cursor is None)
-                // After first request, we'll ignore oldest_timestamp
-                oldest_str = oldest.to_string();
-                query_params.push(("oldest", &oldest_str));
+            } else {
+                // Only bound the first request (when cursor is None); the cursor carries on
+                // backwards from where it started
+                if let Some(oldest) = oldest_timestamp {
+                    oldest_str = oldest.to_string();
+                    query_params.push(("oldest", &oldest_str));
+                }
+                if let Some(latest) = latest_timestamp {
+                    latest_str = latest.to_string();
+                    query_params.push(("latest", &latest_str));
+                }
             }
             
             let response = self.client
@@ -519,61 +1145,36 @@ impl SlackClient {
                 .bearer_auth(token)
                 .query(&query_params)
                 .send()
-                .await
-                .map_err(|e| -> String {
-                    if e.is_timeout() {
-                        "Timeout na conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else if e.is_connect() {
-                        "Erro de conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else {
-                        format!("Erro na requisição ao Slack: {}", e)
-                    }
-                })?;
+                .await?;
 
             // Check HTTP status and handle rate limiting
             if response.status().as_u16() == 429 {
                 // Rate limited - check Retry-After header
-                let retry_after = response.headers()
-                    .get("retry-after")
-                    .and_then(|h| h.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .unwrap_or(5); // Default to 5 seconds if no header
+                let wait = page_delay.on_rate_limited(retry_after(&response));
                     
-                println!("⚠️ [DEBUG] Rate limited, waiting {} seconds...", retry_after);
-                tokio::time::sleep(tokio::time::Duration::from_secs(retry_after)).await;
+                println!("⚠️ [DEBUG] Rate limited, waiting {:?}...", wait);
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = cancel.cancelled() => {}
+                }
                 continue; // Retry the same request
             }
             
             if !response.status().is_success() {
-                return Err(format!("Erro HTTP {}: {}", response.status().as_u16(), 
-                    match response.status().as_u16() {
-                        401 => "Token de acesso inválido ou expirado",
-                        403 => "Sem permissão para acessar este canal. Verifique se o bot tem acesso ao canal",
-                        404 => "Canal não encontrado",
-                        500..=599 => "Erro interno do Slack. Tente novamente mais tarde",
-                        _ => "Erro desconhecido"
-                    }).into());
+                return Err(SlackError::from_status(response.status(), retry_after(&response)));
             }
 
-            let response_text = response.text().await
-                .map_err(|e| format!("Erro ao ler resposta do Slack: {}", e))?;
+            let response_text = response.text().await?;
             
             let messages_response: SlackListResponse<SlackMessage> = serde_json::from_str(&response_text)
                 .map_err(|e| {
                     eprintln!("Slack Messages API Response: {}", response_text);
-                    format!("Erro ao processar resposta do Slack: {}. Response: {}", e, response_text.chars().take(500).collect::<String>())
+                    SlackError::Parse(format!("{}. Response: {}", e, response_text.chars().take(500).collect::<String>()))
                 })?;
             
             if !messages_response.ok {
                 let error_msg = messages_response.error.unwrap_or_else(|| "Erro desconhecido".to_string());
-                return Err(match error_msg.as_str() {
-                    "invalid_auth" => "Token de acesso inválido. Execute a autenticação OAuth novamente".into(),
-                    "channel_not_found" => "Canal não encontrado".into(),
-                    "not_in_channel" => "Bot não tem acesso a este canal. Adicione o bot ao canal primeiro".into(),
-                    "missing_scope" => "Permissões insuficientes. A aplicação precisa do escopo 'channels:history'".into(),
-                    "rate_limited" => "Limite de requisições excedido. Tente novamente em alguns segundos".into(),
-                    _ => format!("Erro do Slack: {}", error_msg).into()
-                });
+                return Err(SlackError::from_api_error(&error_msg, Some("channels:history")));
             }
 
             // Extract messages from this page
@@ -584,6 +1185,8 @@ impl SlackClient {
             println!("🔍 [DEBUG] Slack has_more: {:?}", messages_response.has_more);
             println!("🔍 [DEBUG] Slack next_cursor: {:?}", messages_response.response_metadata.as_ref().and_then(|m| m.next_cursor.as_ref()));
             
+            let reached_oldest = trim_to_window(&mut page_messages, oldest_timestamp, latest_timestamp);
+            
             // Fill in the channel field for all messages (Slack API doesn't always include it)
             for message in &mut page_messages {
                 if message.channel.is_none() {
@@ -592,6 +1195,17 @@ impl SlackClient {
             }
             
             all_messages.extend(page_messages);
+            pages_fetched += 1;
+            page_delay.on_success();
+            if let Some(on_progress) = on_progress {
+                on_progress(&ChannelFetchProgress {
+                    channel_id: channel_id.to_string(),
+                    pages_fetched,
+                    messages_so_far: all_messages.len(),
+                    limit,
+                    has_more: messages_response.has_more.unwrap_or(false),
+                });
+            }
             
             // For widget requests, stop after first page
             if !enable_pagination {
@@ -599,6 +1213,11 @@ impl SlackClient {
                 break;
             }
             
+            if reached_oldest {
+                println!("✅ [DEBUG] Reached the start of the requested window, stopping pagination");
+                break;
+            }
+            
             // Check if there are more pages
             if messages_response.has_more.unwrap_or(false) {
                 let next_cursor = messages_response.response_metadata
@@ -632,10 +1251,15 @@ impl SlackClient {
                         break;
                     }
                     
-                    // Rate limiting: reduced to 1 second to prevent frontend timeouts
-                    // while still respecting Slack API limits
-                    println!("⏱️ [DEBUG] Waiting 1 second between pagination requests...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    // Only slow down once Slack has started rate limiting us
+                    let delay = page_delay.delay();
+                    if !delay.is_zero() {
+                        println!("⏱️ [DEBUG] Waiting {:?} between pagination requests...", delay);
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = cancel.cancelled() => {}
+                        }
+                    }
                 } else {
                     println!("⚠️ [DEBUG] has_more is true but no valid cursor provided, stopping pagination");
                     break;
@@ -646,23 +1270,16 @@ impl SlackClient {
             }
         }
         
-        // Release the sync lock
-        {
-            if let Ok(mut is_syncing) = channel_lock.lock() {
-                *is_syncing = false;
-            }
-        }
-        
         println!("✅ [DEBUG] Total messages fetched: {} for channel {}", all_messages.len(), channel_id);
         Ok(all_messages)
     }
 
-    pub async fn estimate_sync_time(&self, channel_id: &str) -> Result<SyncEstimate, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
+    pub async fn estimate_sync_time(&self, channel_id: &str) -> Result<SyncEstimate, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
         // Validate channel_id
         if channel_id.trim().is_empty() {
-            return Err("Channel ID não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::ChannelId));
         }
 
         // First, get channel info to see if it has a message count estimate
@@ -671,15 +1288,14 @@ impl SlackClient {
             .bearer_auth(token)
             .query(&[("channel", channel_id)])
             .send()
-            .await
-            .map_err(|e| format!("Erro na requisição ao Slack: {}", e))?;
+            .await?;
 
         if !info_response.status().is_success() {
-            return Err(format!("Erro HTTP {}", info_response.status().as_u16()).into());
+            return Err(SlackError::from_status(info_response.status(), retry_after(&info_response)));
         }
 
         let _info_json: serde_json::Value = info_response.json().await
-            .map_err(|e| format!("Erro ao parsear resposta JSON: {}", e))?;
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
 
         // Try to get an estimate by fetching just the first page to see pagination info
         let response = self.client
@@ -690,15 +1306,14 @@ impl SlackClient {
                 ("limit", "15"), // Use the rate-limited page size
             ])
             .send()
-            .await
-            .map_err(|e| format!("Erro na requisição ao Slack: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!("Erro HTTP {}", response.status().as_u16()).into());
+            return Err(SlackError::from_status(response.status(), retry_after(&response)));
         }
 
         let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Erro ao parsear resposta JSON: {}", e))?;
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
 
         let messages_in_first_page = json["messages"]
             .as_array()
@@ -733,12 +1348,12 @@ impl SlackClient {
         })
     }
 
-    pub async fn join_channel(&self, channel_id: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
+    pub async fn join_channel(&self, channel_id: &str) -> Result<bool, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
         // Validate channel_id
         if channel_id.trim().is_empty() {
-            return Err("Channel ID não pode estar vazio".into());
+            return Err(SlackError::EmptyInput(SlackInput::ChannelId));
         }
         
         #[derive(Serialize)]
@@ -755,26 +1370,11 @@ impl SlackClient {
             .bearer_auth(token)
             .json(&request_body)
             .send()
-            .await
-            .map_err(|e| -> String {
-                if e.is_timeout() {
-                    "Timeout na conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                } else if e.is_connect() {
-                    "Erro de conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                } else {
-                    format!("Erro na requisição ao Slack: {}", e)
-                }
-            })?;
+            .await?;
 
         // Check HTTP status
         if !response.status().is_success() {
-            return Err(format!("Erro HTTP {}: {}", response.status().as_u16(), 
-                match response.status().as_u16() {
-                    401 => "Token de acesso inválido ou expirado",
-                    403 => "Sem permissão para entrar neste canal",
-                    429 => "Muitas requisições. Tente novamente em alguns segundos",
-                    _ => "Erro desconhecido do Slack"
-                }).into());
+            return Err(SlackError::from_status(response.status(), retry_after(&response)));
         }
 
         #[derive(Deserialize)]
@@ -784,38 +1384,69 @@ impl SlackClient {
         }
 
         let join_response: JoinResponse = response.json().await
-            .map_err(|e| format!("Erro ao processar resposta do Slack: {}", e))?;
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
 
         if !join_response.ok {
             let error_msg = join_response.error.unwrap_or_else(|| "Erro desconhecido".to_string());
-            return Err(match error_msg.as_str() {
-                "invalid_auth" => "Token de acesso inválido. Execute a autenticação OAuth novamente".into(),
-                "channel_not_found" => "Canal não encontrado".into(),
-                "is_archived" => "Não é possível entrar em canal arquivado".into(),
-                "method_not_supported_for_channel_type" => "Não é possível entrar neste tipo de canal (privado ou DM)".into(),
-                "missing_scope" => "Permissões insuficientes. A aplicação precisa do escopo 'channels:join'".into(),
-                "rate_limited" => "Limite de requisições excedido. Tente novamente em alguns segundos".into(),
-                "already_in_channel" => return Ok(true), // Already in channel is considered success
-                _ => format!("Erro do Slack: {}", error_msg).into()
-            });
+            if error_msg == "already_in_channel" {
+                return Ok(true); // Already in channel is considered success
+            }
+            return Err(SlackError::from_api_error(&error_msg, Some("channels:join")));
         }
 
         Ok(true)
     }
 
-    /// List all users in the Slack workspace
-    pub async fn list_users(&self) -> Result<Vec<SlackUser>, Box<dyn Error + Send + Sync>> {
-        let token = self.access_token.as_ref().ok_or("Token de acesso não configurado")?;
-        
-        let mut all_users = Vec::new();
-        let mut cursor: Option<String> = None;
+    /// Invalidate the token on Slack's side (`auth.revoke`). Returns `false` when Slack no
+    /// longer accepted the token anyway, so there was nothing left to revoke.
+    pub async fn revoke_token(&self) -> Result<bool, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
         
-        // Paginate through all users
-        loop {
-            let mut query_params = vec![
-                ("limit", "1000"), // Maximum allowed by Slack API
-            ];
-            
+        let response = self.client
+            .post("https://slack.com/api/auth.revoke")
+            .bearer_auth(token)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(SlackError::from_status(response.status(), retry_after(&response)));
+        }
+
+        #[derive(Deserialize)]
+        struct RevokeResponse {
+            ok: bool,
+            #[serde(default)]
+            revoked: bool,
+            error: Option<String>,
+        }
+
+        let revoke_response: RevokeResponse = response.json().await
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
+
+        if !revoke_response.ok {
+            let error_msg = revoke_response.error.unwrap_or_else(|| "Erro desconhecido".to_string());
+            return match SlackError::from_api_error(&error_msg, None) {
+                SlackError::Auth(_) => Ok(false),
+                other => Err(other),
+            };
+        }
+
+        Ok(revoke_response.revoked)
+    }
+
+    /// List all users in the Slack workspace
+    pub async fn list_users(&self) -> Result<Vec<SlackUser>, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
+        
+        let mut all_users = Vec::new();
+        let mut cursor: Option<String> = None;
+        
+        // Paginate through all users
+        loop {
+            let mut query_params = vec![
+                ("limit", "1000"), // Maximum allowed by Slack API
+            ];
+            
             if let Some(ref c) = cursor {
                 query_params.push(("cursor", c.as_str()));
             }
@@ -825,26 +1456,11 @@ impl SlackClient {
                 .bearer_auth(token)
                 .query(&query_params)
                 .send()
-                .await
-                .map_err(|e| -> String {
-                    if e.is_timeout() {
-                        "Timeout na conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else if e.is_connect() {
-                        "Erro de conexão com Slack. Verifique sua conexão com a internet.".to_string()
-                    } else {
-                        format!("Erro na requisição ao Slack: {}", e)
-                    }
-                })?;
+                .await?;
 
             // Check HTTP status
             if !response.status().is_success() {
-                return Err(format!("Erro HTTP {}: {}", response.status().as_u16(), 
-                    match response.status().as_u16() {
-                        401 => "Token de acesso inválido ou expirado",
-                        403 => "Sem permissão para listar usuários",
-                        429 => "Muitas requisições. Tente novamente em alguns segundos",
-                        _ => "Erro desconhecido do Slack"
-                    }).into());
+                return Err(SlackError::from_status(response.status(), retry_after(&response)));
             }
 
             #[derive(Deserialize)]
@@ -856,16 +1472,11 @@ impl SlackClient {
             }
 
             let users_response: UsersListResponse = response.json().await
-                .map_err(|e| format!("Erro ao processar resposta do Slack: {}", e))?;
+                .map_err(|e| SlackError::Parse(e.to_string()))?;
 
             if !users_response.ok {
                 let error_msg = users_response.error.unwrap_or_else(|| "Erro desconhecido".to_string());
-                return Err(match error_msg.as_str() {
-                    "invalid_auth" => "Token de acesso inválido. Execute a autenticação OAuth novamente".into(),
-                    "missing_scope" => "Permissões insuficientes. A aplicação precisa do escopo 'users:read'".into(),
-                    "rate_limited" => "Limite de requisições excedido. Tente novamente em alguns segundos".into(),
-                    _ => format!("Erro do Slack: {}", error_msg).into()
-                });
+                return Err(SlackError::from_api_error(&error_msg, Some("users:read")));
             }
 
             if let Some(users) = users_response.members {
@@ -897,10 +1508,64 @@ impl SlackClient {
         Ok(all_users)
     }
 
-    pub fn build_oauth_url(client_id: &str, redirect_uri: &str, scopes: &[&str], state: Option<&str>) -> Result<String, Box<dyn Error + Send + Sync>> {
+    /// Fetch a user's presence via `users.getPresence`
+    pub async fn get_presence(&self, user_id: &str) -> Result<SlackPresence, SlackError> {
+        let token = self.access_token.as_ref().ok_or_else(SlackError::missing_token)?;
+        
+        let response = self.client
+            .get("https://slack.com/api/users.getPresence")
+            .bearer_auth(token)
+            .query(&[("user", user_id)])
+            .send()
+            .await?;
+        
+        if !response.status().is_success() {
+            return Err(SlackError::from_status(response.status(), retry_after(&response)));
+        }
+        
+        let body = response.text().await?;
+        
+        parse_presence_response(&body)
+    }
+
+    /// Attach presence to suggested assignees and list tasks with active assignees first.
+    /// Assignees are matched to `users` by id or name, and each member's presence is
+    /// looked up once, a few at a time.
+    pub async fn annotate_assignee_presence(&self, tasks: &mut Vec<PotentialTask>, users: &[SlackUser]) {
+        use futures::StreamExt;
+        
+        let assignee_ids: Vec<Option<String>> = tasks.iter()
+            .map(|task| task.suggested_assignee.as_deref().and_then(|assignee| resolve_assignee_id(assignee, users)))
+            .collect();
+        let unique_ids: std::collections::BTreeSet<&String> = assignee_ids.iter().flatten().collect();
+        
+        let presence: HashMap<String, Option<String>> = futures::stream::iter(unique_ids)
+            .map(|user_id| async move {
+                let presence = match self.get_presence(user_id).await {
+                    Ok(presence) => Some(presence.presence),
+                    Err(e) => {
+                        println!("⚠️ [SLACK_PRESENCE] Could not get presence for {}: {}", user_id, e);
+                        None
+                    }
+                };
+                (user_id.clone(), presence)
+            })
+            .buffer_unordered(PRESENCE_LOOKUP_CONCURRENCY)
+            .collect()
+            .await;
+        
+        for (task, user_id) in tasks.iter_mut().zip(assignee_ids) {
+            task.assignee_presence = user_id.and_then(|id| presence.get(&id).cloned().flatten());
+        }
+        
+        tasks.sort_by_key(|task| task.assignee_presence.as_deref() != Some("active"));
+    }
+
+    pub fn build_oauth_url(client_id: &str, redirect_uri: &str, scopes: &[&str], state: Option<&str>) -> Result<String, SlackError> {
         println!("🔗 Building OAuth URL with redirect_uri: '{}'", redirect_uri);
         
-        let mut url = Url::parse("https://slack.com/oauth/v2/authorize")?;
+        let mut url = Url::parse("https://slack.com/oauth/v2/authorize")
+            .map_err(|e| SlackError::Parse(e.to_string()))?;
         
         let mut query_pairs = url.query_pairs_mut();
         query_pairs
@@ -921,26 +1586,161 @@ impl SlackClient {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackSyncState {
     pub project_id: String,
     pub channel_id: String,
     pub last_sync: DateTime<Utc>,
     pub is_active: bool,
+    /// Authors (user or bot ids) whose messages are analyzed; empty means everyone
+    #[serde(default)]
+    pub included_users: Vec<String>,
+    /// Authors whose messages are never analyzed
+    #[serde(default)]
+    pub excluded_users: Vec<String>,
+    /// Snoozed until this time
+    #[serde(default)]
+    pub paused_until: Option<DateTime<Utc>>,
+}
+
+impl SlackSyncState {
+    /// Whether the sync is snoozed at `now`. Pauses resume on their own once they expire.
+    pub fn is_paused(&self, now: DateTime<Utc>) -> bool {
+        self.paused_until.map_or(false, |until| until > now)
+    }
+
+    /// Whether `message` passes this sync's author allow/deny lists. A message matches a list
+    /// through either its user or its bot id.
+    pub fn allows_author(&self, message: &SlackMessage) -> bool {
+        let authors = [message.user.as_deref(), message.bot_id.as_deref()];
+        let listed = |list: &[String]| authors.iter().flatten().any(|author| list.iter().any(|id| id == author));
+        
+        if listed(&self.excluded_users) {
+            return false;
+        }
+        self.included_users.is_empty() || listed(&self.included_users)
+    }
+}
+
+/// Run a sync's new history through task detection: edits are re-analyzed as they read now,
+/// deletions dropped, filtered authors skipped and Slack markup resolved. `users` is the
+/// workspace member list, used to name mentions and resolve assignees. With a `task_store`
+/// database, tasks already applied from an edited or deleted message are updated or removed
+/// instead of being suggested again. Shared by the polling scheduler and the Events API receiver.
+pub async fn analyze_channel_messages(
+    client: &SlackClient,
+    sync_config: &SlackSyncState,
+    messages: Vec<SlackMessage>,
+    users: &[SlackUser],
+    task_store: Option<&std::path::Path>,
+) -> Vec<PotentialTask> {
+    // Edited messages are re-analyzed as they read now; tasks from deleted ones are stale
+    let mut current_messages = Vec::new();
+    let mut changes = Vec::new();
+    for event in classify_message_events(messages) {
+        match event {
+            SlackMessageEvent::New(message) => current_messages.push(message),
+            SlackMessageEvent::Edited { ts, message } => {
+                println!("✏️ [SLACK_SYNC] Message {} was edited, re-deriving its tasks", ts);
+                changes.push((ts, Some(message.text.clone())));
+                current_messages.push(message);
+            }
+            SlackMessageEvent::Deleted { ts } => {
+                println!("🗑️ [SLACK_SYNC] Message {} was deleted, removing tasks derived from it", ts);
+                changes.push((ts, None));
+            }
+        }
+    }
+    
+    // An edited message that already became a task keeps that task, now with the new wording
+    if let Some(db_path) = task_store.filter(|_| !changes.is_empty()) {
+        let tracked = reconcile_changed_messages(db_path, &sync_config.project_id, changes).await;
+        current_messages.retain(|message| !tracked.contains(&message.ts));
+    }
+    
+    // Skip authors the sync filters out (e.g. automated posts in a noisy channel)
+    let before_filter = current_messages.len();
+    current_messages.retain(|message| sync_config.allows_author(message));
+    if current_messages.len() < before_filter {
+        println!("🧹 [SLACK_SYNC] Skipped {} messages from filtered authors in channel {}",
+            before_filter - current_messages.len(), sync_config.channel_id);
+    }
+    
+    // Process messages for potential tasks, without Slack markup leaking into task names
+    let text_options = SlackTextOptions::for_users(users);
+    let normalized = current_messages.into_iter()
+        .map(|mut message| {
+            text_options.apply_to_message(&mut message);
+            message
+        })
+        .collect();
+    let mut potential_tasks = process_messages_for_tasks(normalized).await;
+    if potential_tasks.iter().any(|task| task.suggested_assignee.is_some()) {
+        client.annotate_assignee_presence(&mut potential_tasks, users).await;
+    }
+    
+    if !potential_tasks.is_empty() {
+        println!("🔍 [SLACK_SYNC] Found {} potential tasks in channel {}", 
+            potential_tasks.len(), sync_config.channel_id);
+        
+        // Here you could emit events to the frontend to handle these tasks
+        // For now, we'll just log them
+        for task in &potential_tasks {
+            println!("📋 [TASK_DETECTED] {} (confidence: {:.2})", 
+                task.name, task.confidence_score);
+        }
+    }
+    
+    potential_tasks
+}
+
+/// Apply edits and deletions to the tasks created from those messages. Returns the timestamps
+/// of edited messages that still have a task; failures are logged and leave tasks as they were.
+async fn reconcile_changed_messages(
+    db_path: &std::path::Path,
+    project_id: &str,
+    changes: Vec<(String, Option<String>)>,
+) -> std::collections::HashSet<String> {
+    let db_path = db_path.to_path_buf();
+    let project_id = project_id.to_string();
+    let reconciled = crate::app_db::with_db_at(db_path, move |conn| {
+        let now = Utc::now().to_rfc3339();
+        let mut tracked = std::collections::HashSet::new();
+        for (ts, current_text) in changes {
+            let linked = crate::commands::ai_automation::reconcile_message_tasks(
+                conn, &project_id, &ts, current_text.as_deref(), &now,
+            )?;
+            if linked > 0 {
+                println!("🔄 [SLACK_SYNC] Updated {} tasks from message {}", linked, ts);
+                if current_text.is_some() {
+                    tracked.insert(ts);
+                }
+            }
+        }
+        Ok(tracked)
+    })
+    .await;
+    
+    reconciled.unwrap_or_else(|e| {
+        println!("⚠️ [SLACK_SYNC] Could not update tasks from edited or deleted messages: {}", e);
+        Default::default()
+    })
 }
 
 pub async fn process_messages_for_tasks(messages: Vec<SlackMessage>) -> Vec<PotentialTask> {
     let mut potential_tasks = Vec::new();
 
     for message in messages {
-        if let Some(tasks) = extract_action_items(&message.text) {
+        let content = message.analyzable_text();
+        if let Some(tasks) = extract_action_items(&content) {
             for task_text in tasks {
                 potential_tasks.push(PotentialTask {
                     name: task_text.clone(),
-                    description: format!("From Slack message: {}", message.text),
+                    description: format!("From Slack message: {}", content),
                     source_message_ts: message.ts.clone(),
                     source_channel: message.channel.clone().unwrap_or_default(),
                     suggested_assignee: extract_assignee(&task_text),
+                    assignee_presence: None,
                     confidence_score: calculate_task_confidence(&task_text, &message),
                 });
             }
@@ -957,6 +1757,8 @@ pub struct PotentialTask {
     pub source_message_ts: String,
     pub source_channel: String,
     pub suggested_assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee_presence: Option<String>,
     pub confidence_score: f32,
 }
 
@@ -1002,6 +1804,167 @@ fn extract_action_items(text: &str) -> Option<Vec<String>> {
     }
 }
 
+/// Name lookups and switches for `normalize_slack_text`, as sent by the frontend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlackTextOptions {
+    /// Slack user id -> display name
+    #[serde(default)]
+    pub user_names: HashMap<String, String>,
+    /// Slack channel id -> channel name
+    #[serde(default)]
+    pub channel_names: HashMap<String, String>,
+    #[serde(default)]
+    pub strip_emoji: bool,
+}
+
+impl SlackTextOptions {
+    /// Options that resolve mentions of `users` to their names
+    pub fn for_users(users: &[SlackUser]) -> Self {
+        Self {
+            user_names: users.iter().map(|user| (user.id.clone(), user.mention_name().to_string())).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Normalize a message's text and its attachments' titles and text
+    pub fn apply_to_message(&self, message: &mut SlackMessage) {
+        message.text = self.apply(&message.text);
+        for attachment in message.attachments.iter_mut().flatten() {
+            attachment.title = attachment.title.as_deref().map(|title| self.apply(title));
+            attachment.text = attachment.text.as_deref().map(|text| self.apply(text));
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let normalized = normalize_slack_text(text, &self.user_names, &self.channel_names);
+        if self.strip_emoji {
+            strip_slack_emoji(&normalized)
+        } else {
+            normalized
+        }
+    }
+}
+
+/// Turn Slack markup into plain text: `<url|label>` becomes the label, `<@U123>` becomes
+/// `@name`, `<#C123|general>` becomes `#general`, and `&amp;`/`&lt;`/`&gt;` are unescaped.
+/// Ids missing from the maps fall back to the label Slack sent, then to the raw id.
+pub fn normalize_slack_text(
+    text: &str,
+    user_map: &HashMap<String, String>,
+    channel_map: &HashMap<String, String>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    
+    while let Some(open) = rest.find('<') {
+        result.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        match after_open.find('>') {
+            Some(close) => {
+                result.push_str(&resolve_slack_token(&after_open[..close], user_map, channel_map));
+                rest = &after_open[close + 1..];
+            }
+            None => {
+                result.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    
+    result.replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
+}
+
+/// Resolve the inside of one `<...>` token
+fn resolve_slack_token(
+    token: &str,
+    user_map: &HashMap<String, String>,
+    channel_map: &HashMap<String, String>,
+) -> String {
+    let (target, label) = match token.split_once('|') {
+        Some((target, label)) => (target, Some(label)),
+        None => (token, None),
+    };
+    
+    if let Some(user_id) = target.strip_prefix('@') {
+        let name = user_map.get(user_id).map(|s| s.as_str()).or(label).unwrap_or(user_id);
+        return format!("@{}", name.trim_start_matches('@'));
+    }
+    if let Some(channel_id) = target.strip_prefix('#') {
+        let name = channel_map.get(channel_id).map(|s| s.as_str()).or(label).unwrap_or(channel_id);
+        return format!("#{}", name.trim_start_matches('#'));
+    }
+    if let Some(special) = target.strip_prefix('!') {
+        // <!here>, <!channel>, <!subteam^S123|@team>, <!date^...|fallback>
+        return match label {
+            Some(label) => label.to_string(),
+            None => format!("@{}", special.split('^').next().unwrap_or(special)),
+        };
+    }
+    
+    label.unwrap_or_else(|| target.strip_prefix("mailto:").unwrap_or(target)).to_string()
+}
+
+/// Remove `:emoji:` shortcodes (including skin tone suffixes) and tidy the leftover spaces.
+/// Colons inside words or numbers, like `10:30:45`, are left alone.
+pub fn strip_slack_emoji(text: &str) -> String {
+    let is_shortcode_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '+' | '-');
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    
+    while i < chars.len() {
+        if chars[i] == ':' && (i == 0 || !chars[i - 1].is_alphanumeric() || chars[i - 1] == ':') {
+            let name_len = chars[i + 1..].iter().take_while(|c| is_shortcode_char(**c)).count();
+            let close = i + 1 + name_len;
+            let followed_by_word = chars.get(close + 1).map_or(false, |c| c.is_alphanumeric());
+            if name_len > 0 && chars.get(close) == Some(&':') && !followed_by_word {
+                i = close + 1;
+                continue;
+            }
+        }
+        result.push(chars[i]);
+        i += 1;
+    }
+    
+    result.split(' ').filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ")
+        .lines().map(|line| line.trim()).collect::<Vec<_>>().join("\n")
+}
+
+/// The workspace member a suggested assignee refers to: a user id (`U123`, `<@U123>`) or a
+/// handle, display name or real name, compared case-insensitively. Unknown ids are kept
+/// as they are so presence can still be looked up when the member list is unavailable.
+pub fn resolve_assignee_id(assignee: &str, users: &[SlackUser]) -> Option<String> {
+    let mention = assignee.trim()
+        .trim_start_matches("<@")
+        .trim_start_matches('@')
+        .trim_end_matches('>')
+        .trim_end_matches(|c: char| matches!(c, ',' | '.' | ':' | ';' | '!' | '?'));
+    let mention = mention.split('|').next().unwrap_or(mention);
+    if mention.is_empty() {
+        return None;
+    }
+    
+    let is_named = |name: Option<&str>| name.map_or(false, |name| name.eq_ignore_ascii_case(mention));
+    users.iter()
+        .find(|user| user.id == mention)
+        .or_else(|| users.iter().find(|user| {
+            let profile = user.profile.as_ref();
+            is_named(Some(user.name.as_str()))
+                || is_named(user.display_name.as_deref())
+                || is_named(user.real_name.as_deref())
+                || is_named(profile.and_then(|p| p.display_name.as_deref()))
+                || is_named(profile.and_then(|p| p.real_name.as_deref()))
+        }))
+        .map(|user| user.id.clone())
+        .or_else(|| {
+            let looks_like_id = mention.len() > 1
+                && (mention.starts_with('U') || mention.starts_with('W'))
+                && mention.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+            looks_like_id.then(|| mention.to_string())
+        })
+}
+
 fn extract_assignee(text: &str) -> Option<String> {
     if text.contains("@") {
         let parts: Vec<&str> = text.split_whitespace().collect();
@@ -1037,62 +2000,340 @@ fn calculate_task_confidence(task_text: &str, message: &SlackMessage) -> f32 {
 
 
 
+/// Channel syncs in progress, by (project id, channel id), with when each started
+static RUNNING_CHANNEL_SYNCS: Lazy<Mutex<HashMap<(String, String), DateTime<Utc>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A channel sync currently running in this process
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningChannelSync {
+    pub project_id: String,
+    pub channel_id: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Channel syncs running right now, scheduled or on demand
+pub fn running_channel_syncs() -> Vec<RunningChannelSync> {
+    RUNNING_CHANNEL_SYNCS.lock().unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|((project_id, channel_id), started_at)| RunningChannelSync {
+            project_id: project_id.clone(),
+            channel_id: channel_id.clone(),
+            started_at: *started_at,
+        })
+        .collect()
+}
+
+/// Listed in `RUNNING_CHANNEL_SYNCS` until dropped
+struct RunningSyncEntry((String, String));
+
+impl RunningSyncEntry {
+    fn start(sync_config: &SlackSyncState) -> Self {
+        let key = (sync_config.project_id.clone(), sync_config.channel_id.clone());
+        RUNNING_CHANNEL_SYNCS.lock().unwrap_or_else(|e| e.into_inner()).insert(key.clone(), Utc::now());
+        Self(key)
+    }
+}
+
+impl Drop for RunningSyncEntry {
+    fn drop(&mut self) {
+        RUNNING_CHANNEL_SYNCS.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.0);
+    }
+}
+
+/// What one incremental channel sync fetched and detected
+#[derive(Debug, Clone)]
+pub struct ChannelSyncOutcome {
+    pub message_count: usize,
+    pub tasks: Vec<PotentialTask>,
+}
+
+/// Fetch a channel's messages since its last sync and run them through task detection. With a
+/// `message_store` database the fetched history is also kept locally, and tasks applied from
+/// edited messages are updated there.
+pub async fn sync_channel_once(
+    client: &SlackClient,
+    sync_config: &SlackSyncState,
+    message_store: Option<&std::path::Path>,
+    users: &[SlackUser],
+) -> Result<ChannelSyncOutcome, SlackError> {
+    let _running = RunningSyncEntry::start(sync_config);
+    
+    // Calculate timestamp to fetch messages from (since last sync)
+    let oldest_timestamp = sync_config.last_sync.timestamp() as f64;
+    
+    // Fetch recent messages from the channel
+    let messages = client.fetch_channel_messages(
+        &sync_config.channel_id,
+        Some(oldest_timestamp),
+        None,
+        Some(1000), // Increased limit for better context
+        &channel_fetch_token(&sync_config.channel_id),
+        None,
+    ).await?;
+    
+    println!("📥 [SLACK_SYNC] Fetched {} messages from channel {}", 
+        messages.len(), sync_config.channel_id);
+    
+    // A storage failure shouldn't lose the sync; the next one stores the history again
+    if let Some(db_path) = message_store {
+        let stored = crate::slack_message_store::persist_messages(
+            db_path.to_path_buf(),
+            sync_config.channel_id.clone(),
+            messages.clone(),
+        ).await;
+        if let Err(e) = stored {
+            println!("⚠️ [SLACK_SYNC] Failed to store messages for channel {}: {}", sync_config.channel_id, e);
+        }
+    }
+    
+    let message_count = messages.len();
+    let tasks = analyze_channel_messages(client, sync_config, messages, users, message_store).await;
+    
+    Ok(ChannelSyncOutcome { message_count, tasks })
+}
+
+/// Channels synced at the same time when no concurrency is configured
+pub const DEFAULT_SYNC_CONCURRENCY: usize = 3;
+
+/// users.getPresence calls made at the same time while annotating tasks
+const PRESENCE_LOOKUP_CONCURRENCY: usize = 5;
+
+/// Share of the sync interval over which channel syncs are spread, so channels sharing an
+/// interval don't all hit the Slack API on the same tick
+pub const SYNC_JITTER_FRACTION: f64 = 0.5;
+
+/// Offset of a channel's sync within each cycle: below `SYNC_JITTER_FRACTION` of `period`,
+/// random per channel (via the randomly seeded `seed`) but the same every cycle
+pub fn sync_jitter(seed: &std::collections::hash_map::RandomState, channel_id: &str, period: std::time::Duration) -> std::time::Duration {
+    use std::hash::BuildHasher;
+    
+    let window_ms = (period.as_millis() as f64 * SYNC_JITTER_FRACTION) as u64;
+    if window_ms == 0 {
+        return std::time::Duration::ZERO;
+    }
+    std::time::Duration::from_millis(seed.hash_one(channel_id) % window_ms)
+}
+
+/// Run `sync` for every active config, at most `concurrency` at a time, each starting
+/// no earlier than its `delay`. Syncs still waiting out their delay when `cancel` fires are
+/// skipped. Returns each channel id with its sync's result, in completion order.
+pub async fn sync_channels_concurrently<F, Fut, D, T>(
+    sync_configs: &[SlackSyncState],
+    concurrency: usize,
+    delay: D,
+    cancel: &tokio_util::sync::CancellationToken,
+    sync: F,
+) -> Vec<(String, Result<T, String>)>
+where
+    F: Fn(SlackSyncState) -> Fut,
+    Fut: std::future::Future<Output = Result<T, String>>,
+    D: Fn(&SlackSyncState) -> std::time::Duration,
+{
+    let semaphore = tokio::sync::Semaphore::new(concurrency.max(1));
+    let syncs = sync_configs.iter()
+        .filter(|sync_config| sync_config.is_active)
+        .map(|sync_config| {
+            let semaphore = &semaphore;
+            let sync = &sync;
+            let delay = delay(sync_config);
+            async move {
+                // Wait before taking a permit so delayed channels don't hold up the others
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = cancel.cancelled() => return None,
+                }
+                let _permit = semaphore.acquire().await.expect("sync semaphore is never closed");
+                Some((sync_config.channel_id.clone(), sync(sync_config.clone()).await))
+            }
+        });
+    
+    futures::future::join_all(syncs).await.into_iter().flatten().collect()
+}
+
 #[derive(Clone)]
 pub struct SlackSyncScheduler {
     client: SlackClient,
-    interval_minutes: u64,
+    concurrency: usize,
+    message_store: Option<std::path::PathBuf>,
+    interval_minutes: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    interval_changed: std::sync::Arc<tokio::sync::Notify>,
     is_running: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Cancelled by `stop`
+    stopped: tokio_util::sync::CancellationToken,
+    /// Cancelled when the current cycle's remaining delayed syncs should be dropped
+    cycle: std::sync::Arc<Mutex<tokio_util::sync::CancellationToken>>,
+    /// Pauses set or cleared since the last cycle, by (project id, channel id)
+    pause_changes: std::sync::Arc<Mutex<Vec<(String, String, Option<DateTime<Utc>>)>>>,
 }
 
 impl SlackSyncScheduler {
     pub fn new(client: SlackClient, interval_minutes: u64) -> Self {
         Self {
             client,
-            interval_minutes,
+            concurrency: DEFAULT_SYNC_CONCURRENCY,
+            message_store: None,
+            interval_minutes: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(interval_minutes)),
+            interval_changed: std::sync::Arc::new(tokio::sync::Notify::new()),
             is_running: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            stopped: tokio_util::sync::CancellationToken::new(),
+            cycle: std::sync::Arc::new(Mutex::new(tokio_util::sync::CancellationToken::new())),
+            pause_changes: std::sync::Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// How many channels to sync at the same time (at least one)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Keep synced messages in the database at `db_path`
+    pub fn with_message_store(mut self, db_path: std::path::PathBuf) -> Self {
+        self.message_store = Some(db_path);
+        self
+    }
+
+    pub fn interval_minutes(&self) -> u64 {
+        self.interval_minutes.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Change the sync interval of a running scheduler; the next run is rescheduled from now,
+    /// and channels of the current run that haven't started yet wait for it
+    pub fn set_interval_minutes(&self, interval_minutes: u64) {
+        let previous = self.interval_minutes.swap(interval_minutes, std::sync::atomic::Ordering::SeqCst);
+        if previous != interval_minutes {
+            println!("⏱️ [SLACK_SYNC] Interval changed from {} to {} minutes", previous, interval_minutes);
+            self.cycle.lock().unwrap_or_else(|e| e.into_inner()).cancel();
+            self.interval_changed.notify_one();
         }
     }
 
-    pub async fn start(&self, sync_configs: Vec<SlackSyncState>) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// Pause a running scheduler's sync of a project's channel until `until`, or resume it
+    /// with `None`; applied at the start of the next cycle
+    pub fn set_paused_until(&self, project_id: &str, channel_id: &str, until: Option<DateTime<Utc>>) {
+        self.pause_changes.lock().unwrap_or_else(|e| e.into_inner())
+            .push((project_id.to_string(), channel_id.to_string(), until));
+    }
+
+    pub async fn start(&self, mut sync_configs: Vec<SlackSyncState>) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.is_running.store(true, std::sync::atomic::Ordering::SeqCst);
         
         println!("🔄 [SLACK_SYNC] Starting background sync for {} channels", sync_configs.len());
         
         let client = self.client.clone();
-        let interval_minutes = self.interval_minutes;
+        let concurrency = self.concurrency;
+        let message_store = self.message_store.clone();
+        let interval_minutes = Arc::clone(&self.interval_minutes);
+        let interval_changed = Arc::clone(&self.interval_changed);
         let is_running = Arc::clone(&self.is_running);
+        let stopped = self.stopped.clone();
+        let current_cycle = Arc::clone(&self.cycle);
+        let pause_changes = Arc::clone(&self.pause_changes);
         
         // Spawn background task for periodic sync
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_minutes * 60));
+            let period = |minutes: u64| tokio::time::Duration::from_secs(minutes.max(1) * 60);
+            let mut interval = tokio::time::interval(period(interval_minutes.load(std::sync::atomic::Ordering::SeqCst)));
+            // A slow, jittered cycle shouldn't be followed by a burst of catch-up ticks
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let jitter_seed = std::collections::hash_map::RandomState::new();
+            let mut last_poll_at: Option<i64> = None;
             
             while is_running.load(std::sync::atomic::Ordering::SeqCst) {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stopped.cancelled() => break,
+                    _ = interval_changed.notified() => {
+                        let next = period(interval_minutes.load(std::sync::atomic::Ordering::SeqCst));
+                        interval = tokio::time::interval_at(tokio::time::Instant::now() + next, next);
+                        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                        continue;
+                    }
+                }
                 
                 if !is_running.load(std::sync::atomic::Ordering::SeqCst) {
                     break;
                 }
                 
-                println!("🔄 [SLACK_SYNC] Running periodic sync...");
+                // Polling is only the fallback for when Slack pushes messages to us
+                if crate::slack_events::should_skip_poll(last_poll_at, Utc::now().timestamp()) {
+                    println!("⚡ [SLACK_SYNC] Receiving Slack events, skipping periodic poll");
+                    continue;
+                }
+                last_poll_at = Some(Utc::now().timestamp());
                 
-                for sync_config in &sync_configs {
-                    if !sync_config.is_active {
-                        continue;
+                // Spread channels over the interval, including the first cycle at startup
+                let cycle_period = period(interval_minutes.load(std::sync::atomic::Ordering::SeqCst));
+                let jitter = |channel_id: &str| sync_jitter(&jitter_seed, channel_id, cycle_period);
+                
+                // Snoozed channels stay connected and are picked up again once their pause ends
+                for (project_id, channel_id, until) in pause_changes.lock().unwrap_or_else(|e| e.into_inner()).drain(..) {
+                    for sync_config in sync_configs.iter_mut().filter(|c| c.project_id == project_id && c.channel_id == channel_id) {
+                        sync_config.paused_until = until;
                     }
-                    
-                    match Self::sync_channel_messages(&client, sync_config).await {
-                        Ok(message_count) => {
+                }
+                let now = Utc::now();
+                let due: Vec<SlackSyncState> = sync_configs.iter().filter(|c| !c.is_paused(now)).cloned().collect();
+                if due.len() < sync_configs.len() {
+                    println!("⏸️ [SLACK_SYNC] Skipping {} paused channels", sync_configs.len() - due.len());
+                }
+                
+                // Queued syncs survive a crash; only channels the queue can't take are synced here
+                let unqueued = Self::queue_channel_syncs(&client, &due, &jitter).await;
+                if unqueued.is_empty() {
+                    continue;
+                }
+                
+                println!("🔄 [SLACK_SYNC] Running periodic sync ({} channels at a time)...", concurrency);
+                let cycle_start = std::time::Instant::now();
+                
+                // One member list per cycle names mentions and resolves assignees in every channel
+                let users = client.list_users().await.unwrap_or_else(|e| {
+                    println!("⚠️ [SLACK_SYNC] Could not list workspace members, mentions stay as ids: {}", e);
+                    vec![]
+                });
+                
+                // Per-channel locks in fetch_channel_messages still keep a channel from being
+                // fetched twice at once
+                let cycle = stopped.child_token();
+                *current_cycle.lock().unwrap_or_else(|e| e.into_inner()) = cycle.clone();
+                let results = sync_channels_concurrently(&unqueued, concurrency, |sync_config| jitter(&sync_config.channel_id), &cycle, |sync_config| {
+                    let client = &client;
+                    let message_store = message_store.as_deref();
+                    let users = &users;
+                    async move {
+                        let synced_at = Utc::now();
+                        let message_count = Self::sync_channel_messages(client, &sync_config, message_store, users).await.map_err(|e| e.to_string())?;
+                        if let Some(db_path) = message_store {
+                            Self::record_sync(db_path, &sync_config, synced_at).await;
+                        }
+                        Ok::<_, String>((sync_config.project_id, message_count, synced_at))
+                    }
+                }).await;
+                
+                let mut synced_messages = 0;
+                let mut failed_channels = 0;
+                for (channel_id, result) in &results {
+                    match result {
+                        Ok((project_id, message_count, synced_at)) => {
+                            synced_messages += message_count;
                             println!("✅ [SLACK_SYNC] Synced {} messages from channel {}", 
-                                message_count, sync_config.channel_id);
+                                message_count, channel_id);
+                            // The next cycle only fetches what was posted since this sync started
+                            for sync_config in sync_configs.iter_mut().filter(|c| &c.project_id == project_id && &c.channel_id == channel_id) {
+                                sync_config.last_sync = *synced_at;
+                            }
                         }
                         Err(e) => {
+                            failed_channels += 1;
                             eprintln!("❌ [SLACK_SYNC] Failed to sync channel {}: {}", 
-                                sync_config.channel_id, e);
+                                channel_id, e);
                         }
                     }
                 }
                 
-                println!("🔄 [SLACK_SYNC] Periodic sync completed");
+                println!("🔄 [SLACK_SYNC] Periodic sync completed in {:?}: {} channels, {} failed, {} messages",
+                    cycle_start.elapsed(), results.len(), failed_channels, synced_messages);
             }
             
             println!("🛑 [SLACK_SYNC] Background sync stopped");
@@ -1104,42 +2345,79 @@ impl SlackSyncScheduler {
     pub async fn stop(&self) {
         println!("🛑 [SLACK_SYNC] Stopping background sync...");
         self.is_running.store(false, std::sync::atomic::Ordering::SeqCst);
+        self.stopped.cancel();
     }
     
-    /// Sync messages from a specific channel
-    async fn sync_channel_messages(
+    /// Hand each active channel's sync to the queue service. The queue keeps one pending job
+    /// per channel, and channels already syncing in-process are left alone. Returns the configs
+    /// that couldn't be queued (all of them when the queue service is unreachable), which are
+    /// then synced in-process. Each job is delayed by its channel's `jitter`.
+    async fn queue_channel_syncs(
         client: &SlackClient,
-        sync_config: &SlackSyncState,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
-        // Calculate timestamp to fetch messages from (since last sync)
-        let oldest_timestamp = sync_config.last_sync.timestamp() as f64;
-        
-        // Fetch recent messages from the channel
-        let messages = client.fetch_channel_messages(
-            &sync_config.channel_id,
-            Some(oldest_timestamp),
-            Some(1000), // Increased limit for better context
-        ).await?;
-        
-        println!("📥 [SLACK_SYNC] Fetched {} messages from channel {}", 
-            messages.len(), sync_config.channel_id);
+        sync_configs: &[SlackSyncState],
+        jitter: &impl Fn(&str) -> std::time::Duration,
+    ) -> Vec<SlackSyncState> {
+        let active: Vec<SlackSyncState> = sync_configs.iter().filter(|c| c.is_active).cloned().collect();
+        let token = match client.get_token() {
+            Some(token) => token.clone(),
+            None => return active,
+        };
         
-        // Process messages for potential tasks
-        let potential_tasks = process_messages_for_tasks(messages.clone()).await;
+        let queue = crate::queue_service_client::QueueServiceClient::new(None);
+        if !queue.health_check().await.unwrap_or(false) {
+            println!("⚠️ [SLACK_SYNC] Queue service unavailable, syncing in-process");
+            return active;
+        }
         
-        if !potential_tasks.is_empty() {
-            println!("🔍 [SLACK_SYNC] Found {} potential tasks in channel {}", 
-                potential_tasks.len(), sync_config.channel_id);
-            
-            // Here you could emit events to the frontend to handle these tasks
-            // For now, we'll just log them
-            for task in &potential_tasks {
-                println!("📋 [TASK_DETECTED] {} (confidence: {:.2})", 
-                    task.name, task.confidence_score);
+        let running = running_channel_syncs();
+        let mut unqueued = Vec::new();
+        for sync_config in active {
+            if running.iter().any(|r| r.channel_id == sync_config.channel_id) {
+                println!("⏭️ [SLACK_SYNC] Channel {} is already syncing in-process", sync_config.channel_id);
+                continue;
+            }
+            let request = crate::queue_service_client::SlackSyncRequest {
+                project_id: sync_config.project_id.clone(),
+                channel_id: sync_config.channel_id.clone(),
+                channel_name: sync_config.channel_id.clone(),
+                access_token: token.clone(),
+                last_timestamp: Some(sync_config.last_sync.timestamp().max(0) as u64),
+            };
+            match queue.queue_slack_sync_after(request, jitter(&sync_config.channel_id)).await {
+                Ok(job) if job.deduplicated => println!("⏭️ [SLACK_SYNC] Channel {} already has sync job {}", sync_config.channel_id, job.id),
+                Ok(job) => println!("📬 [SLACK_SYNC] Queued sync for channel {} as job {}", sync_config.channel_id, job.id),
+                Err(e) => {
+                    println!("⚠️ [SLACK_SYNC] Could not queue channel {}, syncing in-process: {}", sync_config.channel_id, e);
+                    unqueued.push(sync_config);
+                }
             }
         }
-        
-        Ok(messages.len())
+        unqueued
+    }
+    
+    /// Store when a channel was last synced. A failure only costs the next sync some messages
+    /// it has already seen, so it is logged rather than failing the sync.
+    async fn record_sync(db_path: &std::path::Path, sync_config: &SlackSyncState, synced_at: DateTime<Utc>) {
+        let db_path = db_path.to_path_buf();
+        let project_id = sync_config.project_id.clone();
+        let channel_id = sync_config.channel_id.clone();
+        let recorded = crate::app_db::with_db_at(db_path, move |conn| {
+            crate::slack_sync::record_channel_sync(conn, &project_id, &channel_id, &synced_at.to_rfc3339())
+        })
+        .await;
+        if let Err(e) = recorded {
+            println!("⚠️ [SLACK_SYNC] Failed to record sync time for channel {}: {}", sync_config.channel_id, e);
+        }
+    }
+    
+    /// Sync messages from a specific channel
+    async fn sync_channel_messages(
+        client: &SlackClient,
+        sync_config: &SlackSyncState,
+        message_store: Option<&std::path::Path>,
+        users: &[SlackUser],
+    ) -> Result<usize, SlackError> {
+        sync_channel_once(client, sync_config, message_store, users).await.map(|outcome| outcome.message_count)
     }
 
     pub async fn is_running(&self) -> bool {
@@ -1148,3 +2426,408 @@ impl SlackSyncScheduler {
 }
 
 // OAuth flow implementation
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slack_markup_is_resolved_to_readable_text() {
+        let users = HashMap::from([("U123".to_string(), "Ana".to_string())]);
+        let channels = HashMap::from([("C1".to_string(), "launch".to_string())]);
+
+        let text = "<@U123> please review <https://example.com/doc|the spec> in <#C1|old-name> \
+                    and ping <@U999> &amp; <!here> about <#C2|design> via <mailto:a@b.com>";
+        assert_eq!(
+            normalize_slack_text(text, &users, &channels),
+            "@Ana please review the spec in #launch and ping @U999 & @here about #design via a@b.com"
+        );
+        assert_eq!(normalize_slack_text("a < b", &users, &channels), "a < b");
+    }
+
+    #[test]
+    fn assignees_are_resolved_to_member_ids() {
+        let users: Vec<SlackUser> = serde_json::from_value(serde_json::json!([
+            {"id": "U1", "name": "ana.souza", "real_name": "Ana Souza", "profile": {"display_name": "Ana"}},
+            {"id": "U2", "name": "bruno"}
+        ])).unwrap();
+
+        assert_eq!(resolve_assignee_id("Ana,", &users).as_deref(), Some("U1"));
+        assert_eq!(resolve_assignee_id("@ana.souza", &users).as_deref(), Some("U1"));
+        assert_eq!(resolve_assignee_id("<@U2>", &users).as_deref(), Some("U2"));
+        assert_eq!(resolve_assignee_id("U999", &[]).as_deref(), Some("U999"));
+        assert_eq!(resolve_assignee_id("carla", &users), None);
+    }
+
+    #[test]
+    fn member_list_names_mentions() {
+        let users: Vec<SlackUser> = serde_json::from_value(serde_json::json!([
+            {"id": "U1", "name": "ana.souza", "real_name": "Ana Souza", "profile": {"display_name": "Ana"}},
+            {"id": "U2", "name": "bruno", "profile": {"display_name": "", "real_name": "Bruno Lima"}},
+            {"id": "U3", "name": "carla"}
+        ])).unwrap();
+
+        let options = SlackTextOptions::for_users(&users);
+        assert_eq!(options.apply("<@U1> and <@U2> ask <@U3> and <@U4>"), "@Ana and @Bruno Lima ask @carla and @U4");
+        assert_eq!(resolve_assignee_id("@Ana", &users).as_deref(), Some("U1"));
+    }
+
+    #[test]
+    fn channel_sync_guard_releases_on_drop_and_panic() {
+        let guard = ChannelSyncGuard::try_acquire("C-guard").unwrap();
+        assert!(ChannelSyncGuard::try_acquire("C-guard").is_none());
+        drop(guard);
+
+        let result = std::panic::catch_unwind(|| {
+            let _guard = ChannelSyncGuard::try_acquire("C-guard").unwrap();
+            panic!("fetch blew up");
+        });
+        assert!(result.is_err());
+        assert!(ChannelSyncGuard::try_acquire("C-guard").is_some());
+    }
+
+    #[test]
+    fn stale_channel_sync_locks_are_taken_over() {
+        let start = std::time::Instant::now();
+        let leaked = ChannelSyncGuard::try_acquire_at("C-stale", start).unwrap();
+        std::mem::forget(leaked);
+
+        assert!(ChannelSyncGuard::try_acquire_at("C-stale", start).is_none());
+        assert!(ChannelSyncGuard::try_acquire_at("C-stale", start + CHANNEL_SYNC_STALE_AFTER).is_some());
+    }
+
+    #[test]
+    fn dropping_a_taken_over_guard_keeps_the_channel_locked() {
+        let start = std::time::Instant::now();
+        let later = start + CHANNEL_SYNC_STALE_AFTER;
+        let slow = ChannelSyncGuard::try_acquire_at("C-takeover", start).unwrap();
+        let current = ChannelSyncGuard::try_acquire_at("C-takeover", later).unwrap();
+
+        drop(slow);
+        assert!(ChannelSyncGuard::try_acquire_at("C-takeover", later).is_none());
+
+        drop(current);
+        assert!(ChannelSyncGuard::try_acquire_at("C-takeover", later).is_some());
+    }
+
+    #[test]
+    fn edits_and_deletions_are_classified_from_history() {
+        let history: Vec<SlackMessage> = serde_json::from_value(serde_json::json!([
+            {"type": "message", "ts": "1.0", "user": "U1", "text": "TODO: ship it"},
+            {"type": "message", "ts": "2.0", "user": "U1", "text": "never mind", "edited": {"user": "U1", "ts": "2.5"}},
+            {"type": "message", "subtype": "message_changed", "ts": "3.5", "channel": "C1",
+             "message": {"type": "message", "ts": "3.0", "user": "U2", "text": "updated"}},
+            {"type": "message", "subtype": "message_deleted", "ts": "4.5", "deleted_ts": "4.0"}
+        ])).unwrap();
+
+        let events = classify_message_events(history);
+        assert_eq!(events.iter().map(|e| e.source_ts()).collect::<Vec<_>>(), vec!["1.0", "2.0", "3.0", "4.0"]);
+        assert!(matches!(&events[0], SlackMessageEvent::New(_)));
+        assert!(matches!(&events[1], SlackMessageEvent::Edited { message, .. } if message.text == "never mind"));
+        assert!(matches!(&events[2], SlackMessageEvent::Edited { message, .. }
+            if message.text == "updated" && message.channel.as_deref() == Some("C1")));
+        assert!(matches!(&events[3], SlackMessageEvent::Deleted { .. }));
+    }
+
+    #[test]
+    fn sync_author_lists_filter_messages() {
+        let message = |user: Option<&str>, bot_id: Option<&str>| -> SlackMessage {
+            serde_json::from_value(serde_json::json!({"ts": "1.0", "user": user, "bot_id": bot_id})).unwrap()
+        };
+        let mut sync = SlackSyncState {
+            project_id: "p1".to_string(),
+            channel_id: "C1".to_string(),
+            last_sync: Utc::now(),
+            is_active: true,
+            included_users: vec![],
+            excluded_users: vec![],
+            paused_until: None,
+        };
+        assert!(sync.allows_author(&message(Some("U1"), None)));
+
+        sync.excluded_users = vec!["B1".to_string()];
+        assert!(!sync.allows_author(&message(Some("U9"), Some("B1"))));
+        assert!(sync.allows_author(&message(Some("U1"), None)));
+
+        sync.included_users = vec!["U1".to_string()];
+        assert!(sync.allows_author(&message(Some("U1"), None)));
+        assert!(!sync.allows_author(&message(Some("U2"), None)));
+        assert!(!sync.allows_author(&message(None, None)));
+    }
+
+    #[test]
+    fn pages_are_trimmed_to_the_requested_window() {
+        let page = || -> Vec<SlackMessage> {
+            serde_json::from_value(serde_json::json!([
+                {"ts": "30.0", "text": "too new"},
+                {"ts": "20.0", "text": "in window"},
+                {"ts": "10.0", "text": "too old"}
+            ])).unwrap()
+        };
+
+        let mut messages = page();
+        assert!(trim_to_window(&mut messages, Some(15.0), Some(25.0)));
+        assert_eq!(messages.iter().map(|m| m.ts.as_str()).collect::<Vec<_>>(), vec!["20.0"]);
+
+        let mut messages = page();
+        assert!(!trim_to_window(&mut messages, None, Some(25.0)));
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn slack_timestamps_parse_or_report_malformed_input() {
+        assert_eq!(parse_slack_ts("1699999999.000100"), Some(1699999999.0001));
+        assert_eq!(parse_slack_ts(" 1700000000 "), Some(1700000000.0));
+        assert_eq!(parse_slack_ts(""), None);
+        assert_eq!(parse_slack_ts("yesterday"), None);
+        assert_eq!(parse_slack_ts("-5"), None);
+        assert_eq!(parse_slack_ts("NaN"), None);
+
+        let datetime = slack_ts_to_datetime("1700000000.250000").unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2023-11-14T22:13:20.250+00:00");
+    }
+
+    #[test]
+    fn page_delay_grows_on_rate_limits_and_decays_on_success() {
+        let mut delay = AdaptivePageDelay::new();
+        assert!(delay.delay().is_zero());
+
+        assert_eq!(delay.on_rate_limited(None), std::time::Duration::from_secs(1));
+        assert_eq!(delay.on_rate_limited(None), std::time::Duration::from_secs(2));
+        assert_eq!(delay.on_rate_limited(Some(std::time::Duration::from_secs(10))), std::time::Duration::from_secs(10));
+        assert_eq!(delay.delay(), std::time::Duration::from_secs(4));
+
+        for _ in 0..3 {
+            delay.on_success();
+        }
+        assert_eq!(delay.delay(), std::time::Duration::from_millis(500));
+        for _ in 0..4 {
+            delay.on_success();
+        }
+        assert!(delay.delay().is_zero());
+
+        for _ in 0..10 {
+            delay.on_rate_limited(None);
+        }
+        assert_eq!(delay.delay(), MAX_PAGE_DELAY);
+    }
+
+    #[test]
+    fn cancelling_a_channel_stops_its_current_fetches_only() {
+        let running = channel_fetch_token("C-cancel");
+        assert!(!running.is_cancelled());
+
+        assert!(cancel_channel_fetches("C-cancel"));
+        assert!(running.is_cancelled());
+        assert!(!channel_fetch_token("C-cancel").is_cancelled());
+        assert!(!cancel_channel_fetches("C-never-fetched"));
+    }
+
+    #[test]
+    fn rotated_tokens_are_read_from_the_oauth_response() {
+        let now = Utc::now();
+        let response: SlackOAuthResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "access_token": "xoxe.xoxb-1-new",
+            "refresh_token": "xoxe-1-refresh",
+            "expires_in": 43200
+        }))
+        .unwrap();
+
+        let rotated = response.rotated_token(now).unwrap();
+        assert_eq!(rotated.access_token, "xoxe.xoxb-1-new");
+        assert_eq!(rotated.refresh_token.as_deref(), Some("xoxe-1-refresh"));
+        assert_eq!(rotated.expires_at, Some(now + chrono::Duration::seconds(43200)));
+
+        let without_rotation: SlackOAuthResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "access_token": "xoxb-1"
+        }))
+        .unwrap();
+        assert!(without_rotation.rotated_token(now).is_none());
+    }
+
+    #[tokio::test]
+    async fn channel_syncs_run_concurrently_up_to_the_limit() {
+        let config = |channel_id: &str, is_active: bool| SlackSyncState {
+            project_id: "p1".to_string(),
+            channel_id: channel_id.to_string(),
+            last_sync: Utc::now(),
+            is_active,
+            included_users: vec![],
+            excluded_users: vec![],
+            paused_until: None,
+        };
+        let configs = vec![
+            config("C1", true), config("C2", true), config("C3", true),
+            config("C4", false), config("C5", true), config("C6", true),
+        ];
+        let running = std::sync::atomic::AtomicUsize::new(0);
+        let max_running = std::sync::atomic::AtomicUsize::new(0);
+
+        let results = sync_channels_concurrently(&configs, 2, |_| std::time::Duration::ZERO, &tokio_util::sync::CancellationToken::new(), |sync_config| {
+            let running = &running;
+            let max_running = &max_running;
+            let fails = sync_config.channel_id == "C3";
+            async move {
+                let now = running.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, std::sync::atomic::Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                running.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                if fails { Err("not_in_channel".to_string()) } else { Ok(10) }
+            }
+        }).await;
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(max_running.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(results.iter().filter(|(_, result)| result.is_err()).count(), 1);
+        assert!(results.iter().all(|(channel_id, _)| channel_id != "C4"));
+    }
+
+    #[tokio::test]
+    async fn delayed_channel_syncs_are_skipped_once_cancelled() {
+        let config = |channel_id: &str| SlackSyncState {
+            project_id: "p1".to_string(),
+            channel_id: channel_id.to_string(),
+            last_sync: Utc::now(),
+            is_active: true,
+            included_users: vec![],
+            excluded_users: vec![],
+            paused_until: None,
+        };
+        let configs = vec![config("C1"), config("C2")];
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let delay = |sync_config: &SlackSyncState| if sync_config.channel_id == "C1" {
+            std::time::Duration::ZERO
+        } else {
+            std::time::Duration::from_secs(3600)
+        };
+
+        let started = std::time::Instant::now();
+        let results = sync_channels_concurrently(&configs, 2, delay, &cancel, |sync_config| {
+            let cancel = &cancel;
+            async move {
+                cancel.cancel();
+                Ok::<_, String>(sync_config.channel_id)
+            }
+        }).await;
+
+        assert_eq!(results, vec![("C1".to_string(), Ok("C1".to_string()))]);
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn sync_jitter_is_stable_per_channel_and_within_the_window() {
+        let seed = std::collections::hash_map::RandomState::new();
+        let period = std::time::Duration::from_secs(15 * 60);
+        let window = period.mul_f64(SYNC_JITTER_FRACTION);
+
+        let offsets: Vec<_> = (0..20).map(|i| sync_jitter(&seed, &format!("C{}", i), period)).collect();
+        assert!(offsets.iter().all(|offset| *offset < window));
+        assert!(offsets.iter().any(|offset| *offset != offsets[0]));
+        assert_eq!(sync_jitter(&seed, "C1", period), sync_jitter(&seed, "C1", period));
+        assert_eq!(sync_jitter(&seed, "C1", std::time::Duration::ZERO), std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn attachments_are_included_in_analyzable_text() {
+        let attachment = |author: Option<&str>, title: Option<&str>, text: Option<&str>| SlackAttachment {
+            title: title.map(String::from),
+            text: text.map(String::from),
+            author_name: author.map(String::from),
+            color: None,
+        };
+        let message = SlackMessage {
+            ts: "1.0".to_string(),
+            user: Some("U1".to_string()),
+            text: "FYI".to_string(),
+            channel: None,
+            msg_type: "message".to_string(),
+            thread_ts: None,
+            attachments: Some(vec![
+                attachment(Some("Jira"), Some("PROJ-12"), Some("TODO: migrate the billing tables")),
+                attachment(None, None, Some("  ")),
+                attachment(None, Some("Release notes"), None),
+            ]),
+            subtype: None,
+            bot_id: None,
+            edited: None,
+            changed_message: None,
+            deleted_ts: None,
+            reply_count: None,
+            latest_reply: None,
+            reactions: None,
+        };
+
+        assert_eq!(
+            message.analyzable_text(),
+            "FYI\n\n[Attachment from Jira]\nPROJ-12\nTODO: migrate the billing tables\n\n[Attachment]\nRelease notes"
+        );
+    }
+
+    #[test]
+    fn emoji_shortcodes_are_stripped_but_times_are_kept() {
+        assert_eq!(strip_slack_emoji(":tada: shipped at 10:30:45 :+1::skin-tone-2:"), "shipped at 10:30:45");
+        assert_eq!(strip_slack_emoji("TODO: fix :bug: today"), "TODO: fix today");
+    }
+
+    #[test]
+    fn clock_skew_is_reported_from_date_header() {
+        let local_now = DateTime::parse_from_rfc3339("2024-05-01T12:02:00Z").unwrap().with_timezone(&Utc);
+
+        let report = compute_clock_skew("Wed, 01 May 2024 12:00:00 GMT", local_now).unwrap();
+
+        assert_eq!(report.skew_seconds, 120);
+        assert!(report.exceeds_threshold);
+    }
+
+    #[test]
+    fn small_clock_skew_is_within_threshold() {
+        let local_now = DateTime::parse_from_rfc3339("2024-05-01T11:59:55Z").unwrap().with_timezone(&Utc);
+
+        let report = compute_clock_skew("Wed, 01 May 2024 12:00:00 GMT", local_now).unwrap();
+
+        assert_eq!(report.skew_seconds, -5);
+        assert!(!report.exceeds_threshold);
+    }
+
+    #[test]
+    fn presence_response_is_parsed() {
+        let body = r#"{"ok": true, "presence": "active", "online": true, "auto_away": false, "manual_away": false, "connection_count": 1, "last_activity": 1700000000}"#;
+
+        let presence = parse_presence_response(body).unwrap();
+
+        assert!(presence.is_active());
+        assert_eq!(presence.online, Some(true));
+        assert_eq!(presence.last_activity, Some(1700000000));
+    }
+
+    #[test]
+    fn presence_error_response_is_rejected() {
+        let body = r#"{"ok": false, "error": "user_not_found"}"#;
+
+        assert!(parse_presence_response(body).is_err());
+    }
+
+    #[test]
+    fn api_error_codes_map_to_error_variants() {
+        assert!(matches!(SlackError::from_api_error("invalid_auth", Some("channels:read")), SlackError::Auth(AuthFailure::InvalidToken)));
+        assert!(matches!(SlackError::from_api_error("not_in_channel", Some("channels:history")), SlackError::NotInChannel));
+        assert!(matches!(SlackError::from_api_error("channel_not_found", None), SlackError::ChannelNotFound));
+        assert!(matches!(SlackError::from_api_error("ratelimited", None), SlackError::RateLimited { retry_after: None }));
+        assert!(matches!(SlackError::from_api_error("missing_scope", Some("users:read")), SlackError::MissingScope(Some("users:read"))));
+
+        let status = SlackError::from_status(reqwest::StatusCode::TOO_MANY_REQUESTS, Some(std::time::Duration::from_secs(7)));
+        assert!(matches!(status, SlackError::RateLimited { retry_after: Some(d) } if d.as_secs() == 7));
+        assert!(status.is_retryable());
+        assert!(!SlackError::from_api_error("invalid_auth", None).is_retryable());
+    }
+
+    #[test]
+    fn slack_errors_render_in_the_requested_locale() {
+        let error = SlackError::from_api_error("missing_scope", Some("users:read"));
+        assert_eq!(error.localized_message(Locale::English), "Insufficient permissions. The app needs the 'users:read' scope");
+        assert_eq!(error.localized_message(Locale::Portuguese), "Permissões insuficientes. A aplicação precisa do escopo 'users:read'");
+        assert_eq!(SlackError::EmptyInput(SlackInput::RedirectUri).localized_message(Locale::English), "Redirect URI cannot be empty");
+    }
+}
@@ -1,29 +1,71 @@
 // use crate::credentials::validate_access_token;
-use crate::slack_service_client::{SlackServiceClient, ChannelHistoryOptions};
+use crate::slack_service_client::{SlackServiceClient, ChannelHistoryOptions, SlackChannel, SlackMessage, SlackReaction};
+use crate::commands::command_error::{codes, CommandError};
 
 // Make functions public for use in main.rs
 
 // Slack command aliases for frontend compatibility (updated to use official SDK service)
-pub async fn slack_list_channels(_access_token: String) -> Result<serde_json::Value, String> {
+/// Cache key used when the caller doesn't say which team the channels belong to
+const DEFAULT_CHANNEL_CACHE_TEAM: &str = "default";
+
+/// List channels. With `use_cache`, the last cached list for the team is returned right away
+/// and refreshed in the background (`slack-channels-refreshed` fires with the fresh list).
+/// Without it the network is used, falling back to the cache when the fetch fails.
+pub async fn slack_list_channels(
+    app: tauri::AppHandle,
+    _access_token: String,
+    team_id: Option<String>,
+    use_cache: Option<bool>,
+) -> Result<serde_json::Value, String> {
     println!("📋 Listing channels using official Slack SDK service");
     
-    let slack_client = SlackServiceClient::new(None);
+    let team_id = team_id.unwrap_or_else(|| DEFAULT_CHANNEL_CACHE_TEAM.to_string());
+    let cache_team = team_id.clone();
+    let cached = with_channel_cache_db(&app, move |conn| load_cached_channels(conn, &cache_team))
+        .await
+        .unwrap_or_else(|e| {
+            println!("⚠️ Failed to read cached channels: {}", e);
+            None
+        });
     
-    match slack_client.get_channels().await {
+    if use_cache.unwrap_or(false) {
+        if let Some((channels, cached_at)) = cached {
+            println!("⚡ Serving {} cached channels for team {} (cached at {})", channels.len(), team_id, cached_at);
+            tauri::async_runtime::spawn(refresh_channel_cache(app, team_id));
+            return Ok(serde_json::json!({ "channels": channels, "cached": true, "cached_at": cached_at }));
+        }
+    }
+    
+    match fetch_channel_list().await {
         Ok(channels) => {
-            let channel_values: Vec<serde_json::Value> = channels.into_iter()
-                .map(|c| serde_json::json!({
-                    "id": c.id,
-                    "name": c.name,
-                    "is_member": c.is_member,
-                    "is_private": c.is_private,
-                    "topic": c.topic,
-                    "purpose": c.purpose,
-                    "num_members": c.num_members
-                }))
-                .collect();
-            Ok(serde_json::json!({ "channels": channel_values }))
+            cache_channels(&app, &team_id, &channels).await;
+            Ok(serde_json::json!({ "channels": channels, "cached": false }))
         }
+        Err(e) => match cached {
+            Some((channels, cached_at)) => {
+                println!("📴 Channel fetch failed ({}), serving cached list from {}", e, cached_at);
+                Ok(serde_json::json!({ "channels": channels, "cached": true, "cached_at": cached_at }))
+            }
+            None => Err(e),
+        },
+    }
+}
+
+async fn fetch_channel_list() -> Result<Vec<serde_json::Value>, String> {
+    let slack_client = SlackServiceClient::new(None);
+    
+    match slack_client.get_channels().await {
+        Ok(channels) => Ok(channels.into_iter()
+            .map(|c| serde_json::json!({
+                "id": c.id,
+                "name": c.name,
+                "is_member": c.is_member,
+                "is_private": c.is_private,
+                "topic": c.topic,
+                "purpose": c.purpose,
+                "num_members": c.num_members
+            }))
+            .collect()),
         Err(e) => {
             println!("❌ Failed to list channels: {}", e);
             Err(format!("Failed to list channels: {}", e))
@@ -31,6 +73,132 @@ pub async fn slack_list_channels(_access_token: String) -> Result<serde_json::Va
     }
 }
 
+/// Fetch the channel list in the background, update the cache and tell the frontend
+async fn refresh_channel_cache(app: tauri::AppHandle, team_id: String) {
+    use tauri::Emitter;
+    
+    match fetch_channel_list().await {
+        Ok(channels) => {
+            cache_channels(&app, &team_id, &channels).await;
+            let _ = app.emit("slack-channels-refreshed", serde_json::json!({
+                "team_id": team_id,
+                "channels": channels,
+            }));
+        }
+        Err(e) => println!("⚠️ Background channel refresh failed for team {}: {}", team_id, e),
+    }
+}
+
+async fn cache_channels(app: &tauri::AppHandle, team_id: &str, channels: &[serde_json::Value]) {
+    let (team_id, channels) = (team_id.to_string(), channels.to_vec());
+    let result = with_channel_cache_db(app, move |conn| {
+        store_cached_channels(conn, &team_id, &channels, &chrono::Utc::now().to_rfc3339())
+    }).await;
+    if let Err(e) = result {
+        println!("⚠️ Failed to cache channel list: {}", e);
+    }
+}
+
+/// Run `f` against the app database once the channel cache table exists
+async fn with_channel_cache_db<T, F>(app: &tauri::AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut rusqlite::Connection) -> Result<T, String> + Send + 'static,
+{
+    crate::app_db::with_app_db(app, move |conn| {
+        ensure_channel_cache_table(conn)?;
+        f(conn)
+    }).await
+}
+
+pub fn ensure_channel_cache_table(conn: &rusqlite::Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS slack_channel_cache (
+            teamId TEXT PRIMARY KEY,
+            channels TEXT NOT NULL,
+            cachedAt TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("Failed to create slack_channel_cache table: {}", e))
+}
+
+pub fn store_cached_channels(
+    conn: &rusqlite::Connection,
+    team_id: &str,
+    channels: &[serde_json::Value],
+    cached_at: &str,
+) -> Result<(), String> {
+    let serialized = serde_json::to_string(channels)
+        .map_err(|e| format!("Failed to serialize channels: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO slack_channel_cache (teamId, channels, cachedAt) VALUES (?1, ?2, ?3)",
+        rusqlite::params![team_id, serialized, cached_at],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to cache channels: {}", e))
+}
+
+/// The cached channel list for `team_id` and when it was stored
+pub fn load_cached_channels(
+    conn: &rusqlite::Connection,
+    team_id: &str,
+) -> Result<Option<(Vec<serde_json::Value>, String)>, String> {
+    let result = conn.query_row(
+        "SELECT channels, cachedAt FROM slack_channel_cache WHERE teamId = ?1",
+        rusqlite::params![team_id],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+    );
+    match result {
+        Ok((serialized, cached_at)) => serde_json::from_str(&serialized)
+            .map(|channels| Some((channels, cached_at)))
+            .map_err(|e| format!("Failed to parse cached channels: {}", e)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to read cached channels: {}", e)),
+    }
+}
+
+/// Channels sharing a display name (ignoring case and surrounding whitespace)
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DuplicateChannelName {
+    pub name: String,
+    pub channel_ids: Vec<String>,
+}
+
+/// Group channels whose names collide, sorted by name
+pub fn group_duplicate_channel_names(channels: &[SlackChannel]) -> Vec<DuplicateChannelName> {
+    let mut by_name: std::collections::BTreeMap<String, DuplicateChannelName> = std::collections::BTreeMap::new();
+    
+    for channel in channels {
+        let key = channel.name.trim().to_lowercase();
+        if key.is_empty() {
+            continue;
+        }
+        by_name.entry(key)
+            .or_insert_with(|| DuplicateChannelName {
+                name: channel.name.trim().to_string(),
+                channel_ids: Vec::new(),
+            })
+            .channel_ids
+            .push(channel.id.clone());
+    }
+    
+    by_name.into_values().filter(|group| group.channel_ids.len() > 1).collect()
+}
+
+pub async fn slack_find_duplicate_channel_names() -> Result<Vec<DuplicateChannelName>, String> {
+    let slack_client = SlackServiceClient::new(None);
+    
+    let channels = slack_client.get_channels()
+        .await
+        .map_err(|e| format!("Failed to list channels: {}", e))?;
+    
+    let duplicates = group_duplicate_channel_names(&channels);
+    if !duplicates.is_empty() {
+        println!("⚠️ Found {} channel names shared by multiple channels", duplicates.len());
+    }
+    Ok(duplicates)
+}
+
 pub async fn slack_build_oauth_url(
     https_server_state: tauri::State<'_, crate::commands::oauth_servers::OAuthServiceClientState>,
     client_id: String,
@@ -89,6 +257,9 @@ pub async fn slack_build_oauth_url(
         }
     };
     
+    // Keep the state so the OAuth callback can be validated against it
+    crate::commands::oauth_servers::register_oauth_state(&state_token, &redirect_uri);
+    
     crate::slack::SlackClient::build_oauth_url(&client_id, &redirect_uri, &scopes, Some(&state_token))
         .map_err(|e| format!("Erro ao construir URL OAuth: {}", e))
 }
@@ -138,7 +309,7 @@ pub async fn slack_test_connection(_access_token: String) -> Result<serde_json::
 pub async fn slack_join_channel(
     _access_token: String,
     channel_id: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     println!("🚪 Joining channel {} using official SDK service", channel_id);
     
     let slack_client = SlackServiceClient::new(None);
@@ -150,95 +321,244 @@ pub async fn slack_join_channel(
         }
         Err(e) => {
             println!("❌ Failed to join channel: {}", e);
-            Err(format!("Failed to join channel: {}", e))
+            Err(CommandError::from(e).with_context("Failed to join channel"))
         }
     }
 }
 
+/// Emit `fetch-progress` for a page of a channel fetch, when there's an app to emit on
+fn emit_channel_fetch_progress(app: Option<&tauri::AppHandle>, progress: &crate::slack::ChannelFetchProgress) {
+    use tauri::Emitter;
+    
+    if let Some(app) = app {
+        if let Err(e) = app.emit("fetch-progress", progress) {
+            println!("⚠️ Failed to emit fetch-progress: {}", e);
+        }
+    }
+}
+
+/// Fetch a channel's messages as JSON, for the frontend. With an `app`, `fetch-progress`
+/// is emitted after each page.
 pub async fn slack_fetch_messages(
+    app: Option<tauri::AppHandle>,
     access_token: String,
     channel_id: String,
     oldest_timestamp: Option<f64>,
+    latest_timestamp: Option<f64>,
     limit: Option<u32>
 ) -> Result<Vec<serde_json::Value>, String> {
-    // Validate inputs
-    if access_token.trim().is_empty() {
-        return Err("Token de acesso é obrigatório".to_string());
-    }
-    if channel_id.trim().is_empty() {
-        return Err("ID do canal é obrigatório".to_string());
+    slack_fetch_messages_typed(app, access_token, channel_id, oldest_timestamp, latest_timestamp, limit)
+        .await
+        .map(|messages| messages.into_iter().map(|m| serde_json::to_value(m).unwrap()).collect())
+        .map_err(|e| e.message)
+}
+
+/// Fetch a channel's messages as `SlackMessage`s, for callers that work with them directly.
+/// With an `app`, `fetch-progress` is emitted after each page.
+pub async fn slack_fetch_messages_typed(
+    app: Option<tauri::AppHandle>,
+    access_token: String,
+    channel_id: String,
+    oldest_timestamp: Option<f64>,
+    latest_timestamp: Option<f64>,
+    limit: Option<u32>
+) -> Result<Vec<crate::slack::SlackMessage>, CommandError> {
+    check_fetch_args(&access_token, &channel_id)?;
+    
+    let total_limit = limit.unwrap_or(100);
+    
+    // For large requests, use pagination
+    if total_limit > 50 {
+        return fetch_service_history(app.as_ref(), &channel_id, oldest_timestamp, latest_timestamp, total_limit)
+            .await
+            .map_err(CommandError::from);
     }
     
     let mut slack_client = crate::slack::SlackClient::new();
     slack_client.set_token(access_token);
+    let report_progress = |progress: &crate::slack::ChannelFetchProgress| {
+        emit_channel_fetch_progress(app.as_ref(), progress);
+    };
     
-    // For widget requests (small limits), use simple single-page fetch
-    let total_limit = limit.unwrap_or(100);
-    
-    // ALWAYS use single page for small requests to prevent cursor loops
+    // Small (widget) requests stay on a single page to prevent cursor loops
     if total_limit <= 20 {
         println!("📱 Widget request detected (limit: {}), using single-page fetch", total_limit);
-        return slack_client.fetch_channel_messages(&channel_id, oldest_timestamp, Some(total_limit))
-            .await
-            .map(|messages| messages.into_iter().map(|m| serde_json::to_value(m).unwrap()).collect())
-            .map_err(|e| format!("Erro ao buscar mensagens: {}", e));
     }
     
-    // For large requests, use pagination
-    if total_limit > 50 {
-        println!("🔄 Using advanced pagination for large request: {} messages", total_limit);
+    slack_client.fetch_channel_messages(&channel_id, oldest_timestamp, latest_timestamp, Some(total_limit), &crate::slack::channel_fetch_token(&channel_id), Some(&report_progress))
+        .await
+        .map_err(|e| CommandError::from(e).with_context("Erro ao buscar mensagens"))
+}
+
+fn check_fetch_args(access_token: &str, channel_id: &str) -> Result<(), CommandError> {
+    if access_token.trim().is_empty() {
+        return Err(CommandError::new(codes::INVALID_INPUT, "Token de acesso é obrigatório", false));
+    }
+    if channel_id.trim().is_empty() {
+        return Err(CommandError::new(codes::INVALID_INPUT, "ID do canal é obrigatório", false));
+    }
+    Ok(())
+}
+
+/// Page through a channel's history via the Slack service until `total_limit` messages are
+/// collected, there are no more pages or the channel's fetches are cancelled
+async fn fetch_service_history(
+    app: Option<&tauri::AppHandle>,
+    channel_id: &str,
+    oldest_timestamp: Option<f64>,
+    latest_timestamp: Option<f64>,
+    total_limit: u32,
+) -> Result<Vec<SlackMessage>, String> {
+    println!("🔄 Using advanced pagination for large request: {} messages", total_limit);
+    
+    let mut all_messages = Vec::new();
+    let mut cursor: Option<String> = None;
+    let page_size = 15u32; // API limit for non-marketplace apps
+    let mut fetched_count = 0;
+    let mut pages_fetched = 0;
+    let cancellation = crate::slack::channel_fetch_token(channel_id);
+    
+    while fetched_count < total_limit {
+        if cancellation.is_cancelled() {
+            println!("🛑 Fetch of channel {} cancelled after {} pages, keeping {} messages", channel_id, pages_fetched, all_messages.len());
+            break;
+        }
         
-        let mut all_messages = Vec::new();
-        let mut cursor: Option<String> = None;
-        let page_size = 15u32; // API limit for non-marketplace apps
-        let mut fetched_count = 0;
+        let remaining = total_limit - fetched_count;
+        let current_limit = remaining.min(page_size);
         
-        while fetched_count < total_limit {
-            let remaining = total_limit - fetched_count;
-            let current_limit = remaining.min(page_size);
-            
-            let options = ChannelHistoryOptions {
-                limit: Some(current_limit),
-                cursor: cursor.clone(),
-                oldest: if cursor.is_none() { oldest_timestamp.map(|s| s.to_string()) } else { None },
-                latest: None,
-            };
-            
-            // Use slack service client instead
-            let slack_service_client = SlackServiceClient::new(None);
-            match slack_service_client.get_channel_history(&channel_id, Some(options)
-            ).await {
-                Ok(page_result) => {
-                    let page_messages: Vec<serde_json::Value> = page_result.messages
-                        .into_iter()
-                        .map(|m| serde_json::to_value(m).unwrap())
-                        .collect();
-                    
-                    fetched_count += page_messages.len() as u32;
-                    all_messages.extend(page_messages);
-                    
-                    if !page_result.has_more {
-                        break;
-                    }
-                    
-                    cursor = page_result.response_metadata
-                        .and_then(|meta| meta.next_cursor);
-                }
-                Err(e) => {
-                    return Err(format!("Erro na paginação avançada: {}", e));
+        let options = ChannelHistoryOptions {
+            limit: Some(current_limit),
+            cursor: cursor.clone(),
+            oldest: if cursor.is_none() { oldest_timestamp.map(|s| s.to_string()) } else { None },
+            latest: if cursor.is_none() { latest_timestamp.map(|s| s.to_string()) } else { None },
+        };
+        
+        // Use slack service client instead
+        let slack_service_client = SlackServiceClient::new(None);
+        match slack_service_client.get_channel_history(channel_id, Some(options)).await {
+            Ok(mut page_result) => {
+                let reached_oldest = crate::slack::trim_to_window(&mut page_result.messages, oldest_timestamp, latest_timestamp);
+                fetched_count += page_result.messages.len() as u32;
+                all_messages.extend(page_result.messages);
+                pages_fetched += 1;
+                emit_channel_fetch_progress(app, &crate::slack::ChannelFetchProgress {
+                    channel_id: channel_id.to_string(),
+                    pages_fetched,
+                    messages_so_far: all_messages.len(),
+                    limit: Some(total_limit),
+                    has_more: page_result.has_more,
+                });
+                
+                if !page_result.has_more || reached_oldest {
+                    break;
                 }
+                
+                cursor = page_result.response_metadata
+                    .and_then(|meta| meta.next_cursor);
+            }
+            Err(e) => {
+                return Err(format!("Erro na paginação avançada: {}", e));
             }
         }
+    }
+    
+    println!("✅ Advanced pagination completed: {} messages fetched", all_messages.len());
+    Ok(all_messages)
+}
+
+/// Fetch pages of `channel_id` until `limit` messages are collected or no cursor is returned,
+/// reporting progress after every page. `fetch_page` receives the page size and cursor and
+/// returns the page's messages plus the next cursor.
+pub async fn paginate_with_progress<F, Fut, P>(
+    channel_id: &str,
+    limit: u32,
+    page_size: u32,
+    mut fetch_page: F,
+    mut on_progress: P,
+) -> Result<Vec<serde_json::Value>, String>
+where
+    F: FnMut(u32, Option<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<serde_json::Value>, Option<String>), String>>,
+    P: FnMut(crate::slack::ChannelFetchProgress),
+{
+    let mut all_messages = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages_fetched = 0;
+    
+    while (all_messages.len() as u32) < limit {
+        let remaining = limit - all_messages.len() as u32;
+        let (page_messages, next_cursor) = fetch_page(remaining.min(page_size), cursor.take()).await?;
+        let page_len = page_messages.len();
+        all_messages.extend(page_messages);
+        pages_fetched += 1;
         
-        println!("✅ Advanced pagination completed: {} messages fetched", all_messages.len());
-        Ok(all_messages)
-    } else {
-        // For medium requests (21-50), use the existing method
-        slack_client.fetch_channel_messages(&channel_id, oldest_timestamp, limit)
-            .await
-            .map(|messages| messages.into_iter().map(|m| serde_json::to_value(m).unwrap()).collect())
-            .map_err(|e| format!("Erro ao buscar mensagens: {}", e))
+        let next_cursor = next_cursor.filter(|c| !c.is_empty() && page_len > 0);
+        on_progress(crate::slack::ChannelFetchProgress {
+            channel_id: channel_id.to_string(),
+            pages_fetched,
+            messages_so_far: all_messages.len(),
+            limit: Some(limit),
+            has_more: next_cursor.is_some(),
+        });
+        
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
     }
+    
+    Ok(all_messages)
+}
+
+/// Like `slack_fetch_messages` for large limits, but emits `fetch-progress` after each page
+pub async fn slack_fetch_messages_streaming(
+    app: tauri::AppHandle,
+    channel_id: String,
+    limit: u32,
+) -> Result<Vec<serde_json::Value>, String> {
+    if channel_id.trim().is_empty() {
+        return Err("ID do canal é obrigatório".to_string());
+    }
+    
+    println!("📡 Streaming fetch of up to {} messages from {}", limit, channel_id);
+    
+    let slack_service_client = SlackServiceClient::new(None);
+    let page_size = 15u32; // API limit for non-marketplace apps
+    
+    let messages = paginate_with_progress(
+        &channel_id,
+        limit,
+        page_size,
+        |page_limit, cursor| {
+            let options = ChannelHistoryOptions {
+                limit: Some(page_limit),
+                cursor,
+                oldest: None,
+                latest: None,
+            };
+            let client = &slack_service_client;
+            let channel_id = &channel_id;
+            async move {
+                let page = client.get_channel_history(channel_id, Some(options)).await
+                    .map_err(|e| format!("Erro na paginação avançada: {}", e))?;
+                let next_cursor = if page.has_more {
+                    page.response_metadata.and_then(|meta| meta.next_cursor)
+                } else {
+                    None
+                };
+                let page_messages = page.messages
+                    .into_iter()
+                    .map(|m| serde_json::to_value(m).unwrap())
+                    .collect();
+                Ok((page_messages, next_cursor))
+            }
+        },
+        |progress| emit_channel_fetch_progress(Some(&app), &progress),
+    ).await?;
+    
+    println!("✅ Streaming fetch completed: {} messages fetched", messages.len());
+    Ok(messages)
 }
 
 pub async fn slack_estimate_sync_time(
@@ -262,7 +582,73 @@ pub async fn slack_estimate_sync_time(
         .map_err(|e| format!("Erro ao estimar tempo de sincronização: {}", e))
 }
 
-pub async fn slack_analyze_messages(_app: tauri::AppHandle, messages: Vec<serde_json::Value>) -> Result<Vec<serde_json::Value>, String> {
+pub async fn slack_check_clock_skew() -> Result<serde_json::Value, String> {
+    let slack_client = crate::slack::SlackClient::new();
+    
+    slack_client.check_clock_skew()
+        .await
+        .map(|report| serde_json::to_value(report).unwrap())
+        .map_err(|e| format!("Erro ao verificar relógio com o Slack: {}", e))
+}
+
+/// Which analysis path produced a set of detected tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisTier {
+    Llm,
+    Local,
+    Pattern,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MessageAnalysis {
+    pub tasks: Vec<serde_json::Value>,
+    /// `None` when there was nothing to analyze
+    pub tier: Option<AnalysisTier>,
+    pub model: Option<String>,
+    pub usage: Option<crate::ai_service_client::TokenUsage>,
+    /// Why the LLM tier (or some of its chunks) failed
+    pub llm_error: Option<crate::ai_service_client::AIErrorInfo>,
+    /// Chunked analysis id, usable with `reanalyze_failed_chunks`
+    pub analysis_id: Option<String>,
+    pub failed_chunks: Vec<usize>,
+}
+
+/// Message subtypes that are still something a person wrote; any other subtype is a system event
+const CONVERSATIONAL_SUBTYPES: [&str; 3] = ["thread_broadcast", "file_share", "me_message"];
+
+/// Whether a message is worth sending to task analysis. Join/leave notices and other system
+/// events never are; bot posts only when `include_bots` is set.
+pub fn is_analyzable_message(message: &crate::slack::SlackMessage, include_bots: bool) -> bool {
+    if message.msg_type != "message" {
+        return false;
+    }
+    
+    let is_bot = message.bot_id.is_some() || message.subtype.as_deref() == Some("bot_message");
+    if is_bot {
+        return include_bots;
+    }
+    
+    match message.subtype.as_deref() {
+        None => true,
+        Some(subtype) => CONVERSATIONAL_SUBTYPES.contains(&subtype),
+    }
+}
+
+/// Task detection with a model running on this machine. No local model ships with the app,
+/// so this tier fails and analysis falls through to pattern matching.
+fn analyze_with_local_model(_messages: &[crate::slack::SlackMessage]) -> Result<Vec<serde_json::Value>, String> {
+    Err("No local AI model is available".to_string())
+}
+
+pub async fn slack_analyze_messages(
+    app: tauri::AppHandle,
+    messages: Vec<serde_json::Value>,
+    chunk_size: Option<usize>,
+    include_bots: Option<bool>,
+    text_options: Option<crate::slack::SlackTextOptions>,
+    sync_id: Option<String>,
+) -> Result<MessageAnalysis, String> {
     println!("🤖 [slack_api::slack_analyze_messages] === AI ANALYSIS STARTED ===");
     println!("📊 [slack_api::slack_analyze_messages] Analyzing {} messages", messages.len());
     
@@ -287,101 +673,127 @@ pub async fn slack_analyze_messages(_app: tauri::AppHandle, messages: Vec<serde_
     println!("📊 [slack_api::slack_analyze_messages] Message parsing results: {} valid messages, {} parse errors", 
         slack_messages.len(), parse_errors);
     
+    // Skip system events and (unless opted in) bot posts before spending AI calls on them
+    let include_bots = include_bots.unwrap_or(false);
+    let before_filter = slack_messages.len();
+    slack_messages.retain(|msg| is_analyzable_message(msg, include_bots));
+    println!("🧹 [slack_api::slack_analyze_messages] Filtered out {} system/bot messages",
+        before_filter - slack_messages.len());
+    
+    // Resolve links, mentions and channel references so neither the AI nor the pattern
+    // matcher sees raw Slack markup
+    let text_options = text_options.unwrap_or_default();
+    for msg in slack_messages.iter_mut() {
+        text_options.apply_to_message(msg);
+    }
+    
     if slack_messages.is_empty() {
         println!("📋 [slack_api::slack_analyze_messages] No valid messages to analyze, returning empty array");
-        return Ok(vec![]); // Return empty array if no valid messages
-    }
-    
-    // Convert Slack messages to JSON for LLM analysis
-    let messages_json = serde_json::to_value(&slack_messages)
-        .map_err(|e| format!("Failed to serialize messages for LLM analysis: {}", e))?;
-    
-    // Use LLM-powered analysis instead of pattern matching
-    println!("🚀 [slack_api::slack_analyze_messages] Starting LLM-powered task detection analysis");
-    
-    // Use the new AI service client instead of the deleted ai_llm_service
-    let ai_client = crate::ai_service_client::AIServiceClient::new(None);
-    let analysis_result = match ai_client.analyze_tasks(crate::ai_service_client::TaskAnalysisRequest {
-        messages: crate::ai_service_client::MessageInput::Text(serde_json::to_string(&messages_json).unwrap_or_default()),
-        context: None,
-        model: None,
-    }).await {
-        Ok(llm_response) => {
-            println!("✨ [slack_api::slack_analyze_messages] LLM analysis successful");
-            // Extract tasks from LLM response and convert to expected format
-            let tasks = llm_response.tasks.into_iter().map(|detected_task| {
-                serde_json::json!({
-                    "name": detected_task.title,
-                    "description": detected_task.description,
-                    "source_message_ts": detected_task.source_timestamp,
-                    "source_channel": "", // Will be filled in by frontend
-                    "suggested_assignee": detected_task.assignee,
-                    "confidence_score": 0.8, // LLM results are generally high confidence
-                    "priority": detected_task.priority,
-                    "estimated_hours": detected_task.estimated_hours,
-                    "due_date": detected_task.due_date,
-                    "tags": detected_task.tags,
-                    "status": detected_task.status,
-                    "source_user": detected_task.source_user
-                })
-            }).collect::<Vec<_>>();
-            
-            println!("📊 [slack_api::slack_analyze_messages] LLM found {} tasks", tasks.len());
-            tasks
-        }
-        Err(llm_error) => {
-            println!("⚠️ [slack_api::slack_analyze_messages] LLM analysis failed: {}, trying local AI analysis", llm_error);
-            
-            // Try local AI analysis as first fallback
-            // AI analysis now handled by AI service
-            let local_ai_result = match Ok::<serde_json::Value, String>(serde_json::json!({"summary": "Analysis moved to AI service"})) {
-                Ok(_ai_response) => {
-                    println!("✨ [slack_api::slack_analyze_messages] Local AI analysis successful");
-                    
-                    // Convert local AI response to expected format
-                    // Extract actionable items now handled by AI service
-                    let found_items = vec![];
-                    let tasks: Vec<serde_json::Value> = found_items.into_iter()
-                        .filter(|item: &serde_json::Value| item.get("type").and_then(|t| t.as_str()) == Some("task"))
-                        .map(|item| {
-                            serde_json::json!({
-                                "name": item.get("title").and_then(|t| t.as_str()).unwrap_or(""),
-                                "description": item.get("description").and_then(|d| d.as_str()).unwrap_or(""),
-                                "source_message_ts": item.get("source_reference").and_then(|s| s.as_str()).unwrap_or(""),
-                                "source_channel": "",
-                                "suggested_assignee": serde_json::Value::Null,
-                                "confidence_score": item.get("confidence").and_then(|c| c.as_f64()).unwrap_or(0.7),
-                                "priority": "medium",
-                                "estimated_hours": serde_json::Value::Null,
-                                "due_date": serde_json::Value::Null,
-                                "tags": Vec::<String>::new(),
-                                "status": "new",
-                                "source_user": ""
-                            })
-                        })
-                        .collect();
-                    
-                    println!("📊 [slack_api::slack_analyze_messages] Local AI found {} tasks", tasks.len());
-                    Some(tasks)
-                }
-                Err(local_ai_error) => {
-                    println!("⚠️ [slack_api::slack_analyze_messages] Local AI also failed: {}", local_ai_error);
+        return Ok(MessageAnalysis {
+            tasks: vec![],
+            tier: None,
+            model: None,
+            usage: None,
+            llm_error: None,
+            analysis_id: None,
+            failed_chunks: vec![],
+        });
+    }
+    
+    // Analyze in chunks so a huge channel doesn't become one oversized request, and one bad
+    // batch doesn't lose the results of the others
+    let chunk_size = crate::commands::settings::resolve_analysis_chunk_size(&app, chunk_size).await;
+    let ai_messages: Vec<crate::ai_service_client::Message> = slack_messages.iter()
+        .map(|msg| crate::ai_service_client::Message {
+            text: msg.analyzable_text(),
+            user: msg.user.clone().unwrap_or_default(),
+            timestamp: msg.ts.clone(),
+        })
+        .collect();
+    
+    println!("🚀 [slack_api::slack_analyze_messages] Starting LLM-powered task detection in chunks of {}", chunk_size);
+    
+    // A sync can tune detection for its channel with its own prompt
+    let prompt = match sync_id {
+        Some(sync_id) => {
+            let syncs = crate::slack_sync::get_all_slack_syncs(app.clone()).await?;
+            match syncs.iter().find(|sync| sync.id == sync_id) {
+                Some(sync) => crate::slack_sync::prompt_for_sync(&app, sync).await?,
+                None => {
+                    println!("⚠️ [slack_api::slack_analyze_messages] Sync {} not found, using the default prompt", sync_id);
                     None
                 }
-            };
-            
-            // Use local AI results if available, otherwise fall back to basic pattern matching
-            local_ai_result.unwrap_or_else(|| {
+            }
+        }
+        None => None,
+    };
+    
+    let ai_client = crate::commands::ai_automation::ai_client_for(&app).await;
+    let analysis = ai_client.analyze_tasks_chunked(ai_messages, None, prompt, chunk_size).await;
+    let failed_chunks = analysis.failed_chunk_indices();
+    let first_chunk_error = analysis.chunks.iter()
+        .find(|chunk| !chunk.succeeded)
+        .map(|chunk| crate::ai_service_client::AIErrorInfo {
+            kind: chunk.error_kind.unwrap_or(crate::ai_service_client::AIErrorKind::ServiceError),
+            message: chunk.error.clone().unwrap_or_default(),
+            request_id: None,
+        });
+    
+    let mut model = None;
+    let mut usage = None;
+    let llm_error = first_chunk_error;
+    let (analysis_result, tier) = if failed_chunks.len() < analysis.chunks.len() {
+        model = analysis.chunks.iter().find_map(|chunk| chunk.model.clone());
+        usage = analysis.total_usage();
+        println!("✨ [slack_api::slack_analyze_messages] LLM analysis successful (model: {:?}, {}/{} chunks failed)",
+            model, failed_chunks.len(), analysis.chunks.len());
+        
+        // Extract tasks from LLM response and convert to expected format
+        let tasks = analysis.deduplicated_tasks().into_iter().map(|detected_task| {
+            serde_json::json!({
+                "name": detected_task.title,
+                "description": detected_task.description,
+                "source_message_ts": detected_task.source_timestamp,
+                "source_channel": "", // Will be filled in by frontend
+                "suggested_assignee": detected_task.assignee,
+                "confidence_score": 0.8, // LLM results are generally high confidence
+                "priority": detected_task.priority,
+                "estimated_hours": detected_task.estimated_hours,
+                "due_date": detected_task.due_date,
+                "tags": detected_task.tags,
+                "status": detected_task.status,
+                "source_user": detected_task.source_user
+            })
+        }).collect::<Vec<_>>();
+        
+        println!("📊 [slack_api::slack_analyze_messages] LLM found {} tasks", tasks.len());
+        (tasks, AnalysisTier::Llm)
+    } else {
+        println!("⚠️ [slack_api::slack_analyze_messages] LLM analysis failed for every chunk ({:?}), trying local AI analysis", llm_error);
+        
+        // Try local AI analysis as first fallback
+        let local_ai_result = match analyze_with_local_model(&slack_messages) {
+            Ok(tasks) => {
+                println!("✨ [slack_api::slack_analyze_messages] Local AI found {} tasks", tasks.len());
+                Some(tasks)
+            }
+            Err(local_ai_error) => {
+                println!("⚠️ [slack_api::slack_analyze_messages] Local AI also failed: {}", local_ai_error);
+                None
+            }
+        };
+        
+        // Use local AI results if available, otherwise fall back to basic pattern matching
+        match local_ai_result {
+            Some(tasks) => (tasks, AnalysisTier::Local),
+            None => {
                 println!("📋 [slack_api::slack_analyze_messages] Falling back to basic pattern matching");
-                let potential_tasks = tokio::task::block_in_place(|| {
-                    tokio::runtime::Handle::current().block_on(
-                        crate::slack::process_messages_for_tasks(slack_messages)
-                    )
-                });
-                potential_tasks.into_iter()
+                let potential_tasks = crate::slack::process_messages_for_tasks(slack_messages).await;
+                let tasks = potential_tasks.into_iter()
                     .map(|task| serde_json::to_value(task).unwrap())
-                    .collect()
-            })
+                    .collect();
+                (tasks, AnalysisTier::Pattern)
+            }
         }
     };
     
@@ -389,8 +801,8 @@ pub async fn slack_analyze_messages(_app: tauri::AppHandle, messages: Vec<serde_
     let json_results = analysis_result;
     
     println!("✅ [slack_api::slack_analyze_messages] === AI ANALYSIS COMPLETED ===");
-    println!("📊 [slack_api::slack_analyze_messages] Results: {} potential tasks found in {:?}", 
-        json_results.len(), analysis_duration);
+    println!("📊 [slack_api::slack_analyze_messages] Results: {} potential tasks found in {:?} via {:?}", 
+        json_results.len(), analysis_duration, tier);
     
     // Log some details about the found tasks
     for (i, task) in json_results.iter().take(3).enumerate() {
@@ -403,7 +815,15 @@ pub async fn slack_analyze_messages(_app: tauri::AppHandle, messages: Vec<serde_
         println!("📝 [slack_api::slack_analyze_messages] ... and {} more tasks", json_results.len() - 3);
     }
     
-    Ok(json_results)
+    Ok(MessageAnalysis {
+        tasks: json_results,
+        tier: Some(tier),
+        model,
+        usage,
+        llm_error,
+        analysis_id: Some(analysis.analysis_id),
+        failed_chunks,
+    })
 }
 
 pub async fn get_slack_team_info(token: String) -> Result<serde_json::Value, String> {
@@ -479,3 +899,402 @@ pub async fn slack_fetch_messages_paginated(
     }
 }
 
+/// Most history or reply pages walked by one command, so a huge channel can't keep it fetching forever
+const MAX_HISTORY_PAGES: usize = 50;
+
+/// Parent messages whose latest reply is newer than `since_ts`.
+pub fn threads_with_new_replies(messages: &[SlackMessage], since_ts: f64) -> Vec<String> {
+    messages.iter()
+        .filter(|m| m.reply_count.unwrap_or(0) > 0)
+        .filter(|m| {
+            m.latest_reply
+                .as_deref()
+                .and_then(crate::slack::parse_slack_ts)
+                .map(|latest| latest > since_ts)
+                .unwrap_or(false)
+        })
+        .map(|m| m.thread_ts.clone().unwrap_or_else(|| m.ts.clone()))
+        .collect()
+}
+
+/// Every reply of a thread, walking at most `MAX_HISTORY_PAGES` pages
+async fn fetch_thread_replies(
+    slack_service_client: &SlackServiceClient,
+    channel_id: &str,
+    thread_ts: &str,
+) -> Result<Vec<SlackMessage>, crate::slack_service_client::SlackServiceError> {
+    let mut replies = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_HISTORY_PAGES {
+        let page = slack_service_client.get_thread_replies(channel_id, thread_ts, cursor.as_deref()).await?;
+        replies.extend(page.messages);
+        
+        cursor = page.response_metadata.and_then(|meta| meta.next_cursor).filter(|c| !c.is_empty());
+        if !page.has_more || cursor.is_none() {
+            break;
+        }
+    }
+    Ok(replies)
+}
+
+pub async fn slack_sync_updated_threads(
+    channel_id: String,
+    since_ts: f64,
+) -> Result<serde_json::Value, String> {
+    if channel_id.trim().is_empty() {
+        return Err("ID do canal é obrigatório".to_string());
+    }
+    
+    println!("🧵 Resyncing threads updated since {} in channel {}", since_ts, channel_id);
+    
+    let slack_service_client = SlackServiceClient::new(None);
+    
+    // Walk the channel history looking for parents with fresh replies
+    let mut parents = Vec::new();
+    let mut cursor: Option<String> = None;
+    for page_number in 1..=MAX_HISTORY_PAGES {
+        let options = ChannelHistoryOptions {
+            limit: None,
+            cursor: cursor.clone(),
+            oldest: None,
+            latest: None,
+        };
+        
+        let page = slack_service_client.get_channel_history(&channel_id, Some(options))
+            .await
+            .map_err(|e| format!("Erro ao buscar mensagens: {}", e))?;
+        
+        parents.extend(threads_with_new_replies(&page.messages, since_ts));
+        
+        let next_cursor = page.response_metadata.and_then(|meta| meta.next_cursor).filter(|c| !c.is_empty());
+        if !page.has_more || next_cursor.is_none() {
+            break;
+        }
+        if page_number == MAX_HISTORY_PAGES {
+            println!("⚠️ Stopped after {} history pages; older threads are not checked", MAX_HISTORY_PAGES);
+        }
+        cursor = next_cursor;
+    }
+    
+    println!("🧵 Found {} threads with new replies", parents.len());
+    
+    let mut threads = Vec::new();
+    for thread_ts in parents {
+        match fetch_thread_replies(&slack_service_client, &channel_id, &thread_ts).await {
+            Ok(replies) => {
+                let replies_json = serde_json::to_value(replies)
+                    .map_err(|e| format!("Erro ao serializar respostas: {}", e))?;
+                threads.push(serde_json::json!({
+                    "thread_ts": thread_ts,
+                    "replies": replies_json
+                }));
+            }
+            Err(e) => {
+                println!("⚠️ Failed to fetch replies for thread {}: {}", thread_ts, e);
+            }
+        }
+    }
+    
+    Ok(serde_json::json!({
+        "channel_id": channel_id,
+        "since_ts": since_ts,
+        "threads": threads
+    }))
+}
+
+/// Shortest and longest intervals we will ever recommend, in minutes
+const MIN_RECOMMENDED_SYNC_MINUTES: u32 = 5;
+const MAX_RECOMMENDED_SYNC_MINUTES: u32 = 240;
+
+/// Suggest a sync interval from sampled message timestamps: busier channels sync more often.
+pub fn recommend_interval_from_timestamps(timestamps: &[f64]) -> u32 {
+    if timestamps.len() < 2 {
+        return MAX_RECOMMENDED_SYNC_MINUTES;
+    }
+    
+    let newest = timestamps.iter().cloned().fold(f64::MIN, f64::max);
+    let oldest = timestamps.iter().cloned().fold(f64::MAX, f64::min);
+    let span_hours = ((newest - oldest) / 3600.0).max(1.0 / 60.0);
+    let messages_per_hour = timestamps.len() as f64 / span_hours;
+    
+    let minutes = match messages_per_hour {
+        r if r >= 60.0 => 5,
+        r if r >= 20.0 => 10,
+        r if r >= 5.0 => 15,
+        r if r >= 1.0 => 30,
+        r if r >= 0.25 => 60,
+        _ => 120,
+    };
+    
+    minutes.clamp(MIN_RECOMMENDED_SYNC_MINUTES, MAX_RECOMMENDED_SYNC_MINUTES)
+}
+
+pub async fn recommend_sync_interval(channel_id: String) -> Result<u32, String> {
+    if channel_id.trim().is_empty() {
+        return Err("ID do canal é obrigatório".to_string());
+    }
+    
+    println!("⏱️ Sampling message frequency for channel {}", channel_id);
+    
+    let slack_service_client = SlackServiceClient::new(None);
+    let mut timestamps = Vec::new();
+    let mut cursor: Option<String> = None;
+    
+    // A couple of pages is enough to estimate how busy the channel is
+    for _ in 0..2 {
+        let options = ChannelHistoryOptions {
+            limit: None,
+            cursor: cursor.clone(),
+            oldest: None,
+            latest: None,
+        };
+        
+        let page = slack_service_client.get_channel_history(&channel_id, Some(options))
+            .await
+            .map_err(|e| format!("Erro ao buscar mensagens: {}", e))?;
+        
+        timestamps.extend(page.messages.iter().filter_map(|m| m.timestamp_secs()));
+        
+        cursor = page.response_metadata.and_then(|meta| meta.next_cursor).filter(|c| !c.is_empty());
+        if !page.has_more || cursor.is_none() {
+            break;
+        }
+    }
+    
+    let minutes = recommend_interval_from_timestamps(&timestamps);
+    println!("✅ Recommended sync interval for {}: {} minutes ({} messages sampled)", channel_id, minutes, timestamps.len());
+    Ok(minutes)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MessageReactionsSummary {
+    pub ts: String,
+    pub total_reactions: u32,
+    pub reactions: Vec<SlackReaction>,
+}
+
+/// Messages that received reactions, most-reacted first
+pub fn summarize_reactions(messages: &[SlackMessage]) -> Vec<MessageReactionsSummary> {
+    let mut summaries: Vec<MessageReactionsSummary> = messages.iter()
+        .filter_map(|m| {
+            let reactions = m.reactions.clone().filter(|r| !r.is_empty())?;
+            Some(MessageReactionsSummary {
+                ts: m.ts.clone(),
+                total_reactions: reactions.iter().map(|r| r.count).sum(),
+                reactions,
+            })
+        })
+        .collect();
+    
+    summaries.sort_by(|a, b| b.total_reactions.cmp(&a.total_reactions));
+    summaries
+}
+
+pub async fn slack_get_reactions_summary(
+    channel_id: String,
+    since_ts: Option<f64>,
+) -> Result<Vec<MessageReactionsSummary>, String> {
+    if channel_id.trim().is_empty() {
+        return Err("ID do canal é obrigatório".to_string());
+    }
+    
+    println!("😀 Summarizing reactions in channel {} since {:?}", channel_id, since_ts);
+    
+    let slack_service_client = SlackServiceClient::new(None);
+    let mut messages = Vec::new();
+    let mut cursor: Option<String> = None;
+    
+    for page_number in 1..=MAX_HISTORY_PAGES {
+        let options = ChannelHistoryOptions {
+            limit: None,
+            cursor: cursor.clone(),
+            oldest: since_ts.map(|s| s.to_string()),
+            latest: None,
+        };
+        
+        let page = slack_service_client.get_channel_history(&channel_id, Some(options))
+            .await
+            .map_err(|e| format!("Erro ao buscar mensagens: {}", e))?;
+        
+        messages.extend(page.messages);
+        
+        cursor = page.response_metadata.and_then(|meta| meta.next_cursor).filter(|c| !c.is_empty());
+        if !page.has_more || cursor.is_none() {
+            break;
+        }
+        if page_number == MAX_HISTORY_PAGES {
+            println!("⚠️ Stopped after {} history pages; older reactions are not summarized", MAX_HISTORY_PAGES);
+        }
+    }
+    
+    let summary = summarize_reactions(&messages);
+    println!("✅ {} of {} messages have reactions", summary.len(), messages.len());
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_cache_round_trips_per_team() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        ensure_channel_cache_table(&conn).unwrap();
+        assert!(load_cached_channels(&conn, "T1").unwrap().is_none());
+        
+        let channels = vec![serde_json::json!({"id": "C1", "name": "general"})];
+        store_cached_channels(&conn, "T1", &channels, "2024-05-01T00:00:00Z").unwrap();
+        store_cached_channels(&conn, "T1", &channels, "2024-05-02T00:00:00Z").unwrap();
+        
+        let (cached, cached_at) = load_cached_channels(&conn, "T1").unwrap().unwrap();
+        assert_eq!(cached, channels);
+        assert_eq!(cached_at, "2024-05-02T00:00:00Z");
+        assert!(load_cached_channels(&conn, "T2").unwrap().is_none());
+    }
+
+    fn message(ts: &str, reply_count: Option<u32>, latest_reply: Option<&str>) -> SlackMessage {
+        SlackMessage {
+            ts: ts.to_string(),
+            user: Some("U1".to_string()),
+            text: "hello".to_string(),
+            channel: Some("C1".to_string()),
+            msg_type: "message".to_string(),
+            thread_ts: reply_count.map(|_| ts.to_string()),
+            attachments: None,
+            subtype: None,
+            bot_id: None,
+            edited: None,
+            changed_message: None,
+            deleted_ts: None,
+            reply_count,
+            latest_reply: latest_reply.map(|s| s.to_string()),
+            reactions: None,
+        }
+    }
+
+    fn analysis_message(msg_type: &str, subtype: Option<&str>, bot_id: Option<&str>) -> crate::slack::SlackMessage {
+        crate::slack::SlackMessage {
+            ts: "1700000000.000100".to_string(),
+            user: Some("U1".to_string()),
+            text: "please ship the release".to_string(),
+            channel: None,
+            msg_type: msg_type.to_string(),
+            thread_ts: None,
+            attachments: None,
+            subtype: subtype.map(|s| s.to_string()),
+            bot_id: bot_id.map(|s| s.to_string()),
+            edited: None,
+            changed_message: None,
+            deleted_ts: None,
+            reply_count: None,
+            latest_reply: None,
+            reactions: None,
+        }
+    }
+
+    #[test]
+    fn system_events_and_bots_are_not_analyzed() {
+        assert!(is_analyzable_message(&analysis_message("message", None, None), false));
+        assert!(is_analyzable_message(&analysis_message("message", Some("thread_broadcast"), None), false));
+
+        assert!(!is_analyzable_message(&analysis_message("message", Some("channel_join"), None), false));
+        assert!(!is_analyzable_message(&analysis_message("message", Some("channel_topic"), None), true));
+        assert!(!is_analyzable_message(&analysis_message("event", None, None), false));
+
+        assert!(!is_analyzable_message(&analysis_message("message", Some("bot_message"), Some("B1")), false));
+        assert!(is_analyzable_message(&analysis_message("message", Some("bot_message"), Some("B1")), true));
+        assert!(!is_analyzable_message(&analysis_message("message", None, Some("B1")), false));
+    }
+
+    #[test]
+    fn only_threads_with_new_replies_are_selected() {
+        let messages = vec![
+            message("1700000000.000100", Some(3), Some("1700000500.000100")),
+            message("1700000001.000100", Some(2), Some("1699999000.000100")),
+            message("1700000002.000100", None, None),
+        ];
+
+        let threads = threads_with_new_replies(&messages, 1700000100.0);
+
+        assert_eq!(threads, vec!["1700000000.000100".to_string()]);
+    }
+
+    #[test]
+    fn busy_channels_get_shorter_sync_intervals() {
+        // 120 messages in one hour vs 10 messages over two days
+        let busy: Vec<f64> = (0..120).map(|i| 1700000000.0 + i as f64 * 30.0).collect();
+        let quiet: Vec<f64> = (0..10).map(|i| 1700000000.0 + i as f64 * 17280.0).collect();
+
+        assert!(recommend_interval_from_timestamps(&busy) < recommend_interval_from_timestamps(&quiet));
+    }
+
+    #[test]
+    fn messages_with_reactions_are_summarized() {
+        let reaction = |name: &str, count: u32| SlackReaction { name: name.to_string(), count, users: vec![] };
+        let mut flagged = message("1700000000.000100", None, None);
+        flagged.reactions = Some(vec![reaction("eyes", 2), reaction("white_check_mark", 3)]);
+        let mut liked = message("1700000001.000100", None, None);
+        liked.reactions = Some(vec![reaction("thumbsup", 1)]);
+        let plain = message("1700000002.000100", None, None);
+
+        let summary = summarize_reactions(&[liked, plain, flagged]);
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].ts, "1700000000.000100");
+        assert_eq!(summary[0].total_reactions, 5);
+        assert_eq!(summary[1].ts, "1700000001.000100");
+    }
+
+    #[tokio::test]
+    async fn streaming_fetch_reports_progress_per_page() {
+        let pages = vec![
+            (vec![serde_json::json!({"ts": "1"}), serde_json::json!({"ts": "2"})], Some("c1".to_string())),
+            (vec![serde_json::json!({"ts": "3"}), serde_json::json!({"ts": "4"})], Some("c2".to_string())),
+            (vec![serde_json::json!({"ts": "5"})], None),
+        ];
+        let mut pages = pages.into_iter();
+        let mut progress = Vec::new();
+
+        let messages = paginate_with_progress(
+            "C1",
+            10,
+            2,
+            |_, _| {
+                let page = pages.next().unwrap_or_default();
+                async move { Ok(page) }
+            },
+            |p| progress.push(p),
+        ).await.unwrap();
+
+        assert_eq!(messages.len(), 5);
+        let progress: Vec<(u32, usize, bool)> = progress.iter()
+            .map(|p| (p.pages_fetched, p.messages_so_far, p.has_more))
+            .collect();
+        assert_eq!(progress, vec![(1, 2, true), (2, 4, true), (3, 5, false)]);
+    }
+
+    #[test]
+    fn channels_sharing_a_name_are_grouped() {
+        let channel = |id: &str, name: &str| SlackChannel {
+            id: id.to_string(),
+            name: name.to_string(),
+            is_member: false,
+            is_private: false,
+            topic: String::new(),
+            purpose: String::new(),
+            num_members: None,
+        };
+        let channels = vec![
+            channel("C1", "general"),
+            channel("C2", "standup"),
+            channel("C3", "General "),
+        ];
+
+        let duplicates = group_duplicate_channel_names(&channels);
+
+        assert_eq!(duplicates, vec![DuplicateChannelName {
+            name: "general".to_string(),
+            channel_ids: vec!["C1".to_string(), "C3".to_string()],
+        }]);
+    }
+}
@@ -0,0 +1,399 @@
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use tauri::AppHandle;
+
+use crate::local_http::{read_request, write_response, HttpRequest, READ_TIMEOUT};
+use crate::slack::{SlackClient, SlackMessage, SlackSyncState};
+
+/// Path Slack posts events to (the Request URL configured in the Slack app)
+pub const EVENTS_PATH: &str = "/slack/events";
+/// Requests signed longer ago than this are rejected as possible replays
+const MAX_SIGNATURE_AGE_SECS: i64 = 5 * 60;
+/// Slack event payloads are small; anything bigger is not from Slack
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Event emitted with the tasks detected in a pushed message
+pub const REALTIME_TASKS_EVENT: &str = "slack-realtime-tasks";
+
+/// Polling backs off only while a verified event arrived within this long
+const EVENT_FRESHNESS_SECS: i64 = 10 * 60;
+/// Even while events arrive, channels are polled this often to catch up on missed events
+const CATCH_UP_POLL_SECS: i64 = 60 * 60;
+
+// Set while the receiver is listening
+static RECEIVING_EVENTS: AtomicBool = AtomicBool::new(false);
+// Unix time of the last request that passed the signature check, 0 before the first one
+static LAST_VERIFIED_EVENT_AT: AtomicI64 = AtomicI64::new(0);
+
+/// Whether Slack events are being received
+pub fn is_receiving_events() -> bool {
+    RECEIVING_EVENTS.load(Ordering::SeqCst)
+}
+
+/// Whether the periodic poll can be skipped: the receiver runs and has verified an event
+/// recently, and channels were polled within `CATCH_UP_POLL_SECS`. A bound port alone proves
+/// nothing, since Slack only reaches it through a tunnel. `last_poll_at` is `None` before the
+/// first poll, which always runs to catch up on messages posted while the app was closed.
+pub fn can_skip_poll(listening: bool, last_event_at: i64, last_poll_at: Option<i64>, now: i64) -> bool {
+    listening
+        && last_event_at > 0
+        && now - last_event_at <= EVENT_FRESHNESS_SECS
+        && last_poll_at.is_some_and(|polled_at| now - polled_at < CATCH_UP_POLL_SECS)
+}
+
+/// `can_skip_poll` for the running receiver
+pub fn should_skip_poll(last_poll_at: Option<i64>, now: i64) -> bool {
+    can_skip_poll(is_receiving_events(), LAST_VERIFIED_EVENT_AT.load(Ordering::SeqCst), last_poll_at, now)
+}
+
+/// Check a request's `X-Slack-Signature` (`v0=<hex hmac>`) against the signing secret.
+/// `now` is the current unix time, used to reject stale timestamps.
+pub fn verify_signature(signing_secret: &str, timestamp: &str, body: &[u8], signature: &str, now: i64) -> bool {
+    let sent_at = match timestamp.trim().parse::<i64>() {
+        Ok(sent_at) => sent_at,
+        Err(_) => return false,
+    };
+    if (now - sent_at).abs() > MAX_SIGNATURE_AGE_SECS {
+        return false;
+    }
+    let expected = match signature.strip_prefix("v0=").and_then(|hex_digest| hex::decode(hex_digest).ok()) {
+        Some(expected) => expected,
+        None => return false,
+    };
+
+    let mut mac = match Hmac::<Sha256>::new_from_slice(signing_secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(format!("v0:{}:", timestamp.trim()).as_bytes());
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// The parts of an Events API payload the receiver acts on
+#[derive(Debug, Deserialize)]
+struct EventEnvelope {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    challenge: Option<String>,
+    #[serde(default)]
+    event: Option<serde_json::Value>,
+}
+
+/// How to answer a request, and the message it delivered, if any
+#[derive(Debug)]
+pub struct EventResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+    pub message: Option<SlackMessage>,
+}
+
+impl EventResponse {
+    fn status(status: u16) -> Self {
+        Self { status, content_type: "text/plain", body: String::new(), message: None }
+    }
+}
+
+/// Verify and interpret one Events API request. Answers `url_verification` challenges and
+/// extracts `message` events; every other event is acknowledged and ignored. Nothing,
+/// including the challenge, is answered before the signature checks out.
+pub fn handle_event_request(signing_secret: &str, request: &HttpRequest, now: i64) -> EventResponse {
+    if request.method != "POST" || request.path != EVENTS_PATH {
+        return EventResponse::status(404);
+    }
+
+    let header = |name: &str| request.headers.get(name).map(String::as_str).unwrap_or("");
+    if !verify_signature(signing_secret, header("x-slack-request-timestamp"), &request.body, header("x-slack-signature"), now) {
+        println!("⚠️ [SLACK_EVENTS] Rejected request with an invalid or stale signature");
+        return EventResponse::status(401);
+    }
+
+    let envelope: EventEnvelope = match serde_json::from_slice(&request.body) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            println!("⚠️ [SLACK_EVENTS] Could not parse event payload: {}", e);
+            return EventResponse::status(400);
+        }
+    };
+
+    match envelope.kind.as_str() {
+        "url_verification" => {
+            println!("🤝 [SLACK_EVENTS] Answering URL verification challenge");
+            EventResponse {
+                status: 200,
+                content_type: "application/json",
+                body: serde_json::json!({ "challenge": envelope.challenge.unwrap_or_default() }).to_string(),
+                message: None,
+            }
+        }
+        "event_callback" => {
+            let message = envelope.event
+                .filter(|event| event.get("type").and_then(|t| t.as_str()) == Some("message"))
+                .and_then(|event| serde_json::from_value::<SlackMessage>(event).ok())
+                .filter(|message| message.channel.is_some());
+            EventResponse { message, ..EventResponse::status(200) }
+        }
+        other => {
+            println!("ℹ️ [SLACK_EVENTS] Ignoring {} payload", other);
+            EventResponse::status(200)
+        }
+    }
+}
+
+/// Analyze a pushed message for every active sync of its channel and emit the tasks found
+async fn dispatch_message(app: AppHandle, message: SlackMessage) {
+    use tauri::Emitter;
+
+    let channel_id = message.channel.clone().unwrap_or_default();
+    let syncs = match crate::slack_sync::get_all_slack_syncs(app.clone()).await {
+        Ok(syncs) => syncs,
+        Err(e) => {
+            println!("⚠️ [SLACK_EVENTS] Could not load syncs for channel {}: {}", channel_id, e);
+            return;
+        }
+    };
+
+    // Keep pushed messages alongside polled history for channels some project syncs
+    if syncs.iter().any(|s| s.is_active && s.channel_id == channel_id) {
+        let stored = match crate::app_db::app_db_path(&app) {
+            Ok(db_path) => crate::slack_message_store::persist_messages(db_path, channel_id.clone(), vec![message.clone()]).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            println!("⚠️ [SLACK_EVENTS] Failed to store message for channel {}: {}", channel_id, e);
+        }
+    }
+
+    let mut client = SlackClient::new();
+    if let Ok(Some(credentials)) = crate::credentials::get_slack_credentials(app.clone()).await {
+        if let Some(token) = credentials.access_token {
+            client.set_token(token);
+        }
+    }
+
+    // Listed once the message turns out to belong to an active sync
+    let mut users: Option<Vec<crate::slack::SlackUser>> = None;
+    for sync in syncs.into_iter().filter(|s| s.is_active && s.channel_id == channel_id) {
+        let sync_config = SlackSyncState {
+            project_id: sync.project_id,
+            channel_id: sync.channel_id,
+            last_sync: chrono::Utc::now(),
+            is_active: true,
+            included_users: sync.included_users,
+            excluded_users: sync.excluded_users,
+            paused_until: sync.paused_until,
+        };
+        if sync_config.is_paused(chrono::Utc::now()) {
+            continue;
+        }
+        if users.is_none() {
+            users = Some(client.list_users().await.unwrap_or_else(|e| {
+                println!("⚠️ [SLACK_EVENTS] Could not list workspace members, mentions stay as ids: {}", e);
+                vec![]
+            }));
+        }
+        let members = users.as_deref().unwrap_or_default();
+        let task_store = crate::app_db::app_db_path(&app).ok();
+        let tasks = crate::slack::analyze_channel_messages(
+            &client, &sync_config, vec![message.clone()], members, task_store.as_deref(),
+        ).await;
+        if tasks.is_empty() {
+            continue;
+        }
+
+        let payload = serde_json::json!({
+            "projectId": sync_config.project_id,
+            "channelId": sync_config.channel_id,
+            "tasks": tasks,
+        });
+        if let Err(e) = app.emit(REALTIME_TASKS_EVENT, payload) {
+            println!("⚠️ [SLACK_EVENTS] Failed to emit detected tasks: {}", e);
+        }
+    }
+}
+
+/// Start the Events API receiver on 127.0.0.1 (`slack.events_port`), to be exposed to Slack
+/// through a tunnel. Without a signing secret nothing is started and channels keep being
+/// polled. Returns whether the receiver is running.
+pub async fn start_slack_events_server(app: AppHandle) -> Result<bool, String> {
+    if is_receiving_events() {
+        return Ok(true);
+    }
+
+    let signing_secret = match crate::credentials::get_slack_signing_secret(app.clone()).await? {
+        Some(signing_secret) => signing_secret,
+        None => {
+            println!("ℹ️ [SLACK_EVENTS] No signing secret configured, channels will be polled");
+            return Ok(false);
+        }
+    };
+    let port = crate::commands::settings::get_setting_as::<u16>(&app, crate::commands::settings::SLACK_EVENTS_PORT)
+        .await
+        .unwrap_or(3010);
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await
+        .map_err(|e| format!("Failed to bind Slack events receiver on port {}: {}", port, e))?;
+    RECEIVING_EVENTS.store(true, Ordering::SeqCst);
+    println!("⚡ [SLACK_EVENTS] Receiving Slack events on http://127.0.0.1:{}{}", port, EVENTS_PATH);
+
+    tokio::spawn(async move {
+        loop {
+            let mut stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    println!("❌ [SLACK_EVENTS] Receiver failed, falling back to polling: {}", e);
+                    RECEIVING_EVENTS.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            let signing_secret = signing_secret.clone();
+            let app = app.clone();
+            tokio::spawn(async move {
+                let request = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream, MAX_BODY_BYTES)).await {
+                    Ok(Some(request)) => request,
+                    Ok(None) => return,
+                    Err(_) => {
+                        println!("⚠️ [SLACK_EVENTS] Dropped a request that wasn't sent within {:?}", READ_TIMEOUT);
+                        return;
+                    }
+                };
+                let now = chrono::Utc::now().timestamp();
+                let response = handle_event_request(&signing_secret, &request, now);
+                // Only requests that passed the signature check answer 200
+                if response.status == 200 {
+                    LAST_VERIFIED_EVENT_AT.store(now, Ordering::SeqCst);
+                }
+                // Slack retries unless it gets a 200 within 3 seconds, so answer before analyzing
+                write_response(&mut stream, response.status, response.content_type, &response.body).await;
+                if let Some(message) = response.message {
+                    dispatch_message(app, message).await;
+                }
+            });
+        }
+    });
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "8f742231b10e8888abcd99aaabbb85a5";
+
+    fn sign(timestamp: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn signed_request(body: &str, timestamp: &str) -> HttpRequest {
+        let mut headers = HashMap::new();
+        headers.insert("x-slack-request-timestamp".to_string(), timestamp.to_string());
+        headers.insert("x-slack-signature".to_string(), sign(timestamp, body));
+        HttpRequest {
+            method: "POST".to_string(),
+            path: EVENTS_PATH.to_string(),
+            headers,
+            body: body.as_bytes().to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn signatures_are_checked_and_expire() {
+        let body = br#"{"type":"event_callback"}"#;
+        let signature = sign("1700000000", r#"{"type":"event_callback"}"#);
+
+        assert!(verify_signature(SECRET, "1700000000", body, &signature, 1700000060));
+        assert!(!verify_signature("another-secret", "1700000000", body, &signature, 1700000060));
+        assert!(!verify_signature(SECRET, "1700000000", b"{}", &signature, 1700000060));
+        assert!(!verify_signature(SECRET, "1700000000", body, &signature, 1700000000 + MAX_SIGNATURE_AGE_SECS + 1));
+        assert!(!verify_signature(SECRET, "1700000000", body, "v0=not-hex", 1700000060));
+    }
+
+    #[test]
+    fn url_verification_echoes_the_challenge() {
+        let request = signed_request(r#"{"type":"url_verification","challenge":"abc123"}"#, "1700000000");
+        let response = handle_event_request(SECRET, &request, 1700000000);
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/json");
+        assert_eq!(response.body, r#"{"challenge":"abc123"}"#);
+    }
+
+    #[test]
+    fn stale_and_forged_challenges_are_rejected() {
+        let body = r#"{"type":"url_verification","challenge":"abc123"}"#;
+        let stale = handle_event_request(SECRET, &signed_request(body, "1700000000"), 1700000000 + 10 * 60);
+        assert_eq!(stale.status, 401);
+        assert!(stale.body.is_empty());
+
+        let mut forged = signed_request(body, "1700000000");
+        forged.body = br#"{"type":"url_verification","challenge":"evil"}"#.to_vec();
+        assert_eq!(handle_event_request(SECRET, &forged, 1700000000).status, 401);
+    }
+
+    #[test]
+    fn message_events_are_extracted() {
+        let body = r#"{"type":"event_callback","event":{"type":"message","channel":"C1","user":"U1","text":"TODO: ship","ts":"1700000000.000100"}}"#;
+        let response = handle_event_request(SECRET, &signed_request(body, "1700000000"), 1700000000);
+
+        assert_eq!(response.status, 200);
+        let message = response.message.unwrap();
+        assert_eq!(message.channel.as_deref(), Some("C1"));
+        assert_eq!(message.text, "TODO: ship");
+
+        let reaction = r#"{"type":"event_callback","event":{"type":"reaction_added","user":"U1"}}"#;
+        assert!(handle_event_request(SECRET, &signed_request(reaction, "1700000000"), 1700000000).message.is_none());
+    }
+
+    #[test]
+    fn edit_and_delete_events_are_extracted() {
+        let edit = r#"{"type":"event_callback","event":{"type":"message","subtype":"message_changed","channel":"C1","ts":"1700000100.000100","message":{"type":"message","user":"U1","text":"TODO: ship friday","ts":"1700000000.000100","edited":{"user":"U1","ts":"1700000100.000000"}}}}"#;
+        let message = handle_event_request(SECRET, &signed_request(edit, "1700000100"), 1700000100).message.unwrap();
+        match crate::slack::classify_message_events(vec![message]).pop().unwrap() {
+            crate::slack::SlackMessageEvent::Edited { ts, message } => {
+                assert_eq!(ts, "1700000000.000100");
+                assert_eq!(message.channel.as_deref(), Some("C1"));
+                assert_eq!(message.text, "TODO: ship friday");
+            }
+            other => panic!("expected an edit, got {:?}", other),
+        }
+
+        let delete = r#"{"type":"event_callback","event":{"type":"message","subtype":"message_deleted","channel":"C1","ts":"1700000200.000100","deleted_ts":"1700000000.000100"}}"#;
+        let message = handle_event_request(SECRET, &signed_request(delete, "1700000200"), 1700000200).message.unwrap();
+        assert!(matches!(
+            crate::slack::classify_message_events(vec![message]).pop(),
+            Some(crate::slack::SlackMessageEvent::Deleted { ts }) if ts == "1700000000.000100"
+        ));
+    }
+
+    #[test]
+    fn polling_is_skipped_only_after_recent_verified_events() {
+        let now = 1700000000;
+        let polled = Some(now - 60);
+
+        assert!(can_skip_poll(true, now - 30, polled, now));
+        // Listening without anything arriving, e.g. no tunnel set up
+        assert!(!can_skip_poll(true, 0, polled, now));
+        assert!(!can_skip_poll(true, now - EVENT_FRESHNESS_SECS - 1, polled, now));
+        assert!(!can_skip_poll(false, now - 30, polled, now));
+        // Startup and hourly catch-up polls run regardless
+        assert!(!can_skip_poll(true, now - 30, None, now));
+        assert!(!can_skip_poll(true, now - 30, Some(now - CATCH_UP_POLL_SECS), now));
+    }
+
+    #[test]
+    fn unsigned_requests_are_rejected() {
+        let mut request = signed_request(r#"{"type":"url_verification","challenge":"abc"}"#, "1700000000");
+        request.headers.remove("x-slack-signature");
+
+        assert_eq!(handle_event_request(SECRET, &request, 1700000000).status, 401);
+    }
+}
@@ -0,0 +1,205 @@
+// Local copy of synced Slack messages, kept in the frontend's `slack_messages` table so history
+// can be browsed offline and re-analyzed without hitting the Slack API again.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::slack::{classify_message_events, SlackMessage, SlackMessageEvent};
+
+/// Messages returned by `load_messages` when no limit is given
+pub const DEFAULT_STORED_MESSAGE_LIMIT: u32 = 100;
+/// Most messages returned by one `load_messages` call
+pub const MAX_STORED_MESSAGE_LIMIT: u32 = 1000;
+
+/// A message as stored locally
+#[derive(Debug, Clone, PartialEq, Serialize, specta::Type)]
+pub struct StoredSlackMessage {
+    pub channel_id: String,
+    pub ts: String,
+    pub user: String,
+    pub text: String,
+    pub thread_ts: Option<String>,
+    pub subtype: Option<String>,
+    /// Timestamp of the latest edit
+    pub edited: Option<String>,
+    /// When the message was seen deleted; deleted messages are kept for their tasks' history
+    pub deleted: Option<String>,
+}
+
+/// Same schema the frontend creates, for when the backend syncs before the frontend has run
+pub fn ensure_slack_messages_table(conn: &rusqlite::Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS slack_messages (
+            id TEXT PRIMARY KEY,
+            messageId TEXT NOT NULL,
+            channelId TEXT NOT NULL,
+            text TEXT NOT NULL,
+            user TEXT NOT NULL,
+            username TEXT,
+            timestamp TEXT NOT NULL,
+            threadTs TEXT,
+            reactions TEXT,
+            files TEXT,
+            edited TEXT,
+            deleted TEXT,
+            messageType TEXT DEFAULT 'message',
+            subtype TEXT,
+            UNIQUE(messageId, channelId)
+        );
+        CREATE INDEX IF NOT EXISTS idx_slack_messages_channel ON slack_messages(channelId);",
+    )
+    .map_err(|e| format!("Failed to create slack_messages table: {}", e))
+}
+
+/// Store a batch of channel history, one row per `channel_id + ts`. Edits overwrite the stored
+/// text and deletions mark the row; returns how many rows were written.
+pub fn store_messages(
+    conn: &mut rusqlite::Connection,
+    channel_id: &str,
+    messages: Vec<SlackMessage>,
+    now: &str,
+) -> Result<usize, String> {
+    let tx = conn.transaction().map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut written = 0;
+
+    for event in classify_message_events(messages) {
+        let changed = match event {
+            SlackMessageEvent::New(message) | SlackMessageEvent::Edited { message, .. } => {
+                let reactions = message.reactions.as_ref()
+                    .and_then(|reactions| serde_json::to_string(reactions).ok());
+                tx.execute(
+                    "INSERT INTO slack_messages (
+                        id, messageId, channelId, text, user, timestamp, threadTs, reactions, edited, messageType, subtype
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?2, ?6, ?7, ?8, ?9, ?10)
+                    ON CONFLICT(messageId, channelId) DO UPDATE SET
+                        text = excluded.text,
+                        reactions = excluded.reactions,
+                        edited = excluded.edited",
+                    rusqlite::params![
+                        format!("{}-{}", channel_id, message.ts),
+                        message.ts,
+                        channel_id,
+                        message.text,
+                        message.user.as_deref().or(message.bot_id.as_deref()).unwrap_or_default(),
+                        message.thread_ts,
+                        reactions,
+                        message.edited.as_ref().map(|edited| edited.ts.clone()),
+                        message.msg_type,
+                        message.subtype,
+                    ],
+                )
+            }
+            SlackMessageEvent::Deleted { ts } => tx.execute(
+                "UPDATE slack_messages SET deleted = ?1 WHERE messageId = ?2 AND channelId = ?3",
+                rusqlite::params![now, ts, channel_id],
+            ),
+        }
+        .map_err(|e| format!("Failed to store message: {}", e))?;
+        written += changed;
+    }
+
+    tx.commit().map_err(|e| format!("Failed to commit messages: {}", e))?;
+    Ok(written)
+}
+
+/// Newest stored messages of a channel, newest first, optionally only those older than `before_ts`
+pub fn load_messages(
+    conn: &rusqlite::Connection,
+    channel_id: &str,
+    limit: u32,
+    before_ts: Option<&str>,
+) -> Result<Vec<StoredSlackMessage>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT channelId, messageId, user, text, threadTs, subtype, edited, deleted
+             FROM slack_messages
+             WHERE channelId = ?1 AND (?2 IS NULL OR CAST(messageId AS REAL) < CAST(?2 AS REAL))
+             ORDER BY CAST(messageId AS REAL) DESC
+             LIMIT ?3",
+        )
+        .map_err(|e| format!("Failed to prepare message query: {}", e))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![channel_id, before_ts, limit.min(MAX_STORED_MESSAGE_LIMIT)], |row| {
+            Ok(StoredSlackMessage {
+                channel_id: row.get(0)?,
+                ts: row.get(1)?,
+                user: row.get(2)?,
+                text: row.get(3)?,
+                thread_ts: row.get(4)?,
+                subtype: row.get(5)?,
+                edited: row.get(6)?,
+                deleted: row.get(7)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query messages: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read messages: {}", e))
+}
+
+/// `store_messages` against the database at `db_path`, off the async runtime
+pub async fn persist_messages(db_path: PathBuf, channel_id: String, messages: Vec<SlackMessage>) -> Result<usize, String> {
+    crate::app_db::with_db_at(db_path, move |conn| {
+        ensure_slack_messages_table(conn)?;
+        store_messages(conn, &channel_id, messages, &chrono::Utc::now().to_rfc3339())
+    })
+    .await
+}
+
+/// `load_messages` against the database at `db_path`, off the async runtime
+pub async fn read_messages(
+    db_path: PathBuf,
+    channel_id: String,
+    limit: u32,
+    before_ts: Option<String>,
+) -> Result<Vec<StoredSlackMessage>, String> {
+    crate::app_db::with_db_at(db_path, move |conn| {
+        ensure_slack_messages_table(conn)?;
+        load_messages(conn, &channel_id, limit, before_ts.as_deref())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(ts: &str, text: &str) -> SlackMessage {
+        serde_json::from_value(serde_json::json!({ "ts": ts, "user": "U1", "text": text })).unwrap()
+    }
+
+    #[test]
+    fn messages_are_deduplicated_by_channel_and_ts() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        ensure_slack_messages_table(&conn).unwrap();
+
+        store_messages(&mut conn, "C1", vec![message("100.1", "first"), message("100.2", "second")], "now").unwrap();
+        store_messages(&mut conn, "C1", vec![message("100.2", "second, again"), message("100.3", "third")], "now").unwrap();
+        store_messages(&mut conn, "C2", vec![message("100.1", "other channel")], "now").unwrap();
+
+        let stored = load_messages(&conn, "C1", 10, None).unwrap();
+        let texts: Vec<&str> = stored.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["third", "second, again", "first"]);
+
+        let older = load_messages(&conn, "C1", 1, Some("100.3")).unwrap();
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].ts, "100.2");
+    }
+
+    #[test]
+    fn deletions_mark_the_stored_message() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        ensure_slack_messages_table(&conn).unwrap();
+        store_messages(&mut conn, "C1", vec![message("100.1", "oops")], "now").unwrap();
+
+        let deleted: SlackMessage = serde_json::from_value(serde_json::json!({
+            "ts": "100.9", "subtype": "message_deleted", "deleted_ts": "100.1"
+        })).unwrap();
+        store_messages(&mut conn, "C1", vec![deleted], "2026-01-01T00:00:00Z").unwrap();
+
+        let stored = load_messages(&conn, "C1", 10, None).unwrap();
+        assert_eq!(stored[0].deleted.as_deref(), Some("2026-01-01T00:00:00Z"));
+    }
+}
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
 use reqwest;
+use crate::service_timeouts::TimeoutPolicy;
 use log::{info, warn, error, debug};
 use thiserror::Error;
-use std::time::Duration;
+use crate::http_client::{send_traced, RequestTraced, Traced};
+
+/// Page size used for channel history when the caller doesn't specify one
+pub const DEFAULT_HISTORY_PAGE_SIZE: u32 = 100;
+/// Largest page size we will ask the service for; bigger pages tend to time out
+pub const MAX_HISTORY_PAGE_SIZE: u32 = 200;
 
 #[derive(Error, Debug)]
 pub enum SlackServiceError {
@@ -16,6 +22,17 @@ pub enum SlackServiceError {
     SlackApiError(String),
     #[error("Authentication failed: {0}")]
     AuthenticationFailed(String),
+    #[error(transparent)]
+    Traced(#[from] Traced<SlackServiceError>),
+}
+
+impl RequestTraced for SlackServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            SlackServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
 }
 
 // Request/Response types
@@ -30,19 +47,9 @@ pub struct SlackChannel {
     pub num_members: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlackMessage {
-    pub ts: String,
-    pub user: String,
-    pub text: String,
-    pub channel: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub thread_ts: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub bot_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub subtype: Option<String>,
-}
+/// Messages from the service share the Web API message type, so thread metadata, reactions
+/// and attachments survive either fetch path
+pub use crate::slack::{SlackMessage, SlackReaction};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelHistory {
@@ -146,6 +153,7 @@ pub struct ServiceResponse<T> {
 pub struct SlackServiceClient {
     base_url: String,
     client: reqwest::Client,
+    timeouts: TimeoutPolicy,
 }
 
 impl SlackServiceClient {
@@ -154,12 +162,16 @@ impl SlackServiceClient {
         
         info!("🚀 Initializing Slack Service Client at {}", base_url);
         
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
-        Self { base_url, client }
+        Self { base_url, client: crate::http_client::shared_client(), timeouts: TimeoutPolicy::configured() }
+    }
+    
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+    
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
     }
     
     pub async fn health_check(&self) -> Result<bool, SlackServiceError> {
@@ -167,7 +179,8 @@ impl SlackServiceClient {
         
         let url = format!("{}/health", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("slack-service", self.client.get(&url).timeout(self.timeouts.health)).await;
+        match response {
             Ok(response) => {
                 if response.status().is_success() {
                     info!("✅ Slack service health check passed");
@@ -179,7 +192,7 @@ impl SlackServiceClient {
             }
             Err(e) => {
                 error!("❌ Slack service health check request failed: {}", e);
-                Err(SlackServiceError::Http(e))
+                Err(SlackServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -189,14 +202,15 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/test", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let service_response: ServiceResponse<serde_json::Value> = response.json().await
-                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if service_response.success {
                 info!("✅ Slack connection test successful");
@@ -209,12 +223,12 @@ impl SlackServiceClient {
             } else {
                 let error_msg = service_response.error.unwrap_or_else(|| "Unknown error".to_string());
                 error!("❌ Slack connection test failed: {}", error_msg);
-                Err(SlackServiceError::SlackApiError(error_msg))
+                Err(SlackServiceError::SlackApiError(error_msg).with_request_id(&request_id))
             }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Slack connection test failed: {}", error_text);
-            Err(SlackServiceError::ServiceUnavailable(error_text))
+            Err(SlackServiceError::ServiceUnavailable(error_text).with_request_id(&request_id))
         }
     }
     
@@ -223,12 +237,13 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/channels", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<Vec<SlackChannel>>(response, "channels").await
+        self.handle_response::<Vec<SlackChannel>>(response, "channels").await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn get_channel_history(&self, channel_id: &str, options: Option<ChannelHistoryOptions>) -> Result<ChannelHistory, SlackServiceError> {
@@ -236,11 +251,11 @@ impl SlackServiceClient {
         
         let mut url = format!("{}/api/slack/channels/{}/history", self.base_url, channel_id);
         
+        let mut params = Vec::new();
+        let requested_limit = options.as_ref().and_then(|opts| opts.limit);
+        params.push(format!("limit={}", clamp_history_limit(requested_limit)));
+        
         if let Some(opts) = options {
-            let mut params = Vec::new();
-            if let Some(limit) = opts.limit {
-                params.push(format!("limit={}", limit));
-            }
             if let Some(cursor) = opts.cursor {
                 params.push(format!("cursor={}", cursor));
             }
@@ -250,19 +265,36 @@ impl SlackServiceClient {
             if let Some(latest) = opts.latest {
                 params.push(format!("latest={}", latest));
             }
-            
-            if !params.is_empty() {
-                url.push('?');
-                url.push_str(&params.join("&"));
-            }
         }
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        url.push('?');
+        url.push_str(&params.join("&"));
+        
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.history),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_channel_history_response(response).await
+        self.handle_channel_history_response(response).await.map_err(|e| e.with_request_id(&request_id))
+    }
+    
+    /// One page of a thread's replies (`conversations.replies`), parent message first
+    pub async fn get_thread_replies(&self, channel_id: &str, thread_ts: &str, cursor: Option<&str>) -> Result<ChannelHistory, SlackServiceError> {
+        info!("🧵 Fetching thread replies for: {} ({})", channel_id, thread_ts);
+        
+        let mut url = format!("{}/api/slack/channels/{}/threads/{}/replies", self.base_url, channel_id, thread_ts);
+        if let Some(cursor) = cursor {
+            url.push_str(&format!("?cursor={}", cursor));
+        }
+        
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.history),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
+        
+        self.handle_channel_history_response(response).await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn join_channel(&self, channel_id: &str) -> Result<SlackChannel, SlackServiceError> {
@@ -270,12 +302,13 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/channels/{}/join", self.base_url, channel_id);
         
-        let response = self.client
-            .post(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.post(&url).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<SlackChannel>(response, "channel").await
+        self.handle_response::<SlackChannel>(response, "channel").await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn send_message(&self, channel_id: &str, request: MessageRequest) -> Result<String, SlackServiceError> {
@@ -283,15 +316,15 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/channels/{}/message", self.base_url, channel_id);
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let service_response: ServiceResponse<serde_json::Value> = response.json().await
-                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if service_response.success {
                 info!("✅ Message sent successfully");
@@ -299,12 +332,12 @@ impl SlackServiceClient {
             } else {
                 let error_msg = service_response.error.unwrap_or_else(|| "Unknown error".to_string());
                 error!("❌ Failed to send message: {}", error_msg);
-                Err(SlackServiceError::SlackApiError(error_msg))
+                Err(SlackServiceError::SlackApiError(error_msg).with_request_id(&request_id))
             }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to send message: {}", error_text);
-            Err(SlackServiceError::ServiceUnavailable(error_text))
+            Err(SlackServiceError::ServiceUnavailable(error_text).with_request_id(&request_id))
         }
     }
     
@@ -313,12 +346,13 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/team", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<SlackTeam>(response, "team").await
+        self.handle_response::<SlackTeam>(response, "team").await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn get_user_info(&self, user_id: &str) -> Result<SlackUser, SlackServiceError> {
@@ -326,12 +360,13 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/users/{}", self.base_url, user_id);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.get(&url).timeout(self.timeouts.status),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<SlackUser>(response, "user").await
+        self.handle_response::<SlackUser>(response, "user").await.map_err(|e| e.with_request_id(&request_id))
     }
     
     pub async fn sync_channel(&self, channel_id: &str, request: SyncRequest) -> Result<String, SlackServiceError> {
@@ -339,15 +374,15 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/sync/{}", self.base_url, channel_id);
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let service_response: ServiceResponse<serde_json::Value> = response.json().await
-                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if service_response.success {
                 info!("✅ Channel sync queued successfully");
@@ -358,12 +393,12 @@ impl SlackServiceClient {
             } else {
                 let error_msg = service_response.error.unwrap_or_else(|| "Unknown error".to_string());
                 error!("❌ Failed to queue channel sync: {}", error_msg);
-                Err(SlackServiceError::SlackApiError(error_msg))
+                Err(SlackServiceError::SlackApiError(error_msg).with_request_id(&request_id))
             }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to queue channel sync: {}", error_text);
-            Err(SlackServiceError::ServiceUnavailable(error_text))
+            Err(SlackServiceError::ServiceUnavailable(error_text).with_request_id(&request_id))
         }
     }
     
@@ -372,15 +407,15 @@ impl SlackServiceClient {
         
         let url = format!("{}/api/slack/analyze", self.base_url);
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "slack-service",
+            self.client.post(&url).json(&request).timeout(self.timeouts.analysis),
+        ).await;
+        let response = response.map_err(|e| SlackServiceError::from(e).with_request_id(&request_id))?;
         
         if response.status().is_success() {
             let service_response: ServiceResponse<serde_json::Value> = response.json().await
-                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()))?;
+                .map_err(|e| SlackServiceError::InvalidResponse(e.to_string()).with_request_id(&request_id))?;
             
             if service_response.success {
                 info!("✅ Message analysis queued successfully");
@@ -391,12 +426,12 @@ impl SlackServiceClient {
             } else {
                 let error_msg = service_response.error.unwrap_or_else(|| "Unknown error".to_string());
                 error!("❌ Failed to queue message analysis: {}", error_msg);
-                Err(SlackServiceError::SlackApiError(error_msg))
+                Err(SlackServiceError::SlackApiError(error_msg).with_request_id(&request_id))
             }
         } else {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("❌ Failed to queue message analysis: {}", error_text);
-            Err(SlackServiceError::ServiceUnavailable(error_text))
+            Err(SlackServiceError::ServiceUnavailable(error_text).with_request_id(&request_id))
         }
     }
     
@@ -490,4 +525,48 @@ pub struct ChannelHistoryOptions {
     pub cursor: Option<String>,
     pub oldest: Option<String>,
     pub latest: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Resolve the page size for a history request, applying the default and the hard cap
+pub fn clamp_history_limit(limit: Option<u32>) -> u32 {
+    match limit {
+        None | Some(0) => DEFAULT_HISTORY_PAGE_SIZE,
+        Some(requested) if requested > MAX_HISTORY_PAGE_SIZE => {
+            warn!("⚠️ Requested history limit {} exceeds cap, clamping to {}", requested, MAX_HISTORY_PAGE_SIZE);
+            MAX_HISTORY_PAGE_SIZE
+        }
+        Some(requested) => requested,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_limit_defaults_when_missing() {
+        assert_eq!(clamp_history_limit(None), DEFAULT_HISTORY_PAGE_SIZE);
+        assert_eq!(clamp_history_limit(Some(0)), DEFAULT_HISTORY_PAGE_SIZE);
+    }
+
+    #[test]
+    fn history_limit_is_clamped_to_cap() {
+        assert_eq!(clamp_history_limit(Some(10_000)), MAX_HISTORY_PAGE_SIZE);
+        assert_eq!(clamp_history_limit(Some(50)), 50);
+    }
+
+    #[test]
+    fn service_messages_keep_thread_metadata_and_default_their_type() {
+        let message: SlackMessage = serde_json::from_value(serde_json::json!({
+            "ts": "1700000000.000100", "user": "U1", "text": "TODO: ship", "channel": "C1",
+            "thread_ts": "1700000000.000100", "reply_count": 2, "latest_reply": "1700000100.000000",
+            "reactions": [{ "name": "eyes", "count": 1 }]
+        })).unwrap();
+
+        assert_eq!(message.msg_type, "message");
+        assert_eq!(message.user.as_deref(), Some("U1"));
+        assert_eq!(message.thread_ts.as_deref(), Some("1700000000.000100"));
+        assert_eq!(message.reply_count, Some(2));
+        assert_eq!(message.reactions.map(|r| r.len()), Some(1));
+    }
+}
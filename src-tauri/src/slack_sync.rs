@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct SlackSyncMetadata {
     pub id: String,
     #[serde(rename = "projectId")]
@@ -25,13 +25,25 @@ pub struct SlackSyncMetadata {
     pub last_sync_at: Option<String>,
     #[serde(rename = "teamId")]
     pub team_id: Option<String>,
+    /// Prompt key used instead of the default when analyzing this channel's messages
+    #[serde(rename = "analysisPromptKey", default, skip_serializing_if = "Option::is_none")]
+    pub analysis_prompt_key: Option<String>,
+    /// Only messages from these users (or bots) are analyzed; empty means everyone
+    #[serde(rename = "includedUsers", default)]
+    pub included_users: Vec<String>,
+    /// Messages from these users (or bots) are never analyzed
+    #[serde(rename = "excludedUsers", default)]
+    pub excluded_users: Vec<String>,
+    /// Snoozed until this time: the sync stays connected but isn't synced before then
+    #[serde(rename = "pausedUntil", default, skip_serializing_if = "Option::is_none")]
+    pub paused_until: Option<DateTime<Utc>>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct ConnectedChannel {
     pub channel_id: String,
     pub channel_name: String,
@@ -58,56 +70,166 @@ pub async fn create_slack_sync_metadata(
     Ok(metadata)
 }
 
+/// Write `updates` (column name -> value) to a stored sync and return the stored result
 pub async fn update_slack_sync_metadata(
-    _app: AppHandle,
+    app: AppHandle,
     sync_id: String,
-    _updates: std::collections::HashMap<String, serde_json::Value>,
+    updates: std::collections::HashMap<String, serde_json::Value>,
 ) -> Result<SlackSyncMetadata, String> {
     println!("🔄 [SLACK_SYNC] Updating sync metadata: {}", sync_id);
     
-    // For now, return a dummy updated metadata
-    let updated_metadata = SlackSyncMetadata {
-        id: sync_id.clone(),
-        project_id: "project-1".to_string(),
-        channel_id: "C12345".to_string(),
-        channel_name: "general".to_string(),
-        last_sync_timestamp: Some(Utc::now().to_rfc3339()),
-        last_message_timestamp: None,
-        is_active: true,
-        sync_interval_minutes: Some(15),
-        sync_status: Some("synced".to_string()),
-        last_sync_at: Some(Utc::now().to_rfc3339()),
-        team_id: Some("T12345".to_string()),
-        created_at: Utc::now().to_rfc3339(),
-        updated_at: Utc::now().to_rfc3339(),
-    };
+    let id = sync_id.clone();
+    let updated = crate::app_db::with_existing_app_db(&app, move |conn| {
+        apply_sync_updates(conn, &id, &updates, &Utc::now().to_rfc3339())
+    })
+    .await?
+    .ok_or_else(|| format!("Slack sync {} not found", sync_id))?;
+    
+    println!("✅ [SLACK_SYNC] Sync metadata updated successfully: {}", updated.id);
+    Ok(updated)
+}
+
+/// Columns of `slack_sync_metadata` that can be updated from here
+const UPDATABLE_SYNC_COLUMNS: &[&str] = &[
+    "isEnabled",
+    "syncStatus",
+    "lastSyncAt",
+    "lastMessageTimestamp",
+    "lastSyncError",
+    "analysisPromptKey",
+    "includedUsers",
+    "excludedUsers",
+    "pausedUntil",
+];
+
+/// Columns added after the table was first created, added here if the frontend hasn't yet
+const OPTIONAL_SYNC_COLUMNS: &[&str] = &["analysisPromptKey", "includedUsers", "excludedUsers", "pausedUntil"];
+
+/// Values allowed by the table's CHECK constraint on `syncStatus`
+const SYNC_STATUSES: &[&str] = &["local", "synced", "conflict"];
+
+fn sql_value(column: &str, value: &serde_json::Value) -> Result<rusqlite::types::Value, String> {
+    use rusqlite::types::Value as Sql;
+    use serde_json::Value;
+    
+    Ok(match value {
+        Value::Null => Sql::Null,
+        Value::Bool(flag) => Sql::Integer(*flag as i64),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Sql::Integer(integer),
+            None => Sql::Real(number.as_f64().ok_or_else(|| format!("Invalid number for {}", column))?),
+        },
+        Value::String(text) => Sql::Text(text.clone()),
+        // User lists and other structured values are stored as JSON text
+        Value::Array(_) | Value::Object(_) => Sql::Text(value.to_string()),
+    })
+}
+
+/// Apply `updates` to one sync in a single UPDATE, bumping `updatedAt`, and reload it
+pub fn apply_sync_updates(
+    conn: &rusqlite::Connection,
+    sync_id: &str,
+    updates: &std::collections::HashMap<String, serde_json::Value>,
+    now: &str,
+) -> Result<SlackSyncMetadata, String> {
+    let mut assignments = vec!["updatedAt = ?1".to_string()];
+    let mut values = vec![rusqlite::types::Value::Text(now.to_string())];
+    
+    let mut columns: Vec<&String> = updates.keys().collect();
+    columns.sort();
+    for column in columns {
+        let value = &updates[column];
+        if !UPDATABLE_SYNC_COLUMNS.contains(&column.as_str()) {
+            return Err(format!("Unknown sync field: {}", column));
+        }
+        if column == "syncStatus" && !(value.is_null() || value.as_str().map_or(false, |status| SYNC_STATUSES.contains(&status))) {
+            return Err(format!("Invalid sync status {}, expected one of {:?}", value, SYNC_STATUSES));
+        }
+        if OPTIONAL_SYNC_COLUMNS.contains(&column.as_str()) && !has_column(conn, "slack_sync_metadata", column)? {
+            conn.execute(&format!("ALTER TABLE slack_sync_metadata ADD COLUMN {} TEXT", column), [])
+                .map_err(|e| format!("Failed to add column {}: {}", column, e))?;
+        }
+        
+        values.push(sql_value(column, value)?);
+        assignments.push(format!("{} = ?{}", column, values.len()));
+    }
     
-    println!("✅ [SLACK_SYNC] Sync metadata updated successfully: {}", sync_id);
-    Ok(updated_metadata)
+    values.push(rusqlite::types::Value::Text(sync_id.to_string()));
+    let changed = conn
+        .execute(
+            &format!(
+                "UPDATE slack_sync_metadata SET {} WHERE id = ?{} AND isDeleted = 0",
+                assignments.join(", "),
+                values.len()
+            ),
+            rusqlite::params_from_iter(values),
+        )
+        .map_err(|e| format!("Failed to update sync {}: {}", sync_id, e))?;
+    if changed == 0 {
+        return Err(format!("Slack sync {} not found", sync_id));
+    }
+    
+    load_all_syncs(conn)?
+        .into_iter()
+        .find(|sync| sync.id == sync_id)
+        .ok_or_else(|| format!("Slack sync {} not found", sync_id))
 }
 
 pub async fn get_slack_sync_for_project(
-    _app: AppHandle,
+    app: AppHandle,
     project_id: String,
 ) -> Result<Vec<SlackSyncMetadata>, String> {
     println!("📋 [SLACK_SYNC] Getting sync data for project: {}", project_id);
     
-    // Return empty for now - frontend will handle database queries
-    // This avoids foreign key constraint issues
-    Ok(vec![])
+    let syncs = get_all_slack_syncs(app).await?;
+    Ok(syncs.into_iter().filter(|sync| sync.project_id == project_id).collect())
 }
 
 pub async fn delete_slack_sync_metadata(
-    _app: AppHandle,
+    app: AppHandle,
     sync_id: String,
 ) -> Result<String, String> {
     println!("🗑️ [SLACK_SYNC] Deleting sync metadata: {}", sync_id);
     
-    // Frontend will handle database deletion
-    println!("✅ [SLACK_SYNC] Sync metadata deletion delegated to frontend: {}", sync_id);
+    let id = sync_id.clone();
+    crate::app_db::with_existing_app_db(&app, move |conn| mark_sync_deleted(conn, &id, &Utc::now().to_rfc3339()))
+        .await?
+        .ok_or_else(|| format!("Slack sync {} not found", sync_id))?;
+    
+    println!("✅ [SLACK_SYNC] Sync metadata deleted: {}", sync_id);
     Ok(format!("Sync metadata {} deleted successfully", sync_id))
 }
 
+/// Soft-delete a sync through `isDeleted`, so it stops being listed and synced
+pub fn mark_sync_deleted(conn: &rusqlite::Connection, sync_id: &str, now: &str) -> Result<(), String> {
+    let changed = conn
+        .execute(
+            "UPDATE slack_sync_metadata SET isDeleted = 1, isEnabled = 0, updatedAt = ?1 WHERE id = ?2 AND isDeleted = 0",
+            rusqlite::params![now, sync_id],
+        )
+        .map_err(|e| format!("Failed to delete sync {}: {}", sync_id, e))?;
+    if changed == 0 {
+        return Err(format!("Slack sync {} not found", sync_id));
+    }
+    Ok(())
+}
+
+/// Record a successful scheduled sync of a project's channel, so the next sync (also after a
+/// restart) fetches from `synced_at` on. Returns how many syncs were updated.
+pub fn record_channel_sync(
+    conn: &rusqlite::Connection,
+    project_id: &str,
+    channel_id: &str,
+    synced_at: &str,
+) -> Result<usize, String> {
+    conn.execute(
+        "UPDATE slack_sync_metadata SET lastSyncAt = ?1, syncStatus = 'synced', updatedAt = ?1
+         WHERE projectId = ?2 AND channelId = ?3 AND isDeleted = 0",
+        rusqlite::params![synced_at, project_id, channel_id],
+    )
+    .map_err(|e| format!("Failed to record sync of channel {}: {}", channel_id, e))
+}
+
 pub async fn disconnect_project_from_channel(
     _app: AppHandle,
     project_id: String,
@@ -121,22 +243,144 @@ pub async fn disconnect_project_from_channel(
 }
 
 pub async fn get_project_connected_channels(
-    _app: AppHandle,
+    app: AppHandle,
 ) -> Result<Vec<ConnectedChannel>, String> {
     println!("📋 [SLACK_SYNC] Getting all channels connected to projects");
     
-    // The frontend handles all database operations directly using tauri-plugin-sql
-    // This command exists for compatibility but delegates to frontend
-    // The actual data is stored in the frontend SQLite database
-    // 
-    // To properly implement this, we would need to:
-    // 1. Access the SQLite database from Rust (complex with current architecture)
-    // 2. Query the slack_sync_metadata table
-    // 3. Return the results
-    //
-    // However, the current architecture delegates all database operations to the frontend
-    // So this function returns empty and the frontend should use its own database queries
-    Ok(vec![])
+    let syncs = get_all_slack_syncs(app).await?;
+    Ok(syncs.into_iter().map(|sync| ConnectedChannel {
+        channel_id: sync.channel_id,
+        channel_name: sync.channel_name,
+        project_id: sync.project_id,
+        project_name: None,
+        is_active: sync.is_active,
+        last_sync_at: sync.last_sync_at,
+    }).collect())
+}
+
+/// Every sync that hasn't been deleted, across all projects. Reads the frontend's database
+/// directly; returns nothing before the database or table has been created.
+pub async fn get_all_slack_syncs(app: AppHandle) -> Result<Vec<SlackSyncMetadata>, String> {
+    let syncs = crate::app_db::with_existing_app_db(&app, |conn| load_all_syncs(conn)).await?;
+    if syncs.is_none() {
+        println!("ℹ️ [SLACK_SYNC] Database file doesn't exist, no syncs yet");
+    }
+    Ok(syncs.unwrap_or_default())
+}
+
+pub fn load_all_syncs(conn: &rusqlite::Connection) -> Result<Vec<SlackSyncMetadata>, String> {
+    let table_exists: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'slack_sync_metadata'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .map_err(|e| format!("Failed to inspect database: {}", e))?;
+    if !table_exists {
+        return Ok(vec![]);
+    }
+    
+    // Columns added after the table was first created are missing until the frontend migrates it
+    let optional_column = |column: &'static str| -> Result<&'static str, String> {
+        Ok(if has_column(conn, "slack_sync_metadata", column)? { column } else { "NULL" })
+    };
+    
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, projectId, channelId, channelName, lastMessageTimestamp, isEnabled,
+                    syncStatus, lastSyncAt, teamId, createdAt, updatedAt, {}, {}, {}, {}
+             FROM slack_sync_metadata
+             WHERE isDeleted = 0
+             ORDER BY projectId, channelName",
+            optional_column("analysisPromptKey")?,
+            optional_column("includedUsers")?,
+            optional_column("excludedUsers")?,
+            optional_column("pausedUntil")?,
+        ))
+        .map_err(|e| format!("Failed to prepare sync query: {}", e))?;
+    
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SlackSyncMetadata {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                channel_id: row.get(2)?,
+                channel_name: row.get(3)?,
+                last_sync_timestamp: None,
+                last_message_timestamp: row.get(4)?,
+                is_active: row.get::<_, Option<i64>>(5)?.unwrap_or(1) != 0,
+                sync_interval_minutes: None,
+                sync_status: row.get(6)?,
+                last_sync_at: row.get(7)?,
+                team_id: row.get(8)?,
+                analysis_prompt_key: row.get(11)?,
+                included_users: parse_user_list(row.get(12)?),
+                excluded_users: parse_user_list(row.get(13)?),
+                paused_until: parse_paused_until(row.get(14)?),
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query syncs: {}", e))?;
+    
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read syncs: {}", e))
+}
+
+/// User lists are stored as JSON arrays of user or bot ids
+fn parse_user_list(stored: Option<String>) -> Vec<String> {
+    match stored.filter(|value| !value.trim().is_empty()) {
+        Some(value) => serde_json::from_str(&value).unwrap_or_else(|e| {
+            println!("⚠️ [SLACK_SYNC] Ignoring malformed user list {}: {}", value, e);
+            vec![]
+        }),
+        None => vec![],
+    }
+}
+
+/// Trim author ids and drop blanks and repeats, keeping the order they were given in
+pub fn normalize_user_list(users: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for user in users {
+        let user = user.trim();
+        if !user.is_empty() && !normalized.iter().any(|existing| existing == user) {
+            normalized.push(user.to_string());
+        }
+    }
+    normalized
+}
+
+/// Pauses are stored as RFC 3339 timestamps; anything else means not paused
+fn parse_paused_until(stored: Option<String>) -> Option<DateTime<Utc>> {
+    stored
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+pub(crate) fn has_column(conn: &rusqlite::Connection, table: &str, column: &str) -> Result<bool, String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .map_err(|e| format!("Failed to inspect {}: {}", table, e))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| format!("Failed to inspect {}: {}", table, e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to inspect {}: {}", table, e))?;
+    Ok(columns.iter().any(|name| name == column))
+}
+
+/// Resolve the prompt to analyze a sync's messages with: its `analysis_prompt_key` if set,
+/// otherwise `None` for the service default
+pub async fn prompt_for_sync(app: &AppHandle, sync: &SlackSyncMetadata) -> Result<Option<String>, String> {
+    match &sync.analysis_prompt_key {
+        Some(key) => crate::commands::prompt_commands::get_effective_prompt(
+            app.clone(),
+            key.clone(),
+            Some(sync.channel_id.clone()),
+        ).await.map(Some),
+        None => Ok(None),
+    }
 }
 
 // Helper functions for metadata management
@@ -169,6 +413,43 @@ pub fn validate_sync_metadata(metadata: &SlackSyncMetadata) -> Result<(), String
     Ok(())
 }
 
+/// A channel that is synced into more than one project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelOverlap {
+    pub channel_id: String,
+    pub project_ids: Vec<String>,
+}
+
+pub fn detect_overlapping_channels(channels: &[ConnectedChannel]) -> Vec<ChannelOverlap> {
+    let mut by_channel: std::collections::BTreeMap<&str, Vec<String>> = std::collections::BTreeMap::new();
+    
+    for channel in channels.iter().filter(|c| c.is_active) {
+        let projects = by_channel.entry(channel.channel_id.as_str()).or_default();
+        if !projects.contains(&channel.project_id) {
+            projects.push(channel.project_id.clone());
+        }
+    }
+    
+    by_channel.into_iter()
+        .filter(|(_, project_ids)| project_ids.len() > 1)
+        .map(|(channel_id, project_ids)| ChannelOverlap {
+            channel_id: channel_id.to_string(),
+            project_ids,
+        })
+        .collect()
+}
+
+pub async fn find_overlapping_channel_syncs(app: AppHandle) -> Result<Vec<ChannelOverlap>, String> {
+    let channels = get_project_connected_channels(app).await?;
+    let overlaps = detect_overlapping_channels(&channels);
+    
+    for overlap in &overlaps {
+        println!("⚠️ [SLACK_SYNC] Channel {} is synced into {} projects: {:?}", 
+            overlap.channel_id, overlap.project_ids.len(), overlap.project_ids);
+    }
+    
+    Ok(overlaps)
+}
 
 // Aliases for the functions expected by commands/slack_integration.rs
 pub use create_slack_sync_metadata as create_sync;
@@ -177,4 +458,154 @@ pub use get_slack_sync_for_project as get_syncs_for_project;
 pub use delete_slack_sync_metadata as delete_sync;
 pub use disconnect_project_from_channel as disconnect_channel;
 pub use get_project_connected_channels as get_connected_channels_for_project;
-pub use SlackSyncMetadata as SlackSync;
\ No newline at end of file
+pub use SlackSyncMetadata as SlackSync;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(channel_id: &str, project_id: &str) -> ConnectedChannel {
+        ConnectedChannel {
+            channel_id: channel_id.to_string(),
+            channel_name: "general".to_string(),
+            project_id: project_id.to_string(),
+            project_name: None,
+            is_active: true,
+            last_sync_at: None,
+        }
+    }
+
+    #[test]
+    fn all_syncs_are_loaded_across_projects() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        assert!(load_all_syncs(&conn).unwrap().is_empty());
+
+        conn.execute_batch(
+            "CREATE TABLE slack_sync_metadata (
+                 id TEXT PRIMARY KEY, projectId TEXT NOT NULL, channelId TEXT NOT NULL,
+                 channelName TEXT NOT NULL, teamId TEXT, isEnabled INTEGER DEFAULT 1,
+                 lastSyncAt TEXT, lastMessageTimestamp TEXT, isDeleted INTEGER DEFAULT 0,
+                 createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL, syncStatus TEXT DEFAULT 'local'
+             );
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, isEnabled, lastSyncAt, createdAt, updatedAt)
+                 VALUES ('s1', 'project-a', 'C1', 'general', 1, '2024-05-01T00:00:00Z', 'x', 'x');
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, isEnabled, createdAt, updatedAt)
+                 VALUES ('s2', 'project-b', 'C2', 'design', 0, 'x', 'x');
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, isDeleted, createdAt, updatedAt)
+                 VALUES ('s3', 'project-b', 'C3', 'old', 1, 'x', 'x');",
+        )
+        .unwrap();
+
+        let syncs = load_all_syncs(&conn).unwrap();
+        assert_eq!(syncs.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["s1", "s2"]);
+        assert!(syncs[0].is_active);
+        assert_eq!(syncs[0].last_sync_at.as_deref(), Some("2024-05-01T00:00:00Z"));
+        assert_eq!(syncs[0].sync_status.as_deref(), Some("local"));
+        assert!(!syncs[1].is_active);
+        assert!(syncs[0].analysis_prompt_key.is_none());
+
+        conn.execute_batch(
+            "ALTER TABLE slack_sync_metadata ADD COLUMN analysisPromptKey TEXT;
+             UPDATE slack_sync_metadata SET analysisPromptKey = 'engineering-tasks' WHERE id = 's2';",
+        )
+        .unwrap();
+        let syncs = load_all_syncs(&conn).unwrap();
+        assert_eq!(syncs[1].analysis_prompt_key.as_deref(), Some("engineering-tasks"));
+        assert!(syncs[1].excluded_users.is_empty());
+
+        conn.execute_batch(
+            "ALTER TABLE slack_sync_metadata ADD COLUMN includedUsers TEXT;
+             ALTER TABLE slack_sync_metadata ADD COLUMN excludedUsers TEXT;
+             UPDATE slack_sync_metadata SET excludedUsers = '[\"B01\",\"U02\"]' WHERE id = 's2';",
+        )
+        .unwrap();
+        let syncs = load_all_syncs(&conn).unwrap();
+        assert!(syncs[0].included_users.is_empty());
+        assert_eq!(syncs[1].excluded_users, vec!["B01", "U02"]);
+
+        let included = serde_json::json!(normalize_user_list(vec![" U02 ".to_string(), "".to_string(), "U02".to_string(), "B01".to_string()]));
+        let updates = std::collections::HashMap::from([("includedUsers".to_string(), included)]);
+        let sync = apply_sync_updates(&conn, "s1", &updates, "now").unwrap();
+        assert_eq!(sync.included_users, vec!["U02", "B01"]);
+    }
+
+    #[test]
+    fn sync_updates_are_written_to_the_row() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE slack_sync_metadata (
+                 id TEXT PRIMARY KEY, projectId TEXT NOT NULL, channelId TEXT NOT NULL,
+                 channelName TEXT NOT NULL, teamId TEXT, isEnabled INTEGER DEFAULT 1,
+                 lastSyncAt TEXT, lastMessageTimestamp TEXT, isDeleted INTEGER DEFAULT 0,
+                 createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL,
+                 syncStatus TEXT DEFAULT 'local' CHECK (syncStatus IN ('local', 'synced', 'conflict'))
+             );
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, createdAt, updatedAt)
+                 VALUES ('s1', 'project-a', 'C1', 'general', 'x', 'x');",
+        )
+        .unwrap();
+
+        let updates: std::collections::HashMap<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "analysisPromptKey": "engineering-tasks",
+            "excludedUsers": ["B01"],
+            "syncStatus": "synced",
+            "isEnabled": false
+        }))
+        .unwrap();
+        let sync = apply_sync_updates(&conn, "s1", &updates, "2024-05-01T00:00:00Z").unwrap();
+        assert_eq!(sync.analysis_prompt_key.as_deref(), Some("engineering-tasks"));
+        assert_eq!(sync.excluded_users, vec!["B01"]);
+        assert_eq!(sync.sync_status.as_deref(), Some("synced"));
+        assert!(!sync.is_active);
+        assert_eq!(sync.updated_at, "2024-05-01T00:00:00Z");
+
+        let invalid = |field: &str, value: serde_json::Value| {
+            std::collections::HashMap::from([(field.to_string(), value)])
+        };
+        assert!(apply_sync_updates(&conn, "s1", &invalid("syncStatus", serde_json::json!("disconnected")), "now").is_err());
+        assert!(apply_sync_updates(&conn, "s1", &invalid("isActive", serde_json::json!(false)), "now").is_err());
+        assert!(apply_sync_updates(&conn, "missing", &invalid("lastSyncAt", serde_json::json!("now")), "now").is_err());
+    }
+
+    #[test]
+    fn scheduled_syncs_record_their_sync_time() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE slack_sync_metadata (
+                 id TEXT PRIMARY KEY, projectId TEXT NOT NULL, channelId TEXT NOT NULL,
+                 channelName TEXT NOT NULL, teamId TEXT, isEnabled INTEGER DEFAULT 1,
+                 lastSyncAt TEXT, lastMessageTimestamp TEXT, isDeleted INTEGER DEFAULT 0,
+                 createdAt TEXT NOT NULL, updatedAt TEXT NOT NULL,
+                 syncStatus TEXT DEFAULT 'local' CHECK (syncStatus IN ('local', 'synced', 'conflict'))
+             );
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, createdAt, updatedAt)
+                 VALUES ('s1', 'project-a', 'C1', 'general', 'x', 'x');
+             INSERT INTO slack_sync_metadata (id, projectId, channelId, channelName, createdAt, updatedAt)
+                 VALUES ('s2', 'project-b', 'C1', 'general', 'x', 'x');",
+        )
+        .unwrap();
+
+        assert_eq!(record_channel_sync(&conn, "project-a", "C1", "2024-05-01T00:00:00Z").unwrap(), 1);
+        assert_eq!(record_channel_sync(&conn, "project-a", "C9", "2024-05-01T00:00:00Z").unwrap(), 0);
+
+        let syncs = load_all_syncs(&conn).unwrap();
+        assert_eq!(syncs[0].last_sync_at.as_deref(), Some("2024-05-01T00:00:00Z"));
+        assert_eq!(syncs[0].sync_status.as_deref(), Some("synced"));
+        assert!(syncs[1].last_sync_at.is_none());
+    }
+
+    #[test]
+    fn channel_connected_to_two_projects_is_reported() {
+        let channels = vec![
+            connected("C1", "project-a"),
+            connected("C1", "project-b"),
+            connected("C2", "project-a"),
+        ];
+
+        let overlaps = detect_overlapping_channels(&channels);
+
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].channel_id, "C1");
+        assert_eq!(overlaps[0].project_ids, vec!["project-a", "project-b"]);
+    }
+}
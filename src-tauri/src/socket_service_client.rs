@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 use log::{info, warn, error};
+use crate::service_timeouts::TimeoutPolicy;
+use crate::http_client::{send_traced, RequestTraced, Traced};
 
 #[derive(Debug, Clone)]
 pub struct SocketServiceClient {
     client: reqwest::Client,
     base_url: String,
+    timeouts: TimeoutPolicy,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -69,21 +72,38 @@ pub enum SocketServiceError {
     JsonError(#[from] serde_json::Error),
     #[error("Service error: {status} - {message}")]
     ServiceError { status: u16, message: String },
+    #[error(transparent)]
+    Traced(#[from] Traced<SocketServiceError>),
+}
+
+impl RequestTraced for SocketServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            SocketServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
 }
 
 impl SocketServiceClient {
     pub fn new(base_url: &str) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
 
         Self {
-            client,
+            client: crate::http_client::shared_client(),
             base_url: base_url.to_string(),
+            timeouts: TimeoutPolicy::configured(),
         }
     }
 
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
+    }
+
     async fn handle_response<T: for<'de> Deserialize<'de>>(&self, response: reqwest::Response) -> Result<T, SocketServiceError> {
         let status = response.status();
         
@@ -104,9 +124,10 @@ impl SocketServiceClient {
         info!("🏥 Checking Socket.io service health");
         
         let url = format!("{}/health", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let (request_id, response) = send_traced("socket", self.client.get(&url).timeout(self.timeouts.health)).await;
+        let response = response.map_err(|e| SocketServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<HealthResponse>(response).await
+        self.handle_response::<HealthResponse>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
 
     // Broadcast message to room
@@ -114,13 +135,13 @@ impl SocketServiceClient {
         info!("📢 Broadcasting to room: {} (event: {})", request.room, request.event);
         
         let url = format!("{}/api/broadcast", self.base_url);
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let (request_id, response) = send_traced(
+            "socket",
+            self.client.post(&url).json(&request).timeout(self.timeouts.standard),
+        ).await;
+        let response = response.map_err(|e| SocketServiceError::from(e).with_request_id(&request_id))?;
             
-        self.handle_response::<BroadcastResponse>(response).await
+        self.handle_response::<BroadcastResponse>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
 
     // Get connected clients
@@ -128,9 +149,10 @@ impl SocketServiceClient {
         info!("👥 Fetching connected clients");
         
         let url = format!("{}/api/clients", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let (request_id, response) = send_traced("socket", self.client.get(&url).timeout(self.timeouts.status)).await;
+        let response = response.map_err(|e| SocketServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<ClientsResponse>(response).await
+        self.handle_response::<ClientsResponse>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
 
     // Get room information
@@ -138,9 +160,10 @@ impl SocketServiceClient {
         info!("🏠 Fetching room info: {}", room_name);
         
         let url = format!("{}/api/rooms/{}", self.base_url, room_name);
-        let response = self.client.get(&url).send().await?;
+        let (request_id, response) = send_traced("socket", self.client.get(&url).timeout(self.timeouts.status)).await;
+        let response = response.map_err(|e| SocketServiceError::from(e).with_request_id(&request_id))?;
         
-        self.handle_response::<RoomInfo>(response).await
+        self.handle_response::<RoomInfo>(response).await.map_err(|e| e.with_request_id(&request_id))
     }
 
     // Convenience methods for common broadcasts
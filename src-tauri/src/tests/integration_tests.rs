@@ -226,6 +226,11 @@ mod slack_integration_tests {
                 msg_type: "message".to_string(),
                 thread_ts: None,
                 attachments: None,
+                subtype: None,
+                bot_id: None,
+                edited: None,
+                changed_message: None,
+                deleted_ts: None,
             },
             crate::slack::SlackMessage {
                 ts: "1234567891.123456".to_string(),
@@ -235,6 +240,11 @@ mod slack_integration_tests {
                 msg_type: "message".to_string(),
                 thread_ts: None,
                 attachments: None,
+                subtype: None,
+                bot_id: None,
+                edited: None,
+                changed_message: None,
+                deleted_ts: None,
             },
         ];
 
@@ -291,6 +301,11 @@ mod slack_integration_tests {
             msg_type: "message".to_string(),
             thread_ts: Some("1234567890.123456".to_string()),
             attachments: None,
+            subtype: None,
+            bot_id: None,
+            edited: None,
+            changed_message: None,
+            deleted_ts: None,
         };
 
         let low_confidence_message = crate::slack::SlackMessage {
@@ -301,6 +316,11 @@ mod slack_integration_tests {
             msg_type: "message".to_string(),
             thread_ts: None,
             attachments: None,
+            subtype: None,
+            bot_id: None,
+            edited: None,
+            changed_message: None,
+            deleted_ts: None,
         };
 
         let high_tasks = crate::slack::process_messages_for_tasks(vec![high_confidence_message]).await;
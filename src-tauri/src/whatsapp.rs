@@ -29,14 +29,17 @@ pub struct WhatsAppMessage {
     pub created_at: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WhatsAppDatabase {
-    // Database operations now handled by database service
+    // Database operations now handled by database service; gaps are tracked in memory
+    gaps: Arc<std::sync::Mutex<Vec<MessageGap>>>,
+    /// When monitoring stopped, until it resumes and the missed span is recorded as a gap
+    connection_lost_at: Arc<std::sync::Mutex<Option<i64>>>,
 }
 
 impl WhatsAppDatabase {
     pub fn new(_db_path: &str) -> Result<Self, WhatsAppError> {
-        Ok(WhatsAppDatabase {})
+        Ok(WhatsAppDatabase::default())
     }
     
     pub fn initialize(&self) -> Result<(), WhatsAppError> {
@@ -64,22 +67,69 @@ impl WhatsAppDatabase {
         Ok(())
     }
 
+    pub fn record_gap(&self, gap: MessageGap) -> Result<(), WhatsAppError> {
+        let mut gaps = self.gaps.lock().map_err(|e| WhatsAppError::Database(e.to_string()))?;
+        if !gaps.iter().any(|g| g.id == gap.id) {
+            gaps.push(gap);
+        }
+        Ok(())
+    }
+
+    pub fn list_gaps(&self) -> Result<Vec<MessageGap>, WhatsAppError> {
+        let gaps = self.gaps.lock().map_err(|e| WhatsAppError::Database(e.to_string()))?;
+        Ok(gaps.clone())
+    }
+
+    /// Remember that messages stopped being watched at `at`; the earliest loss is kept
+    pub fn mark_connection_lost(&self, at: i64) {
+        let mut lost_at = self.connection_lost_at.lock().unwrap_or_else(|e| e.into_inner());
+        if lost_at.map_or(true, |earlier| at < earlier) {
+            *lost_at = Some(at);
+        }
+    }
+
+    /// Record the span since the connection was lost as a gap, once monitoring resumes
+    pub fn close_connection_gap(&self, reconnected_at: i64) -> Result<Option<MessageGap>, WhatsAppError> {
+        let lost_at = self.connection_lost_at.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let gap = match lost_at.filter(|lost_at| *lost_at < reconnected_at) {
+            Some(lost_at) => MessageGap {
+                id: format!("gap-{}-{}", lost_at, reconnected_at),
+                gap_start: lost_at.to_string(),
+                gap_end: reconnected_at.to_string(),
+                recovery_attempted: false,
+            },
+            None => return Ok(None),
+        };
+        self.record_gap(gap.clone())?;
+        Ok(Some(gap))
+    }
+
     pub fn get_unrecovered_gaps(&self) -> Result<Vec<MessageGap>, WhatsAppError> {
-        // Placeholder implementation
-        Ok(vec![])
+        Ok(self.list_gaps()?
+            .into_iter()
+            .filter(|g| !g.recovery_attempted)
+            .collect())
     }
 
-    pub fn mark_gap_recovery_attempted(&self, _gap_id: &str) -> Result<(), WhatsAppError> {
-        // Placeholder implementation
-        Ok(())
+    pub fn mark_gap_recovery_attempted(&self, gap_id: &str) -> Result<(), WhatsAppError> {
+        let mut gaps = self.gaps.lock().map_err(|e| WhatsAppError::Database(e.to_string()))?;
+        match gaps.iter_mut().find(|g| g.id == gap_id) {
+            Some(gap) => {
+                gap.recovery_attempted = true;
+                Ok(())
+            }
+            None => Err(WhatsAppError::Database(format!("Gap not found: {}", gap_id))),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageGap {
     pub id: String,
     pub gap_start: String,
     pub gap_end: String,
+    #[serde(default)]
+    pub recovery_attempted: bool,
 }
 
 #[derive(Error, Debug)]
@@ -153,7 +203,7 @@ static WHATSAPP_MONITOR: Lazy<Arc<Mutex<WhatsAppMonitor>>> = Lazy::new(|| {
         WhatsAppMonitor {
             browser: None,
             tab: None,
-            database: WhatsAppDatabase { },
+            database: WhatsAppDatabase::default(),
             state: Arc::new(Mutex::new(WhatsAppConnectionState {
                 status: ConnectionStatus::Disconnected,
                 qr_code: None,
@@ -213,6 +263,19 @@ impl WhatsAppMonitor {
         WHATSAPP_MONITOR.clone()
     }
 
+    pub fn list_gaps(&self) -> Result<Vec<MessageGap>, WhatsAppError> {
+        self.database.list_gaps()
+    }
+
+    pub async fn recover_gap(&self, gap_id: &str) -> Result<(), WhatsAppError> {
+        info!("[WhatsApp] Manual recovery requested for gap {}", gap_id);
+        self.database.mark_gap_recovery_attempted(gap_id)?;
+        
+        let mut state = self.state.lock().await;
+        state.health_status.last_recovery_attempt = Some(Utc::now().timestamp());
+        Ok(())
+    }
+
     pub async fn connect(&mut self) -> Result<(), WhatsAppError> {
         // Logging disabled
         info!("[WhatsApp] Starting WhatsApp Web connection...");
@@ -256,7 +319,7 @@ impl WhatsAppMonitor {
                     // Logging disabled
                 }
                 Err(e) => {
-                    // Logging disabled
+                    error!("[WhatsApp] Navigation failed: {}", e);
                     return Err(WhatsAppError::BrowserInit(format!("Navigation failed: {}", e)));
                 }
             }
@@ -270,7 +333,7 @@ impl WhatsAppMonitor {
                     // Logging disabled
                 }
                 Err(e) => {
-                    // Logging disabled
+                    error!("[WhatsApp] Page load timed out: {}", e);
                     return Err(WhatsAppError::BrowserInit(format!("Page load timeout: {}", e)));
                 }
             }
@@ -339,17 +402,17 @@ impl WhatsAppMonitor {
                         Ok(browser)
                     }
                     Err(e) => {
-                        // Logging disabled
+                        error!("[WhatsApp] Browser launch failed: {}", e);
                         Err(WhatsAppError::BrowserInit(e.to_string()))
                     }
                 }
             }
             Ok(Err(e)) => {
-                // Logging disabled
+                error!("[WhatsApp] Browser launch task failed: {}", e);
                 Err(WhatsAppError::BrowserInit(format!("Task failed: {}", e)))
             }
             Err(_) => {
-                // Logging disabled
+                error!("[WhatsApp] Browser launch timed out");
                 Err(WhatsAppError::BrowserInit("Browser launch timeout".to_string()))
             }
         }
@@ -799,6 +862,17 @@ impl WhatsAppMonitor {
         info!("[WhatsApp] Updating status to Monitoring...");
         self.update_status(ConnectionStatus::Monitoring).await;
 
+        // Messages sent while we weren't watching are a gap to recover
+        match self.database.close_connection_gap(Utc::now().timestamp()) {
+            Ok(Some(gap)) => {
+                info!("[WhatsApp] Recorded message gap {} to {}", gap.gap_start, gap.gap_end);
+                let mut state = self.state.lock().await;
+                state.health_status.gap_count += 1;
+            }
+            Ok(None) => {}
+            Err(e) => error!("[WhatsApp] Failed to record message gap: {}", e),
+        }
+
         // Start message listener
         info!("[WhatsApp] Starting message monitoring loop task...");
         let database = self.database.clone();
@@ -859,7 +933,10 @@ impl WhatsAppMonitor {
                                     // Update state
                                     {
                                         let mut s = state.lock().await;
-                                        s.last_message_timestamp = Some(message.timestamp.parse().unwrap_or(0));
+                                        match message.timestamp.trim().parse::<i64>() {
+                                            Ok(timestamp) => s.last_message_timestamp = Some(timestamp),
+                                            Err(_) => warn!("[WhatsApp] Ignoring malformed timestamp '{}' on message {}", message.timestamp, message.id),
+                                        }
                                         s.message_count += 1;
                                         s.health_status.last_heartbeat = Utc::now().timestamp();
                                     }
@@ -892,6 +969,7 @@ impl WhatsAppMonitor {
                         // Logging disabled
                         error!("[WhatsApp] Too many consecutive failures ({}), marking connection as lost", s.health_status.consecutive_failures);
                         s.status = ConnectionStatus::Error("Connection lost - too many scan failures".to_string());
+                        database.mark_connection_lost(s.health_status.last_heartbeat);
                         break;
                     }
                 }
@@ -919,9 +997,28 @@ impl WhatsAppMonitor {
                     if (msgTime <= {}) return; // Skip old messages
                     
                     const textEl = msgEl.querySelector('[data-testid="selectable-text"]');
-                    const content = textEl ? textEl.innerText : '';
+                    let content = textEl ? textEl.innerText : '';
+                    
+                    // Detect media messages from their icon/placeholder elements
+                    let messageType = 'text';
+                    if (msgEl.querySelector('[data-testid="media-url-provider"] video, [data-icon="media-play"], [data-icon="video-pip"]')) {{
+                        messageType = 'video';
+                    }} else if (msgEl.querySelector('img[src^="blob:"], [data-testid="image-thumb"], [data-icon="media-gif"]')) {{
+                        messageType = 'image';
+                    }} else if (msgEl.querySelector('[data-testid="audio-player"], [data-icon="audio-play"], [data-icon="ptt-status"]')) {{
+                        messageType = 'audio';
+                    }} else if (msgEl.querySelector('[data-testid="document-thumb"], [data-icon="audio-file"], [data-icon="document-PDF-icon"]')) {{
+                        messageType = 'document';
+                    }}
                     
-                    if (!content) return;
+                    if (messageType !== 'text') {{
+                        // Keep a short caption (or the document title) for media messages
+                        const captionEl = textEl || msgEl.querySelector('[data-testid="document-thumb"] span[title]');
+                        const caption = captionEl ? (captionEl.innerText || captionEl.getAttribute('title') || '') : '';
+                        content = caption.length > 200 ? caption.substring(0, 200) + '…' : caption;
+                    }} else if (!content) {{
+                        return;
+                    }}
                     
                     // Try to determine sender
                     const isOutgoing = msgEl.classList.contains('message-out') || 
@@ -929,7 +1026,16 @@ impl WhatsAppMonitor {
                     const sender = isOutgoing ? 'me' : 'contact';
                     
                     // Generate unique message ID based on content and timestamp
-                    const msgId = btoa(content + msgTime + sender).replace(/[^a-zA-Z0-9]/g, '');
+                    // Keep the original btoa ids so messages stored before stay deduplicated;
+                    // only text btoa can't encode (outside Latin-1, previously skipped) goes through UTF-8
+                    const idSource = (messageType === 'text' ? content : messageType + content) + msgTime + sender;
+                    let encodedId;
+                    try {{
+                        encodedId = btoa(idSource);
+                    }} catch (encodeError) {{
+                        encodedId = btoa(unescape(encodeURIComponent(idSource)));
+                    }}
+                    const msgId = encodedId.replace(/[^a-zA-Z0-9]/g, '');
                     
                     messages.push({{
                         id: msgId,
@@ -937,7 +1043,7 @@ impl WhatsAppMonitor {
                         timestamp: Math.floor(msgTime),
                         sender: sender,
                         chat_id: 'current_chat', // Will be improved to get actual chat ID
-                        message_type: 'text'
+                        message_type: messageType
                     }});
                 }} catch (e) {{
                     console.error('Error processing message:', e);
@@ -1015,6 +1121,7 @@ impl WhatsAppMonitor {
 
     async fn start_health_monitoring(&self) {
         let state = self.state.clone();
+        let database = self.database.clone();
         let monitoring_active = self.monitoring_active.clone();
         
         tokio::spawn(async move {
@@ -1038,6 +1145,7 @@ impl WhatsAppMonitor {
                         let mut s = state.lock().await;
                         s.status = ConnectionStatus::Reconnecting;
                         s.health_status.last_recovery_attempt = Some(Utc::now().timestamp());
+                        database.mark_connection_lost(s.health_status.last_heartbeat);
                     }
                     
                     // TODO: Implement connection recovery
@@ -1053,6 +1161,9 @@ impl WhatsAppMonitor {
         // Stop monitoring
         {
             let mut monitoring = self.monitoring_active.lock().await;
+            if *monitoring {
+                self.database.mark_connection_lost(Utc::now().timestamp());
+            }
             *monitoring = false;
         }
 
@@ -1271,4 +1382,47 @@ pub async fn whatsapp_check_login() -> Result<WhatsAppConnectionState, String> {
     
     let status = monitor.get_connection_status().await;
     Ok(status)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gap(id: &str) -> MessageGap {
+        MessageGap {
+            id: id.to_string(),
+            gap_start: "1700000000".to_string(),
+            gap_end: "1700003600".to_string(),
+            recovery_attempted: false,
+        }
+    }
+
+    #[test]
+    fn listing_returns_seeded_gaps() {
+        let database = WhatsAppDatabase::default();
+        database.record_gap(gap("gap-1")).unwrap();
+        database.record_gap(gap("gap-2")).unwrap();
+        database.mark_gap_recovery_attempted("gap-2").unwrap();
+
+        let all: Vec<String> = database.list_gaps().unwrap().into_iter().map(|g| g.id).collect();
+        let unrecovered: Vec<String> = database.get_unrecovered_gaps().unwrap().into_iter().map(|g| g.id).collect();
+
+        assert_eq!(all, vec!["gap-1", "gap-2"]);
+        assert_eq!(unrecovered, vec!["gap-1"]);
+    }
+
+    #[test]
+    fn reconnecting_records_the_missed_span() {
+        let database = WhatsAppDatabase::default();
+        assert!(database.close_connection_gap(1700000000).unwrap().is_none());
+
+        database.mark_connection_lost(1700000100);
+        database.mark_connection_lost(1700000050);
+        let gap = database.close_connection_gap(1700003600).unwrap().unwrap();
+
+        assert_eq!(gap.gap_start, "1700000050");
+        assert_eq!(gap.gap_end, "1700003600");
+        assert_eq!(database.get_unrecovered_gaps().unwrap().len(), 1);
+        assert!(database.close_connection_gap(1700007200).unwrap().is_none());
+    }
+}
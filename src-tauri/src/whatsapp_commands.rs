@@ -1,36 +1,39 @@
-use crate::whatsapp_service_client::{WhatsAppServiceClient, WhatsAppConnectionState, WhatsAppMessage, WhatsAppServiceError};
+use crate::whatsapp_service_client::{WhatsAppServiceClient, WhatsAppConnectionState, WhatsAppMessage, WhatsAppServiceError, MarkProcessedResult, WhatsAppChat};
+use crate::whatsapp::{MessageGap, WhatsAppMonitor};
+use crate::commands::settings::{resolve_default_days, WHATSAPP_DEFAULT_LOOKBACK_DAYS};
+use crate::commands::command_error::CommandError;
 use serde::{Deserialize, Serialize};
 use tauri::command;
 use tokio::sync::Mutex;
 use std::sync::Arc;
 use once_cell::sync::Lazy;
 
-// Enhanced logging utility for WhatsApp commands
+// Logging (captured by log_sink when debug logging is on) for WhatsApp commands
 macro_rules! log_info {
     ($msg:expr) => {
-        // Logging disabled
+        log::info!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::info!("{}: {}", $msg, $data)
     };
 }
 
 #[allow(unused_macros)]
 macro_rules! log_warn {
     ($msg:expr) => {
-        // Logging disabled
+        log::warn!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::warn!("{}: {}", $msg, $data)
     };
 }
 
 macro_rules! log_error {
     ($msg:expr) => {
-        // Logging disabled
+        log::error!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::error!("{}: {}", $msg, $data)
     };
 }
 
@@ -64,6 +67,9 @@ pub struct LegacyWhatsAppConnectionState {
     pub message_count: i32,
     pub active_chats: Vec<String>,
     pub health_status: HealthStatus,
+    /// Set when a staleness check found the heartbeat too old to trust the status
+    #[serde(default)]
+    pub heartbeat_stale: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +129,7 @@ impl From<WhatsAppConnectionState> for LegacyWhatsAppConnectionState {
                 gap_count: state.health_status.gap_count,
                 monitoring_active: state.health_status.monitoring_active,
             },
+            heartbeat_stale: false,
         }
     }
 }
@@ -181,8 +188,9 @@ async fn get_client() -> Result<WhatsAppServiceClient, WhatsAppServiceError> {
 }
 
 #[command]
-pub async fn whatsapp_connect_v2(lookback_days: Option<i32>) -> Result<LegacyWhatsAppConnectionState, String> {
+pub async fn whatsapp_connect_v2(app: tauri::AppHandle, lookback_days: Option<i32>) -> Result<LegacyWhatsAppConnectionState, CommandError> {
     log_info!("🔗 WhatsApp connect command called", format!("lookback_days: {:?}", lookback_days));
+    let lookback_days = resolve_default_days(&app, lookback_days, WHATSAPP_DEFAULT_LOOKBACK_DAYS).await;
     
     match get_client().await {
         Ok(client) => {
@@ -193,19 +201,19 @@ pub async fn whatsapp_connect_v2(lookback_days: Option<i32>) -> Result<LegacyWha
                 }
                 Err(e) => {
                     log_error!("❌ WhatsApp connection failed", e.to_string());
-                    Err(format!("Connection failed: {}", e))
+                    Err(CommandError::from(e).with_context("Connection failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
 
 #[command]
-pub async fn whatsapp_disconnect_v2() -> Result<(), String> {
+pub async fn whatsapp_disconnect_v2() -> Result<(), CommandError> {
     log_info!("🔌 WhatsApp disconnect command called");
     
     match get_client().await {
@@ -222,19 +230,37 @@ pub async fn whatsapp_disconnect_v2() -> Result<(), String> {
                 }
                 Err(e) => {
                     log_error!("❌ WhatsApp disconnection failed", e.to_string());
-                    Err(format!("Disconnect failed: {}", e))
+                    Err(CommandError::from(e).with_context("Disconnect failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
 
+/// Legacy state for `state`; with `max_heartbeat_age_secs`, a "connected" state whose
+/// heartbeat is older than that is reported as an error, since monitoring has stalled
+fn legacy_state_with_staleness(state: WhatsAppConnectionState, max_heartbeat_age_secs: Option<i64>) -> LegacyWhatsAppConnectionState {
+    let stale = max_heartbeat_age_secs.is_some_and(|max_age| state.status == "connected" && state.is_stale(max_age));
+    let heartbeat_age = state.seconds_since_heartbeat();
+    
+    let mut legacy: LegacyWhatsAppConnectionState = state.into();
+    if stale {
+        legacy.heartbeat_stale = true;
+        legacy.status = ConnectionStatus::Error(if heartbeat_age == i64::MAX {
+            "Connected, but WhatsApp Web has not answered since; monitoring may have stalled".to_string()
+        } else {
+            format!("Connected, but no activity from WhatsApp Web for {} seconds; monitoring may have stalled", heartbeat_age)
+        });
+    }
+    legacy
+}
+
 #[command]
-pub async fn whatsapp_get_status_v2() -> Result<LegacyWhatsAppConnectionState, String> {
+pub async fn whatsapp_get_status_v2(max_heartbeat_age_secs: Option<i64>) -> Result<LegacyWhatsAppConnectionState, CommandError> {
     log_info!("📊 WhatsApp get status command called");
     
     match get_client().await {
@@ -242,31 +268,31 @@ pub async fn whatsapp_get_status_v2() -> Result<LegacyWhatsAppConnectionState, S
             match client.get_status().await {
                 Ok(state) => {
                     log_info!("✅ WhatsApp status retrieved", state.status.clone());
-                    Ok(state.into())
+                    Ok(legacy_state_with_staleness(state, max_heartbeat_age_secs))
                 }
                 Err(e) => {
                     log_error!("❌ Failed to get WhatsApp status", e.to_string());
-                    Err(format!("Status check failed: {}", e))
+                    Err(CommandError::from(e).with_context("Status check failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
 
 #[command]
-pub async fn whatsapp_check_login_v2() -> Result<LegacyWhatsAppConnectionState, String> {
+pub async fn whatsapp_check_login_v2() -> Result<LegacyWhatsAppConnectionState, CommandError> {
     log_info!("🔍 WhatsApp check login command called");
     
     // This is essentially the same as get_status for the Node.js service
-    whatsapp_get_status_v2().await
+    whatsapp_get_status_v2(None).await
 }
 
 #[command]
-pub async fn whatsapp_get_unprocessed_messages_v2(limit: Option<i32>) -> Result<Vec<LegacyWhatsAppMessage>, String> {
+pub async fn whatsapp_get_unprocessed_messages_v2(limit: Option<i32>) -> Result<Vec<LegacyWhatsAppMessage>, CommandError> {
     log_info!("📥 WhatsApp get unprocessed messages command called", limit.unwrap_or(-1));
     
     match get_client().await {
@@ -278,13 +304,13 @@ pub async fn whatsapp_get_unprocessed_messages_v2(limit: Option<i32>) -> Result<
                 }
                 Err(e) => {
                     log_error!("❌ Failed to get unprocessed messages", e.to_string());
-                    Err(format!("Message retrieval failed: {}", e))
+                    Err(CommandError::from(e).with_context("Message retrieval failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
@@ -294,7 +320,7 @@ pub async fn whatsapp_mark_processed_v2(
     message_id: String,
     work_related: bool,
     task_priority: Option<String>
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     log_info!("✅ WhatsApp mark processed command called", format!("ID: {}, Work: {}", message_id, work_related));
     
     match get_client().await {
@@ -306,20 +332,82 @@ pub async fn whatsapp_mark_processed_v2(
                 }
                 Err(e) => {
                     log_error!("❌ Failed to mark message as processed", e.to_string());
-                    Err(format!("Mark processed failed: {}", e))
+                    Err(CommandError::from(e).with_context("Mark processed failed"))
+                }
+            }
+        }
+        Err(e) => {
+            log_error!("❌ Failed to get WhatsApp client", e.to_string());
+            Err(CommandError::from(e).with_context("Service unavailable"))
+        }
+    }
+}
+
+#[command]
+pub async fn whatsapp_get_chats_v2() -> Result<Vec<WhatsAppChat>, CommandError> {
+    log_info!("📇 WhatsApp get chats command called");
+    
+    match get_client().await {
+        Ok(client) => {
+            match client.get_chats().await {
+                Ok(chats) => {
+                    log_info!("✅ Retrieved WhatsApp chats", chats.len());
+                    Ok(chats)
+                }
+                Err(e) => {
+                    log_error!("❌ Failed to get WhatsApp chats", e.to_string());
+                    Err(CommandError::from(e).with_context("Failed to list chats"))
+                }
+            }
+        }
+        Err(e) => {
+            log_error!("❌ Failed to get WhatsApp client", e.to_string());
+            Err(CommandError::from(e).with_context("Service unavailable"))
+        }
+    }
+}
+
+/// One entry of a `whatsapp_mark_processed_batch_v2` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkProcessedItem {
+    pub message_id: String,
+    pub work_related: bool,
+    pub task_priority: Option<String>,
+}
+
+#[command]
+pub async fn whatsapp_mark_processed_batch_v2(items: Vec<MarkProcessedItem>) -> Result<Vec<MarkProcessedResult>, CommandError> {
+    log_info!("✅ WhatsApp batch mark processed command called", items.len());
+    
+    let items = items
+        .into_iter()
+        .map(|item| (item.message_id, item.work_related, item.task_priority))
+        .collect();
+    
+    match get_client().await {
+        Ok(client) => {
+            match client.mark_messages_processed(items).await {
+                Ok(results) => {
+                    log_info!("✅ Batch marked as processed", results.len());
+                    Ok(results)
+                }
+                Err(e) => {
+                    log_error!("❌ Failed to mark batch as processed", e.to_string());
+                    Err(CommandError::from(e).with_context("Batch mark processed failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
 
 #[command]
-pub async fn whatsapp_refetch_messages_v2(lookback_days: Option<i32>) -> Result<Vec<LegacyWhatsAppMessage>, String> {
+pub async fn whatsapp_refetch_messages_v2(app: tauri::AppHandle, lookback_days: Option<i32>) -> Result<Vec<LegacyWhatsAppMessage>, CommandError> {
     log_info!("🔄 WhatsApp refetch messages command called", format!("lookback_days: {:?}", lookback_days));
+    let lookback_days = resolve_default_days(&app, lookback_days, WHATSAPP_DEFAULT_LOOKBACK_DAYS).await;
     
     match get_client().await {
         Ok(client) => {
@@ -330,13 +418,13 @@ pub async fn whatsapp_refetch_messages_v2(lookback_days: Option<i32>) -> Result<
                 }
                 Err(e) => {
                     log_error!("❌ Failed to refetch messages", e.to_string());
-                    Err(format!("Refetch failed: {}", e))
+                    Err(CommandError::from(e).with_context("Refetch failed"))
                 }
             }
         }
         Err(e) => {
             log_error!("❌ Failed to get WhatsApp client", e.to_string());
-            Err(format!("Service unavailable: {}", e))
+            Err(CommandError::from(e).with_context("Service unavailable"))
         }
     }
 }
@@ -344,9 +432,200 @@ pub async fn whatsapp_refetch_messages_v2(lookback_days: Option<i32>) -> Result<
 // Legacy command - for now, just returns the current status
 // In the Node.js service, monitoring is always active when connected
 #[command]
-pub async fn whatsapp_start_monitoring_v2() -> Result<LegacyWhatsAppConnectionState, String> {
+pub async fn whatsapp_start_monitoring_v2() -> Result<LegacyWhatsAppConnectionState, CommandError> {
     log_info!("▶️ WhatsApp start monitoring command called (monitoring is automatic in Node.js service)");
     
     // Just return current status since monitoring is automatic
-    whatsapp_get_status_v2().await
-}
\ No newline at end of file
+    whatsapp_get_status_v2(None).await
+}
+
+#[command]
+pub async fn whatsapp_list_gaps_v2() -> Result<Vec<MessageGap>, CommandError> {
+    log_info!("🕳️ WhatsApp list gaps command called");
+    
+    match get_client().await {
+        Ok(client) => {
+            match client.get_message_gaps().await {
+                Ok(gaps) => {
+                    log_info!("✅ Retrieved message gaps", gaps.len());
+                    Ok(gaps)
+                }
+                Err(e) => {
+                    log_error!("❌ Failed to get message gaps", e.to_string());
+                    Err(CommandError::from(e).with_context("Gap listing failed"))
+                }
+            }
+        }
+        Err(_) => {
+            // Node.js service isn't running, fall back to the legacy monitor's gaps
+            log_info!("♻️ WhatsApp service unavailable, using legacy monitor gaps");
+            let monitor = WhatsAppMonitor::get_instance();
+            let monitor = monitor.lock().await;
+            monitor.list_gaps().map_err(|e| CommandError::from(format!("Gap listing failed: {}", e)))
+        }
+    }
+}
+
+#[command]
+pub async fn whatsapp_recover_gap_v2(gap_id: String) -> Result<(), CommandError> {
+    log_info!("🩹 WhatsApp recover gap command called", gap_id.clone());
+    
+    match get_client().await {
+        Ok(client) => {
+            match client.recover_gap(&gap_id).await {
+                Ok(_) => {
+                    log_info!("✅ Gap recovery triggered");
+                    Ok(())
+                }
+                Err(e) => {
+                    log_error!("❌ Failed to recover gap", e.to_string());
+                    Err(CommandError::from(e).with_context("Gap recovery failed"))
+                }
+            }
+        }
+        Err(_) => {
+            log_info!("♻️ WhatsApp service unavailable, using legacy monitor");
+            let monitor = WhatsAppMonitor::get_instance();
+            let monitor = monitor.lock().await;
+            monitor.recover_gap(&gap_id).await.map_err(|e| CommandError::from(format!("Gap recovery failed: {}", e)))
+        }
+    }
+}
+
+// Stages run by `whatsapp_selftest_v2`, in order
+const SELFTEST_STAGES: [&str; 3] = ["service_health", "connection_status", "message_fetch"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelftestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelftestStage {
+    pub name: String,
+    pub outcome: SelftestOutcome,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhatsAppSelftestReport {
+    pub ready: bool,
+    pub stages: Vec<SelftestStage>,
+}
+
+/// Turn the results of the stages that ran into a report. Stages after the first failure
+/// (or that never ran) are marked as skipped.
+pub fn build_selftest_report(results: Vec<Result<String, String>>) -> WhatsAppSelftestReport {
+    let mut failed = false;
+    let mut stages = Vec::new();
+    
+    for (index, name) in SELFTEST_STAGES.iter().enumerate() {
+        let (outcome, detail) = match results.get(index) {
+            _ if failed => (SelftestOutcome::Skipped, Some("Skipped because an earlier stage failed".to_string())),
+            Some(Ok(detail)) => (SelftestOutcome::Passed, Some(detail.clone())),
+            Some(Err(error)) => {
+                failed = true;
+                (SelftestOutcome::Failed, Some(error.clone()))
+            }
+            None => (SelftestOutcome::Skipped, None),
+        };
+        stages.push(SelftestStage { name: name.to_string(), outcome, detail });
+    }
+    
+    WhatsAppSelftestReport {
+        ready: stages.iter().all(|stage| stage.outcome == SelftestOutcome::Passed),
+        stages,
+    }
+}
+
+#[command]
+pub async fn whatsapp_selftest_v2() -> Result<WhatsAppSelftestReport, String> {
+    log_info!("🩺 WhatsApp selftest command called");
+    
+    // Use a fresh client so a cached one doesn't hide a service that has since gone down
+    let client = WhatsAppServiceClient::new(None);
+    let mut results = Vec::new();
+    
+    let health = match client.health_check().await {
+        Ok(true) => Ok("WhatsApp service is reachable".to_string()),
+        Ok(false) => Err("WhatsApp service reported unhealthy".to_string()),
+        Err(e) => Err(format!("WhatsApp service unavailable: {}", e)),
+    };
+    let healthy = health.is_ok();
+    results.push(health);
+    
+    if healthy {
+        let status = match client.get_status().await {
+            Ok(state) if state.status == "connected" => Ok(format!("Connected ({} active chats)", state.active_chats.len())),
+            Ok(state) => Err(format!("WhatsApp is not connected (status: {})", state.status)),
+            Err(e) => Err(format!("Status check failed: {}", e)),
+        };
+        let connected = status.is_ok();
+        results.push(status);
+        
+        if connected {
+            results.push(match client.get_unprocessed_messages(Some(1)).await {
+                Ok(messages) => Ok(format!("Fetched {} sample message(s)", messages.len())),
+                Err(e) => Err(format!("Message retrieval failed: {}", e)),
+            });
+        }
+    }
+    
+    Ok(build_selftest_report(results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn down_service_fails_health_and_skips_later_stages() {
+        let report = build_selftest_report(vec![Err("WhatsApp service unavailable: connection refused".to_string())]);
+
+        assert!(!report.ready);
+        assert_eq!(report.stages.len(), 3);
+        assert_eq!(report.stages[0].outcome, SelftestOutcome::Failed);
+        assert_eq!(report.stages[1].outcome, SelftestOutcome::Skipped);
+        assert_eq!(report.stages[2].outcome, SelftestOutcome::Skipped);
+    }
+
+    #[test]
+    fn stale_heartbeat_marks_connected_state_as_broken() {
+        let ten_minutes_ago = chrono::Utc::now().timestamp_millis() - 10 * 60 * 1000;
+        let state = WhatsAppConnectionState {
+            status: "connected".to_string(),
+            qr_code: None,
+            connected_since: None,
+            last_message_timestamp: None,
+            message_count: 0,
+            active_chats: vec![],
+            health_status: crate::whatsapp_service_client::HealthStatus {
+                last_heartbeat: ten_minutes_ago,
+                consecutive_failures: 0,
+                last_recovery_attempt: None,
+                gap_count: 0,
+                monitoring_active: true,
+            },
+            last_error: None,
+        };
+
+        let unchecked = legacy_state_with_staleness(state.clone(), None);
+        assert!(!unchecked.heartbeat_stale);
+        assert!(matches!(unchecked.status, ConnectionStatus::Connected));
+
+        let checked = legacy_state_with_staleness(state.clone(), Some(5 * 60));
+        assert!(checked.heartbeat_stale);
+        assert!(matches!(checked.status, ConnectionStatus::Error(_)));
+
+        let mut never_answered = state.clone();
+        never_answered.health_status.last_heartbeat = 0;
+        assert!(legacy_state_with_staleness(never_answered, Some(5 * 60)).heartbeat_stale);
+
+        let mut disconnected = state;
+        disconnected.status = "disconnected".to_string();
+        assert!(!legacy_state_with_staleness(disconnected, Some(5 * 60)).heartbeat_stale);
+    }
+}
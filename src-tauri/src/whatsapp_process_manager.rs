@@ -4,22 +4,22 @@ use once_cell::sync::Lazy;
 use tauri::{command, Manager};
 use std::path::PathBuf;
 
-// Enhanced logging utility for WhatsApp Process Manager
+// Logging (captured by log_sink when debug logging is on) for WhatsApp Process Manager
 macro_rules! log_info {
     ($msg:expr) => {
-        // Logging disabled
+        log::info!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::info!("{}: {}", $msg, $data)
     };
 }
 
 macro_rules! log_error {
     ($msg:expr) => {
-        // Logging disabled
+        log::error!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::error!("{}: {}", $msg, $data)
     };
 }
 
@@ -142,13 +142,12 @@ impl WhatsAppProcessManager {
     }
 
     pub fn is_service_running() -> Result<bool, String> {
-        let process_guard = WHATSAPP_PROCESS.lock()
+        let mut process_guard = WHATSAPP_PROCESS.lock()
             .map_err(|e| format!("Failed to acquire process lock: {}", e))?;
 
-        if let Some(ref _child) = *process_guard {
-            // We can't call try_wait on an immutable reference, so we'll just assume it's running
-            // A more sophisticated approach would be to ping the HTTP service
-            Ok(true)
+        if let Some(child) = process_guard.as_mut() {
+            // try_wait returns Ok(None) while the process is still alive
+            Ok(matches!(child.try_wait(), Ok(None)))
         } else {
             Ok(false)
         }
@@ -175,7 +174,77 @@ impl WhatsAppProcessManager {
     }
 }
 
+/// A heartbeat older than this means the service is alive but hung. While connected the
+/// service refreshes its heartbeat every 30 seconds by probing WhatsApp Web.
+pub const HEARTBEAT_STALE_AFTER_MS: i64 = 2 * 60 * 1000;
+
+/// Liveness of the WhatsApp service process versus whether it actually responds
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WhatsAppServiceHealth {
+    pub process_running: bool,
+    /// Health endpoint answered and the heartbeat is fresh
+    pub responsive: bool,
+    pub health_endpoint_ok: bool,
+    pub last_heartbeat_age_ms: Option<i64>,
+    pub connection_status: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Age of a heartbeat (epoch millis, 0 = never) relative to `now_ms`
+pub fn heartbeat_age_ms(last_heartbeat: i64, now_ms: i64) -> Option<i64> {
+    if last_heartbeat <= 0 {
+        return None;
+    }
+    Some((now_ms - last_heartbeat).max(0))
+}
+
+/// Responsive needs a passing health endpoint and, when one is reported, a fresh heartbeat
+pub fn is_responsive(health_endpoint_ok: bool, last_heartbeat_age_ms: Option<i64>) -> bool {
+    health_endpoint_ok && last_heartbeat_age_ms.map_or(true, |age| age <= HEARTBEAT_STALE_AFTER_MS)
+}
+
 // Tauri commands
+#[command]
+pub async fn whatsapp_service_health_check() -> Result<WhatsAppServiceHealth, String> {
+    log_info!("💓 Probing WhatsApp service health");
+    
+    let process_running = WhatsAppProcessManager::is_service_running().unwrap_or(false);
+    let client = crate::whatsapp_service_client::WhatsAppServiceClient::new(None);
+    
+    let (health, status) = tokio::join!(client.health_check(), client.get_status());
+    let mut error = None;
+    
+    let health_endpoint_ok = match health {
+        Ok(ok) => ok,
+        Err(e) => {
+            error = Some(format!("Health check failed: {}", e));
+            false
+        }
+    };
+    
+    let (last_heartbeat_age_ms, connection_status) = match status {
+        Ok(state) => (
+            heartbeat_age_ms(state.health_status.last_heartbeat, chrono::Utc::now().timestamp_millis()),
+            Some(state.status),
+        ),
+        Err(e) => {
+            error.get_or_insert(format!("Status check failed: {}", e));
+            (None, None)
+        }
+    };
+    // The heartbeat only keeps ticking while connected; an idle disconnected service is not hung
+    let connected = connection_status.as_deref() == Some("connected");
+    
+    Ok(WhatsAppServiceHealth {
+        process_running,
+        responsive: is_responsive(health_endpoint_ok, last_heartbeat_age_ms.filter(|_| connected)),
+        health_endpoint_ok,
+        last_heartbeat_age_ms,
+        connection_status,
+        error,
+    })
+}
+
 #[command]
 pub async fn whatsapp_service_start(app_handle: tauri::AppHandle) -> Result<(), String> {
     WhatsAppProcessManager::start_service(&app_handle)
@@ -206,4 +275,22 @@ pub async fn whatsapp_service_restart(app_handle: tauri::AppHandle) -> Result<()
     
     // Start the service
     WhatsAppProcessManager::start_service(&app_handle)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_heartbeat_marks_service_unresponsive() {
+        let now = 1_700_000_000_000;
+
+        assert_eq!(heartbeat_age_ms(0, now), None);
+        assert_eq!(heartbeat_age_ms(now - 5_000, now), Some(5_000));
+
+        assert!(is_responsive(true, Some(5_000)));
+        assert!(is_responsive(true, None));
+        assert!(!is_responsive(true, Some(HEARTBEAT_STALE_AFTER_MS + 1)));
+        assert!(!is_responsive(false, Some(5_000)));
+    }
+}
@@ -2,41 +2,44 @@ use serde::{Deserialize, Serialize};
 use reqwest;
 use log::{error};
 use thiserror::Error;
+use crate::whatsapp::MessageGap;
+use crate::service_timeouts::TimeoutPolicy;
+use crate::http_client::{send_traced, RequestTraced, Traced};
 
-// Enhanced logging utility for WhatsApp Service Client
+// Logging (captured by log_sink when debug logging is on) for WhatsApp Service Client
 macro_rules! log_info {
     ($msg:expr) => {
-        // Logging disabled
+        log::info!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::info!("{}: {}", $msg, $data)
     };
 }
 
 macro_rules! log_warn {
     ($msg:expr) => {
-        // Logging disabled
+        log::warn!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::warn!("{}: {}", $msg, $data)
     };
 }
 
 macro_rules! log_error {
     ($msg:expr) => {
-        // Logging disabled
+        log::error!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::error!("{}: {}", $msg, $data)
     };
 }
 
 macro_rules! log_debug {
     ($msg:expr) => {
-        // Logging disabled
+        log::debug!("{}", $msg)
     };
     ($msg:expr, $data:expr) => {
-        // Logging disabled
+        log::debug!("{}: {}", $msg, $data)
     };
 }
 
@@ -49,10 +52,51 @@ pub enum WhatsAppServiceError {
     InvalidResponse(String),
     #[error("Service error: {0}")]
     ServiceError(String),
+    #[error("lookback_days must be between 0 and {max}, got {0}", max = MAX_LOOKBACK_DAYS)]
+    InvalidLookback(i32),
+    #[error(transparent)]
+    Traced(#[from] Traced<WhatsAppServiceError>),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl RequestTraced for WhatsAppServiceError {
+    fn traced(&self) -> Option<&Traced<Self>> {
+        match self {
+            WhatsAppServiceError::Traced(traced) => Some(traced),
+            _ => None,
+        }
+    }
+}
+
+impl WhatsAppServiceError {
+    /// The service isn't accepting connections, usually because it is still starting up
+    pub fn is_connection_refused(&self) -> bool {
+        matches!(self.untraced(), WhatsAppServiceError::Http(e) if e.is_connect())
+    }
+}
+
+/// Longest history the service may be asked to fetch; larger lookbacks can hang it
+pub const MAX_LOOKBACK_DAYS: i32 = 365;
+
+/// Reject lookbacks outside `0..=MAX_LOOKBACK_DAYS` instead of forwarding them to the service
+fn validate_lookback_days(lookback_days: Option<i32>) -> Result<Option<i32>, WhatsAppServiceError> {
+    match lookback_days {
+        Some(days) if !(0..=MAX_LOOKBACK_DAYS).contains(&days) => Err(WhatsAppServiceError::InvalidLookback(days)),
+        other => Ok(other),
+    }
+}
+
+/// Connect attempts made before giving up on a service that refuses connections
+pub const CONNECT_ATTEMPTS: u32 = 3;
+const CONNECT_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Delay before retry number `attempt` (1-based): 0.5s, 1s, 2s, ...
+fn connect_backoff(attempt: u32) -> std::time::Duration {
+    CONNECT_INITIAL_BACKOFF * 2u32.pow(attempt.saturating_sub(1))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct HealthStatus {
+    /// Epoch millis of the last event or answered state probe from WhatsApp Web, 0 if none yet
     pub last_heartbeat: i64,
     pub consecutive_failures: i32,
     pub last_recovery_attempt: Option<i64>,
@@ -60,7 +104,7 @@ pub struct HealthStatus {
     pub monitoring_active: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct WhatsAppConnectionState {
     pub status: String, // disconnected, connecting, qr_ready, connected, error
     pub qr_code: Option<String>,
@@ -73,6 +117,25 @@ pub struct WhatsAppConnectionState {
     pub last_error: Option<String>,
 }
 
+impl WhatsAppConnectionState {
+    /// Seconds since the service's last heartbeat (`i64::MAX` if it never sent one)
+    pub fn seconds_since_heartbeat(&self) -> i64 {
+        self.seconds_since_heartbeat_at(chrono::Utc::now().timestamp_millis())
+    }
+
+    fn seconds_since_heartbeat_at(&self, now_ms: i64) -> i64 {
+        match crate::whatsapp_process_manager::heartbeat_age_ms(self.health_status.last_heartbeat, now_ms) {
+            Some(age_ms) => age_ms / 1000,
+            None => i64::MAX,
+        }
+    }
+
+    /// Whether the heartbeat is older than `max_age` seconds, meaning monitoring has stalled
+    pub fn is_stale(&self, max_age: i64) -> bool {
+        self.seconds_since_heartbeat() > max_age
+    }
+}
+
 // Struct that matches the actual Node.js service API response
 #[derive(Debug, Deserialize)]
 struct ServiceStatusResponse {
@@ -86,7 +149,7 @@ struct ServiceStatusResponse {
     pub health_status: HealthStatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct WhatsAppMessage {
     pub id: String,
     pub from: String,
@@ -111,6 +174,30 @@ pub struct WhatsAppMessage {
     pub created_at: i64,
 }
 
+/// A chat (contact or group) known to the WhatsApp service
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WhatsAppChat {
+    pub chat_id: String,
+    pub name: String,
+    pub is_group: bool,
+    pub participant_count: Option<i32>,
+    /// Unix seconds of the chat's latest message, if any
+    pub last_message_ts: Option<i64>,
+}
+
+/// Outcome of marking one message in a batch (`mark_messages_processed`)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MarkProcessedResult {
+    pub message_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarkProcessedBatchResponse {
+    results: Vec<MarkProcessedResult>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ServiceResponse<T> {
     pub status: Option<String>,
@@ -124,6 +211,7 @@ struct ServiceResponse<T> {
 pub struct WhatsAppServiceClient {
     base_url: String,
     client: reqwest::Client,
+    timeouts: TimeoutPolicy,
 }
 
 impl WhatsAppServiceClient {
@@ -132,14 +220,18 @@ impl WhatsAppServiceClient {
         
         log_info!("🚀 Initializing WhatsApp Service Client", base_url.clone());
         
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
         log_info!("✅ WhatsApp Service Client initialized successfully");
         
-        Self { base_url, client }
+        Self { base_url, client: crate::http_client::shared_client(), timeouts: TimeoutPolicy::configured() }
+    }
+    
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = policy;
+        self
+    }
+    
+    pub fn timeout_policy(&self) -> &TimeoutPolicy {
+        &self.timeouts
     }
     
     pub async fn health_check(&self) -> Result<bool, WhatsAppServiceError> {
@@ -147,7 +239,8 @@ impl WhatsAppServiceClient {
         
         let url = format!("{}/health", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.get(&url).timeout(self.timeouts.health)).await;
+        match response {
             Ok(response) => {
                 if response.status().is_success() {
                     log_info!("✅ Health check passed");
@@ -159,7 +252,7 @@ impl WhatsAppServiceClient {
             }
             Err(e) => {
                 log_error!("❌ Health check request failed", e.to_string());
-                Err(WhatsAppServiceError::Http(e))
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -169,7 +262,8 @@ impl WhatsAppServiceClient {
         
         let url = format!("{}/status", self.base_url);
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.get(&url).timeout(self.timeouts.status)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received status response", response.status());
                 
@@ -201,18 +295,18 @@ impl WhatsAppServiceClient {
                         }
                         Err(_e) => {
                             log_error!("❌ Failed to parse status response", _e.to_string());
-                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")))
+                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")).with_request_id(&request_id))
                         }
                     }
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Status request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(e) => {
                 log_error!("❌ Status request failed", e.to_string());
-                Err(WhatsAppServiceError::Http(e))
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -221,7 +315,25 @@ impl WhatsAppServiceClient {
         self.connect_with_lookback(None).await
     }
 
+    /// Ask the service to connect, retrying with exponential backoff while it refuses
+    /// connections (it often isn't up yet right after launch). Other errors fail immediately.
     pub async fn connect_with_lookback(&self, lookback_days: Option<i32>) -> Result<WhatsAppConnectionState, WhatsAppServiceError> {
+        let lookback_days = validate_lookback_days(lookback_days)?;
+        let mut attempt = 1;
+        loop {
+            match self.connect_once(lookback_days).await {
+                Err(e) if e.is_connection_refused() && attempt < CONNECT_ATTEMPTS => {
+                    let delay = connect_backoff(attempt);
+                    log_warn!("⏳ WhatsApp service not accepting connections yet, retrying", format!("attempt {} in {:?}", attempt, delay));
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn connect_once(&self, lookback_days: Option<i32>) -> Result<WhatsAppConnectionState, WhatsAppServiceError> {
         log_info!("🔗 Initiating WhatsApp connection", format!("lookback_days: {:?}", lookback_days));
         
         let mut url = format!("{}/connect", self.base_url);
@@ -229,7 +341,8 @@ impl WhatsAppServiceClient {
             url = format!("{}?lookback_days={}", url, days);
         }
         
-        match self.client.post(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).timeout(self.timeouts.standard)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received connect response", response.status());
                 
@@ -244,18 +357,18 @@ impl WhatsAppServiceClient {
                         }
                         Err(_e) => {
                             log_error!("❌ Failed to parse connect response", _e.to_string());
-                            Err(WhatsAppServiceError::InvalidResponse(_e.to_string()))
+                            Err(WhatsAppServiceError::InvalidResponse(_e.to_string()).with_request_id(&request_id))
                         }
                     }
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Connect request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(_e) => {
                 log_error!("❌ Connect request failed", _e.to_string());
-                Err(WhatsAppServiceError::Http(_e))
+                Err(WhatsAppServiceError::Http(_e).with_request_id(&request_id))
             }
         }
     }
@@ -265,7 +378,8 @@ impl WhatsAppServiceClient {
         
         let url = format!("{}/disconnect", self.base_url);
         
-        match self.client.post(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).timeout(self.timeouts.standard)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received disconnect response", response.status());
                 
@@ -275,12 +389,12 @@ impl WhatsAppServiceClient {
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Disconnect request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(_e) => {
                 log_error!("❌ Disconnect request failed", _e.to_string());
-                Err(WhatsAppServiceError::Http(_e))
+                Err(WhatsAppServiceError::Http(_e).with_request_id(&request_id))
             }
         }
     }
@@ -293,14 +407,15 @@ impl WhatsAppServiceClient {
             url = format!("{}?limit={}", url, limit);
         }
         
-        match self.client.get(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.get(&url).timeout(self.timeouts.history)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received messages response", response.status());
                 
                 if response.status().is_success() {
                     let response_text = response.text().await.map_err(|e| {
                         log_error!("❌ Failed to read response body", e.to_string());
-                        WhatsAppServiceError::InvalidResponse(format!("Failed to read response: {}", e))
+                        WhatsAppServiceError::InvalidResponse(format!("Failed to read response: {}", e)).with_request_id(&request_id)
                     })?;
                     
                     log_debug!("📋 Raw response text", &response_text);
@@ -313,18 +428,47 @@ impl WhatsAppServiceClient {
                         Err(e) => {
                             log_error!("❌ Failed to parse messages response", e.to_string());
                             log_error!("📋 Response that failed to parse", &response_text);
-                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")))
+                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")).with_request_id(&request_id))
                         }
                     }
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Messages request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(e) => {
                 log_error!("❌ Messages request failed", e.to_string());
-                Err(WhatsAppServiceError::Http(e))
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
+            }
+        }
+    }
+    
+    pub async fn get_chats(&self) -> Result<Vec<WhatsAppChat>, WhatsAppServiceError> {
+        log_debug!("📇 Getting chats");
+        
+        let url = format!("{}/chats", self.base_url);
+        let (request_id, response) = send_traced("whatsapp", self.client.get(&url).timeout(self.timeouts.history)).await;
+        match response {
+            Ok(response) => {
+                log_debug!("📡 Received chats response", response.status());
+                
+                if response.status().is_success() {
+                    let chats: Vec<WhatsAppChat> = response.json().await.map_err(|e| {
+                        log_error!("❌ Failed to parse chats response", e.to_string());
+                        WhatsAppServiceError::InvalidResponse(format!("Failed to parse chats: {}", e)).with_request_id(&request_id)
+                    })?;
+                    log_info!("✅ Retrieved chats successfully", chats.len());
+                    Ok(chats)
+                } else {
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    log_error!("❌ Chats request failed", error_text.clone());
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
+                }
+            }
+            Err(e) => {
+                log_error!("❌ Chats request failed", e.to_string());
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
@@ -338,7 +482,8 @@ impl WhatsAppServiceClient {
             "task_priority": task_priority
         });
         
-        match self.client.post(&url).json(&body).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).json(&body).timeout(self.timeouts.standard)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received mark processed response", response.status());
                 
@@ -348,32 +493,76 @@ impl WhatsAppServiceClient {
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Mark processed request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(e) => {
                 log_error!("❌ Mark processed request failed", e.to_string());
-                Err(WhatsAppServiceError::Http(e))
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
+            }
+        }
+    }
+
+    /// Mark many messages processed in one request; items are (message_id, work_related, task_priority)
+    pub async fn mark_messages_processed(&self, items: Vec<(String, bool, Option<String>)>) -> Result<Vec<MarkProcessedResult>, WhatsAppServiceError> {
+        log_info!("✅ Marking messages as processed in batch", items.len());
+        
+        if items.is_empty() {
+            return Ok(vec![]);
+        }
+        
+        let url = format!("{}/messages/mark-processed-batch", self.base_url);
+        let messages: Vec<serde_json::Value> = items
+            .into_iter()
+            .map(|(message_id, work_related, task_priority)| serde_json::json!({
+                "message_id": message_id,
+                "work_related": work_related,
+                "task_priority": task_priority
+            }))
+            .collect();
+        let body = serde_json::json!({ "messages": messages });
+        
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).json(&body).timeout(self.timeouts.standard)).await;
+        match response {
+            Ok(response) => {
+                log_debug!("📡 Received batch mark processed response", response.status());
+                
+                if response.status().is_success() {
+                    let batch: MarkProcessedBatchResponse = response.json().await
+                        .map_err(|e| WhatsAppServiceError::Http(e).with_request_id(&request_id))?;
+                    log_info!("✅ Batch marked as processed", batch.results.iter().filter(|r| r.success).count());
+                    Ok(batch.results)
+                } else {
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    log_error!("❌ Batch mark processed request failed", error_text.clone());
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
+                }
+            }
+            Err(e) => {
+                log_error!("❌ Batch mark processed request failed", e.to_string());
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
 
     pub async fn refetch_messages_with_lookback(&self, lookback_days: Option<i32>) -> Result<Vec<WhatsAppMessage>, WhatsAppServiceError> {
         log_info!("🔄 Refetching messages with lookback", format!("lookback_days: {:?}", lookback_days));
+        let lookback_days = validate_lookback_days(lookback_days)?;
         
         let mut url = format!("{}/messages/refetch", self.base_url);
         if let Some(days) = lookback_days {
             url = format!("{}?lookback_days={}", url, days);
         }
         
-        match self.client.post(&url).send().await {
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).timeout(self.timeouts.history)).await;
+        match response {
             Ok(response) => {
                 log_debug!("📡 Received refetch response", response.status());
                 
                 if response.status().is_success() {
                     let response_text = response.text().await.map_err(|e| {
                         log_error!("❌ Failed to read response body", e.to_string());
-                        WhatsAppServiceError::InvalidResponse(format!("Failed to read response: {}", e))
+                        WhatsAppServiceError::InvalidResponse(format!("Failed to read response: {}", e)).with_request_id(&request_id)
                     })?;
                     
                     log_debug!("📋 Raw refetch response text", &response_text);
@@ -386,19 +575,136 @@ impl WhatsAppServiceClient {
                         Err(e) => {
                             log_error!("❌ Failed to parse refetch response", e.to_string());
                             log_error!("📋 Response that failed to parse", &response_text);
-                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")))
+                            Err(WhatsAppServiceError::InvalidResponse(format!("error decoding response body")).with_request_id(&request_id))
                         }
                     }
                 } else {
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     log_error!("❌ Refetch request failed", error_text.clone());
-                    Err(WhatsAppServiceError::ServiceError(error_text))
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
                 }
             }
             Err(e) => {
                 log_error!("❌ Refetch request failed", e.to_string());
-                Err(WhatsAppServiceError::Http(e))
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
+            }
+        }
+    }
+
+    pub async fn get_message_gaps(&self) -> Result<Vec<MessageGap>, WhatsAppServiceError> {
+        log_debug!("🕳️ Getting message gaps");
+        
+        let url = format!("{}/gaps", self.base_url);
+        
+        let (request_id, response) = send_traced("whatsapp", self.client.get(&url).timeout(self.timeouts.status)).await;
+        match response {
+            Ok(response) => {
+                if response.status().is_success() {
+                    let response_text = response.text().await.map_err(|e| {
+                        WhatsAppServiceError::InvalidResponse(format!("Failed to read response: {}", e)).with_request_id(&request_id)
+                    })?;
+                    
+                    serde_json::from_str::<Vec<MessageGap>>(&response_text).map_err(|e| {
+                        log_error!("❌ Failed to parse gaps response", e.to_string());
+                        WhatsAppServiceError::InvalidResponse(format!("error decoding response body: {}", e)).with_request_id(&request_id)
+                    })
+                } else {
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    log_error!("❌ Gaps request failed", error_text.clone());
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
+                }
+            }
+            Err(e) => {
+                log_error!("❌ Gaps request failed", e.to_string());
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
             }
         }
     }
-}
\ No newline at end of file
+
+    pub async fn recover_gap(&self, gap_id: &str) -> Result<(), WhatsAppServiceError> {
+        log_info!("🩹 Forcing gap recovery", gap_id);
+        
+        let url = format!("{}/gaps/{}/recover", self.base_url, gap_id);
+        
+        let (request_id, response) = send_traced("whatsapp", self.client.post(&url).timeout(self.timeouts.history)).await;
+        match response {
+            Ok(response) => {
+                if response.status().is_success() {
+                    log_info!("✅ Gap recovery triggered");
+                    Ok(())
+                } else {
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    log_error!("❌ Gap recovery request failed", error_text.clone());
+                    Err(WhatsAppServiceError::ServiceError(error_text).with_request_id(&request_id))
+                }
+            }
+            Err(e) => {
+                log_error!("❌ Gap recovery request failed", e.to_string());
+                Err(WhatsAppServiceError::Http(e).with_request_id(&request_id))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_heartbeat(last_heartbeat: i64) -> WhatsAppConnectionState {
+        WhatsAppConnectionState {
+            status: "connected".to_string(),
+            qr_code: None,
+            connected_since: None,
+            last_message_timestamp: None,
+            message_count: 0,
+            active_chats: vec![],
+            health_status: HealthStatus {
+                last_heartbeat,
+                consecutive_failures: 0,
+                last_recovery_attempt: None,
+                gap_count: 0,
+                monitoring_active: true,
+            },
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn heartbeat_age_is_reported_in_seconds() {
+        let now_ms = 1_700_000_600_000;
+        assert_eq!(state_with_heartbeat(1_700_000_000_000).seconds_since_heartbeat_at(now_ms), 600);
+        assert_eq!(state_with_heartbeat(0).seconds_since_heartbeat_at(now_ms), i64::MAX);
+
+        let fresh = state_with_heartbeat(chrono::Utc::now().timestamp_millis());
+        assert!(!fresh.is_stale(60));
+        assert!(state_with_heartbeat(0).is_stale(600));
+    }
+
+    #[test]
+    fn lookback_outside_range_is_rejected() {
+        assert_eq!(validate_lookback_days(None).unwrap(), None);
+        assert_eq!(validate_lookback_days(Some(0)).unwrap(), Some(0));
+        assert_eq!(validate_lookback_days(Some(MAX_LOOKBACK_DAYS)).unwrap(), Some(MAX_LOOKBACK_DAYS));
+
+        let err = validate_lookback_days(Some(99999)).unwrap_err();
+        assert_eq!(err.to_string(), "lookback_days must be between 0 and 365, got 99999");
+        assert!(validate_lookback_days(Some(-1)).is_err());
+    }
+
+    #[test]
+    fn connect_backoff_doubles() {
+        assert_eq!(connect_backoff(1), std::time::Duration::from_millis(500));
+        assert_eq!(connect_backoff(2), std::time::Duration::from_secs(1));
+        assert_eq!(connect_backoff(3), std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn refused_connections_are_recognized() {
+        // Nothing listens on port 1, so the connection is refused right away
+        let error = crate::http_client::shared_client().post("http://127.0.0.1:1/connect").send().await.unwrap_err();
+        let traced = WhatsAppServiceError::Http(error).with_request_id("req-1");
+
+        assert!(traced.is_connection_refused());
+        assert!(!WhatsAppServiceError::ServiceError("boom".to_string()).is_connection_refused());
+    }
+}
@@ -106,10 +106,18 @@ export const ConnectTab: React.FC<ConnectTabProps> = ({
     setIsSyncing(true);
     try {
       // Sync all connected channels for this project
+      let lastSyncAt: string | null = null;
       for (const channel of connectedChannels) {
-        await slackService.startManualSync(project.id, channel.channelId);
+        const result = await slackService.syncChannelNow(project.id, channel.channelId);
+        if (result.success && result.data) {
+          lastSyncAt = result.data.last_sync_at;
+        } else {
+          console.error(`Sync of channel ${channel.channelId} failed:`, result.error);
+        }
+      }
+      if (lastSyncAt) {
+        setLastSyncTime(lastSyncAt);
       }
-      setLastSyncTime(new Date().toISOString());
     } catch (error) {
       console.error('Sync failed:', error);
     } finally {
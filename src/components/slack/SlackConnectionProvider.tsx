@@ -27,11 +27,11 @@ export const SlackConnectionProvider: React.FC<SlackConnectionProviderProps> = (
       try {
         unlistenOAuth = await listen('slack-oauth-callback', (event: any) => {
           console.log('🔐 Received Tauri OAuth callback:', event);
-          const { code } = event.payload;
+          const { code, state } = event.payload;
           
           if (code) {
             console.log('🔐 Processing OAuth callback with code:', code);
-            slackConnectionManager.completeAuthentication(code)
+            slackConnectionManager.completeAuthentication(code, state)
               .then(() => {
                 console.log('✅ OAuth completed successfully');
               })
@@ -54,9 +54,9 @@ export const SlackConnectionProvider: React.FC<SlackConnectionProviderProps> = (
       const code = urlParams.get('code');
       const state = urlParams.get('state');
       
-      if (code && state === 'slack_oauth') {
+      if (code && state) {
         console.log('🔐 Processing URL OAuth callback...');
-        slackConnectionManager.completeAuthentication(code)
+        slackConnectionManager.completeAuthentication(code, state)
           .then(() => {
             console.log('✅ OAuth completed successfully');
             // Clean up URL
@@ -0,0 +1,105 @@
+import React, { useCallback, useEffect, useRef, useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { invoke, listen } from '../../utils/tauri';
+import { NotificationContainer } from './NotificationContainer';
+
+type NotificationAction =
+  | { type: 'open_project'; project_id: string }
+  | { type: 'open_task'; project_id: string; task_id: string };
+
+interface BackendNotification {
+  id: string;
+  title: string;
+  body: string;
+  action?: NotificationAction | null;
+  icon?: string | null;
+  tag?: string | null;
+  created_at: string;
+}
+
+// How long a notification stays on screen before it is dismissed
+const NOTIFICATION_DURATION_MS = 8000;
+
+/**
+ * Renders notifications sent by the backend (`send_notification`) and keeps the
+ * backend's list of active notifications in sync when they are dismissed.
+ */
+export const AppNotifications: React.FC = () => {
+  const navigate = useNavigate();
+  const [notifications, setNotifications] = useState<BackendNotification[]>([]);
+  const timers = useRef<Map<string, ReturnType<typeof setTimeout>>>(new Map());
+
+  const forget = useCallback((id: string) => {
+    const timer = timers.current.get(id);
+    if (timer) {
+      clearTimeout(timer);
+      timers.current.delete(id);
+    }
+    setNotifications(prev => prev.filter(n => n.id !== id));
+  }, []);
+
+  const dismiss = useCallback((id: string) => {
+    forget(id);
+    invoke('clear_notification', { id }).catch(() => {
+      // Already pruned or cleared by the backend
+    });
+  }, [forget]);
+
+  useEffect(() => {
+    let unlistenShown: (() => void) | undefined;
+    let unlistenCleared: (() => void) | undefined;
+    let cancelled = false;
+
+    const setupListeners = async () => {
+      try {
+        const shown = await listen('notification-shown', (event: any) => {
+          const notification = event.payload as BackendNotification;
+          setNotifications(prev => [...prev.filter(n => n.id !== notification.id), notification]);
+          timers.current.set(notification.id, setTimeout(() => dismiss(notification.id), NOTIFICATION_DURATION_MS));
+        });
+        const cleared = await listen('notification-cleared', (event: any) => {
+          forget(event.payload as string);
+        });
+        if (cancelled) {
+          shown?.();
+          cleared?.();
+        } else {
+          unlistenShown = shown;
+          unlistenCleared = cleared;
+        }
+      } catch (error) {
+        console.error('❌ Failed to setup notification listeners:', error);
+      }
+    };
+
+    setupListeners();
+
+    return () => {
+      cancelled = true;
+      unlistenShown?.();
+      unlistenCleared?.();
+      timers.current.forEach(timer => clearTimeout(timer));
+      timers.current.clear();
+    };
+  }, [dismiss, forget]);
+
+  const handleSelect = (id: string) => {
+    const action = notifications.find(n => n.id === id)?.action;
+    if (action?.type === 'open_project') {
+      navigate(`/project/${action.project_id}`);
+    } else if (action?.type === 'open_task') {
+      navigate(`/project/${action.project_id}/task/${action.task_id}`);
+    }
+    dismiss(id);
+  };
+
+  return (
+    <NotificationContainer
+      notifications={notifications.map(n => ({ id: n.id, type: 'info' as const, title: n.title, message: n.body }))}
+      onRemove={dismiss}
+      onSelect={handleSelect}
+    />
+  );
+};
+
+export default AppNotifications;
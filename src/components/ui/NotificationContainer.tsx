@@ -12,11 +12,13 @@ interface Notification {
 interface NotificationContainerProps {
   notifications: Notification[];
   onRemove: (id: string) => void;
+  onSelect?: (id: string) => void;
 }
 
 export const NotificationContainer: React.FC<NotificationContainerProps> = ({
   notifications,
   onRemove,
+  onSelect,
 }) => {
   if (notifications.length === 0) {
     return null;
@@ -60,7 +62,10 @@ export const NotificationContainer: React.FC<NotificationContainerProps> = ({
           className={`p-4 border rounded-lg shadow-lg transition-all duration-300 ${getStyles(notification.type)}`}
         >
           <div className="flex items-start justify-between">
-            <div className="flex items-start space-x-3">
+            <div
+              className={`flex items-start space-x-3 ${onSelect ? 'cursor-pointer' : ''}`}
+              onClick={() => onSelect?.(notification.id)}
+            >
               {getIcon(notification.type)}
               <div className="flex-1 min-w-0">
                 <p className="text-sm font-medium">
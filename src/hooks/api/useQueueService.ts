@@ -51,8 +51,9 @@ interface JobInfo {
   progress?: any;
   result?: any;
   failed_reason?: string;
-  created_at: string;
+  created_at?: string;
   finished_at?: string;
+  failed_at?: string;
 }
 
 interface QueueStats {
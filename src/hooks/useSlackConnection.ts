@@ -25,8 +25,8 @@ export function useSlackConnection() {
     // Legacy compatibility methods
     storeCredentials: slackConnectionManager.configure.bind(slackConnectionManager),
     startOAuth: slackConnectionManager.authenticate.bind(slackConnectionManager),
-    completeOAuth: (code: string) => 
-      slackConnectionManager.completeAuthentication(code),
+    completeOAuth: (code: string, state?: string) => 
+      slackConnectionManager.completeAuthentication(code, state),
     deleteCredentials: slackConnectionManager.disconnect.bind(slackConnectionManager),
   }), []);
 
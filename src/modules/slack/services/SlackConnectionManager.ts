@@ -303,7 +303,7 @@ class SlackConnectionManager {
     }
   }
 
-  async completeAuthentication(code: string): Promise<void> {
+  async completeAuthentication(code: string, state?: string): Promise<void> {
     await this.ensureInitialized();
     if (!this.state.isConfigured) {
       throw new Error('Must configure credentials first');
@@ -314,7 +314,7 @@ class SlackConnectionManager {
         success: boolean; 
         data?: { team?: { name: string; id: string } }; 
         error?: string;
-      }>('slack_complete_oauth', { code });
+      }>('slack_complete_oauth', { code, state });
 
       if (result.success && result.data) {
         this.updateState({
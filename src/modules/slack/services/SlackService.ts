@@ -15,6 +15,7 @@ import { SlackChannelConnection } from './SlackChannelConnection';
 import { SlackChannelService, autoJoinEvents } from './SlackChannelService';
 import { SlackCredentialsService } from './SlackCredentialsService';
 import { SlackSyncService } from './SlackSyncService';
+import type { SyncNowResult } from '../../../types/bindings';
 
 // Re-export the autoJoinEvents for backward compatibility
 export { autoJoinEvents };
@@ -240,6 +241,19 @@ export class SlackService {
     }
   }
 
+  /**
+   * Sync a project-channel connection now, through the same backend sync as the scheduler
+   */
+  async syncChannelNow(projectId: string, channelId: string): Promise<ServiceResult<SyncNowResult>> {
+    try {
+      const result = await SlackSyncService.syncChannelNow(projectId, channelId);
+      return { success: true, data: result };
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      return { success: false, error: `Failed to sync channel: ${errorMessage}` };
+    }
+  }
+
   /**
    * Update sync frequency for a connection
    */
@@ -3,6 +3,7 @@
 import { SlackSyncMetadata } from '../../../../types';
 import { getSlackCredentialsSecure } from '../../../utils/slackCredentialsSecure';
 import { invoke } from '../../../utils/tauri';
+import type { SyncNowResult } from '../../../types/bindings';
 
 export class SlackSyncService {
   // Circuit breaker for rate limiting
@@ -36,46 +37,107 @@ export class SlackSyncService {
   }
 
   /**
-   * Update sync metadata
+   * Update sync metadata, including the per-sync prompt and author filters
    */
   static async updateSyncMetadata(
     projectId: string,
     channelId: string,
     updates: Partial<SlackSyncMetadata>
-  ): Promise<void> {
+  ): Promise<SlackSyncMetadata> {
     try {
-      // First get existing sync record to update
-      const syncs = await this.getSyncMetadata(projectId);
-      const existingSync = syncs.find(s => s.channel_id === channelId);
+      // Start from the stored record so fields that aren't being updated keep their values
+      const { initDatabase } = await import('../../../utils/database');
+      const db = await initDatabase();
+      const rows: any[] = await db.select(
+        'SELECT * FROM slack_sync_metadata WHERE projectId = ? AND channelId = ? AND isDeleted = 0',
+        [projectId, channelId]
+      );
+      const existingSync = rows[0];
       
       if (!existingSync) {
         throw new Error(`No sync record found for project ${projectId} and channel ${channelId}`);
       }
 
-      // Create updated sync object with all required fields (camelCase for backend)
+      const parseUsers = (stored: string | null): string[] => (stored ? JSON.parse(stored) : []);
       const sync = {
         id: existingSync.id,
-        projectId: projectId,
-        channelId: channelId,
-        channelName: existingSync.channel_name || '',
-        lastSyncTimestamp: existingSync.last_sync_timestamp || null,
-        lastMessageTimestamp: existingSync.last_message_timestamp || null,
-        isActive: existingSync.is_active || true,
-        syncIntervalMinutes: existingSync.sync_interval_minutes || null,
-        syncStatus: existingSync.sync_status || null,
-        lastSyncAt: updates.lastSyncAt || existingSync.last_sync_at || null,
-        teamId: existingSync.team_id || null,
-        createdAt: existingSync.created_at || new Date().toISOString(),
+        projectId,
+        channelId,
+        channelName: existingSync.channelName || '',
+        lastSyncTimestamp: null,
+        lastMessageTimestamp: updates.lastMessageTimestamp ?? existingSync.lastMessageTimestamp ?? null,
+        isActive: existingSync.isEnabled !== 0,
+        syncIntervalMinutes: null,
+        syncStatus: existingSync.syncStatus ?? null,
+        lastSyncAt: updates.lastSyncAt ?? existingSync.lastSyncAt ?? null,
+        teamId: existingSync.teamId ?? null,
+        analysisPromptKey: updates.analysisPromptKey !== undefined ? updates.analysisPromptKey : existingSync.analysisPromptKey ?? null,
+        includedUsers: updates.includedUsers ?? parseUsers(existingSync.includedUsers),
+        excludedUsers: updates.excludedUsers ?? parseUsers(existingSync.excludedUsers),
+        createdAt: existingSync.createdAt,
         updatedAt: new Date().toISOString()
       };
 
-      await invoke('update_slack_sync', { sync });
+      return await invoke('update_slack_sync', { sync });
     } catch (error) {
       console.error('Failed to update sync metadata:', error);
       throw error;
     }
   }
 
+  /**
+   * Limit which authors' messages are analyzed for a sync; empty includedUsers means everyone
+   */
+  static async updateAuthorFilters(
+    projectId: string,
+    channelId: string,
+    includedUsers: string[],
+    excludedUsers: string[]
+  ): Promise<SlackSyncMetadata> {
+    return await this.updateSyncMetadata(projectId, channelId, { includedUsers, excludedUsers });
+  }
+
+  /**
+   * Snooze a sync without disconnecting it; it resumes on its own after the pause
+   */
+  static async pauseSync(syncId: string, durationMinutes: number): Promise<SlackSyncMetadata> {
+    const sync: SlackSyncMetadata = await invoke('pause_sync', { id: syncId, durationMinutes });
+    await this.storePausedUntil(syncId, sync.pausedUntil ?? null);
+    return sync;
+  }
+
+  /**
+   * End a sync's pause early
+   */
+  static async resumeSync(syncId: string): Promise<SlackSyncMetadata> {
+    const sync: SlackSyncMetadata = await invoke('resume_sync', { id: syncId });
+    await this.storePausedUntil(syncId, null);
+    return sync;
+  }
+
+  private static async storePausedUntil(syncId: string, pausedUntil: string | null): Promise<void> {
+    const { initDatabase } = await import('../../../utils/database');
+    const db = await initDatabase();
+    await db.execute(
+      'UPDATE slack_sync_metadata SET pausedUntil = ?, updatedAt = ? WHERE id = ?',
+      [pausedUntil, new Date().toISOString(), syncId]
+    );
+  }
+
+  /**
+   * Locally stored messages of a channel, newest first; pass the oldest ts shown as beforeTs to page back
+   */
+  static async getStoredMessages(channelId: string, limit?: number, beforeTs?: string): Promise<any[]> {
+    return await invoke('get_stored_messages', { channelId, limit, beforeTs });
+  }
+
+  /**
+   * Sync a channel in the backend right away; it stores the returned lastSyncAt on the sync
+   */
+  static async syncChannelNow(projectId: string, channelId: string): Promise<SyncNowResult> {
+    return await invoke('sync_channel_now', { projectId, channelId });
+  }
+
   /**
    * Delete sync metadata
    */
@@ -58,6 +58,7 @@ export interface SlackSyncMetadata extends ItemTimestamps {
   syncStatus?: 'local' | 'synced' | 'conflict';
   lastSyncAt?: string;
   teamId?: string;
+  pausedUntil?: string;
 }
 
 export interface SlackDerivedTask extends ItemTimestamps {
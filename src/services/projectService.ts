@@ -1,9 +1,25 @@
 import { invoke } from '@tauri-apps/api/core';
+import type { ProjectPage } from '../types/bindings';
 
-export const getProjects = async () => {
+export type { ProjectPage };
+
+export type ProjectSortBy = 'name' | 'created' | 'updated' | 'status';
+
+export interface ProjectListOptions {
+  offset?: number;
+  limit?: number;
+  sortBy?: ProjectSortBy;
+  statusFilter?: string;
+}
+
+export const getProjects = async (options: ProjectListOptions = {}): Promise<ProjectPage> => {
   try {
-    const projects = await invoke('get_projects');
-    return projects;
+    return await invoke<ProjectPage>('get_projects', {
+      offset: options.offset,
+      limit: options.limit,
+      sortBy: options.sortBy,
+      statusFilter: options.statusFilter,
+    });
   } catch (error) {
     console.error('Error fetching projects:', error);
     throw error;
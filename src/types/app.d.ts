@@ -212,6 +212,13 @@ export interface SlackSyncMetadata extends ItemTimestamps {
   syncStatus?: 'local' | 'synced' | 'conflict';
   lastSyncAt?: string;
   teamId?: string;
+  /** Prompt used instead of the default when analyzing this channel */
+  analysisPromptKey?: string | null;
+  /** Only these users' messages are analyzed; empty means everyone */
+  includedUsers?: string[];
+  /** These users' messages are never analyzed */
+  excludedUsers?: string[];
+  pausedUntil?: string;
 }
 
 export interface SlackDerivedTask extends ItemTimestamps {
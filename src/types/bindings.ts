@@ -0,0 +1,59 @@
+// Generated from the Rust command types by src-tauri/src/bindings.rs; do not edit.
+
+export type SlackSyncMetadata = { id: string; projectId: string; channelId: string; channelName: string; lastSyncTimestamp: string | null; lastMessageTimestamp: string | null; isActive: boolean; syncIntervalMinutes: number | null; syncStatus: string | null; lastSyncAt: string | null; teamId: string | null; analysisPromptKey?: string | null; includedUsers: string[]; excludedUsers: string[]; pausedUntil?: string | null; createdAt: string; updatedAt: string }
+
+export type ConnectedChannel = { channel_id: string; channel_name: string; project_id: string; project_name: string | null; is_active: boolean; last_sync_at: string | null }
+
+export type SlackCredentialsStatus = "Configured" | "PartiallyConfigured" | "NotConfigured"
+
+/**
+ * Whether a sync still works with the current token
+ */
+export type SyncRevalidation = { sync_id: string; channel_id: string; status: string; error?: string | null }
+
+/**
+ * What an on-demand channel sync fetched
+ */
+export type SyncNowResult = { sync_id: string; message_count: number; task_count: number; last_sync_at: string }
+
+/**
+ * A newly connected channel sync and how the bot got into the channel
+ */
+export type ChannelConnection = { sync: SlackSyncMetadata; channel_access: string; overlapping_projects: string[] }
+
+/**
+ * A message as stored locally
+ */
+export type StoredSlackMessage = { channel_id: string; ts: string; user: string; text: string; thread_ts: string | null; subtype: string | null; edited: string | null; deleted: string | null }
+
+export type HealthStatus = { last_heartbeat: number; consecutive_failures: number; last_recovery_attempt: number | null; gap_count: number; monitoring_active: boolean }
+
+export type WhatsAppConnectionState = { status: string; qr_code: string | null; connected_since: string | null; last_message_timestamp: number | null; message_count: number; active_chats: string[]; health_status: HealthStatus; last_error: string | null }
+
+export type WhatsAppMessage = { id: string; from: string; to: string | null; body: string; type: string; timestamp: number; isGroupMsg: boolean; author: string | null; chatId: string; hasMedia: boolean; receivedAt: string; processed_by_llm: boolean; work_related: boolean | null; task_priority: string | null; created_at: number }
+
+/**
+ * A chat (contact or group) known to the WhatsApp service
+ */
+export type WhatsAppChat = { chat_id: string; name: string; is_group: boolean; participant_count: number | null; last_message_ts: number | null }
+
+/**
+ * Outcome of marking one message in a batch (`mark_messages_processed`)
+ */
+export type MarkProcessedResult = { message_id: string; success: boolean; error: string | null }
+
+export type JobStatus = { id: string; queue: string; type: string; status: string; progress: any; data: any; result?: any | null; failed_reason?: string | null; createdAt: string; processedAt: string | null; finishedAt: string | null }
+
+export type JobInfo = { id: string; type: string; data?: any | null; progress?: any | null; result?: any | null; failed_reason?: string | null; createdAt: string | null; finishedAt: string | null; failedAt: string | null }
+
+/**
+ * One page of projects and how many match the filter in total
+ */
+export type ProjectPage = { items: any[]; total: number }
+
+export type LogLine = { timestamp: string; level: string; target: string; message: string }
+
+/**
+ * Error returned by commands that report a machine-readable cause alongside the message
+ */
+export type CommandError = { code: string; message: string; retryable: boolean }
@@ -2,6 +2,8 @@
  * Structured error taxonomy for better debugging
  */
 
+import type { CommandError } from './bindings';
+
 export enum ConnectionErrorType {
   IMPORT_MISSING = 'import_missing',
   EVENT_NOT_HANDLED = 'event_not_handled',
@@ -70,4 +72,23 @@ export class JobError extends Error {
       stack: this.stack
     };
   }
-}
\ No newline at end of file
+}
+// Error returned by Tauri commands that report a stable cause (see command_error.rs)
+export type { CommandError };
+
+const REAUTH_CODES = ['auth_required', 'missing_scope'];
+
+export function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null
+    && typeof (error as CommandError).code === 'string'
+    && typeof (error as CommandError).message === 'string';
+}
+
+/**
+ * How the UI should react to a failed command
+ */
+export function commandErrorAction(error: unknown): 'retry' | 'reauth' | 'fatal' {
+  if (!isCommandError(error)) return 'fatal';
+  if (REAUTH_CODES.includes(error.code)) return 'reauth';
+  return error.retryable ? 'retry' : 'fatal';
+}
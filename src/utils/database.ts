@@ -202,6 +202,7 @@ async function createTables(): Promise<void> {
       attendees TEXT, -- JSON array
       duration INTEGER,
       recurring TEXT,
+      timezone TEXT, -- IANA name the event's local times are in
       createdAt TEXT NOT NULL,
       updatedAt TEXT NOT NULL,
       syncStatus TEXT DEFAULT 'local' CHECK (syncStatus IN ('local', 'synced', 'conflict')),
@@ -347,10 +348,65 @@ async function createTables(): Promise<void> {
       syncStatus TEXT DEFAULT 'local' CHECK (syncStatus IN ('local', 'synced', 'conflict')),
       lastSyncAt_meta TEXT,
       teamId_meta TEXT,
+      analysisPromptKey TEXT,
+      includedUsers TEXT, -- JSON array of user/bot ids
+      excludedUsers TEXT, -- JSON array of user/bot ids
+      pausedUntil TEXT, -- RFC 3339; sync is snoozed until then
       UNIQUE(projectId, channelId)
     )
   `);
 
+  try {
+    await db.execute(`ALTER TABLE tasks ADD COLUMN sourceText TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE tasks ADD COLUMN sourcePermalink TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE tasks ADD COLUMN suggestionKey TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  // One task per applied AI suggestion
+  await db.execute(`CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_suggestion_key ON tasks(suggestionKey)`);
+
+  try {
+    await db.execute(`ALTER TABLE slack_sync_metadata ADD COLUMN analysisPromptKey TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE slack_sync_metadata ADD COLUMN includedUsers TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE slack_sync_metadata ADD COLUMN excludedUsers TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE slack_sync_metadata ADD COLUMN pausedUntil TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
+  try {
+    await db.execute(`ALTER TABLE events ADD COLUMN timezone TEXT`);
+  } catch (error) {
+    // Column might already exist, ignore error
+  }
+
   // Slack derived tasks table
   await db.execute(`
     CREATE TABLE IF NOT EXISTS slack_derived_tasks (
@@ -5,6 +5,10 @@ const QRCode = require('qrcode');
 const { io: SocketClient } = require('socket.io-client');
 const axios = require('axios');
 
+// How often a connected client is asked for its state, and how long it gets to answer
+const ACTIVITY_PROBE_INTERVAL_MS = 30 * 1000;
+const ACTIVITY_PROBE_TIMEOUT_MS = 10 * 1000;
+
 // Enhanced logging utility
 const log = {
     info: (message, data = null) => {
@@ -35,6 +39,11 @@ class WhatsAppService {
         this.messages = []; // Store recent messages
         this.lookbackDays = 7; // Default lookback period
         this.queuedStatusChange = null; // Store status changes when socket is not connected
+        this.gaps = []; // Spans where messages were missed while disconnected
+        this.connectionLostAt = null; // Set on disconnect, closed into a gap once ready again
+        this.lastActivityAt = 0; // Epoch ms of the last event or answered probe from WhatsApp Web, 0 = never
+        this.consecutiveProbeFailures = 0;
+        this.activityProbe = null;
         
         this.initializeClient();
         this.setupSocketClient();
@@ -161,6 +170,7 @@ class WhatsAppService {
         // QR Code event
         this.client.on('qr', async (qr) => {
             log.info('📱 QR Code received from WhatsApp Web');
+            this.recordActivity();
             try {
                 // Generate QR code as base64 data URL
                 this.currentQR = await QRCode.toDataURL(qr);
@@ -188,12 +198,16 @@ class WhatsAppService {
             this.connectedSince = new Date().toISOString();
             this.currentQR = null; // Clear QR code
             this.lastError = null;
+            this.recordActivity();
+            this.startActivityProbe();
             
             log.info('✅ WhatsApp connection established', {
                 status: this.status,
                 connectedSince: this.connectedSince
             });
 
+            this.closeConnectionGap();
+
             // Fetch historical messages based on lookback period
             try {
                 log.info('📥 Fetching historical messages on connection', { lookbackDays: this.lookbackDays });
@@ -278,6 +292,7 @@ class WhatsAppService {
         // Authentication success
         this.client.on('authenticated', () => {
             log.info('🔐 WhatsApp authentication successful');
+            this.recordActivity();
         });
         
         // Authentication failure
@@ -291,6 +306,9 @@ class WhatsAppService {
         // Disconnection
         this.client.on('disconnected', (reason) => {
             log.warn('🔌 WhatsApp client disconnected', { reason });
+            this.recordActivity();
+            this.stopActivityProbe();
+            this.markConnectionLost();
             this.isReady = false;
             this.status = 'disconnected';
             this.connectedSince = null;
@@ -301,6 +319,7 @@ class WhatsAppService {
         // Message received
         this.client.on('message', async (message) => {
             // New message received (silent logging)
+            this.recordActivity();
             
             try {
                 // Process and store message
@@ -355,11 +374,122 @@ class WhatsAppService {
         // Error handling
         this.client.on('change_state', (state) => {
             log.debug('🔄 WhatsApp client state changed', { state });
+            this.recordActivity();
         });
         
         log.info('✅ WhatsApp client event handlers setup complete');
     }
     
+    recordActivity() {
+        this.lastActivityAt = Date.now();
+        this.consecutiveProbeFailures = 0;
+    }
+    
+    // While connected, ask WhatsApp Web for its state so a quiet but healthy connection still
+    // counts as activity. A hung browser never answers, and the activity time goes stale.
+    startActivityProbe() {
+        this.stopActivityProbe();
+        this.activityProbe = setInterval(async () => {
+            if (!this.isReady) return;
+            
+            try {
+                const state = await Promise.race([
+                    this.client.getState(),
+                    new Promise((_, reject) => setTimeout(() => reject(new Error('State probe timed out')), ACTIVITY_PROBE_TIMEOUT_MS))
+                ]);
+                if (state) {
+                    this.recordActivity();
+                } else {
+                    this.consecutiveProbeFailures++;
+                }
+            } catch (error) {
+                this.consecutiveProbeFailures++;
+                log.warn('⚠️ WhatsApp state probe failed', { error: error.message, failures: this.consecutiveProbeFailures });
+            }
+        }, ACTIVITY_PROBE_INTERVAL_MS);
+    }
+    
+    stopActivityProbe() {
+        if (this.activityProbe) {
+            clearInterval(this.activityProbe);
+            this.activityProbe = null;
+        }
+    }
+    
+    markConnectionLost() {
+        if (this.isReady && !this.connectionLostAt) {
+            this.connectionLostAt = Math.floor(Date.now() / 1000);
+        }
+    }
+
+    // Record the span since the connection was lost as a gap, in epoch seconds
+    closeConnectionGap() {
+        if (!this.connectionLostAt) return;
+
+        const reconnectedAt = Math.floor(Date.now() / 1000);
+        if (reconnectedAt > this.connectionLostAt) {
+            const gap = {
+                id: `gap-${this.connectionLostAt}-${reconnectedAt}`,
+                gap_start: String(this.connectionLostAt),
+                gap_end: String(reconnectedAt),
+                recovery_attempted: false
+            };
+            this.gaps.push(gap);
+            log.info('🕳️ Recorded message gap', gap);
+        }
+        this.connectionLostAt = null;
+    }
+
+    // Fetch the messages sent during a gap and send them for analysis
+    async recoverGap(gap) {
+        const start = parseInt(gap.gap_start);
+        const end = parseInt(gap.gap_end);
+        const chats = await this.client.getChats();
+        const recovered = [];
+
+        for (const chat of chats) {
+            if (!chat.timestamp || chat.timestamp < start) continue; // Nothing new in this chat
+            try {
+                const messages = await chat.fetchMessages({ limit: 100 });
+                for (const message of messages) {
+                    if (message.timestamp < start || message.timestamp > end) continue;
+                    recovered.push({
+                        id: message.id._serialized,
+                        from: message.from,
+                        to: message.to,
+                        body: message.body,
+                        type: message.type,
+                        timestamp: new Date(message.timestamp * 1000).toISOString(),
+                        isGroupMsg: message.isGroupMsg,
+                        author: message.author,
+                        chatId: message.id.remote,
+                        hasMedia: message.hasMedia,
+                        receivedAt: new Date().toISOString()
+                    });
+                }
+            } catch (chatError) {
+                log.warn('⚠️ Failed to recover messages from chat', {
+                    chatId: chat.id._serialized,
+                    error: chatError.message
+                });
+            }
+        }
+
+        if (recovered.length > 0) {
+            const queueServiceUrl = process.env.QUEUE_SERVICE_URL || 'http://localhost:3005';
+            await axios.post(`${queueServiceUrl}/api/queue/whatsapp/analyze`, {
+                messages: recovered,
+                analysisType: 'task-detection',
+                context: {
+                    source: 'whatsapp-gap-recovery',
+                    gapId: gap.id
+                }
+            });
+        }
+
+        return recovered.length;
+    }
+
     async checkExistingSession() {
         log.info('🔍 Checking for existing WhatsApp session...');
         
@@ -451,9 +581,9 @@ class WhatsAppService {
                 message_count: this.messageCount,
                 last_error: this.lastError,
                 health_status: {
-                    last_heartbeat: Date.now(),
-                    consecutive_failures: 0,
-                    gap_count: 0,
+                    last_heartbeat: this.lastActivityAt,
+                    consecutive_failures: this.consecutiveProbeFailures,
+                    gap_count: this.gaps.length,
                     monitoring_active: this.isReady
                 }
             };
@@ -504,6 +634,8 @@ class WhatsAppService {
             log.info('🔌 Disconnect request received');
             
             try {
+                this.markConnectionLost();
+                this.stopActivityProbe();
                 if (this.client && typeof this.client.destroy === 'function') {
                     await this.client.destroy();
                     log.info('✅ WhatsApp client destroyed');
@@ -539,7 +671,35 @@ class WhatsAppService {
             
             res.json(messages);
         });
-        
+
+        // List known chats (contacts and groups) so they can be picked without waiting for messages
+        this.app.get('/chats', async (req, res) => {
+            if (!this.isReady) {
+                return res.status(400).json({
+                    error: 'WhatsApp not connected',
+                    status: this.status
+                });
+            }
+
+            try {
+                const chats = await this.client.getChats();
+                const directory = chats.map(chat => ({
+                    chat_id: chat.id._serialized,
+                    name: chat.name || chat.id.user,
+                    is_group: chat.isGroup,
+                    participant_count: chat.isGroup ? (chat.participants?.length ?? null) : null,
+                    last_message_ts: chat.timestamp || null
+                }));
+
+                log.debug('📇 Chats requested', { returnedCount: directory.length });
+
+                res.json(directory);
+            } catch (error) {
+                log.error('❌ Failed to list chats', { error: error.message });
+                res.status(500).json({ error: error.message });
+            }
+        });
+
         // Get unprocessed messages (for compatibility with existing Tauri code)
         this.app.get('/messages/unprocessed', (req, res) => {
             const limit = parseInt(req.query.limit) || 50;
@@ -576,6 +736,55 @@ class WhatsAppService {
             res.json({ success: true, messageId, work_related, task_priority });
         });
 
+        // Mark a batch of messages as processed in one request
+        this.app.post('/messages/mark-processed-batch', (req, res) => {
+            const { messages } = req.body;
+
+            if (!Array.isArray(messages)) {
+                return res.status(400).json({ error: 'messages must be an array' });
+            }
+
+            log.info('✅ Batch of messages marked as processed', { count: messages.length });
+
+            const results = messages.map((message) => {
+                if (!message || !message.message_id) {
+                    return { message_id: message?.message_id || '', success: false, error: 'Missing message_id' };
+                }
+                return { message_id: message.message_id, success: true, error: null };
+            });
+
+            res.json({ results });
+        });
+
+        // List message gaps recorded across disconnections
+        this.app.get('/gaps', (req, res) => {
+            res.json(this.gaps);
+        });
+
+        // Recover the messages of one gap
+        this.app.post('/gaps/:gapId/recover', async (req, res) => {
+            const gap = this.gaps.find(g => g.id === req.params.gapId);
+            if (!gap) {
+                return res.status(404).json({ error: `Gap not found: ${req.params.gapId}` });
+            }
+            if (!this.isReady) {
+                return res.status(400).json({
+                    error: 'WhatsApp not connected',
+                    status: this.status
+                });
+            }
+
+            try {
+                gap.recovery_attempted = true;
+                const recovered = await this.recoverGap(gap);
+                log.info('🩹 Gap recovered', { gapId: gap.id, recovered });
+                res.json({ success: true, gap_id: gap.id, recovered });
+            } catch (error) {
+                log.error('❌ Failed to recover gap', error);
+                res.status(500).json({ error: error.message });
+            }
+        });
+
         // Refetch messages with lookback period
         this.app.post('/messages/refetch', async (req, res) => {
             const lookbackDays = req.query.lookback_days ? parseInt(req.query.lookback_days) : this.lookbackDays;